@@ -0,0 +1,24 @@
+use checked_rs::prelude::*;
+use criterion::{criterion_group, criterion_main};
+
+#[clamped(u16, default = 200, lower = 100, upper = 599, benchmark)]
+#[derive(Debug, Clone, Copy)]
+enum StatusCode {
+    #[range(100..=199)]
+    Informational,
+    #[range(200..=299)]
+    Success,
+    #[range(300..=399)]
+    Redirection,
+    #[range(400..=499)]
+    ClientError,
+    #[range(500..=599)]
+    ServerError,
+}
+
+#[clamped(u32 as Hard, default = 1, lower = 1, upper = 1_000_000, benchmark)]
+#[derive(Debug, Clone, Copy)]
+struct Quantity;
+
+criterion_group!(benches, status_code_bench::benches, quantity_bench::benches);
+criterion_main!(benches);
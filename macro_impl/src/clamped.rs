@@ -1,12 +1,19 @@
 use proc_macro2::TokenStream;
-use proc_macro_error::abort;
 
 mod common_impl;
 mod enum_impl;
 mod hard_repr;
+mod singleton_impl;
 mod soft_repr;
 
-use crate::params::{attr_params::AttrParams, AsSoftOrHard};
+use quote::quote;
+use syn::parse_quote;
+
+use crate::{
+    diagnostic::{self, abort},
+    params::{attr_params::AttrParams, crate_config::CrateConfig, AsSoftOrHard},
+};
+use common_impl::has_derive;
 
 /// Generate the implementation for a clamped enum. This macro generates the following:
 /// - An inner type that wraps the enum's value
@@ -15,16 +22,307 @@ use crate::params::{attr_params::AttrParams, AsSoftOrHard};
 /// - Implementations of various conversions for the enum
 /// - Implementations of equality and comparison for the enum
 /// - Implementations of various binary operations for the enum
-pub fn clamped(attr: AttrParams, item: syn::Item) -> TokenStream {
+pub fn clamped(attr: AttrParams, mut item: syn::Item) -> TokenStream {
     let is_enum = matches!(&item, syn::Item::Enum(_));
+    // Parsing `attr` has already reported the errors of the crate's configuration.
+    let config = CrateConfig::current().ok().flatten();
 
-    if is_enum {
-        enum_impl::define_mod(attr, item)
+    if let Some((config, _)) = &config {
+        apply_crate_derives(&mut item, config);
+    }
+
+    let expanded = if is_enum {
+        EnumCodegen::expand(attr, item)
+    } else if attr.singleton_kw.is_some() && attr.as_soft_or_hard.is_none() {
+        singleton_impl::define_mod(attr, item)
     } else {
         match attr.as_soft_or_hard {
-            Some(AsSoftOrHard::Soft { .. }) => soft_repr::define_mod(attr, item),
-            Some(AsSoftOrHard::Hard { .. }) => hard_repr::define_mod(attr, item),
+            Some(AsSoftOrHard::Soft { .. }) => SoftCodegen::expand(attr, item),
+            Some(AsSoftOrHard::Hard { .. }) => HardCodegen::expand(attr, item),
             None => abort!(item, "The `clamped` attribute must specify either `as Soft` or `as Hard` when applied to a struct."),
         }
+    };
+
+    match config {
+        // Including the configuration file makes the compiler rebuild the crate when it changes.
+        Some((_, path)) => {
+            let path = path.to_string_lossy();
+
+            quote! {
+                #expanded
+
+                const _: &[u8] = include_bytes!(#path);
+            }
+        }
+        None => expanded,
+    }
+}
+
+/// One of the modes of the `clamped` code generation, for procedural macro crates that build
+/// clamped types from their own syntax and want to reuse the generated code as a library.
+///
+/// The signature of `expand` is stable and only changes in a major release, while the code it
+/// generates may change in any release like the expansion of `clamped` does. Unlike `clamped`,
+/// `expand` does not add the derives of the crate's `configure!` invocation.
+///
+/// Invalid input is reported with `proc_macro_error::abort!`, so `expand` must be called from a
+/// procedural macro annotated with `#[proc_macro_error]`, or within `diagnostic::catch` to get the
+/// errors back as a `syn::Error`.
+pub trait Codegen {
+    /// Generate the clamped type for `item` as configured by `attr`, along with its module.
+    fn expand(attr: AttrParams, item: syn::Item) -> TokenStream;
+}
+
+/// The code generation of structs declared `as Soft`.
+pub enum SoftCodegen {}
+
+/// The code generation of structs declared `as Hard`.
+pub enum HardCodegen {}
+
+/// The code generation of enums.
+pub enum EnumCodegen {}
+
+impl Codegen for SoftCodegen {
+    /// Structs are treated as `as Soft` when `attr` does not say otherwise.
+    fn expand(mut attr: AttrParams, item: syn::Item) -> TokenStream {
+        match &attr.as_soft_or_hard {
+            Some(AsSoftOrHard::Soft { .. }) => {}
+            Some(AsSoftOrHard::Hard { hard, .. }) => {
+                abort!(
+                    hard,
+                    "`SoftCodegen` cannot generate a struct declared `as Hard`"
+                )
+            }
+            None => {
+                attr.as_soft_or_hard = Some(AsSoftOrHard::Soft {
+                    as_token: Default::default(),
+                    soft: Default::default(),
+                })
+            }
+        }
+
+        soft_repr::define_mod(attr, item)
+    }
+}
+
+impl Codegen for HardCodegen {
+    /// Structs are treated as `as Hard` when `attr` does not say otherwise.
+    fn expand(mut attr: AttrParams, item: syn::Item) -> TokenStream {
+        match &attr.as_soft_or_hard {
+            Some(AsSoftOrHard::Hard { .. }) => {}
+            Some(AsSoftOrHard::Soft { soft, .. }) => {
+                abort!(
+                    soft,
+                    "`HardCodegen` cannot generate a struct declared `as Soft`"
+                )
+            }
+            None => {
+                attr.as_soft_or_hard = Some(AsSoftOrHard::Hard {
+                    as_token: Default::default(),
+                    hard: Default::default(),
+                })
+            }
+        }
+
+        hard_repr::define_mod(attr, item)
+    }
+}
+
+impl Codegen for EnumCodegen {
+    fn expand(attr: AttrParams, item: syn::Item) -> TokenStream {
+        enum_impl::define_mod(attr, item)
+    }
+}
+
+/// Add the derives given by the crate's `configure!` invocation that the item does not already
+/// have.
+fn apply_crate_derives(item: &mut syn::Item, config: &CrateConfig) {
+    let attrs = match item {
+        syn::Item::Struct(data) => &mut data.attrs,
+        syn::Item::Enum(data) => &mut data.attrs,
+        _ => return,
+    };
+
+    let missing = config
+        .derives
+        .iter()
+        .filter(|path| {
+            path.segments
+                .last()
+                .is_some_and(|segment| !has_derive(attrs, &segment.ident.to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    // Derives go first so that their helper attributes are introduced before they are used.
+    if !missing.is_empty() {
+        attrs.insert(0, parse_quote!(#[derive(#(#missing),*)]));
+    }
+}
+
+/// How `expand_to_string` lays out the generated code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExpansionFormat {
+    /// The tokens on a single line, as `TokenStream` displays them.
+    Tokens,
+    /// The code formatted with `prettyplease`, with trailing whitespace removed and runs of blank
+    /// lines collapsed, so that snapshots only change when the generated code does.
+    #[default]
+    Stable,
+}
+
+/// Expand the `clamped` attribute for `item` as a string, so that downstream crates can snapshot
+/// the expansion of their own types in CI and notice when an upgrade of checked-rs changes it. No
+/// spans are kept, and the output is the same on every build for the same input and crate
+/// configuration.
+///
+/// # Errors
+///
+/// Returns the errors of the macro when it rejects the input.
+pub fn expand_to_string(
+    attr: TokenStream,
+    item: TokenStream,
+    format: ExpansionFormat,
+) -> syn::Result<String> {
+    let tokens = diagnostic::catch(|| -> syn::Result<TokenStream> {
+        let attr = syn::parse2::<AttrParams>(attr)?;
+        let item = syn::parse2::<syn::Item>(item)?;
+        Ok(clamped(attr, item))
+    })??;
+
+    match format {
+        ExpansionFormat::Tokens => Ok(tokens.to_string()),
+        ExpansionFormat::Stable => {
+            let file = syn::parse2::<syn::File>(tokens)?;
+            let mut output = String::new();
+
+            for line in prettyplease::unparse(&file).lines().map(str::trim_end) {
+                if line.is_empty() && (output.is_empty() || output.ends_with("\n\n")) {
+                    continue;
+                }
+
+                output.push_str(line);
+                output.push('\n');
+            }
+
+            Ok(output)
+        }
+    }
+}
+
+/// Expand the `clamped` attribute and format the result with `prettyplease`, for golden tests of
+/// the generated code. This is `expand_to_string` with `ExpansionFormat::Stable`.
+///
+/// # Errors
+///
+/// Returns the errors of the macro when it rejects the input.
+pub fn expand_formatted(attr: TokenStream, item: TokenStream) -> syn::Result<String> {
+    expand_to_string(attr, item, ExpansionFormat::Stable)
+}
+
+// The expansion depends on the enabled features, so the snapshot only covers the default ones.
+#[cfg(all(
+    test,
+    not(any(
+        feature = "anyhow-compat",
+        feature = "bounded-integer",
+        feature = "bytemuck",
+        feature = "deranged",
+        feature = "fuzz",
+        feature = "metrics",
+        feature = "never-panic",
+        feature = "reflect",
+        feature = "roundtrip-tests",
+        feature = "testing",
+        feature = "ui",
+        feature = "zeroize",
+    ))
+))]
+mod tests {
+    use super::*;
+
+    /// Run with `SNAPSHOTS=overwrite` to update the snapshot after changing the generated code.
+    const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/status.expanded");
+
+    fn expand_status() -> syn::Result<String> {
+        expand_formatted(
+            quote!(u16, default = 200, lower = 100, upper = 599),
+            quote! {
+                #[derive(Debug, Clone, Copy)]
+                pub enum Status {
+                    #[eq(200)]
+                    Ok,
+                    #[eq(204, 205, 206)]
+                    NoContent,
+                    #[eq(408, 425, 429, 500..=504)]
+                    Retry,
+                    #[range(400..=499)]
+                    ClientError,
+                    #[range(500..=599)]
+                    ServerError,
+                    #[other]
+                    Other,
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn test_expand_formatted_snapshot() -> syn::Result<()> {
+        let expanded = expand_status()?;
+        assert_eq!(expanded, expand_status()?);
+
+        if std::env::var_os("SNAPSHOTS").is_some_and(|mode| mode == "overwrite") {
+            std::fs::write(SNAPSHOT, &expanded).unwrap();
+        }
+
+        let snapshot = std::fs::read_to_string(SNAPSHOT).unwrap();
+        assert!(
+            expanded == snapshot,
+            "the expansion differs from {SNAPSHOT}, run with `SNAPSHOTS=overwrite` to update it"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_formatted_errors() {
+        let error = expand_formatted(
+            quote!(u8, default = 0),
+            quote! {
+                struct Level;
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "The `clamped` attribute must specify either `as Soft` or `as Hard` when applied to a struct."
+        );
+
+        let error = expand_formatted(
+            quote!(u8, default = 0),
+            quote! {
+                enum Level {
+                    #[eq(1)]
+                    Low,
+                    #[eq(1)]
+                    High,
+                }
+            },
+        )
+        .unwrap_err();
+
+        // The help of a diagnostic follows its message on the next lines.
+        let messages = error
+            .into_iter()
+            .map(|e| e.to_string().lines().next().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "The value `1` is already used by variant `Low`",
+                "The value `0` is not covered by any variant",
+                "The values `2..=255` are not covered by any variant",
+            ]
+        );
     }
 }
@@ -1,10 +1,359 @@
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
+use syn::{punctuated::Punctuated, Token};
+
+use crate::{
+    diagnostic::abort,
+    params::{
+        attr_params::AttrParams, AsSoftOrHard, BehaviorArg, DivByZeroArg, NumberArg, NumberKind,
+        NumberValue, BEHAVIOR_OPS,
+    },
+};
+
+/// Combine the generated implementations, leaving out the trait implementations named by
+/// `skip_impls` so that they do not conflict with ones written by hand. Each one is named after its
+/// trait, such as `FromStr` or `AddAssign`, with a `Primitive` suffix when a primitive integer is
+/// one side of it, such as `PartialOrdPrimitive` for `PartialOrd<u32>`. Unsafe traits, and the
+/// limits and behavior they rely on, are never skipped.
+pub fn aggregate_impls(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    impls: Vec<TokenStream>,
+) -> TokenStream {
+    let skipped = attr.skipped_impls();
+    let output = TokenStream::from_iter(impls);
+
+    if skipped.is_empty() && !cfg!(feature = "reflect") {
+        return output;
+    }
+
+    let file = syn::parse2::<syn::File>(output).unwrap_or_else(|e| {
+        abort!(
+            name,
+            "failed to parse the generated implementations of `{}`: {}",
+            name,
+            e
+        )
+    });
+
+    let mut available = BTreeSet::new();
+    let mut found = vec![false; skipped.len()];
+
+    let items = file
+        .items
+        .into_iter()
+        .filter(|item| {
+            let Some(impl_name) = skippable_impl_name(item) else {
+                return true;
+            };
+
+            let position = skipped.iter().position(|ident| **ident == impl_name);
+            available.insert(impl_name);
+
+            match position {
+                Some(i) => {
+                    found[i] = true;
+                    false
+                }
+                None => true,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if let Some((ident, _)) = skipped.iter().zip(found).find(|(_, found)| !found) {
+        abort!(
+            ident,
+            "`{}` is not generated for `{}`",
+            ident,
+            name;
+            help = "expected one of: {}", available.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    let manifest = if cfg!(feature = "reflect") {
+        conversion_manifest(name, attr, &items)
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #(#items)*
+        #manifest
+    }
+}
+
+/// The traits listed in the conversion manifest of the `reflect` feature.
+const CONVERSION_TRAITS: [&str; 5] = ["From", "TryFrom", "FromStr", "Deref", "AsRef"];
+
+/// Generate `CONVERSIONS`, which lists the conversion traits among the implementations that were
+/// kept, for the descriptor of the `reflect` feature.
+fn conversion_manifest(name: &syn::Ident, attr: &AttrParams, items: &[syn::Item]) -> TokenStream {
+    let type_name = name.to_string();
+    let conversions = items.iter().filter_map(|item| {
+        let syn::Item::Impl(item) = item else {
+            return None;
+        };
+
+        let segment = item.trait_.as_ref()?.1.segments.last()?;
+        let trait_name = segment.ident.to_string();
+
+        if !CONVERSION_TRAITS.contains(&trait_name.as_str()) {
+            return None;
+        }
+
+        let self_ty = type_label(&item.self_ty);
+        let arg = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(type_label(ty)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let (from, to) = match trait_name.as_str() {
+            "FromStr" => ("&str".to_string(), self_ty),
+            "Deref" => {
+                let target = item.items.iter().find_map(|item| match item {
+                    syn::ImplItem::Type(ty) if ty.ident == "Target" => Some(type_label(&ty.ty)),
+                    _ => None,
+                })?;
+
+                (self_ty, target)
+            }
+            "AsRef" => (self_ty, arg?),
+            _ => (arg?, self_ty),
+        };
+
+        let may_panic = trait_name == "From" && to == type_name && !covers_primitive(attr, &from);
+
+        Some(quote! {
+            Conversion {
+                trait_name: #trait_name,
+                from: #from,
+                to: #to,
+                may_panic: #may_panic,
+            }
+        })
+    });
+
+    quote! {
+        const CONVERSIONS: &[Conversion] = &[#(#conversions),*];
+    }
+}
+
+/// Write a type the way it reads in source, without the spaces that token streams put around
+/// every token or the private path of the crates the generated code uses.
+fn type_label(ty: &syn::Type) -> String {
+    let mut label = ty
+        .to_token_stream()
+        .to_string()
+        .replace(":: checked_rs :: __private :: ", "");
+
+    for (from, to) in [
+        (" :: ", "::"),
+        (" < ", "<"),
+        (" >", ">"),
+        ("& ", "&"),
+        (" ,", ","),
+    ] {
+        label = label.replace(from, to);
+    }
+
+    label
+}
 
-use crate::params::{attr_params::AttrParams, BehaviorArg, NumberArg, NumberKind};
+/// Whether the limits include every value of the type named `primitive`, so that a `From`
+/// conversion from it cannot panic. `usize` is assumed to be as wide as `u64`, and types other
+/// than primitive integers, such as the `deranged` types whose bounds are checked at compile time,
+/// are always included.
+fn covers_primitive(attr: &AttrParams, primitive: &str) -> bool {
+    let max = match primitive {
+        "u8" => u8::MAX as u128,
+        "u16" => u16::MAX as u128,
+        "u32" => u32::MAX as u128,
+        "u64" | "usize" => u64::MAX as u128,
+        "u128" => u128::MAX,
+        _ => return !PRIMITIVE_INTEGERS.contains(&primitive),
+    };
+
+    let bits = |n: NumberValue| n.to_string().parse::<u128>().ok();
+
+    bits(attr.lower_limit_value()) == Some(0) && bits(attr.upper_limit_value()) >= Some(max)
+}
+
+/// Get the name that `skip_impls` refers to a trait implementation by.
+fn skippable_impl_name(item: &syn::Item) -> Option<String> {
+    let syn::Item::Impl(item) = item else {
+        return None;
+    };
+
+    if item.unsafety.is_some() {
+        return None;
+    }
+
+    let segment = item.trait_.as_ref()?.1.segments.last()?;
+
+    // the limits and behavior are what the unsafe traits rely on
+    if segment.ident == "InherentLimits" || segment.ident == "InherentBehavior" {
+        return None;
+    }
+
+    let is_primitive = |ty: &syn::Type| match ty {
+        syn::Type::Path(ty) => ty
+            .path
+            .get_ident()
+            .is_some_and(|ident| PRIMITIVE_INTEGERS.contains(&ident.to_string().as_str())),
+        _ => false,
+    };
+
+    let has_primitive_arg = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .any(|arg| matches!(arg, syn::GenericArgument::Type(ty) if is_primitive(ty))),
+        _ => false,
+    };
+
+    if is_primitive(&item.self_ty) || has_primitive_arg {
+        Some(format!("{}Primitive", segment.ident))
+    } else {
+        Some(segment.ident.to_string())
+    }
+}
+
+/// The names of `checked_rs::__private::codegen` that generated code may use, imported by
+/// `explicit_imports`.
+const CODEGEN_NAMES: &[&str] = &[
+    "AnyClamped",
+    "AsPrimitive",
+    "Behavior",
+    "BoundedValue",
+    "ClampError",
+    "ClampObserver",
+    "ClampSpec",
+    "ClampedEnum",
+    "ClampedInteger",
+    "CompactEncoding",
+    "DisplaySeparated",
+    "Described",
+    "DiffDisplay",
+    "DivByZero",
+    "Explanation",
+    "Frozen",
+    "GuardDropPolicy",
+    "HardClamp",
+    "InherentBehavior",
+    "InherentLimits",
+    "KindRanges",
+    "Panicking",
+    "ParseClampedError",
+    "PartitionedCounts",
+    "RangeOffset",
+    "RangeSummary",
+    "Rounding",
+    "Saturating",
+    "SaturatingWithLog",
+    "SoftClamp",
+    "StderrObserver",
+    "TransitionError",
+    "TransparentClamp",
+    "Unvalidated",
+    "VariantKind",
+    "VariantPath",
+    "View",
+    "WarnOnDrop",
+    "clamped",
+    "add_scalar_saturating",
+    "as_primitive_slice",
+    "checked_within",
+    "clamp_saturating",
+    "cyclic_add",
+    "cyclic_distance",
+    "cyclic_is_between",
+    "cyclic_lt",
+    "decode_compact",
+    "diff_display_of",
+    "encode_compact",
+    "is_canonical_integer",
+    "range_fraction",
+    "scale_between",
+    "scale_ratio",
+    "sub_scalar_saturating",
+    "try_clamp",
+    "try_clamp_op",
+    "try_from_primitive_slice",
+    "value_at_fraction",
+];
+
+/// The names of `checked_rs::__private::codegen` that generated code uses with the `fuzz` feature.
+const FUZZ_CODEGEN_NAMES: &[&str] = &["FuzzInput", "FuzzTarget"];
+
+/// The names of `checked_rs::__private::codegen` that generated code uses with the `metrics` feature.
+const METRICS_CODEGEN_NAMES: &[&str] = &["ClampMetrics", "Metered", "MetricsSource"];
+
+/// The names of `checked_rs::__private::codegen` that generated code uses with the `deranged` feature.
+const DERANGED_CODEGEN_NAMES: &[&str] = &["GenericForm"];
+
+/// The names of `checked_rs::__private::codegen` that generated code uses with the `reflect` feature.
+const REFLECT_CODEGEN_NAMES: &[&str] = &[
+    "ClampedTypeDescriptor",
+    "Conversion",
+    "DescriptorEntry",
+    "PrimitiveKind",
+    "Reflect",
+];
+
+/// The names of `checked_rs::__private::codegen` that generated code uses with the `testing` feature.
+const TESTING_CODEGEN_NAMES: &[&str] = &["FaultInjecting"];
+
+/// The names of `checked_rs::__private::codegen` that generated code uses with the `ui` feature.
+const UI_CODEGEN_NAMES: &[&str] = &["UiRange"];
+
+/// The primitive integer types, which give a trait implementation the `Primitive` suffix.
+const PRIMITIVE_INTEGERS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
 
 pub fn define_guard(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams) -> TokenStream {
     let integer = &attr.integer;
+    let auto_guard_name = attr.auto_guard_name(name);
+    let guard_drop = attr.guard_drop_policy();
+    let context = range_context(name, attr);
+
+    let mut preview_methods = Vec::new();
+
+    for op in ["add", "sub", "mul"] {
+        let checked = format_ident!("checked_{}", op);
+        let saturating = format_ident!("saturating_{}", op);
+        let preview = format_ident!("preview_{}", op);
+        let apply = format_ident!("apply_{}", op);
+        let behavior = attr.behavior_for(op);
+
+        preview_methods.push(quote! {
+            /// Compute the result of this operation on the staged value as the operator's behavior
+            /// would resolve it, without changing the staged value.
+            #[inline(always)]
+            pub fn #preview(&self, rhs: #integer) -> std::result::Result<#integer, ClampError<#integer>> {
+                <#behavior as Behavior>::resolve(try_clamp_op(
+                    self.0.#checked(rhs),
+                    self.0.#saturating(rhs),
+                    <#name as InherentLimits<#integer>>::MIN,
+                    <#name as InherentLimits<#integer>>::MAX,
+                ))
+            }
+
+            /// Stage the result of this operation if the operator's behavior resolves it to a valid value.
+            #[inline(always)]
+            pub fn #apply(&mut self, rhs: #integer) -> std::result::Result<#integer, ClampError<#integer>> {
+                let val = self.#preview(rhs)?;
+                self.0 = val;
+                Ok(val)
+            }
+        });
+    }
 
     quote! {
         pub struct #guard_name<'a>(#integer, &'a mut #name);
@@ -41,10 +390,7 @@ pub fn define_guard(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParam
 
         impl<'a> Drop for #guard_name<'a> {
             fn drop(&mut self) {
-                #[cfg(debug_assertions)]
-                {
-                    eprintln!("A `Guard` was dropped without calling `commit` or `discard` first");
-                }
+                <#guard_drop as GuardDropPolicy>::on_unfinished_drop(stringify!(#name));
             }
         }
 
@@ -63,21 +409,26 @@ pub fn define_guard(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParam
             }
 
             #[inline(always)]
-            pub fn check(&self) -> ::anyhow::Result<()> {
+            pub fn will_be_valid(&self) -> bool {
+                self.check().is_ok()
+            }
+
+            #[inline(always)]
+            pub fn check(&self) -> ::checked_rs::__private::anyhow::Result<()> {
                 #name::validate(self.0)?;
                 Ok(())
             }
 
             #[inline(always)]
-            pub fn commit(self) -> ::anyhow::Result<(), Self> {
+            pub fn commit(self) -> ::checked_rs::__private::anyhow::Result<(), Self> {
                 let mut this = std::mem::ManuallyDrop::new(self);
 
                 match this.check() {
-                    ::anyhow::Result::Ok(_) => {
+                    ::checked_rs::__private::anyhow::Result::Ok(_) => {
                         *this.1 = <#name as ClampedInteger<#integer>>::from_primitive(this.0).expect("value should be within bounds");
-                        ::anyhow::Result::Ok(())
+                        ::checked_rs::__private::anyhow::Result::Ok(())
                     }
-                    ::anyhow::Result::Err(_) => ::anyhow::Result::Err(std::mem::ManuallyDrop::into_inner(this)),
+                    ::checked_rs::__private::anyhow::Result::Err(_) => ::checked_rs::__private::anyhow::Result::Err(std::mem::ManuallyDrop::into_inner(this)),
                 }
             }
 
@@ -85,393 +436,2764 @@ pub fn define_guard(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParam
             pub fn discard(self) {
                 std::mem::forget(self);
             }
+
+            #(#preview_methods)*
         }
-    }
-}
 
-pub fn impl_deref(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
-    let integer = &attr.integer;
+        pub struct #auto_guard_name<'a>(#integer, &'a mut #name);
 
-    quote! {
-        impl std::ops::Deref for #name {
+        impl<'a> std::ops::Deref for #auto_guard_name<'a> {
             type Target = #integer;
 
             #[inline(always)]
             fn deref(&self) -> &Self::Target {
-                self.as_primitive()
+                &self.0
             }
         }
 
-        impl AsRef<#integer> for #name {
+        impl<'a> std::ops::DerefMut for #auto_guard_name<'a> {
             #[inline(always)]
-            fn as_ref(&self) -> &#integer {
-                self.as_primitive()
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
             }
         }
-    }
-}
 
-pub fn impl_conversions(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
-    let integer = &attr.integer;
-    let mut conversions = Vec::with_capacity(24);
+        impl<'a> AsRef<#integer> for #auto_guard_name<'a> {
+            #[inline(always)]
+            fn as_ref(&self) -> &#integer {
+                &self.0
+            }
+        }
 
-    if attr.is_u128_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for u128 {
-                #[inline(always)]
-                fn from(val: #name ) -> Self {
-                    val.into_primitive() as u128
-                }
+        impl<'a> AsMut<#integer> for #auto_guard_name<'a> {
+            #[inline(always)]
+            fn as_mut(&mut self) -> &mut #integer {
+                &mut self.0
             }
-        });
-    }
+        }
 
-    if matches!(attr.kind(), NumberKind::U128) {
-        conversions.push(quote! {
-            impl From<u128> for #name {
-                #[inline(always)]
-                fn from(val: u128) -> Self {
-                    Self::from_primitive(val).expect("value should be within bounds")
+        /// Commit the staged value, resolving an invalid one with the type's behavior. Nothing is
+        /// committed while the thread is already panicking.
+        impl<'a> Drop for #auto_guard_name<'a> {
+            fn drop(&mut self) {
+                if std::thread::panicking() {
+                    return;
                 }
+
+                let val = if #name::validate(self.0).is_ok() {
+                    self.0
+                } else {
+                    match <<#name as InherentBehavior>::Behavior as Behavior>::resolve(try_clamp(
+                        self.0,
+                        <#name as InherentLimits<#integer>>::MIN,
+                        <#name as InherentLimits<#integer>>::MAX,
+                    )) {
+                        Ok(val) => val,
+                        Err(e) => panic!("invalid value staged {}: {}", #context, e),
+                    }
+                };
+
+                *self.1 = <#name as ClampedInteger<#integer>>::from_primitive(val).expect("value should be within bounds");
             }
-        });
-    }
+        }
 
-    if attr.is_usize_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for usize {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as usize
-                }
+        impl<'a> #auto_guard_name<'a> {
+            #[inline(always)]
+            pub fn is_changed(&self) -> bool {
+                self.0 != self.1.into_primitive()
             }
-        });
-    }
 
-    if attr.is_usize_or_larger() {
-        conversions.push(quote! {
-            impl From<usize> for #name {
-                #[inline(always)]
-                fn from(val: usize) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
-                }
+            #[inline(always)]
+            pub fn will_be_valid(&self) -> bool {
+                #name::validate(self.0).is_ok()
             }
-        });
-    }
 
-    if attr.is_u64_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for u64 {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as u64
-                }
+            /// Drop the staged value without committing it.
+            #[inline(always)]
+            pub fn discard(self) {
+                std::mem::forget(self);
             }
-        });
-    }
+        }
 
-    if attr.is_u64_or_larger() {
-        conversions.push(quote! {
-            impl From<u64> for #name {
-                #[inline(always)]
-                fn from(val: u64) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
-                }
+        impl #name {
+            /// Get a guard that stages changes to the value and commits them when it is dropped.
+            /// An invalid staged value is resolved with the type's behavior, so it saturates to the
+            /// nearest limit or panics.
+            #[inline(always)]
+            pub fn modify_auto(&mut self) -> #auto_guard_name<'_> {
+                #auto_guard_name(self.into_primitive(), self)
             }
-        });
+        }
     }
+}
 
-    if attr.is_u32_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for u32 {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as u32
+/// Define the `const` constructor used by the literal macro and the `const` operations used by
+/// `clamped_const!`, along with the macro itself when the `literal` parameter is given.
+pub fn define_literal(
+    vis: &syn::Visibility,
+    name: &syn::Ident,
+    attr: &AttrParams,
+) -> (TokenStream, TokenStream) {
+    let integer = &attr.integer;
+    let context = range_context(name, attr);
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    let const_ops = [
+        ("const_add", "checked_add", "Add"),
+        ("const_sub", "checked_sub", "Subtract"),
+        ("const_mul", "checked_mul", "Multiply"),
+        ("const_div", "checked_div", "Divide"),
+        ("const_rem", "checked_rem", "Take the remainder of"),
+    ]
+    .into_iter()
+    .map(|(method, checked, verb)| {
+        let method = format_ident!("{}", method);
+        let checked = format_ident!("{}", checked);
+        let doc = format!(
+            "{} two values in a `const` context, failing to compile when the result is out of bounds.",
+            verb
+        );
+
+        quote! {
+            #[doc = #doc]
+            #[inline(always)]
+            pub const fn #method(self, rhs: Self) -> Self {
+                match self.0.#checked(rhs.0) {
+                    Some(value) => Self::new_const(value),
+                    None => panic!(concat!("arithmetic overflow or division by zero ", #context)),
                 }
             }
-        });
-    }
+        }
+    });
 
-    if attr.is_u32_or_larger() {
-        conversions.push(quote! {
-            impl From<u32> for #name {
-                #[inline(always)]
-                fn from(val: u32) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+    let new_const = quote! {
+        impl #name {
+            /// Create a new value in a `const` context, failing to compile when the value is out of bounds.
+            #[inline(always)]
+            pub const fn new_const(value: #integer) -> Self {
+                match value {
+                    #lower_limit..=#upper_limit => Self(value),
+                    _ => panic!(concat!("value is out of bounds ", #context)),
                 }
             }
-        });
-    }
 
-    if attr.is_u16_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for u16 {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as u16
+            #(#const_ops)*
+        }
+    };
+
+    // The macro is exported from the crate root under a name that is unique to the module of the
+    // type, and re-exported next to the type under the given name.
+    let literal_macro = match attr.literal_macro() {
+        Some((literal, modules)) => {
+            let exported = format_ident!(
+                "__clamped_literal_{}",
+                modules
+                    .iter()
+                    .chain([&literal])
+                    .map(|ident| ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("_")
+            );
+            let vis = match vis {
+                syn::Visibility::Inherited => quote!(pub(crate)),
+                vis => quote!(#vis),
+            };
+
+            quote! {
+                #[doc(hidden)]
+                #[macro_export]
+                macro_rules! #exported {
+                    ($value:literal) => {
+                        const { $crate #(::#modules)* ::#name::new_const($value) }
+                    };
                 }
+
+                #[allow(unused_imports)]
+                #vis use #exported as #literal;
             }
-        });
-    }
+        }
+        None => TokenStream::new(),
+    };
 
-    if attr.is_u16_or_larger() {
-        conversions.push(quote! {
-            impl From<u16> for #name {
-                #[inline(always)]
-                fn from(val: u16) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
-                }
+    (new_const, literal_macro)
+}
+
+pub fn impl_runtime_bounds(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let behavior = &attr.behavior_val;
+
+    quote! {
+        impl #name {
+            /// Get the limits of this type as a `ClampSpec`.
+            #[inline(always)]
+            pub fn runtime_spec() -> ClampSpec<#integer> {
+                ClampSpec::new(
+                    <Self as InherentLimits<#integer>>::MIN,
+                    <Self as InherentLimits<#integer>>::MAX,
+                )
+                .expect("limits should be ordered")
             }
-        });
-    }
 
-    if matches!(attr.kind(), NumberKind::U8) {
-        conversions.push(quote! {
-            impl From<#name> for u8 {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as u8
-                }
+            /// Move the value into a `BoundedValue` with limits that are only known at runtime.
+            #[inline(always)]
+            pub fn with_runtime_bounds(
+                self,
+                spec: ClampSpec<#integer>,
+            ) -> std::result::Result<BoundedValue<#integer, #behavior>, ClampError<#integer>> {
+                BoundedValue::new(self.into_primitive(), spec)
             }
-        });
+        }
     }
+}
 
-    if attr.is_i128_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for i128 {
-                #[inline(always)]
-                fn from(val: #name ) -> Self {
-                    val.into_primitive() as i128
-                }
+/// Implement scaling by a ratio, where a result outside of the limits is resolved with the behavior
+/// of the `mul` operator.
+pub fn impl_scale(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let behavior = metered_behavior(name, attr, attr.behavior_for("mul"));
+    let context = range_context(name, attr);
+
+    quote! {
+        impl #name {
+            /// Get `pct` percent of the value, rounding down. Percentages above 100 are allowed, and
+            /// a result outside of the limits saturates or panics like `*` does.
+            #[inline(always)]
+            pub fn percent_of(self, pct: impl Into<u32>) -> Self {
+                self.scale(pct.into(), 100, Rounding::Floor)
             }
-        });
-    }
 
-    if matches!(attr.kind(), NumberKind::U128) {
-        conversions.push(quote! {
-            impl From<u128> for #name {
-                #[inline(always)]
-                fn from(val: i128) -> Self {
-                    Self::from_primitive(val).expect("value should be within bounds")
+            /// Get the value as a percentage of `whole`, rounding down, such as the progress of a
+            /// task. Values above `whole` give `100`, and a `whole` of zero gives `0`.
+            pub fn as_percent_of(&self, whole: Self) -> u8 {
+                let part = self.into_primitive();
+                let whole = whole.into_primitive();
+
+                if whole == 0 {
+                    return 0;
                 }
+
+                scale_between(part.min(whole), 0, whole, 0u8, 100u8)
             }
-        });
-    }
 
-    if attr.is_isize_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for isize {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as isize
+            /// Multiply the value by `num / den`, rounding with `rounding`. The product is widened
+            /// so it never overflows, and a result outside of the limits saturates or panics like
+            /// `*` does.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `den` is zero.
+            pub fn scale(self, num: u32, den: u32, rounding: Rounding) -> Self {
+                let result = match scale_ratio(self.into_primitive() as u128, num, den, rounding)
+                    .and_then(|val| #integer::try_from(val).ok())
+                {
+                    Some(val) => try_clamp(
+                        val,
+                        <Self as InherentLimits<#integer>>::MIN,
+                        <Self as InherentLimits<#integer>>::MAX,
+                    ),
+                    None => Err(ClampError::TooLarge {
+                        val: #integer::MAX,
+                        max: <Self as InherentLimits<#integer>>::MAX,
+                    }),
+                };
+
+                match #behavior::resolve(result) {
+                    Ok(val) => Self::from_primitive(val).expect("value should be within the limits"),
+                    Err(e) => panic!("{} {}", e, #context),
                 }
             }
-        });
+        }
     }
+}
 
-    if attr.is_isize_or_larger() {
-        conversions.push(quote! {
-            impl From<usize> for #name {
-                #[inline(always)]
-                fn from(val: isize) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
-                }
+/// Generate the helpers that place the value within its limits as a fraction and map it onto the
+/// limits of another clamped type.
+pub fn impl_fraction(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+
+    quote! {
+        impl #name {
+            /// Get the value saturated to the limits, since soft values may lie outside of them.
+            #[inline(always)]
+            fn saturated_primitive(&self) -> #integer {
+                clamp_saturating(
+                    self.into_primitive(),
+                    <Self as InherentLimits<#integer>>::MIN,
+                    <Self as InherentLimits<#integer>>::MAX,
+                )
             }
-        });
-    }
 
-    if attr.is_i64_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for i64 {
+            /// Get the position of the value within the limits, from `0.0` at the lower limit to
+            /// `1.0` at the upper limit. Values outside of the limits are placed at the nearest one.
+            #[inline(always)]
+            pub fn fraction(&self) -> f64 {
+                range_fraction(
+                    self.saturated_primitive(),
+                    <Self as InherentLimits<#integer>>::MIN,
+                    <Self as InherentLimits<#integer>>::MAX,
+                )
+            }
+
+            /// Get the position of the value within the limits as a percentage.
+            #[inline(always)]
+            pub fn to_percent_of_range(&self) -> f64 {
+                self.fraction() * 100.0
+            }
+
+            /// Create the value at the given position within the limits, rounded to the nearest
+            /// value. Fractions outside of `0.0..=1.0` are clamped to the limits.
+            #[inline(always)]
+            pub fn from_fraction(fraction: f64) -> Self {
+                Self::from_primitive(value_at_fraction(
+                    fraction,
+                    <Self as InherentLimits<#integer>>::MIN,
+                    <Self as InherentLimits<#integer>>::MAX,
+                ))
+                .expect("value should be within the limits")
+            }
+
+            /// Map the value proportionally onto the limits of another clamped type, rounding
+            /// down. Every value within the limits of `U` must be valid for `U`.
+            #[inline(always)]
+            pub fn scale_to<U, P>(self) -> U
+            where
+                U: ClampedInteger<P> + InherentLimits<P>,
+                P: RangeOffset,
+            {
+                U::from_primitive(scale_between(
+                    self.saturated_primitive(),
+                    <Self as InherentLimits<#integer>>::MIN,
+                    <Self as InherentLimits<#integer>>::MAX,
+                    U::MIN,
+                    U::MAX,
+                ))
+                .expect("value should be within the limits")
+            }
+        }
+    }
+}
+
+/// Define the criterion benchmarks requested by the `benchmark` flag, along with the re-export
+/// that makes them reachable as `<type>_bench::benches`. Both are wrapped in `if_bench!`, so they
+/// are only compiled with the `bench` feature of `checked_rs`, whatever the features of the crate
+/// that defines the type.
+pub fn define_benchmark(
+    vis: &syn::Visibility,
+    name: &syn::Ident,
+    mod_name: &syn::Ident,
+    attr: &AttrParams,
+    item: &syn::Item,
+) -> (TokenStream, TokenStream) {
+    let Some(benchmark) = &attr.benchmark_kw else {
+        return (TokenStream::new(), TokenStream::new());
+    };
+
+    let (attrs, is_struct) = match item {
+        syn::Item::Struct(item) => (&item.attrs, true),
+        syn::Item::Enum(item) => (&item.attrs, false),
+        _ => unreachable!(),
+    };
+
+    if !has_derive(attrs, "Copy") {
+        abort!(
+            benchmark,
+            "The `benchmark` flag requires `{}` to derive `Copy`",
+            name
+        );
+    }
+
+    let integer = &attr.integer;
+    let values = DocExampleValues::new(attr);
+    let (lower, upper, default) = (values.lower, values.upper, values.default);
+    let one = NumberValue::U8(1).cast(attr.kind()).unwrap();
+    let zero = NumberValue::U8(0).cast(attr.kind()).unwrap();
+
+    // Step away from the limits so that the operations never leave them.
+    let add_rhs = if default < upper { one } else { zero };
+    let sub_rhs = if default > lower { one } else { zero };
+
+    let new_bench = if is_struct {
+        quote! {
+            group.bench_function("new", |b| b.iter(|| #name::new(black_box(#default))));
+            group.bench_function("new/baseline", |b| b.iter(|| black_box(#default)));
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let bench_mod_name = format_ident!("{}_bench", name.to_string().to_case(Case::Snake));
+
+    let module = quote! {
+        ::checked_rs::__private::if_bench! {
+            pub mod bench {
+                use super::*;
+                use std::hint::black_box;
+
+                /// Benchmark the hot operations of the type against the same operations on the
+                /// primitive, using values within its limits.
+                pub fn benches(c: &mut ::checked_rs::__private::criterion::Criterion) {
+                    let mut group = c.benchmark_group(stringify!(#name));
+
+                    #new_bench
+
+                    group.bench_function("from_primitive", |b| {
+                        b.iter(|| <#name as ClampedInteger<#integer>>::from_primitive(black_box(#default)))
+                    });
+                    group.bench_function("from_primitive/baseline", |b| {
+                        b.iter(|| (#lower..=#upper).contains(&black_box(#default)))
+                    });
+
+                    let val = <#name as ClampedInteger<#integer>>::from_primitive(#default)
+                        .expect("default should be within the limits");
+
+                    group.bench_function("add", |b| b.iter(|| black_box(val) + black_box(#add_rhs)));
+                    group.bench_function("add/baseline", |b| {
+                        b.iter(|| black_box(#default) + black_box(#add_rhs))
+                    });
+                    group.bench_function("sub", |b| b.iter(|| black_box(val) - black_box(#sub_rhs)));
+                    group.bench_function("sub/baseline", |b| {
+                        b.iter(|| black_box(#default) - black_box(#sub_rhs))
+                    });
+
+                    group.finish();
+                }
+            }
+        }
+    };
+
+    let reexport = quote! {
+        ::checked_rs::__private::if_bench! {
+            #vis use #mod_name::bench as #bench_mod_name;
+        }
+    };
+
+    (module, reexport)
+}
+
+/// The values used to build the generated doctests, taken from the actual parameters.
+pub struct DocExampleValues {
+    pub lower: NumberValue,
+    pub upper: NumberValue,
+    pub default: NumberValue,
+    /// The value just below the lower limit, if the integer type can represent it.
+    pub below: Option<NumberValue>,
+    /// The value just above the upper limit, if the integer type can represent it.
+    pub above: Option<NumberValue>,
+}
+
+impl DocExampleValues {
+    pub fn new(attr: &AttrParams) -> Self {
+        let kind = attr.kind();
+        let lower = attr.lower_limit_value();
+        let upper = attr.upper_limit_value();
+
+        Self {
+            lower,
+            upper,
+            default: attr.default_value(),
+            below: (lower > NumberArg::new_min_constant(kind).into_value(kind)).then(|| lower - 1),
+            above: (upper < NumberArg::new_max_constant(kind).into_value(kind)).then(|| upper + 1),
+        }
+    }
+}
+
+/// Wrap the lines of an example in a `# Examples` doc section when the `doc_examples` flag is
+/// given. The example declares an equivalent type in hidden lines so that it compiles as a
+/// standalone doctest.
+pub fn doc_example(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    lines: Vec<String>,
+) -> Vec<syn::Attribute> {
+    if attr.doc_examples_kw.is_none() {
+        return Vec::new();
+    }
+
+    let integer = attr.integer.to_token_stream().to_string();
+    let repr = match &attr.as_soft_or_hard {
+        Some(AsSoftOrHard::Soft { .. }) => " as Soft",
+        Some(AsSoftOrHard::Hard { .. }) => " as Hard",
+        None => "",
+    };
+    let behavior = attr.behavior_tokens().to_string();
+    let values = DocExampleValues::new(attr);
+
+    let mut doc = vec![
+        " # Examples".to_string(),
+        String::new(),
+        " ```".to_string(),
+        " # use checked_rs::prelude::*;".to_string(),
+        format!(
+            " # #[clamped({}{}, default = {}, behavior = {}, lower = {}, upper = {})]",
+            integer, repr, values.default, behavior, values.lower, values.upper
+        ),
+        " # #[derive(Clone, Copy)]".to_string(),
+        format!(" # struct {};", name),
+        " # fn main() -> checked_rs::__private::anyhow::Result<()> {".to_string(),
+    ];
+
+    doc.extend(lines.into_iter().map(|line| format!(" {}", line)));
+    doc.push(" # Ok(())".to_string());
+    doc.push(" # }".to_string());
+    doc.push(" ```".to_string());
+
+    doc.into_iter()
+        .map(|line| syn::parse_quote!(#[doc = #line]))
+        .collect()
+}
+
+/// Document how the guard returned by `modify` is used.
+pub fn doc_modify_example(name: &syn::Ident, attr: &AttrParams) -> Vec<syn::Attribute> {
+    let values = DocExampleValues::new(attr);
+
+    let mut lines = vec![
+        format!("let mut value = {}::default();", name),
+        "let mut guard = value.modify();".to_string(),
+        format!("*guard = {};", values.upper),
+    ];
+
+    if let Some(above) = values.above {
+        lines.push("assert!(guard.will_be_valid());".to_string());
+        lines.push(format!("*guard = {};", above));
+        lines.push("assert!(!guard.will_be_valid());".to_string());
+        lines.push(format!("*guard = {};", values.upper));
+    }
+
+    lines.push("assert!(guard.commit().is_ok());".to_string());
+    lines.push(format!("assert_eq!(*value, {});", values.upper));
+
+    doc_example(name, attr, lines)
+}
+
+/// Document how `new` treats values outside of the limits.
+pub fn doc_new_example(name: &syn::Ident, attr: &AttrParams) -> Vec<syn::Attribute> {
+    let values = DocExampleValues::new(attr);
+    let is_hard = matches!(attr.as_soft_or_hard, Some(AsSoftOrHard::Hard { .. }));
+    let is_saturating = attr.behavior_val.is_saturating();
+
+    let mut lines = vec![format!(
+        "assert_eq!(*{}::new({}), {});",
+        name, values.default, values.default
+    )];
+
+    for (outside, limit) in [(values.below, values.lower), (values.above, values.upper)] {
+        let Some(outside) = outside else {
+            continue;
+        };
+
+        if !is_hard {
+            lines.push(format!("assert!(!{}::new({}).is_valid());", name, outside));
+        } else if is_saturating {
+            lines.push(format!(
+                "assert_eq!(*{}::new({}), {});",
+                name, outside, limit
+            ));
+        } else {
+            lines.push(format!(
+                "assert!({}::from_primitive({}).is_err());",
+                name, outside
+            ));
+        }
+    }
+
+    doc_example(name, attr, lines)
+}
+
+/// Document how the arithmetic operators resolve results with the type's behavior.
+pub fn doc_ops_example(name: &syn::Ident, attr: &AttrParams) -> Vec<syn::Attribute> {
+    let values = DocExampleValues::new(attr);
+    let is_hard = matches!(attr.as_soft_or_hard, Some(AsSoftOrHard::Hard { .. }));
+
+    let mut lines = vec![format!(
+        "assert_eq!(*({}::default() + 0), {});",
+        name, values.default
+    )];
+
+    if is_hard {
+        match attr.behavior_for("add") {
+            BehaviorArg::Saturating(..) | BehaviorArg::SaturatingWithLog(..) => {
+                lines.push(format!(
+                    "assert_eq!(*({}::new({}) + {}), {});",
+                    name, values.upper, values.upper, values.upper
+                ))
+            }
+            BehaviorArg::Panicking(..) if values.above.is_some() => lines.push(format!(
+                "assert!(std::panic::catch_unwind(|| {}::new({}) + 1).is_err());",
+                name, values.upper
+            )),
+            BehaviorArg::Panicking(..) => {}
+        }
+    }
+
+    doc_example(name, attr, lines)
+}
+
+/// Generate conversions to and from the `deranged` type with the same bounds when the `deranged`
+/// feature is enabled, along with `GenericForm` for the types that always hold a valid value.
+/// Converting from a `deranged` type with different bounds fails to compile.
+pub fn impl_deranged(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if !cfg!(feature = "deranged") {
+        return TokenStream::new();
+    }
+
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    let ranged = format_ident!(
+        "{}",
+        match attr.kind() {
+            NumberKind::U8 => "RangedU8",
+            NumberKind::U16 => "RangedU16",
+            NumberKind::U32 => "RangedU32",
+            NumberKind::U64 => "RangedU64",
+            NumberKind::U128 => "RangedU128",
+            NumberKind::USize => "RangedUsize",
+            NumberKind::I8 => "RangedI8",
+            NumberKind::I16 => "RangedI16",
+            NumberKind::I32 => "RangedI32",
+            NumberKind::I64 => "RangedI64",
+            NumberKind::I128 => "RangedI128",
+            NumberKind::ISize => "RangedIsize",
+        }
+    );
+    let ranged = quote!(::checked_rs::__private::deranged::#ranged);
+
+    let is_soft = matches!(attr.as_soft_or_hard, Some(AsSoftOrHard::Soft { .. }));
+
+    let generic_form = (!is_soft).then(|| {
+        quote! {
+            impl GenericForm for #name {
+                type Generic = #ranged<#lower_limit, #upper_limit>;
+
+                #[inline(always)]
+                fn into_generic(self) -> Self::Generic {
+                    self.into()
+                }
+
+                #[inline(always)]
+                fn from_generic(value: Self::Generic) -> Self {
+                    value.into()
+                }
+            }
+        }
+    });
+
+    let conversions = impl_bounded_conversions(name, attr, &ranged, "deranged");
+
+    quote! {
+        #conversions
+
+        #generic_form
+    }
+}
+
+/// Generate conversions to and from the `bounded-integer` type with the same bounds when the
+/// `bounded-integer` feature is enabled. Converting from a `bounded-integer` type with different
+/// bounds fails to compile.
+pub fn impl_bounded_integer(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if !cfg!(feature = "bounded-integer") {
+        return TokenStream::new();
+    }
+
+    let bounded = format_ident!(
+        "{}",
+        match attr.kind() {
+            NumberKind::U8 => "BoundedU8",
+            NumberKind::U16 => "BoundedU16",
+            NumberKind::U32 => "BoundedU32",
+            NumberKind::U64 => "BoundedU64",
+            NumberKind::U128 => "BoundedU128",
+            NumberKind::USize => "BoundedUsize",
+            NumberKind::I8 => "BoundedI8",
+            NumberKind::I16 => "BoundedI16",
+            NumberKind::I32 => "BoundedI32",
+            NumberKind::I64 => "BoundedI64",
+            NumberKind::I128 => "BoundedI128",
+            NumberKind::ISize => "BoundedIsize",
+        }
+    );
+
+    impl_bounded_conversions(
+        name,
+        attr,
+        &quote!(::checked_rs::__private::bounded_integer::#bounded),
+        "bounded-integer",
+    )
+}
+
+/// Generate `From`/`TryFrom` conversions between the type and a const generic bounded integer
+/// type with `new(value) -> Option<Self>` and `get()`, asserting that the bounds match.
+fn impl_bounded_conversions(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    ranged: &TokenStream,
+    crate_name: &str,
+) -> TokenStream {
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    let assert_bounds = quote! {
+        const {
+            assert!(
+                MIN == #lower_limit && MAX == #upper_limit,
+                concat!("the bounds of the `", #crate_name, "` type must match those of `", stringify!(#name), "`"),
+            )
+        };
+    };
+
+    let is_soft = matches!(attr.as_soft_or_hard, Some(AsSoftOrHard::Soft { .. }));
+
+    let into_ranged = if is_soft {
+        quote! {
+            impl<const MIN: #integer, const MAX: #integer> TryFrom<#name> for #ranged<MIN, MAX> {
+                type Error = ClampError<#integer>;
+
+                #[inline(always)]
+                fn try_from(value: #name) -> std::result::Result<Self, Self::Error> {
+                    #assert_bounds
+                    let value = #name::validate(value.into_primitive())?;
+                    Ok(Self::new(value).expect("value should be within bounds"))
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl<const MIN: #integer, const MAX: #integer> From<#name> for #ranged<MIN, MAX> {
+                #[inline(always)]
+                fn from(value: #name) -> Self {
+                    #assert_bounds
+                    Self::new(value.into_primitive()).expect("value should be within bounds")
+                }
+            }
+        }
+    };
+
+    quote! {
+        impl<const MIN: #integer, const MAX: #integer> From<#ranged<MIN, MAX>> for #name {
+            #[inline(always)]
+            fn from(value: #ranged<MIN, MAX>) -> Self {
+                #assert_bounds
+                <Self as ClampedInteger<#integer>>::from_primitive(value.get())
+                    .expect("value should be within bounds")
+            }
+        }
+
+        #into_ranged
+    }
+}
+
+/// Implement the `bytemuck` traits derived on a struct when the `bytemuck` feature is enabled,
+/// making it `#[repr(transparent)]` so that their safety requirements hold. Deriving a `bytemuck`
+/// trait that the limits make unsound is rejected, and nothing is generated for a struct that
+/// derives none of them.
+pub fn impl_bytemuck(name: &syn::Ident, attr: &AttrParams, item: &mut syn::Item) -> TokenStream {
+    let data = match item {
+        syn::Item::Struct(data) => data,
+        syn::Item::Enum(data) => {
+            for derive_name in BYTEMUCK_DERIVES {
+                if let Some(path) = take_derive(&mut data.attrs, derive_name) {
+                    abort!(path, "`{}` is not supported on clamped enums", derive_name);
+                }
+            }
+
+            return TokenStream::new();
+        }
+        _ => unreachable!(),
+    };
+
+    let kind = attr.kind();
+    let lower = attr.lower_limit_value();
+    let upper = attr.upper_limit_value();
+    let zero = NumberValue::U8(0).cast(kind).unwrap();
+    let is_soft = matches!(attr.as_soft_or_hard, Some(AsSoftOrHard::Soft { .. }));
+
+    // Soft values may hold anything, so every bit pattern is sound for them.
+    let all_valid = is_soft
+        || (lower == NumberArg::new_min_constant(kind).into_value(kind)
+            && upper == NumberArg::new_max_constant(kind).into_value(kind));
+    let zero_valid = is_soft || (lower <= zero && zero <= upper);
+
+    let mut derived = Vec::new();
+
+    for derive_name in BYTEMUCK_DERIVES {
+        let Some(path) = take_derive(&mut data.attrs, derive_name) else {
+            continue;
+        };
+
+        match derive_name {
+            "Zeroable" if !zero_valid => abort!(
+                path,
+                "`{}` cannot be `Zeroable` because zero is outside of {}..={}",
+                name,
+                lower,
+                upper
+            ),
+            "Pod" | "AnyBitPattern" | "TransparentWrapper" if !all_valid => abort!(
+                path,
+                "`{}` cannot be `{}` because values outside of {}..={} are not allowed",
+                name,
+                derive_name,
+                lower,
+                upper;
+                help = "Derive `CheckedBitPattern` instead, which checks each value against the limits"
+            ),
+            _ => {}
+        }
+
+        derived.push((derive_name, path));
+    }
+
+    if derived.is_empty() {
+        return TokenStream::new();
+    }
+
+    if !cfg!(feature = "bytemuck") {
+        // leave the derives in place, since they are sound, and put them first so that their
+        // helper attributes are introduced before they are used
+        let paths = derived.iter().map(|(_, path)| path);
+        data.attrs
+            .insert(0, syn::parse_quote!(#[derive(#(#paths),*)]));
+
+        return TokenStream::new();
+    }
+
+    // The derives are replaced by the impls below, so their helper attributes would be unknown.
+    data.attrs.retain(|attr| {
+        !BYTEMUCK_HELPER_ATTRS
+            .iter()
+            .any(|helper| attr.path().is_ident(helper))
+    });
+
+    ensure_transparent(&mut data.attrs);
+
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    let mut output = quote! {
+        const _: () = assert!(
+            std::mem::size_of::<#name>() == std::mem::size_of::<#integer>()
+                && std::mem::align_of::<#name>() == std::mem::align_of::<#integer>()
+        );
+    };
+
+    // The supertraits, such as `Copy` for `Pod`, are left for the compiler to require.
+    for (derive_name, _) in derived {
+        output.extend(match derive_name {
+            "Zeroable" => quote! {
+                unsafe impl ::checked_rs::__private::bytemuck::Zeroable for #name {}
+            },
+            "Pod" => quote! {
+                unsafe impl ::checked_rs::__private::bytemuck::Pod for #name {}
+            },
+            "AnyBitPattern" => quote! {
+                unsafe impl ::checked_rs::__private::bytemuck::AnyBitPattern for #name {}
+            },
+            "TransparentWrapper" => quote! {
+                unsafe impl ::checked_rs::__private::bytemuck::TransparentWrapper<#integer> for #name {}
+            },
+            "NoUninit" => quote! {
+                unsafe impl ::checked_rs::__private::bytemuck::NoUninit for #name {}
+            },
+            "CheckedBitPattern" => quote! {
+                unsafe impl ::checked_rs::__private::bytemuck::CheckedBitPattern for #name {
+                    type Bits = #integer;
+
+                    #[inline(always)]
+                    fn is_valid_bit_pattern(bits: &#integer) -> bool {
+                        (#lower_limit..=#upper_limit).contains(bits)
+                    }
+                }
+            },
+            _ => unreachable!(),
+        });
+    }
+
+    output
+}
+
+/// Mark a struct as `#[repr(transparent)]` over its primitive, unless it already is.
+fn ensure_transparent(attrs: &mut Vec<syn::Attribute>) {
+    let is_transparent = attrs.iter().any(|attr| {
+        attr.path().is_ident("repr")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|repr| repr == "transparent")
+    });
+
+    if !is_transparent {
+        attrs.push(syn::parse_quote!(#[repr(transparent)]));
+    }
+}
+
+/// Implement `TransparentClamp` for a hard struct along with wrappers for the batch operations,
+/// which work on a whole slice of values through their primitives.
+pub fn impl_batch(name: &syn::Ident, attr: &AttrParams, item: &mut syn::Item) -> TokenStream {
+    let syn::Item::Struct(data) = item else {
+        return TokenStream::new();
+    };
+
+    ensure_transparent(&mut data.attrs);
+
+    let integer = &attr.integer;
+
+    quote! {
+        const _: () = assert!(
+            std::mem::size_of::<#name>() == std::mem::size_of::<#integer>()
+                && std::mem::align_of::<#name>() == std::mem::align_of::<#integer>()
+        );
+
+        unsafe impl TransparentClamp<#integer> for #name {}
+
+        impl #name {
+            /// Add `delta` to every value in the slice, saturating at the upper limit.
+            #[inline(always)]
+            pub fn add_scalar_saturating(items: &mut [Self], delta: #integer) {
+                add_scalar_saturating(items, delta)
+            }
+
+            /// Subtract `delta` from every value in the slice, saturating at the lower limit.
+            #[inline(always)]
+            pub fn sub_scalar_saturating(items: &mut [Self], delta: #integer) {
+                sub_scalar_saturating(items, delta)
+            }
+
+            /// View a slice of values as their primitives without copying them.
+            #[inline(always)]
+            pub fn as_primitive_slice(items: &[Self]) -> &[#integer] {
+                as_primitive_slice(items)
+            }
+
+            /// View a slice of primitives as values without copying them, failing with the
+            /// position of the first one outside of the limits.
+            #[inline(always)]
+            pub fn try_from_primitive_slice(
+                values: &[#integer],
+            ) -> ::checked_rs::__private::anyhow::Result<&[Self]> {
+                try_from_primitive_slice(values)
+            }
+        }
+    }
+}
+
+/// The `bytemuck` traits that are checked against the limits when derived.
+const BYTEMUCK_DERIVES: [&str; 6] = [
+    "Zeroable",
+    "Pod",
+    "AnyBitPattern",
+    "TransparentWrapper",
+    "NoUninit",
+    "CheckedBitPattern",
+];
+
+/// The helper attributes introduced by the `bytemuck` derives.
+const BYTEMUCK_HELPER_ATTRS: [&str; 2] = ["transparent", "zeroable"];
+
+pub fn impl_separated(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let separator = match &attr.separator_val {
+        Some(separator) => separator.value(),
+        None => ",".to_string(),
+    };
+
+    quote! {
+        impl #name {
+            /// Display the value with a separator between every group of three digits.
+            #[inline(always)]
+            pub fn separated(&self) -> DisplaySeparated<#integer> {
+                DisplaySeparated::new(self.into_primitive(), #separator)
+            }
+        }
+    }
+}
+
+pub fn impl_deref(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+
+    quote! {
+        impl std::ops::Deref for #name {
+            type Target = #integer;
+
+            #[inline(always)]
+            fn deref(&self) -> &Self::Target {
+                self.as_primitive()
+            }
+        }
+
+        impl AsRef<#integer> for #name {
+            #[inline(always)]
+            fn as_ref(&self) -> &#integer {
+                self.as_primitive()
+            }
+        }
+    }
+}
+
+/// Generate the conversions to and from `char` for `u32` types whose limits are within the
+/// Unicode scalar values. Converting into `char` cannot fail when every value the type can hold is
+/// a scalar value, which is not the case when the limits span the surrogates `0xD800..=0xDFFF` or
+/// when `in_limits` is false because the type may hold values outside of its limits.
+pub fn impl_char_conversions(name: &syn::Ident, attr: &AttrParams, in_limits: bool) -> TokenStream {
+    if !matches!(attr.kind(), NumberKind::U32) {
+        return TokenStream::new();
+    }
+
+    let lower = attr.lower_limit_value().to_string().parse::<u32>().unwrap();
+    let upper = attr.upper_limit_value().to_string().parse::<u32>().unwrap();
+
+    if upper > char::MAX as u32 {
+        return TokenStream::new();
+    }
+
+    let spans_surrogates = lower <= 0xDFFF && 0xD800 <= upper;
+
+    let into_char = if in_limits && !spans_surrogates {
+        quote! {
+            impl From<#name> for char {
                 #[inline(always)]
                 fn from(val: #name) -> Self {
-                    val.into_primitive() as i64
+                    char::from_u32(val.into_primitive()).expect("value should be a scalar value")
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl TryFrom<#name> for char {
+                type Error = std::char::CharTryFromError;
+
+                #[inline(always)]
+                fn try_from(val: #name) -> std::result::Result<Self, Self::Error> {
+                    char::try_from(val.into_primitive())
+                }
+            }
+        }
+    };
+
+    quote! {
+        #into_char
+
+        impl TryFrom<char> for #name {
+            type Error = ::checked_rs::__private::anyhow::Error;
+
+            #[inline(always)]
+            fn try_from(c: char) -> ::checked_rs::__private::anyhow::Result<Self> {
+                Self::from_primitive(c as u32)
+            }
+        }
+    }
+}
+
+pub fn impl_conversions(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let allowed = allowed_summary(&[(attr.lower_limit_value(), attr.upper_limit_value())]);
+    let mut conversions = Vec::with_capacity(24);
+
+    if attr.is_u128_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for u128 {
+                #[inline(always)]
+                fn from(val: #name ) -> Self {
+                    val.into_primitive() as u128
                 }
             }
         });
     }
 
-    if attr.is_i64_or_larger() {
+    if matches!(attr.kind(), NumberKind::U128) {
         conversions.push(quote! {
-            impl From<u64> for #name {
+            impl From<u128> for #name {
                 #[inline(always)]
-                fn from(val: i64) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                fn from(val: u128) -> Self {
+                    Self::from_primitive(val).expect("value should be within bounds")
                 }
             }
         });
     }
 
-    if attr.is_i32_or_smaller() {
+    if attr.is_usize_or_smaller() {
         conversions.push(quote! {
-            impl From<#name> for i32 {
+            impl From<#name> for usize {
                 #[inline(always)]
                 fn from(val: #name) -> Self {
-                    val.into_primitive() as i32
+                    val.into_primitive() as usize
+                }
+            }
+        });
+    }
+
+    if attr.is_usize_or_larger() {
+        conversions.push(quote! {
+            impl From<usize> for #name {
+                #[inline(always)]
+                fn from(val: usize) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
                 }
             }
         });
     }
 
-    if attr.is_i32_or_larger() {
-        conversions.push(quote! {
-            impl From<u32> for #name {
-                #[inline(always)]
-                fn from(val: i32) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
-                }
+    if attr.is_u64_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for u64 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as u64
+                }
+            }
+        });
+    }
+
+    if attr.is_u64_or_larger() {
+        conversions.push(quote! {
+            impl From<u64> for #name {
+                #[inline(always)]
+                fn from(val: u64) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if attr.is_u32_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for u32 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as u32
+                }
+            }
+        });
+    }
+
+    if attr.is_u32_or_larger() {
+        conversions.push(quote! {
+            impl From<u32> for #name {
+                #[inline(always)]
+                fn from(val: u32) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if attr.is_u16_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for u16 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as u16
+                }
+            }
+        });
+    }
+
+    if attr.is_u16_or_larger() {
+        conversions.push(quote! {
+            impl From<u16> for #name {
+                #[inline(always)]
+                fn from(val: u16) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if matches!(attr.kind(), NumberKind::U8) {
+        conversions.push(quote! {
+            impl From<#name> for u8 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as u8
+                }
+            }
+        });
+    }
+
+    if attr.is_i128_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for i128 {
+                #[inline(always)]
+                fn from(val: #name ) -> Self {
+                    val.into_primitive() as i128
+                }
+            }
+        });
+    }
+
+    if matches!(attr.kind(), NumberKind::I128) {
+        conversions.push(quote! {
+            impl From<i128> for #name {
+                #[inline(always)]
+                fn from(val: i128) -> Self {
+                    Self::from_primitive(val).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if attr.is_isize_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for isize {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as isize
+                }
+            }
+        });
+    }
+
+    if attr.is_isize_or_larger() {
+        conversions.push(quote! {
+            impl From<usize> for #name {
+                #[inline(always)]
+                fn from(val: isize) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if attr.is_i64_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for i64 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as i64
+                }
+            }
+        });
+    }
+
+    if attr.is_i64_or_larger() {
+        conversions.push(quote! {
+            impl From<u64> for #name {
+                #[inline(always)]
+                fn from(val: i64) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if attr.is_i32_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for i32 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as i32
+                }
+            }
+        });
+    }
+
+    if attr.is_i32_or_larger() {
+        conversions.push(quote! {
+            impl From<u32> for #name {
+                #[inline(always)]
+                fn from(val: i32) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if attr.is_i16_or_smaller() {
+        conversions.push(quote! {
+            impl From<#name> for i16 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as i16
+                }
+            }
+        });
+    }
+
+    if attr.is_i16_or_larger() {
+        conversions.push(quote! {
+            impl From<u16> for #name {
+                #[inline(always)]
+                fn from(val: i16) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    if matches!(attr.kind(), NumberKind::I8) {
+        conversions.push(quote! {
+            impl From<#name> for i8 {
+                #[inline(always)]
+                fn from(val: #name) -> Self {
+                    val.into_primitive() as i8
+                }
+            }
+        });
+    }
+
+    if attr.is_signed() {
+        conversions.push(quote! {
+            impl From<i8> for #name {
+                #[inline(always)]
+                fn from(val: i8) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    } else {
+        conversions.push(quote! {
+            impl From<u8> for #name {
+                #[inline(always)]
+                fn from(val: u8) -> Self {
+                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+                }
+            }
+        });
+    }
+
+    let compact = if matches!(attr.kind(), NumberKind::U128) {
+        quote! {
+            impl #name {
+                /// Format the value with a compact encoding, which is shorter than base 10 and safe
+                /// in URLs.
+                #[inline(always)]
+                pub fn to_compact_string(&self, encoding: CompactEncoding) -> String {
+                    encode_compact(self.into_primitive(), encoding)
+                }
+
+                /// Parse a value written by `to_compact_string` with the same encoding.
+                pub fn from_compact_str(
+                    s: &str,
+                    encoding: CompactEncoding,
+                ) -> std::result::Result<Self, ParseClampedError> {
+                    decode_compact(s, encoding)
+                        .and_then(|n| Self::from_primitive(n).map_err(|e| e.root_cause().to_string()))
+                        .map_err(|reason| ParseClampedError {
+                            type_name: stringify!(#name),
+                            input: s.to_string(),
+                            reason,
+                            allowed: #allowed,
+                        })
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // Each parses on its own, so that the traits can be left out with `skip_impls`.
+    let parse_str = quote! {
+        s.parse::<#integer>()
+            .map_err(|e| e.to_string())
+            .and_then(|n| Self::from_primitive(n).map_err(|e| e.root_cause().to_string()))
+            .map_err(|reason| ParseClampedError {
+                type_name: stringify!(#name),
+                input: s.to_string(),
+                reason,
+                allowed: #allowed,
+            })
+    };
+
+    quote! {
+        #(#conversions)*
+
+        impl std::str::FromStr for #name {
+            type Err = ParseClampedError;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                #parse_str
+            }
+        }
+
+        impl TryFrom<&str> for #name {
+            type Error = ParseClampedError;
+
+            fn try_from(s: &str) -> std::result::Result<Self, Self::Error> {
+                #parse_str
+            }
+        }
+
+        impl #name {
+            /// Format the value as a base 10 integer with no separators, which is the same on
+            /// every platform and locale and always parses back with `from_canonical_str`.
+            #[inline(always)]
+            pub fn to_canonical_string(&self) -> String {
+                self.into_primitive().to_string()
+            }
+
+            /// Parse a value written by `to_canonical_string`. Unlike `FromStr`, anything that is
+            /// not in canonical form, such as `+5`, `05` or `-0`, is rejected.
+            pub fn from_canonical_str(s: &str) -> std::result::Result<Self, ParseClampedError> {
+                if !is_canonical_integer(s) {
+                    return Err(ParseClampedError {
+                        type_name: stringify!(#name),
+                        input: s.to_string(),
+                        reason: "not in canonical form".to_string(),
+                        allowed: #allowed,
+                    });
+                }
+
+                #parse_str
+            }
+
+            /// Convert the value into another clamped type, which may store a different primitive,
+            /// failing if `U` does not allow it. The error holds the value and the limits of `U`
+            /// widened to `u128`, since the value may not fit in the primitive of `U`.
+            pub fn cast<U, P>(self) -> std::result::Result<U, ClampError<u128>>
+            where
+                U: ClampedInteger<P>,
+                P: Copy + TryFrom<u128>,
+                u128: TryFrom<P>,
+            {
+                let widen = |val: P| {
+                    u128::try_from(val).unwrap_or_else(|_| unreachable!("limits should be unsigned"))
+                };
+                let val = try_clamp(self.into_primitive() as u128, widen(U::MIN), widen(U::MAX))?;
+                let val = P::try_from(val)
+                    .unwrap_or_else(|_| unreachable!("value should fit within the limits"));
+
+                Ok(U::from_primitive(val).expect("value should be within the limits"))
+            }
+        }
+
+        #compact
+    }
+}
+
+/// Generate a test that every valid value survives a round-trip through its canonical string when
+/// the `roundtrip-tests` feature is enabled.
+pub fn impl_canonical_round_trip(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if !cfg!(feature = "roundtrip-tests") {
+        return TokenStream::new();
+    }
+
+    let integer = &attr.integer;
+    let lower = attr.lower_limit_value();
+    let upper = attr.upper_limit_value();
+
+    quote! {
+        #[cfg(test)]
+        mod canonical_round_trip {
+            use super::*;
+
+            #[test]
+            fn canonical_round_trip() {
+                let samples = [#lower, #upper, #name::default().into_primitive()]
+                    .into_iter()
+                    .chain((0..1024).map(|_| ::checked_rs::__private::rand::random::<#integer>()));
+
+                for n in samples {
+                    if let Ok(value) = #name::from_primitive(n) {
+                        let s = value.to_canonical_string();
+                        let parsed = #name::from_canonical_str(&s)
+                            .unwrap_or_else(|e| panic!("`{}` did not round-trip: {}", s, e));
+
+                        assert_eq!(parsed.into_primitive(), n);
+                        assert_eq!(parsed.to_canonical_string(), s);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The most values or ranges listed when summarizing the allowed values of a type.
+const MAX_ALLOWED_SUMMARY_ITEMS: usize = 8;
+
+/// Summarize the values within the limits, such as `1024..=65535`.
+pub fn range_summary(attr: &AttrParams) -> String {
+    let lower = attr.lower_limit_value();
+    let upper = attr.upper_limit_value();
+
+    if lower == upper {
+        lower.to_string()
+    } else {
+        format!("{}..={}", lower, upper)
+    }
+}
+
+/// The text that generated panic and error messages end with, such as
+/// "for `Port` (valid: 1024..=65535)".
+pub fn range_context(name: &syn::Ident, attr: &AttrParams) -> String {
+    format!("for `{}` (valid: {})", name, range_summary(attr))
+}
+
+/// Generate `RANGE_SUMMARY` and the `RangeSummary` implementation that `Described` reads.
+pub fn impl_range_summary(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let summary = range_summary(attr);
+
+    quote! {
+        impl #name {
+            /// The values the type allows, as shown in its panic and error messages.
+            pub const RANGE_SUMMARY: &'static str = #summary;
+        }
+
+        impl RangeSummary for #name {
+            const TYPE_NAME: &'static str = stringify!(#name);
+            const RANGE_SUMMARY: &'static str = #summary;
+        }
+    }
+}
+
+/// Generate the `AnyClamped` implementation, which replaces the value through `from_primitive` so
+/// that it works the same for structs and enums.
+pub fn impl_any_clamped(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let context = range_context(name, attr);
+
+    quote! {
+        impl AnyClamped for #name {
+            #[inline(always)]
+            fn type_name(&self) -> &'static str {
+                stringify!(#name)
+            }
+
+            #[inline(always)]
+            fn range_summary(&self) -> &'static str {
+                Self::RANGE_SUMMARY
+            }
+
+            #[inline(always)]
+            fn as_i128(&self) -> Option<i128> {
+                i128::try_from(self.into_primitive()).ok()
+            }
+
+            #[inline(always)]
+            fn as_u128(&self) -> Option<u128> {
+                u128::try_from(self.into_primitive()).ok()
+            }
+
+            fn set_i128(&mut self, value: i128) -> ::checked_rs::__private::anyhow::Result<()> {
+                let val = #integer::try_from(value)
+                    .ok()
+                    .and_then(|val| {
+                        try_clamp(
+                            val,
+                            <Self as InherentLimits<#integer>>::MIN,
+                            <Self as InherentLimits<#integer>>::MAX,
+                        )
+                        .ok()
+                    })
+                    .ok_or_else(|| {
+                        ::checked_rs::__private::anyhow::anyhow!("invalid value {} {}", value, #context)
+                    })?;
+
+                *self = Self::from_primitive(val)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Summarize the allowed values for error messages, listing at most `MAX_ALLOWED_SUMMARY_ITEMS`
+/// values or ranges before truncating.
+pub fn allowed_summary(ranges: &[(NumberValue, NumberValue)]) -> String {
+    let mut items = ranges
+        .iter()
+        .take(MAX_ALLOWED_SUMMARY_ITEMS)
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}..={}", start, end)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if ranges.len() > MAX_ALLOWED_SUMMARY_ITEMS {
+        items.push(format!(
+            "and {} more",
+            ranges.len() - MAX_ALLOWED_SUMMARY_ITEMS
+        ));
+    }
+
+    match ranges {
+        [(start, end)] if start != end => format!("a value in {}", items[0]),
+        [_] => items[0].clone(),
+        _ => format!("one of {}", items.join(", ")),
+    }
+}
+
+/// Check whether the item's attributes derive `derive_name`.
+pub fn has_derive(attrs: &[syn::Attribute], derive_name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| {
+            path.segments
+                .last()
+                .is_some_and(|segment| segment.ident == derive_name)
+        })
+}
+
+/// Remove the derive named `derive_name` from the item's attributes, returning the path that was removed.
+pub fn take_derive(attrs: &mut Vec<syn::Attribute>, derive_name: &str) -> Option<syn::Path> {
+    let mut taken = None;
+
+    attrs.retain_mut(|attr| {
+        if !attr.path().is_ident("derive") {
+            return true;
+        }
+
+        let paths = match attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        {
+            Ok(paths) => paths,
+            Err(e) => abort!(attr, e.to_string()),
+        };
+
+        let (removed, kept): (Vec<_>, Vec<_>) = paths.into_iter().partition(|path| {
+            path.segments
+                .last()
+                .is_some_and(|segment| segment.ident == derive_name)
+        });
+
+        if removed.is_empty() {
+            return true;
+        }
+
+        taken = removed.into_iter().next();
+
+        if kept.is_empty() {
+            return false;
+        }
+
+        *attr = syn::parse_quote!(#[derive(#(#kept),*)]);
+        true
+    });
+
+    taken
+}
+
+/// Generate the implementations for a `secret` type. The derived `Debug` is replaced with one that
+/// redacts the value, deriving `Serialize` is rejected, and `zeroize::Zeroize` is implemented when
+/// the `zeroize` feature is enabled.
+pub fn impl_secret(name: &syn::Ident, attr: &AttrParams, item: &mut syn::Item) -> TokenStream {
+    if attr.secret_kw.is_none() {
+        return TokenStream::new();
+    }
+
+    let attrs = match item {
+        syn::Item::Struct(item) => &mut item.attrs,
+        syn::Item::Enum(item) => &mut item.attrs,
+        _ => unreachable!(),
+    };
+
+    if let Some(path) = take_derive(attrs, "Serialize") {
+        abort!(path, "`secret` types cannot derive `Serialize`");
+    }
+
+    let mut output = if take_derive(attrs, "Debug").is_some() {
+        quote! {
+            impl std::fmt::Debug for #name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{}(<redacted>)", stringify!(#name))
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    if cfg!(feature = "zeroize") {
+        output.extend(quote! {
+            impl ::checked_rs::__private::zeroize::Zeroize for #name {
+                fn zeroize(&mut self) {
+                    // The value is overwritten with the default rather than zero since zero may
+                    // not be a valid value for this type.
+                    unsafe { std::ptr::write_volatile(self, Self::default()) };
+                    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+        });
+    }
+
+    output
+}
+
+pub fn impl_self_eq(name: &syn::Ident) -> TokenStream {
+    quote! {
+        impl std::cmp::PartialEq<#name> for #name
+        {
+            #[inline(always)]
+            fn eq(&self, other: &#name ) -> bool {
+                self.into_primitive() == other.into_primitive()
+            }
+        }
+
+        impl std::cmp::Eq for #name
+        {
+        }
+    }
+}
+
+pub fn impl_self_cmp(name: &syn::Ident) -> TokenStream {
+    quote! {
+        impl std::cmp::PartialOrd<#name> for #name
+        {
+            #[inline(always)]
+            fn partial_cmp(&self, rhs: &#name ) -> Option<std::cmp::Ordering> {
+                self.into_primitive().partial_cmp(&rhs.into_primitive())
+            }
+        }
+
+        impl std::cmp::Ord for #name
+        {
+            #[inline(always)]
+            fn cmp(&self, rhs: &#name) -> std::cmp::Ordering {
+                self.into_primitive().cmp(&rhs.into_primitive())
+            }
+        }
+    }
+}
+
+pub fn impl_other_eq(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+
+    quote! {
+        impl std::cmp::PartialEq<#integer> for #name
+        {
+            #[inline(always)]
+            fn eq(&self, other: &#integer ) -> bool {
+                self.into_primitive() == *other
+            }
+        }
+
+        impl std::cmp::PartialEq<#name> for #integer
+        {
+            #[inline(always)]
+            fn eq(&self, other: &#name) -> bool {
+                *self == other.into_primitive()
+            }
+        }
+    }
+}
+
+pub fn impl_other_compare(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+
+    quote! {
+        impl std::cmp::PartialOrd<#integer> for #name
+        {
+            #[inline(always)]
+            fn partial_cmp(&self, other: &#integer ) -> Option<std::cmp::Ordering> {
+                (self.into_primitive()).partial_cmp(other)
+            }
+        }
+
+        impl std::cmp::PartialOrd<#name> for #integer
+        {
+            #[inline(always)]
+            fn partial_cmp(&self, other: &#name) -> Option<std::cmp::Ordering> {
+                self.partial_cmp(&other.into_primitive())
+            }
+        }
+    }
+}
+
+/// Get the behavior an operator resolves with, which names the type and its range when it panics
+/// and records its outcome in the metrics of the type when it was given the `metrics` flag. With
+/// the `testing` feature, it also fails the operations chosen by the fault plan of the thread.
+fn metered_behavior(name: &syn::Ident, attr: &AttrParams, behavior: &BehaviorArg) -> TokenStream {
+    let behavior = if attr.uses_metrics() {
+        quote! { Metered<Described<#behavior, #name>, #name> }
+    } else {
+        quote! { Described<#behavior, #name> }
+    };
+
+    if cfg!(feature = "testing") {
+        quote! { <FaultInjecting<#behavior> as Behavior> }
+    } else {
+        quote! { <#behavior as Behavior> }
+    }
+}
+
+/// Implement `MetricsSource` for types given the `metrics` flag, along with an inherent
+/// `metrics()` to read the counters.
+pub fn impl_metrics(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if !attr.uses_metrics() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        impl MetricsSource for #name {
+            #[inline(always)]
+            fn metrics() -> &'static ClampMetrics {
+                static METRICS: ClampMetrics = ClampMetrics::new(stringify!(#name));
+                &METRICS
+            }
+        }
+
+        impl #name {
+            /// Get the counters of how often operations on this type saturated, panicked or were
+            /// rejected.
+            #[inline(always)]
+            pub fn metrics() -> &'static ClampMetrics {
+                <Self as MetricsSource>::metrics()
+            }
+        }
+    }
+}
+
+/// Generate `explain`, which reports whether a primitive is within the limits of a struct type or
+/// the nearest value that is.
+pub fn impl_explain(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    quote! {
+        impl #name {
+            /// Explain whether `value` is allowed, or which value is the nearest one that is.
+            pub fn explain(value: #integer) -> Explanation<#integer> {
+                let type_name = stringify!(#name);
+
+                if value < #lower_limit {
+                    Explanation::Invalid { type_name, value, nearest: #lower_limit }
+                } else if value > #upper_limit {
+                    Explanation::Invalid { type_name, value, nearest: #upper_limit }
+                } else {
+                    Explanation::Valid { type_name, value, min: #lower_limit, max: #upper_limit }
+                }
+            }
+        }
+    }
+}
+
+/// Implement `DiffDisplay` from the explanation of the value, which names the variant of an enum
+/// or the range of a struct.
+pub fn impl_diff_display(name: &syn::Ident) -> TokenStream {
+    quote! {
+        impl DiffDisplay for #name {
+            fn diff_display(&self) -> String {
+                diff_display_of(&Self::explain(self.into_primitive()), Self::RANGE_SUMMARY)
+            }
+        }
+    }
+}
+
+/// Generate the imports at the top of the module that holds the generated items. By default the
+/// module sees everything in the enclosing module along with the names of `checked_rs` that the
+/// generated code uses. With `explicit_imports` it only imports the latter, so it does not depend
+/// on what the enclosing module has in scope.
+pub fn module_imports(attr: &AttrParams) -> TokenStream {
+    if attr.explicit_imports_kw.is_none() {
+        return quote!(
+            use super::*;
+            #[allow(unused_imports)]
+            use ::checked_rs::__private::codegen::*;
+        );
+    }
+
+    let mut names = CODEGEN_NAMES.to_vec();
+
+    if cfg!(feature = "deranged") {
+        names.extend(DERANGED_CODEGEN_NAMES);
+    }
+
+    if cfg!(feature = "fuzz") {
+        names.extend(FUZZ_CODEGEN_NAMES);
+    }
+
+    if cfg!(feature = "metrics") {
+        names.extend(METRICS_CODEGEN_NAMES);
+    }
+
+    if cfg!(feature = "reflect") {
+        names.extend(REFLECT_CODEGEN_NAMES);
+    }
+
+    if cfg!(feature = "testing") {
+        names.extend(TESTING_CODEGEN_NAMES);
+    }
+
+    if cfg!(feature = "ui") {
+        names.extend(UI_CODEGEN_NAMES);
+    }
+
+    let names = names.into_iter().map(|name| format_ident!("{}", name));
+
+    quote! {
+        #[allow(unused_imports)]
+        use ::checked_rs::__private::codegen::{#(#names),*};
+    }
+}
+
+/// Generate `freeze`, which wraps the value in a read-only `Frozen`.
+pub fn impl_freeze(name: &syn::Ident) -> TokenStream {
+    quote! {
+        impl #name {
+            /// Make the value read-only, such as for configuration that must not change after
+            /// startup.
+            #[inline(always)]
+            pub const fn freeze(self) -> Frozen<Self> {
+                Frozen::new(self)
+            }
+        }
+    }
+}
+
+/// Generate `into_raw`, the escape hatch that takes the primitive out of the validated domain.
+pub fn impl_into_raw(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+
+    quote! {
+        impl #name {
+            /// Take the primitive out as an `Unvalidated` value, which must be validated again or
+            /// explicitly unwrapped before it can be used.
+            #[inline(always)]
+            pub fn into_raw(self) -> Unvalidated<#integer> {
+                Unvalidated::new(self.into_primitive())
+            }
+        }
+    }
+}
+
+/// Generate `DEFAULT` and `default_array`, which fill fixed-size buffers with the default without
+/// requiring `Copy`. `const_default` builds the default in a `const` context, or is `None` when the
+/// default can only be resolved at runtime, in which case `default_array` is not `const`.
+pub fn impl_default_array(name: &syn::Ident, const_default: Option<TokenStream>) -> TokenStream {
+    match const_default {
+        Some(const_default) => quote! {
+            impl #name {
+                /// The default value, usable in `const` contexts.
+                pub const DEFAULT: Self = #const_default;
+
+                /// An array filled with the default value.
+                #[inline(always)]
+                pub const fn default_array<const N: usize>() -> [Self; N] {
+                    [Self::DEFAULT; N]
+                }
+            }
+        },
+        None => quote! {
+            impl #name {
+                /// An array filled with the default value.
+                #[inline(always)]
+                pub fn default_array<const N: usize>() -> [Self; N] {
+                    std::array::from_fn(|_| Self::default())
+                }
+            }
+        },
+    }
+}
+
+/// Define the signed `<Name>Delta` type that holds the difference between two values of a hard
+/// struct, along with `diff` and the operators that apply a delta. Its primitive is the narrowest
+/// signed integer that holds both `MAX - MIN` and `MIN - MAX`. Nothing is defined when the limits
+/// are too far apart for an `i128`.
+pub fn define_delta(name: &syn::Ident, attr: &AttrParams) -> Option<(syn::Ident, TokenStream)> {
+    let integer = &attr.integer;
+    let lower = attr.lower_limit_value().to_string().parse::<i128>().ok()?;
+    let upper = attr.upper_limit_value().to_string().parse::<i128>().ok()?;
+    let span = upper.checked_sub(lower)?;
+    let context = range_context(name, attr);
+
+    let repr = [
+        ("i8", i8::MAX as i128),
+        ("i16", i16::MAX as i128),
+        ("i32", i32::MAX as i128),
+        ("i64", i64::MAX as i128),
+        ("i128", i128::MAX),
+    ]
+    .into_iter()
+    .find(|(_, max)| span <= *max)
+    .map(|(repr, _)| format_ident!("{}", repr))?;
+
+    let delta_name = attr.delta_name(name);
+    let add_behavior = metered_behavior(name, attr, attr.behavior_for("add"));
+    let sub_behavior = metered_behavior(name, attr, attr.behavior_for("sub"));
+
+    let items = quote! {
+        /// The signed difference between two values of the type, as returned by `diff`.
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct #delta_name(#repr);
+
+        impl #delta_name {
+            #[inline(always)]
+            pub const fn new(value: #repr) -> Self {
+                Self(value)
+            }
+
+            #[inline(always)]
+            pub const fn get(self) -> #repr {
+                self.0
+            }
+        }
+
+        impl std::ops::Deref for #delta_name {
+            type Target = #repr;
+
+            #[inline(always)]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl std::ops::Neg for #delta_name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn neg(self) -> Self::Output {
+                Self(-self.0)
+            }
+        }
+
+        impl std::fmt::Display for #delta_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl #name {
+            /// Get the signed difference `self - other`, which always fits in the delta type.
+            #[inline(always)]
+            pub fn diff(self, other: Self) -> #delta_name {
+                #delta_name((self.into_primitive() as i128 - other.into_primitive() as i128) as #repr)
+            }
+
+            /// Move the value by `delta`, failing if the result would be outside of the limits.
+            pub fn checked_add_delta(self, delta: #delta_name) -> Result<Self, ClampError<#integer>> {
+                Self::resolve_delta(
+                    (self.into_primitive() as i128).checked_add(delta.0 as i128),
+                    delta.0 < 0,
+                )
+                .map(|val| Self::from_primitive(val).expect("value should be within the limits"))
             }
-        });
-    }
 
-    if attr.is_i16_or_smaller() {
-        conversions.push(quote! {
-            impl From<#name> for i16 {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as i16
-                }
+            /// Move the value by the negation of `delta`, failing if the result would be outside of
+            /// the limits.
+            pub fn checked_sub_delta(self, delta: #delta_name) -> Result<Self, ClampError<#integer>> {
+                Self::resolve_delta(
+                    (self.into_primitive() as i128).checked_sub(delta.0 as i128),
+                    delta.0 > 0,
+                )
+                .map(|val| Self::from_primitive(val).expect("value should be within the limits"))
             }
-        });
-    }
 
-    if attr.is_i16_or_larger() {
-        conversions.push(quote! {
-            impl From<u16> for #name {
-                #[inline(always)]
-                fn from(val: i16) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+            fn resolve_delta(val: Option<i128>, below: bool) -> Result<#integer, ClampError<#integer>> {
+                const MIN: #integer = <#name as InherentLimits<#integer>>::MIN;
+                const MAX: #integer = <#name as InherentLimits<#integer>>::MAX;
+
+                match val {
+                    Some(val) if val < MIN as i128 => Err(ClampError::TooSmall {
+                        val: #integer::try_from(val).unwrap_or(#integer::MIN),
+                        min: MIN,
+                    }),
+                    Some(val) if val > MAX as i128 => Err(ClampError::TooLarge {
+                        val: #integer::try_from(val).unwrap_or(#integer::MAX),
+                        max: MAX,
+                    }),
+                    Some(val) => Ok(val as #integer),
+                    None if below => Err(ClampError::TooSmall { val: #integer::MIN, min: MIN }),
+                    None => Err(ClampError::TooLarge { val: #integer::MAX, max: MAX }),
                 }
             }
-        });
-    }
+        }
 
-    if matches!(attr.kind(), NumberKind::I8) {
-        conversions.push(quote! {
-            impl From<#name> for i8 {
-                #[inline(always)]
-                fn from(val: #name) -> Self {
-                    val.into_primitive() as i8
+        impl std::ops::Add<#delta_name> for #name {
+            type Output = Self;
+
+            /// A result outside of the limits saturates or panics like `+` does.
+            #[inline(always)]
+            fn add(self, rhs: #delta_name) -> Self::Output {
+                match #add_behavior::resolve(self.checked_add_delta(rhs).map(|val| val.into_primitive())) {
+                    Ok(val) => Self::from_primitive(val).expect("value should be within the limits"),
+                    Err(e) => panic!("{} {}", e, #context),
                 }
             }
-        });
-    }
+        }
 
-    if attr.is_signed() {
-        conversions.push(quote! {
-            impl From<i8> for #name {
-                #[inline(always)]
-                fn from(val: i8) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+        impl std::ops::Sub<#delta_name> for #name {
+            type Output = Self;
+
+            /// A result outside of the limits saturates or panics like `-` does.
+            #[inline(always)]
+            fn sub(self, rhs: #delta_name) -> Self::Output {
+                match #sub_behavior::resolve(self.checked_sub_delta(rhs).map(|val| val.into_primitive())) {
+                    Ok(val) => Self::from_primitive(val).expect("value should be within the limits"),
+                    Err(e) => panic!("{} {}", e, #context),
                 }
             }
-        });
+        }
+    };
+
+    Some((delta_name, items))
+}
+
+/// Generate the adapters between a hard struct and its zero-based offset from the lower limit,
+/// which lets values whose range does not start at zero index dense arrays. `SPAN` is left out when
+/// the number of values does not fit in the primitive.
+pub fn impl_offset(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    let lower = attr.lower_limit_value();
+    let upper = attr.upper_limit_value();
+
+    let span = if lower == NumberArg::new_min_constant(attr.kind()).into_value(attr.kind())
+        && upper == NumberArg::new_max_constant(attr.kind()).into_value(attr.kind())
+    {
+        TokenStream::new()
     } else {
-        conversions.push(quote! {
-            impl From<u8> for #name {
-                #[inline(always)]
-                fn from(val: u8) -> Self {
-                    Self::from_primitive(val as #integer).expect("value should be within bounds")
+        quote! {
+            /// The number of values within the limits.
+            pub const SPAN: #integer = #upper_limit - #lower_limit + 1;
+        }
+    };
+
+    quote! {
+        impl #name {
+            #span
+
+            /// Get the distance of the value above the lower limit, which is `0` for the lower
+            /// limit itself.
+            #[inline(always)]
+            pub fn offset(&self) -> #integer {
+                self.into_primitive() - #lower_limit
+            }
+
+            /// Create a value from its distance above the lower limit, failing when the result
+            /// would be above the upper limit.
+            #[inline(always)]
+            pub fn from_offset(offset: #integer) -> ::checked_rs::__private::anyhow::Result<Self> {
+                if offset > #upper_limit - #lower_limit {
+                    ::checked_rs::__private::anyhow::bail!(
+                        "offset {} is beyond the upper limit of `{}`",
+                        offset,
+                        stringify!(#name)
+                    );
                 }
+
+                Self::from_primitive(#lower_limit + offset)
             }
-        });
+        }
+    }
+}
+
+/// Generate the comparisons of the `cyclic` flag, which treat the limits as a circle where the
+/// upper limit is followed by the lower limit.
+pub fn impl_cyclic(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if !attr.uses_cyclic() {
+        return TokenStream::new();
     }
 
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
     quote! {
-        #(#conversions)*
+        impl #name {
+            /// Get the number of steps forward from this value to `other`, wrapping from the
+            /// upper limit to the lower limit.
+            #[inline(always)]
+            pub fn cyclic_distance(&self, other: &Self) -> #integer {
+                cyclic_distance(self.into_primitive(), other.into_primitive(), #lower_limit, #upper_limit) as #integer
+            }
 
-        impl std::str::FromStr for #name {
-            type Err = ::anyhow::Error;
+            /// Check whether this value comes before `other`, which is when `other` is less than
+            /// half of the range ahead of it, wrapping from the upper limit to the lower limit.
+            #[inline(always)]
+            pub fn cyclic_lt(&self, other: &Self) -> bool {
+                cyclic_lt(self.into_primitive(), other.into_primitive(), #lower_limit, #upper_limit)
+            }
 
+            /// Check whether walking forward from `start` to `end`, wrapping from the upper limit
+            /// to the lower limit, passes this value, including both ends.
             #[inline(always)]
-            fn from_str(s: &str) -> ::anyhow::Result<Self> {
-                let n = s.parse::<#integer>()?;
-                Self::from_primitive(n)
+            pub fn is_between(&self, start: &Self, end: &Self) -> bool {
+                cyclic_is_between(
+                    self.into_primitive(),
+                    start.into_primitive(),
+                    end.into_primitive(),
+                    #lower_limit,
+                    #upper_limit,
+                )
             }
         }
     }
 }
 
-pub fn impl_self_eq(name: &syn::Ident) -> TokenStream {
+/// Generate the sequence number helpers of the `serial` flag, which follow RFC 1982 serial number
+/// arithmetic within the limits.
+pub fn impl_serial(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if attr.serial_kw.is_none() {
+        return TokenStream::new();
+    }
+
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
     quote! {
-        impl std::cmp::PartialEq<#name> for #name
-        {
+        impl #name {
+            /// Check whether this sequence number was issued after `other`, which is when it is
+            /// less than half of the range ahead of `other`.
             #[inline(always)]
-            fn eq(&self, other: &#name ) -> bool {
-                self.into_primitive() == other.into_primitive()
+            pub fn is_newer_than(&self, other: &Self) -> bool {
+                other.cyclic_lt(self)
+            }
+
+            /// Get the sequence number `n` steps after this one, wrapping from the upper limit to
+            /// the lower limit. Comparisons are only meaningful when `n` is less than half of the
+            /// range.
+            #[inline(always)]
+            pub fn wrapping_add(self, n: #integer) -> Self {
+                Self::from_primitive(cyclic_add(self.into_primitive(), n as u128, #lower_limit, #upper_limit))
+                    .expect("value should be within bounds")
+            }
+
+            /// Move to the next sequence number, wrapping from the upper limit to the lower limit.
+            #[inline(always)]
+            pub fn wrapping_increment(&mut self) {
+                *self = self.wrapping_add(1);
             }
         }
+    }
+}
 
-        impl std::cmp::Eq for #name
-        {
+/// Generate `arbitrary_invalid`, which samples the primitives outside of the limits for negative
+/// tests and fuzzers.
+pub fn impl_invalid_sampler(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    quote! {
+        impl #name {
+            /// Pick a random primitive outside of the limits, which `validate` rejects, or `None`
+            /// when the limits cover every value of the primitive. Every such value is equally
+            /// likely, so a seeded `rng` produces the same values on every run.
+            pub fn arbitrary_invalid<R: ::checked_rs::__private::rand::Rng + ?Sized>(
+                rng: &mut R,
+            ) -> Option<#integer> {
+                let below = #lower_limit as u128;
+                let above = (#integer::MAX - #upper_limit) as u128;
+
+                if below + above == 0 {
+                    return None;
+                }
+
+                let pick = rng.gen_range(0..below + above);
+
+                if pick < below {
+                    Some(pick as #integer)
+                } else {
+                    Some(#upper_limit + 1 + (pick - below) as #integer)
+                }
+            }
         }
     }
 }
 
-pub fn impl_self_cmp(name: &syn::Ident) -> TokenStream {
+/// Generate `RANGE_FINGERPRINT`, a hash of the canonical description of the values the type
+/// declares and the behavior of each operator. `extra` describes anything else that decides which
+/// values are allowed, such as the mask variants of enums.
+pub fn impl_fingerprint(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    ranges: &[(NumberValue, NumberValue)],
+    exacts: &[NumberValue],
+    extra: &[String],
+) -> TokenStream {
+    let widen = |n: &NumberValue| n.to_string().parse::<u128>().unwrap();
+
+    let mut ranges = ranges
+        .iter()
+        .map(|(start, end)| (widen(start), widen(end)))
+        .collect::<Vec<_>>();
+    ranges.sort_unstable();
+
+    let mut exacts = exacts.iter().map(widen).collect::<Vec<_>>();
+    exacts.sort_unstable();
+
+    let behaviors = BEHAVIOR_OPS
+        .iter()
+        .map(|op| {
+            format!(
+                "{}={}",
+                op,
+                attr.behavior_for(op)
+                    .to_token_stream()
+                    .to_string()
+                    .replace(' ', "")
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let canonical = format!(
+        "{}:{}..={};ranges={:?};exacts={:?};{};{}",
+        attr.integer.to_token_stream(),
+        widen(&attr.lower_limit_value()),
+        widen(&attr.upper_limit_value()),
+        ranges,
+        exacts,
+        extra.join(";"),
+        behaviors.join(",")
+    );
+
+    // FNV-1a, which is stable across compiler versions unlike `std::hash`.
+    let fingerprint = canonical
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+
     quote! {
-        impl std::cmp::PartialOrd<#name> for #name
-        {
+        impl #name {
+            /// A hash of the values this type allows and the behavior of its operators. It only
+            /// changes when they do, so it can be stored alongside persisted values to detect that
+            /// they were written under different bounds.
+            pub const RANGE_FINGERPRINT: u64 = #fingerprint;
+        }
+    }
+}
+
+/// Implement `Reflect` with a static descriptor of the type and register it with `inventory` when
+/// the `reflect` feature is enabled. `ranges` and `exacts` are the values the type declares.
+pub fn impl_reflect(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    ranges: &[(NumberValue, NumberValue)],
+    exacts: &[NumberValue],
+) -> TokenStream {
+    if !cfg!(feature = "reflect") {
+        return TokenStream::new();
+    }
+
+    let widen = |n: &NumberValue| n.to_string().parse::<u128>().unwrap();
+    let primitive = match attr.kind() {
+        NumberKind::U8 => quote!(U8),
+        NumberKind::U16 => quote!(U16),
+        NumberKind::U32 => quote!(U32),
+        NumberKind::U64 => quote!(U64),
+        NumberKind::U128 => quote!(U128),
+        NumberKind::USize => quote!(Usize),
+        _ => abort!(attr.integer, "expected number type"),
+    };
+    let min = widen(&attr.lower_limit_value());
+    let max = widen(&attr.upper_limit_value());
+    let ranges = ranges.iter().map(|(start, end)| {
+        let start = widen(start);
+        let end = widen(end);
+        quote!((#start, #end))
+    });
+    let mut exacts = exacts.iter().map(widen).collect::<Vec<_>>();
+    exacts.sort_unstable();
+    let default = attr
+        .default_val
+        .base10_parse::<u128>()
+        .unwrap_or_else(|e| abort!(attr.default_val, e));
+    let behavior = attr.behavior_type().to_token_stream().to_string();
+
+    quote! {
+        static DESCRIPTOR: ClampedTypeDescriptor = ClampedTypeDescriptor {
+            type_name: stringify!(#name),
+            primitive: PrimitiveKind::#primitive,
+            min: #min,
+            max: #max,
+            ranges: &[#(#ranges),*],
+            exacts: &[#(#exacts),*],
+            default: #default,
+            behavior: #behavior,
+            conversions: CONVERSIONS,
+        };
+
+        ::checked_rs::__private::inventory::submit! {
+            DescriptorEntry(&DESCRIPTOR)
+        }
+
+        impl Reflect for #name {
             #[inline(always)]
-            fn partial_cmp(&self, rhs: &#name ) -> Option<std::cmp::Ordering> {
-                self.into_primitive().partial_cmp(&rhs.into_primitive())
+            fn descriptor() -> &'static ClampedTypeDescriptor {
+                &DESCRIPTOR
             }
         }
 
-        impl std::cmp::Ord for #name
-        {
+        impl #name {
+            /// Get the runtime description of this type, with its limits, declared values, default
+            /// and behavior.
             #[inline(always)]
-            fn cmp(&self, rhs: &#name) -> std::cmp::Ordering {
-                self.into_primitive().cmp(&rhs.into_primitive())
+            pub fn descriptor() -> &'static ClampedTypeDescriptor {
+                &DESCRIPTOR
             }
         }
     }
 }
 
-pub fn impl_other_eq(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+/// Implement `UiRange` for structs when the `ui` feature is enabled, so that sliders can be bound
+/// to the type.
+pub fn impl_ui_range(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if !cfg!(feature = "ui") {
+        return TokenStream::new();
+    }
+
     let integer = &attr.integer;
 
     quote! {
-        impl std::cmp::PartialEq<#integer> for #name
-        {
+        impl UiRange for #name {
             #[inline(always)]
-            fn eq(&self, other: &#integer ) -> bool {
-                self.into_primitive() == *other
+            fn ui_min() -> f64 {
+                <Self as InherentLimits<#integer>>::MIN as f64
             }
-        }
 
-        impl std::cmp::PartialEq<#name> for #integer
-        {
             #[inline(always)]
-            fn eq(&self, other: &#name) -> bool {
-                *self == other.into_primitive()
+            fn ui_max() -> f64 {
+                <Self as InherentLimits<#integer>>::MAX as f64
+            }
+
+            #[inline(always)]
+            fn to_f64(&self) -> f64 {
+                self.into_primitive() as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                // The cast saturates and maps `NaN` to zero, and clamping the integer rather than
+                // the float avoids rounding past the limits of wide types.
+                let val = (value.round() as #integer).clamp(
+                    <Self as InherentLimits<#integer>>::MIN,
+                    <Self as InherentLimits<#integer>>::MAX,
+                );
+
+                Self::from_primitive(val).expect("value should be within bounds")
             }
         }
     }
 }
 
-pub fn impl_other_compare(name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+/// The kind of generated type a fuzz target is written for, which decides the invariants it checks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FuzzedType {
+    Hard,
+    Soft,
+    Enum,
+}
+
+/// Implement `FuzzTarget` when the `fuzz` feature is enabled, for builds with `--cfg fuzzing` and
+/// for tests. Every primitive decoded from the input must round-trip through `from_primitive` and
+/// be accepted exactly when it is within the limits. Hard structs also apply `+`, `-` and `*` with
+/// the next primitive, whenever the previews say the behavior resolves them without panicking,
+/// and check the result against the preview.
+pub fn impl_fuzz_target(name: &syn::Ident, attr: &AttrParams, fuzzed: FuzzedType) -> TokenStream {
+    if !cfg!(feature = "fuzz") {
+        return TokenStream::new();
+    }
+
     let integer = &attr.integer;
+    let context = range_context(name, attr);
+
+    let accepted = match fuzzed {
+        FuzzedType::Soft => quote!(Self::validate(n).is_ok()),
+        FuzzedType::Hard | FuzzedType::Enum => {
+            quote!(<Self as ClampedInteger<#integer>>::from_primitive(n).is_ok())
+        }
+    };
+
+    let operators = (fuzzed == FuzzedType::Hard).then(|| {
+        let ops = [
+            (quote!(preview_add), quote!(+), "+"),
+            (quote!(preview_sub), quote!(-), "-"),
+            (quote!(preview_mul), quote!(*), "*"),
+        ]
+        .into_iter()
+        .map(|(preview, op, symbol)| {
+            let message = format!(
+                "`{{}} {} {{}}` disagrees with its preview {}",
+                symbol,
+                context.replace('{', "{{").replace('}', "}}")
+            );
+
+            quote! {
+                let mut staged = rebuild();
+                let guard = staged.modify();
+                let expected = guard.#preview(rhs);
+                guard.discard();
+
+                if let Ok(expected) = expected {
+                    assert_eq!((rebuild() #op rhs).into_primitive(), expected, #message, n, rhs);
+                }
+            }
+        });
+
+        quote! {
+            if let Some(rhs) = input.take::<#integer>() {
+                // values may not be `Copy`, so each operation gets its own
+                let rebuild = || {
+                    <Self as ClampedInteger<#integer>>::from_primitive(n)
+                        .expect("value should be within bounds")
+                };
+
+                #(#ops)*
+            }
+        }
+    });
 
     quote! {
-        impl std::cmp::PartialOrd<#integer> for #name
-        {
-            #[inline(always)]
-            fn partial_cmp(&self, other: &#integer ) -> Option<std::cmp::Ordering> {
-                (self.into_primitive()).partial_cmp(other)
+        // `fuzzing` is set by `cargo fuzz`, so other builds of the crate do not expect it, and the
+        // lint is only allowed from an enclosing item
+        #[allow(unexpected_cfgs)]
+        const _: () = {
+            #[cfg(any(fuzzing, test))]
+            impl FuzzTarget for #name {
+                fn fuzz(data: &[u8]) {
+                    let mut input = FuzzInput::new(data);
+
+                    while let Some(n) = input.take::<#integer>() {
+                        let in_limits = (<Self as InherentLimits<#integer>>::MIN..=<Self as InherentLimits<#integer>>::MAX).contains(&n);
+
+                        assert_eq!(#accepted, in_limits, "{} was accepted wrongly {}", n, #context);
+
+                        let Ok(value) = <Self as ClampedInteger<#integer>>::from_primitive(n) else {
+                            continue;
+                        };
+
+                        assert_eq!(value.into_primitive(), n, "{} did not round-trip {}", n, #context);
+
+                        #operators
+                    }
+                }
             }
+        };
+    }
+}
+
+/// Implement the helpers for the value marked as unset by the `null` parameter, along with serde
+/// that maps it to and from `null`.
+pub fn impl_null(name: &syn::Ident, attr: &AttrParams, item: &mut syn::Item) -> TokenStream {
+    let Some(null) = attr.null_value() else {
+        return TokenStream::new();
+    };
+
+    let attrs = match item {
+        syn::Item::Struct(data) => &mut data.attrs,
+        syn::Item::Enum(data) => &mut data.attrs,
+        _ => unreachable!(),
+    };
+
+    for derive_name in ["Serialize", "Deserialize"] {
+        if let Some(path) = take_derive(attrs, derive_name) {
+            abort!(
+                path,
+                "types with a `null` value implement `{}` with the unset value as `null`; remove the derive",
+                derive_name
+            );
         }
+    }
 
-        impl std::cmp::PartialOrd<#name> for #integer
-        {
+    let integer = &attr.integer;
+
+    let serialize = if attr.secret_kw.is_none() {
+        quote! {
+            impl ::checked_rs::__private::serde::Serialize for #name {
+                fn serialize<S: ::checked_rs::__private::serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                    match self.get_set() {
+                        Some(val) => serializer.serialize_some(&val.into_primitive()),
+                        None => serializer.serialize_none(),
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        impl #name {
+            /// The value that marks this type as unset.
+            pub const NULL: #integer = #null;
+
+            /// Get the unset value.
             #[inline(always)]
-            fn partial_cmp(&self, other: &#name) -> Option<std::cmp::Ordering> {
-                self.partial_cmp(other.as_primitive())
+            pub fn unset() -> Self {
+                Self::from_primitive(Self::NULL).expect("value should be within bounds")
+            }
+
+            #[inline(always)]
+            pub fn is_unset(&self) -> bool {
+                self.into_primitive() == Self::NULL
+            }
+
+            /// Get the value unless it is the unset value.
+            #[inline(always)]
+            pub fn get_set(&self) -> Option<Self> {
+                if self.is_unset() {
+                    None
+                } else {
+                    Self::from_primitive(self.into_primitive()).ok()
+                }
+            }
+
+            /// Get the value, or the default value if it is unset.
+            #[inline(always)]
+            pub fn set_or_default(&self) -> Self {
+                self.get_set().unwrap_or_default()
+            }
+        }
+
+        #serialize
+
+        impl<'de> ::checked_rs::__private::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::checked_rs::__private::serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                match <Option<#integer> as ::checked_rs::__private::serde::Deserialize>::deserialize(deserializer)? {
+                    Some(n) => Self::from_primitive(n).map_err(::checked_rs::__private::serde::de::Error::custom),
+                    None => Ok(Self::unset()),
+                }
+            }
+        }
+    }
+}
+
+/// Replace a derived `Deserialize` of a hard struct or an enum with one that checks the value, so
+/// deserializing cannot create a value the type does not allow. A copy of the item that holds plain
+/// primitives derives `Deserialize` with the same serde attributes, which keeps the format the same,
+/// and its value is passed to `from_primitive`. Enums also check that the value belongs to the
+/// variant it was deserialized as.
+pub fn impl_validated_deserialize(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    item: &mut syn::Item,
+) -> TokenStream {
+    let integer = &attr.integer;
+    let storage = attr.storage_type();
+    let unchecked_name = format_ident!("{}Unchecked", name);
+
+    let only_serde_attrs = |attrs: &mut Vec<syn::Attribute>| {
+        attrs.retain(|attr| attr.path().is_ident("serde"));
+    };
+    let without_serde_attrs = |attrs: &mut Vec<syn::Attribute>| {
+        attrs.retain(|attr| !attr.path().is_ident("serde"));
+    };
+
+    let widen = if attr.store_val.is_some() {
+        quote!(as #integer)
+    } else {
+        TokenStream::new()
+    };
+
+    let (unchecked, check) = match item {
+        syn::Item::Struct(data) => {
+            let Some(path) = take_derive(&mut data.attrs, "Deserialize") else {
+                return TokenStream::new();
+            };
+
+            let mut unchecked = data.clone();
+            unchecked.ident = unchecked_name.clone();
+            unchecked.vis = syn::Visibility::Inherited;
+            only_serde_attrs(&mut unchecked.attrs);
+            unchecked
+                .attrs
+                .insert(0, syn::parse_quote!(#[derive(#path)]));
+            unchecked.fields.iter_mut().for_each(|field| {
+                field.vis = syn::Visibility::Inherited;
+                only_serde_attrs(&mut field.attrs);
+            });
+
+            // The serde attributes now only belong to the copy unless `Serialize` is derived.
+            if !has_derive(&data.attrs, "Serialize") {
+                without_serde_attrs(&mut data.attrs);
+                data.fields
+                    .iter_mut()
+                    .for_each(|field| without_serde_attrs(&mut field.attrs));
+            }
+
+            (
+                unchecked.into_token_stream(),
+                quote! {
+                    Self::from_primitive(unchecked.0 #widen)
+                        .map_err(::checked_rs::__private::serde::de::Error::custom)
+                },
+            )
+        }
+        syn::Item::Enum(data) => {
+            let Some(path) = take_derive(&mut data.attrs, "Deserialize") else {
+                return TokenStream::new();
+            };
+
+            let mut unchecked = data.clone();
+            unchecked.ident = unchecked_name.clone();
+            unchecked.vis = syn::Visibility::Inherited;
+            only_serde_attrs(&mut unchecked.attrs);
+            unchecked
+                .attrs
+                .insert(0, syn::parse_quote!(#[derive(#path)]));
+            unchecked.variants.iter_mut().for_each(|variant| {
+                only_serde_attrs(&mut variant.attrs);
+                variant.fields = syn::Fields::Unnamed(syn::parse_quote!((#storage)));
+            });
+
+            if !has_derive(&data.attrs, "Serialize") {
+                without_serde_attrs(&mut data.attrs);
+                data.variants
+                    .iter_mut()
+                    .for_each(|variant| without_serde_attrs(&mut variant.attrs));
+            }
+
+            let variant_idents = data.variants.iter().map(|variant| &variant.ident);
+
+            (
+                unchecked.into_token_stream(),
+                quote! {
+                    let (n, expected) = match unchecked {
+                        #(#unchecked_name::#variant_idents(n) => (n #widen, stringify!(#variant_idents)),)*
+                    };
+
+                    let value = Self::from_primitive(n)
+                        .map_err(::checked_rs::__private::serde::de::Error::custom)?;
+
+                    if value.variant_name() != expected {
+                        return Err(::checked_rs::__private::serde::de::Error::custom(format!(
+                            "{} is not a value of `{}::{}`",
+                            n,
+                            stringify!(#name),
+                            expected
+                        )));
+                    }
+
+                    Ok(value)
+                },
+            )
+        }
+        _ => unreachable!(),
+    };
+
+    quote! {
+        #[doc(hidden)]
+        #unchecked
+
+        impl<'de> ::checked_rs::__private::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::checked_rs::__private::serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let unchecked = <#unchecked_name as ::checked_rs::__private::serde::Deserialize>::deserialize(deserializer)?;
+
+                #check
             }
         }
     }
 }
 
+/// Wrap the body of `from_primitive` so that rejected values are counted when the type was given
+/// the `metrics` flag.
+pub fn count_rejections(attr: &AttrParams, body: TokenStream) -> TokenStream {
+    if !attr.uses_metrics() {
+        return body;
+    }
+
+    quote! {
+        let result = (|| -> ::checked_rs::__private::anyhow::Result<Self> { #body })();
+
+        if result.is_err() {
+            <Self as MetricsSource>::metrics().record_rejected();
+        }
+
+        result
+    }
+}
+
 pub fn impl_binary_op(
     name: &syn::Ident,
     attr: &AttrParams,
@@ -494,14 +3216,86 @@ pub fn impl_binary_op(
 
     let assign_trait_name = format_ident!("{}Assign", trait_name);
     let assign_method_name = format_ident!("{}_assign", method_name);
+    let behavior = metered_behavior(name, attr, behavior);
+    let is_division = method_name == "div" || method_name == "rem";
+
+    if is_division && attr.uses_checked_division() {
+        let checked_method_name = format_ident!("checked_{}", method_name);
+        let doc = format!(
+            "Apply `{}` with the type's behavior, or get `None` when `rhs` is zero.",
+            method_name
+        );
+
+        return quote! {
+            impl #name {
+                #[doc = #doc]
+                #[inline(always)]
+                pub fn #checked_method_name(self, rhs: #integer) -> Option<#name> {
+                    if rhs == 0 {
+                        None
+                    } else {
+                        Some(Self::from_primitive(#behavior::#method_name(self.into_primitive(), rhs, #lower, #upper)).expect("arithmetic operations should be infallible"))
+                    }
+                }
+            }
+        };
+    }
+
+    let checked_method_name = format_ident!("checked_{}", method_name);
+    let checked_doc = format!(
+        "Apply `{}`, or get `None` when the result is not a valid value, regardless of the type's behavior.",
+        method_name
+    );
+    let checked_result = match method_name.to_string().as_str() {
+        "bitand" => quote!(Some(self.into_primitive() & rhs)),
+        "bitor" => quote!(Some(self.into_primitive() | rhs)),
+        "bitxor" => quote!(Some(self.into_primitive() ^ rhs)),
+        _ => quote!(self.into_primitive().#checked_method_name(rhs)),
+    };
+
+    // With a `div_by_zero` policy other than `panic`, a zero divisor is resolved by the behavior
+    // instead of reaching the primitive division.
+    let policy = match &attr.div_by_zero_val {
+        Some(DivByZeroArg::SaturateMax(..)) if is_division => Some(quote!(SaturateMax)),
+        Some(DivByZeroArg::ReturnDefault(..)) if is_division => Some(quote!(ReturnDefault)),
+        _ => None,
+    };
+    let apply = |rhs: TokenStream| match &policy {
+        Some(policy) => {
+            let max = attr.upper_limit_token();
+            let default = attr.default_val.into_literal_as_tokens(kind);
+
+            quote! {
+                if #rhs == 0 {
+                    #behavior::div_by_zero(DivByZero::#policy, #max, #default)
+                } else {
+                    #behavior::#method_name(self.into_primitive(), #rhs, #lower, #upper)
+                }
+            }
+        }
+        None => quote! {
+            #behavior::#method_name(self.into_primitive(), #rhs, #lower, #upper)
+        },
+    };
+    let apply_self = apply(quote!(rhs.into_primitive()));
+    let apply_integer = apply(quote!(rhs));
 
     quote! {
+        impl #name {
+            #[doc = #checked_doc]
+            #[inline(always)]
+            pub fn #checked_method_name(self, rhs: #integer) -> Option<#name> {
+                checked_within(#checked_result, #lower, #upper)
+                    .and_then(|val| Self::from_primitive(val).ok())
+            }
+        }
+
         impl std::ops::#trait_name for #name {
             type Output = #name;
 
             #[inline(always)]
             fn #method_name(self, rhs: #name) -> #name {
-                Self::from_primitive(#behavior::#method_name(self.into_primitive(), rhs.into_primitive(), #lower, #upper)).expect("arithmetic operations should be infallible")
+                Self::from_primitive(#apply_self).expect("arithmetic operations should be infallible")
             }
         }
 
@@ -510,7 +3304,7 @@ pub fn impl_binary_op(
 
             #[inline(always)]
             fn #method_name(self, rhs: #integer) -> #name {
-                Self::from_primitive(#behavior::#method_name(self.into_primitive(), rhs, #lower, #upper)).expect("arithmetic operations should be infallible")
+                Self::from_primitive(#apply_integer).expect("arithmetic operations should be infallible")
             }
         }
 
@@ -535,18 +3329,14 @@ pub fn impl_binary_op(
         impl std::ops::#assign_trait_name for #name {
             #[inline(always)]
             fn #assign_method_name(&mut self, rhs: #name) {
-                *self = Self::from_primitive(
-                    #behavior::#method_name(self.into_primitive(), rhs.into_primitive(), #lower, #upper)
-                ).expect("assignable operations should be infallible");
+                *self = Self::from_primitive(#apply_self).expect("assignable operations should be infallible");
             }
         }
 
         impl std::ops::#assign_trait_name<#integer> for #name {
             #[inline(always)]
             fn #assign_method_name(&mut self, rhs: #integer) {
-                *self = Self::from_primitive(
-                    #behavior::#method_name(self.into_primitive(), rhs, #lower, #upper)
-                ).expect("assignable operations should be infallible");
+                *self = Self::from_primitive(#apply_integer).expect("assignable operations should be infallible");
             }
         }
 
@@ -1,16 +1,26 @@
 use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 
 use crate::{
     clamped::common_impl::{
-        define_guard, impl_binary_op, impl_conversions, impl_deref, impl_other_compare,
-        impl_other_eq, impl_self_cmp, impl_self_eq,
+        aggregate_impls, count_rejections, define_benchmark, define_guard, impl_any_clamped,
+        impl_binary_op, impl_bounded_integer, impl_bytemuck, impl_canonical_round_trip,
+        impl_char_conversions, impl_conversions, impl_default_array, impl_deranged, impl_deref,
+        impl_diff_display, impl_fingerprint, impl_freeze, impl_fuzz_target, impl_into_raw,
+        impl_invalid_sampler, impl_metrics, impl_null, impl_other_compare, impl_other_eq,
+        impl_range_summary, impl_reflect, impl_runtime_bounds, impl_secret, impl_self_cmp,
+        impl_self_eq, impl_separated, impl_validated_deserialize, module_imports, range_context,
+        take_derive, FuzzedType,
     },
+    diagnostic::{abort, emit_error},
     params::{
         attr_params::AttrParams,
-        enum_variants::{ExactVariant, RangeVariant, Variants},
-        NumberArg,
+        enum_variants::{
+            AliasValue, ExactVariant, ExternalMapping, IncludeVariant, MaskVariant, NameAlias,
+            RangeVariant, Transition, Variants,
+        },
+        uncovered_spans, NumberValue,
     },
 };
 
@@ -20,127 +30,224 @@ pub fn define_mod(attr: AttrParams, mut item: syn::Item) -> TokenStream {
     let name = &variants.name;
     let mod_name = &variants.mod_name;
     let value_name = &variants.value_name;
-    let def_inner = define_inner(value_name);
+    let def_inner = define_inner(value_name, &attr);
+    let included_files = &variants.included_files;
 
-    let guard_name = format_ident!("{}Guard", &name);
+    let guard_name = attr.guard_name(name);
     let def_guard = define_guard(name, &guard_name, &attr);
+    let (bench_mod, bench_reexport) = define_benchmark(vis, name, mod_name, &attr, &item);
+    let kind_name = attr.kind_name(name);
 
     let mut range_items = Vec::with_capacity(variants.ranges.len());
+    let declared_ranges = variants
+        .ranges
+        .iter()
+        .map(|v| (v.start, v.end))
+        .collect::<Vec<_>>();
+    let declared_exacts = variants.exacts.iter().map(|v| v.value).collect::<Vec<_>>();
 
-    let implementations = TokenStream::from_iter(vec![
-        impl_enum_repr(
-            name,
-            value_name,
-            &guard_name,
-            &attr,
-            &variants,
-            &mut range_items,
-        ),
-        impl_deref(name, &attr),
-        impl_conversions(name, &attr),
-        impl_self_eq(name),
-        impl_self_cmp(name),
-        impl_other_eq(name, &attr),
-        impl_other_compare(name, &attr),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Add"),
-            format_ident!("add"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Sub"),
-            format_ident!("sub"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Mul"),
-            format_ident!("mul"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Div"),
-            format_ident!("div"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Rem"),
-            format_ident!("rem"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitAnd"),
-            format_ident!("bitand"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitOr"),
-            format_ident!("bitor"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitXor"),
-            format_ident!("bitxor"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        // impl_binary_op(name, &attr, format_ident!("Shl"), format_ident!("shl")),
-        // impl_binary_op(name, &attr, format_ident!("Shr"), format_ident!("shr")),
-    ]);
+    let implementations = aggregate_impls(
+        name,
+        &attr,
+        vec![
+            impl_enum_repr(
+                name,
+                value_name,
+                &guard_name,
+                &attr,
+                &variants,
+                &mut range_items,
+            ),
+            if attr.store_val.is_none() {
+                impl_deref(name, &attr)
+            } else {
+                TokenStream::new()
+            },
+            impl_secret(name, &attr, &mut item),
+            impl_conversions(name, &attr),
+            impl_range_summary(name, &attr),
+            impl_any_clamped(name, &attr),
+            impl_char_conversions(name, &attr, variants.invalid.is_none()),
+            impl_canonical_round_trip(name, &attr),
+            impl_metrics(name, &attr),
+            impl_invalid_sampler(name, &attr),
+            impl_fingerprint(
+                name,
+                &attr,
+                &declared_ranges,
+                &declared_exacts,
+                &fingerprint_extra(&variants),
+            ),
+            impl_reflect(name, &attr, &declared_ranges, &declared_exacts),
+            impl_fuzz_target(name, &attr, FuzzedType::Enum),
+            impl_runtime_bounds(name, &attr),
+            impl_default_array(name, const_default(&attr, &variants)),
+            impl_freeze(name),
+            impl_into_raw(name, &attr),
+            impl_bounded_integer(name, &attr),
+            impl_deranged(name, &attr),
+            impl_bytemuck(name, &attr, &mut item),
+            impl_null(name, &attr, &mut item),
+            impl_separated(name, &attr),
+            impl_self_eq(name),
+            impl_self_cmp(name),
+            impl_other_eq(name, &attr),
+            impl_other_compare(name, &attr),
+            impl_external_mapping(name, &attr, &variants),
+            impl_transitions(name, &variants),
+            impl_variant_paths(name, &item),
+            impl_explain(name, &attr, &variants),
+            impl_diff_display(name),
+            impl_gaps(name, &attr, &variants),
+            impl_kinds(name, &kind_name, &attr, &variants, &item),
+            impl_exact_values(name, &attr, &variants),
+            impl_invalid_serde(name, &attr, &variants, &mut item),
+            impl_aliases(name, &attr, &variants),
+            impl_name_aliases(name, &variants),
+            impl_serde_names(name, &attr, &variants, &mut item),
+            impl_validated_deserialize(name, &attr, &mut item),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Add"),
+                format_ident!("add"),
+                attr.behavior_for("add"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Sub"),
+                format_ident!("sub"),
+                attr.behavior_for("sub"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Mul"),
+                format_ident!("mul"),
+                attr.behavior_for("mul"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Div"),
+                format_ident!("div"),
+                attr.behavior_for("div"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Rem"),
+                format_ident!("rem"),
+                attr.behavior_for("rem"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitAnd"),
+                format_ident!("bitand"),
+                attr.behavior_for("bitand"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitOr"),
+                format_ident!("bitor"),
+                attr.behavior_for("bitor"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitXor"),
+                format_ident!("bitxor"),
+                attr.behavior_for("bitxor"),
+                None,
+                None,
+            ),
+            // impl_binary_op(name, &attr, format_ident!("Shl"), format_ident!("shl")),
+            // impl_binary_op(name, &attr, format_ident!("Shr"), format_ident!("shr")),
+        ],
+    );
+
+    // A type or item that already has one of the names keeps it.
+    let stable_names = [
+        (&guard_name, "Guard"),
+        (&kind_name, "Kind"),
+        (value_name, "Value"),
+    ]
+    .into_iter()
+    .filter(|(ident, alias)| name != alias && *ident != alias)
+    .map(|(ident, alias)| {
+        let alias = format_ident!("{}", alias);
+        quote!(#ident as #alias)
+    });
+
+    let imports = module_imports(&attr);
 
     quote! {
         #vis mod #mod_name {
-            use super::*;
+            #imports
 
             #(#range_items)*
 
+            #(const _: &[u8] = include_bytes!(#included_files);)*
+
             #item
 
             #def_inner
 
             #def_guard
 
+            // Names that stay the same for every type, such as `status::Guard` with
+            // `module = status`.
+            pub use self::{#(#stable_names),*};
+
             #implementations
+
+            #bench_mod
         }
 
         #vis use #mod_name::#name;
+
+        #[allow(unused_imports)]
+        #vis use #mod_name::#kind_name;
+
+        #bench_reexport
     }
 }
 
-fn define_inner(value_name: &syn::Ident) -> TokenStream {
+fn define_inner(value_name: &syn::Ident, attr: &AttrParams) -> TokenStream {
+    if attr.secret_kw.is_some() {
+        return quote! {
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct #value_name<T>(T);
+
+            impl<T> std::fmt::Debug for #value_name<T> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str("<redacted>")
+                }
+            }
+        };
+    }
+
     quote! {
-        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
-        pub struct #value_name<T>(pub(self) T);
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ::checked_rs::__private::serde::Serialize)]
+        #[serde(crate = "::checked_rs::__private::serde")]
+        pub struct #value_name<T>(T);
 
         impl<T> std::fmt::Debug for #value_name<T>
         where
@@ -162,34 +269,89 @@ fn impl_enum_repr(
     range_items: &mut Vec<TokenStream>,
 ) -> TokenStream {
     let integer = &attr.integer;
+    let storage = attr.storage_type();
     let behavior = &attr.behavior_val;
+    let context = range_context(name, attr);
+    let (secret, serialize) = if attr.secret_kw.is_some() {
+        (quote!(secret), TokenStream::new())
+    } else {
+        (
+            TokenStream::new(),
+            quote!(::checked_rs::__private::serde::Serialize,),
+        )
+    };
     let lower_limit = attr.lower_limit_token();
     let upper_limit = attr.upper_limit_token();
 
+    // When a narrower `store` type is given, values are narrowed on the way in and widened on the way out.
+    let (narrow, widen) = if attr.store_val.is_some() {
+        (quote!(as #storage), quote!(as #integer))
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
     let mut factory_methods = Vec::with_capacity(variants.exacts.len());
     let mut is_exact_case_method = Vec::with_capacity(variants.exacts.len());
     let mut is_range_case_method = Vec::with_capacity(variants.ranges.len());
     let mut from_exact_cases = Vec::with_capacity(variants.exacts.len());
     let mut from_range_cases = Vec::with_capacity(variants.ranges.len());
     let mut as_primitive_cases = Vec::with_capacity(variants.exacts.len());
+    let mut into_primitive_cases = Vec::with_capacity(variants.exacts.len());
+
+    // With `codegen = table`, each distinct variant gets an index and a constructor arm, and
+    // every exact value or range becomes a row of a sorted table searched at runtime. Only
+    // `from_primitive` needs it, since `as_primitive`, `into_primitive` and the factory methods
+    // already expand to one arm or method per variant rather than per value.
+    let mut table_rows = Vec::with_capacity(variants.exacts.len() + variants.ranges.len());
+    let mut table_indices = std::collections::BTreeMap::new();
+    let mut table_cases = Vec::new();
 
     let mut is_catchall_case_method = None;
+    let mut is_method_names = Vec::with_capacity(variants.exacts.len() + variants.ranges.len() + 1);
     let from_catchall_case;
 
     // Generate exact match cases
     for ExactVariant { ident, value } in &variants.exacts {
+        let key = ident.to_string();
+        let index = table_indices.len();
+        let index = *table_indices.entry(key).or_insert_with(|| {
+            table_cases.push(quote! {
+                #index => Self::#ident(#value_name(n #narrow)),
+            });
+
+            index
+        });
+        table_rows.push((*value, *value, index));
+
         let value = syn::parse_str::<TokenStream>(&value.to_string()).unwrap();
 
+        from_exact_cases.push(quote! {
+            #value => Self::#ident(#value_name(n #narrow)),
+        });
+    }
+
+    // A variant may claim several exact values, so the per-variant items use its lowest value
+    let mut exact_idents = std::collections::BTreeMap::new();
+
+    for ExactVariant { ident, value } in &variants.exacts {
+        exact_idents
+            .entry(ident.to_string())
+            .and_modify(|(_, min): &mut (&syn::Ident, NumberValue)| *min = (*min).min(*value))
+            .or_insert((ident, *value));
+    }
+
+    for (ident, value) in exact_idents.into_values() {
         let method_name = format_ident!("new_{}", ident.to_string().to_case(Case::Snake));
 
         factory_methods.push(quote! {
             #[inline(always)]
-            pub fn #method_name() -> Self {
-                Self::from_primitive(#value).expect("value should be within bounds")
+            pub const fn #method_name() -> Self {
+                Self::#ident(#value_name(#value #narrow))
             }
         });
 
         let method_name = format_ident!("is_{}", ident.to_string().to_case(Case::Snake));
+        is_method_names.push(method_name.clone());
 
         is_exact_case_method.push(quote! {
             #[inline(always)]
@@ -198,46 +360,32 @@ fn impl_enum_repr(
             }
         });
 
-        from_exact_cases.push(quote! {
-            #value => Self::#ident(#value_name(n)),
-        });
-
         as_primitive_cases.push(quote! {
             Self::#ident(#value_name(n)) => n,
         });
-    }
-
-    let mut range_tokens = Vec::with_capacity(3);
 
-    for RangeVariant {
-        ident,
-        start,
-        end,
-        half_open,
-    } in &variants.ranges
-    {
-        let kind = attr.kind();
-
-        let range_item_name = format_ident!("{}Value", ident);
-        let range_item_end = {
-            let val = end.unwrap_or_else(|| NumberArg::new_max_constant(kind).into_value(kind));
+        into_primitive_cases.push(quote! {
+            Self::#ident(#value_name(n)) => *n #widen,
+        });
+    }
 
-            if !half_open {
-                val - 1
-            } else {
-                val
-            }
-        };
+    for RangeVariant { ident, start, end } in &variants.ranges {
+        let range_item_name = attr.range_value_name(ident);
+        let range_item_start = syn::parse_str::<TokenStream>(&start.to_string()).unwrap();
+        let range_item_end = syn::parse_str::<TokenStream>(&end.to_string()).unwrap();
 
         range_items.push(quote! {
             #[clamped(
-                #integer as Hard,
-                default = #start,
+                #storage as Hard,
+                default = #range_item_start,
                 behavior = #behavior,
-                lower = #start,
+                lower = #range_item_start,
                 upper = #range_item_end,
+                #secret
             )]
-            #[derive(Debug, Clone, Copy, Hash, serde::Serialize, serde::Deserialize)]
+            #[derive(Debug, Clone, Copy, Hash, #serialize ::checked_rs::__private::serde::Deserialize)]
+            #[serde(crate = "::checked_rs::__private::serde")]
+            #[allow(clippy::derived_hash_with_manual_eq)]
             pub struct #range_item_name;
 
             impl From<#range_item_name> for #name {
@@ -274,35 +422,36 @@ fn impl_enum_repr(
             }
         });
 
-        range_tokens.clear();
-
-        if let Some(start) = start {
-            let start = syn::parse_str::<TokenStream>(&start.to_string()).unwrap();
-
-            range_tokens.push(quote! {
-                #start
-            });
-        }
+        let method_name = format_ident!("try_new_{}", ident.to_string().to_case(Case::Snake));
 
-        if *half_open {
-            range_tokens.push(quote! {
-                ..
-            });
-        } else {
-            range_tokens.push(quote! {
-                ..=
-            });
-        }
+        factory_methods.push(quote! {
+            /// Create this variant from a value within its range.
+            #[inline(always)]
+            pub const fn #method_name(n: #integer) -> std::result::Result<Self, ClampError<#integer>> {
+                if n < #start {
+                    Err(ClampError::TooSmall { val: n, min: #start })
+                } else if n > #end {
+                    Err(ClampError::TooLarge { val: n, max: #end })
+                } else {
+                    Ok(Self::#ident(#range_item_name::new_const(n #narrow)))
+                }
+            }
+        });
 
-        if let Some(end) = end {
-            let end = syn::parse_str::<TokenStream>(&end.to_string()).unwrap();
+        if cfg!(feature = "anyhow-compat") {
+            let compat_name = format_ident!("new_{}", ident.to_string().to_case(Case::Snake));
 
-            range_tokens.push(quote! {
-                #end
+            factory_methods.push(quote! {
+                /// Create this variant from a value within its range.
+                #[inline(always)]
+                pub fn #compat_name(n: #integer) -> ::checked_rs::__private::anyhow::Result<Self> {
+                    Ok(Self::#method_name(n)?)
+                }
             });
         }
 
         let method_name = format_ident!("is_{}", ident.to_string().to_case(Case::Snake));
+        is_method_names.push(method_name.clone());
 
         is_range_case_method.push(quote! {
             #[inline(always)]
@@ -311,17 +460,107 @@ fn impl_enum_repr(
             }
         });
 
+        let index = table_indices.len();
+        table_indices.insert(ident.to_string(), index);
+        table_rows.push((*start, *end, index));
+        table_cases.push(quote! {
+            #index => Self::#ident(#range_item_name::new(n #narrow)),
+        });
+
         from_range_cases.push(quote! {
-            #(#range_tokens)* => Self::#ident(#range_item_name::new(n)),
+            #range_item_start..=#range_item_end => Self::#ident(#range_item_name::new(n #narrow)),
         });
 
         as_primitive_cases.push(quote! {
             Self::#ident(n) => n.as_primitive(),
         });
+
+        into_primitive_cases.push(quote! {
+            Self::#ident(n) => n.into_primitive() #widen,
+        });
+    }
+
+    // Values included from a const slice are searched once no exact or range variant matched,
+    // after checking at compile time that the slice stays within the limits.
+    let mut from_include_cases = Vec::with_capacity(variants.includes.len());
+    let mut is_include_case_method = Vec::with_capacity(variants.includes.len());
+    let mut include_checks = Vec::with_capacity(variants.includes.len());
+
+    for IncludeVariant { ident, path } in &variants.includes {
+        let message = format!(
+            "a value of `{}` is outside of the limits of `{}`",
+            path.to_token_stream(),
+            name
+        );
+
+        include_checks.push(quote! {
+            const _: () = {
+                let values: &[#integer] = &#path;
+                let mut i = 0;
+
+                while i < values.len() {
+                    assert!(values[i] >= #lower_limit && values[i] <= #upper_limit, #message);
+                    i += 1;
+                }
+            };
+        });
+
+        from_include_cases.push(quote! {
+            n if #path.contains(&n) => Self::#ident(#value_name(n #narrow)),
+        });
+
+        let method_name = format_ident!("is_{}", ident.to_string().to_case(Case::Snake));
+        is_method_names.push(method_name.clone());
+
+        is_include_case_method.push(quote! {
+            #[inline(always)]
+            pub fn #method_name(&self) -> bool {
+                matches!(self, Self::#ident(_))
+            }
+        });
+
+        as_primitive_cases.push(quote! {
+            Self::#ident(#value_name(n)) => n,
+        });
+
+        into_primitive_cases.push(quote! {
+            Self::#ident(#value_name(n)) => *n #widen,
+        });
+    }
+
+    // Mask variants are tried in declaration order once no exact or range variant matched.
+    let mut from_mask_cases = Vec::with_capacity(variants.masks.len());
+    let mut is_mask_case_method = Vec::with_capacity(variants.masks.len());
+
+    for MaskVariant { ident, mask } in &variants.masks {
+        let mask = syn::parse_str::<TokenStream>(&mask.to_string()).unwrap();
+
+        from_mask_cases.push(quote! {
+            n if (n & #mask) == #mask => Self::#ident(#value_name(n #narrow)),
+        });
+
+        let method_name = format_ident!("is_{}", ident.to_string().to_case(Case::Snake));
+        is_method_names.push(method_name.clone());
+
+        is_mask_case_method.push(quote! {
+            #[inline(always)]
+            pub fn #method_name(&self) -> bool {
+                matches!(self, Self::#ident(_))
+            }
+        });
+
+        as_primitive_cases.push(quote! {
+            Self::#ident(#value_name(n)) => n,
+        });
+
+        into_primitive_cases.push(quote! {
+            Self::#ident(#value_name(n)) => *n #widen,
+        });
     }
 
     if let Some(other) = &variants.catchall {
         let method_name = format_ident!("is_{}", other.to_string().to_lowercase());
+        is_method_names.push(method_name.clone());
 
         is_catchall_case_method = Some(quote! {
             #[inline(always)]
@@ -331,28 +570,231 @@ fn impl_enum_repr(
         });
 
         from_catchall_case = quote! {
-            _ => Self::#other(#value_name(n)),
+            _ => Self::#other(#value_name(n #narrow)),
         };
 
         as_primitive_cases.push(quote! {
             Self::#other(#value_name(n)) => n,
         });
+
+        into_primitive_cases.push(quote! {
+            Self::#other(#value_name(n)) => *n #widen,
+        });
     } else {
         from_catchall_case = quote! {
-            _ => ::anyhow::bail!("invalid value: {}", n)
+            _ => return Err(::checked_rs::__private::anyhow::anyhow!("invalid value: {}", n)
+                .context(format!("invalid value {} {}", n, #context)))
         };
     }
 
+    // The `Invalid` variant always holds the full-width primitive, since it may not fit `store`.
+    let invalid_methods = if let Some(invalid) = &variants.invalid {
+        as_primitive_cases.push(quote! {
+            Self::#invalid(#value_name(n)) => n,
+        });
+
+        into_primitive_cases.push(quote! {
+            Self::#invalid(#value_name(n)) => *n,
+        });
+
+        quote! {
+            /// Create the value from any primitive, capturing values that are not allowed in
+            /// `Invalid` instead of failing.
+            #[inline(always)]
+            pub fn from_primitive_or_invalid(n: #integer) -> Self {
+                <Self as ClampedInteger<#integer>>::from_primitive(n)
+                    .unwrap_or(Self::#invalid(#value_name(n)))
+            }
+
+            #[inline(always)]
+            pub fn is_invalid(&self) -> bool {
+                matches!(self, Self::#invalid(_))
+            }
+
+            /// Get the value back unless it was captured in `Invalid`.
+            #[inline(always)]
+            pub fn into_valid(self) -> ::checked_rs::__private::anyhow::Result<Self> {
+                match self {
+                    Self::#invalid(#value_name(n)) => return Err(::checked_rs::__private::anyhow::anyhow!("invalid value: {}", n)
+                .context(format!("invalid value {} {}", n, #context))),
+                    valid => Ok(valid),
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let from_primitive_body = if attr.uses_table_codegen() {
+        table_rows.sort_by_key(|(start, ..)| *start);
+
+        let len = table_rows.len();
+        let rows = table_rows.iter().map(|(start, end, index)| {
+            let start = syn::parse_str::<TokenStream>(&start.to_string()).unwrap();
+            let end = syn::parse_str::<TokenStream>(&end.to_string()).unwrap();
+
+            quote! { (#start, #end, #index) }
+        });
+
+        quote! {
+            const TABLE: [(#integer, #integer, usize); #len] = [#(#rows),*];
+
+            let found = TABLE.binary_search_by(|&(start, end, _)| {
+                if end < n {
+                    std::cmp::Ordering::Less
+                } else if start > n {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            });
+
+            Ok(match found {
+                Ok(i) => match TABLE[i].2 {
+                    #(#table_cases)*
+                    _ => unreachable!(),
+                },
+                Err(_) => match n {
+                    #(#from_include_cases)*
+                    #(#from_mask_cases)*
+                    #from_catchall_case
+                },
+            })
+        }
+    } else if attr.uses_bitset_lookup() {
+        quote! {
+            Ok(if Self::contains_exact(n) {
+                match n {
+                    #(#from_exact_cases)*
+                    _ => unreachable!(),
+                }
+            } else {
+                match n {
+                    #(#from_range_cases)*
+                    #(#from_include_cases)*
+                    #(#from_mask_cases)*
+                    #from_catchall_case
+                }
+            })
+        }
+    } else {
+        quote! {
+            Ok(match n {
+                #(#from_exact_cases)*
+                #(#from_range_cases)*
+                #(#from_include_cases)*
+                #(#from_mask_cases)*
+                #from_catchall_case
+            })
+        }
+    };
+
+    let bitset_methods = if attr.uses_bitset_lookup() {
+        let offset = |value: NumberValue| {
+            let lower = attr.lower_limit_value().to_string();
+            let value = value.to_string();
+
+            match (lower.parse::<i128>(), value.parse::<i128>()) {
+                (Ok(lower), Ok(value)) => (value - lower) as usize,
+                _ => (value.parse::<u128>().unwrap() - lower.parse::<u128>().unwrap()) as usize,
+            }
+        };
+
+        let mut words = vec![0u64; offset(attr.upper_limit_value()) / 64 + 1];
+
+        for ExactVariant { value, .. } in &variants.exacts {
+            let i = offset(*value);
+            words[i / 64] |= 1 << (i % 64);
+        }
+
+        let len = words.len();
+
+        quote! {
+            /// Whether an `#[eq]` variant declares `n`, which is looked up in a static bitset.
+            #[inline(always)]
+            pub fn contains_exact(n: #integer) -> bool {
+                const EXACT_BITS: [u64; #len] = [#(#words),*];
+
+                if !(#lower_limit..=#upper_limit).contains(&n) {
+                    return false;
+                }
+
+                let i = n.abs_diff(#lower_limit) as usize;
+                EXACT_BITS[i >> 6] & (1 << (i & 63)) != 0
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let staged_method_names = is_method_names
+        .iter()
+        .map(|method_name| format_ident!("staged_{}", method_name))
+        .collect::<Vec<_>>();
+
+    let if_method_names = is_method_names
+        .iter()
+        .map(|method_name| {
+            let method_name = method_name.to_string();
+            format_ident!(
+                "if_{}",
+                method_name.strip_prefix("is_").unwrap_or(&method_name)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // A reference to the primitive can only be handed out when it is stored as-is.
+    let as_primitive_method = if attr.store_val.is_none() {
+        quote! {
+            #[inline(always)]
+            pub fn as_primitive(&self) -> &#integer {
+                match self {
+                    #(#as_primitive_cases)*
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+    let as_primitive_impl = if attr.store_val.is_none() {
+        quote! {
+            impl AsPrimitive<#integer> for #name {
+                #[inline(always)]
+                fn as_primitive(&self) -> &#integer {
+                    Self::as_primitive(self)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
     let default_value = attr.default_val.into_literal_as_tokens(attr.kind());
+    let from_primitive_body = count_rejections(
+        attr,
+        quote! {
+            if !(#lower_limit..=#upper_limit).contains(&n) {
+                return Err(::checked_rs::__private::anyhow::anyhow!("invalid value: {}", n)
+                .context(format!("invalid value {} {}", n, #context)))
+            }
+
+            #from_primitive_body
+        },
+    );
+
     let methods = TokenStream::from_iter(
         factory_methods
             .into_iter()
-            .chain(is_exact_case_method.into_iter())
-            .chain(is_range_case_method.into_iter())
-            .chain(is_catchall_case_method.into_iter()),
+            .chain(is_exact_case_method)
+            .chain(is_range_case_method)
+            .chain(is_include_case_method)
+            .chain(is_mask_case_method)
+            .chain(is_catchall_case_method),
     );
 
     quote! {
+        #(#include_checks)*
+
         impl InherentLimits<#integer> for #name {
             const MIN: #integer = #lower_limit;
             const MAX: #integer = #upper_limit;
@@ -364,22 +806,20 @@ fn impl_enum_repr(
 
         unsafe impl ClampedInteger<#integer> for #name {
             #[inline(always)]
-            fn from_primitive(n: #integer) -> ::anyhow::Result<Self> {
-                Ok(match n {
-                    #(#from_exact_cases)*
-                    #(#from_range_cases)*
-                    #from_catchall_case
-                })
+            fn from_primitive(n: #integer) -> ::checked_rs::__private::anyhow::Result<Self> {
+                #from_primitive_body
             }
 
             #[inline(always)]
-            fn as_primitive(&self) -> &#integer {
-                match &*self {
-                    #(#as_primitive_cases)*
+            fn into_primitive(&self) -> #integer {
+                match self {
+                    #(#into_primitive_cases)*
                 }
             }
         }
 
+        #as_primitive_impl
+
         unsafe impl ClampedEnum<#integer> for #name {}
 
         impl Default for #name {
@@ -392,8 +832,32 @@ fn impl_enum_repr(
         impl #name {
             #methods
 
+            #invalid_methods
+
+            #bitset_methods
+
+            #as_primitive_method
+
+            /// Call `f` with the primitive value, whichever variant holds it.
+            #[inline(always)]
+            pub fn map_value<R>(&self, f: impl FnOnce(#integer) -> R) -> R {
+                f(<Self as ClampedInteger<#integer>>::into_primitive(self))
+            }
+
+            #(
+                /// Call `f` with the primitive value if it belongs to this variant.
+                #[inline(always)]
+                pub fn #if_method_names<R>(&self, f: impl FnOnce(#integer) -> R) -> Option<R> {
+                    if self.#is_method_names() {
+                        Some(self.map_value(f))
+                    } else {
+                        None
+                    }
+                }
+            )*
+
             #[inline(always)]
-            pub fn validate(value: #integer) -> ::anyhow::Result<()> {
+            pub fn validate(value: #integer) -> ::checked_rs::__private::anyhow::Result<()> {
                 <Self as ClampedInteger<#integer>>::from_primitive(value)?;
                 Ok(())
             }
@@ -404,5 +868,985 @@ fn impl_enum_repr(
             }
         }
 
+        impl<'a> #guard_name<'a> {
+            /// The variant the staged value would become if committed.
+            #[inline(always)]
+            pub fn staged(&self) -> ::checked_rs::__private::anyhow::Result<#name> {
+                <#name as ClampedInteger<#integer>>::from_primitive(self.0)
+            }
+
+            #(
+                #[inline(always)]
+                pub fn #staged_method_names(&self) -> bool {
+                    self.staged().is_ok_and(|v| v.#is_method_names())
+                }
+            )*
+        }
+    }
+}
+
+/// Describe the variants that `RANGE_FINGERPRINT` cannot see through the declared ranges and
+/// exact values, in a form that does not depend on the names of the variants.
+fn fingerprint_extra(variants: &Variants) -> Vec<String> {
+    let masks = variants
+        .masks
+        .iter()
+        .map(|v| v.mask.to_string())
+        .collect::<Vec<_>>();
+    let includes = variants
+        .includes
+        .iter()
+        .map(|v| v.path.to_token_stream().to_string().replace(' ', ""))
+        .collect::<Vec<_>>();
+
+    vec![
+        format!("masks={:?}", masks),
+        format!("includes={:?}", includes),
+        format!("other={}", variants.catchall.is_some()),
+        format!("invalid={}", variants.invalid.is_some()),
+    ]
+}
+
+/// Build the default value in a `const` context by resolving its variant the way `from_primitive`
+/// does, or `None` when the variant depends on the values of an `include_values` const.
+fn const_default(attr: &AttrParams, variants: &Variants) -> Option<TokenStream> {
+    let value_name = &variants.value_name;
+    let default = attr.default_value();
+    let narrow = if attr.store_val.is_some() {
+        let storage = attr.storage_type();
+        quote!(as #storage)
+    } else {
+        TokenStream::new()
+    };
+
+    if let Some(v) = variants.exacts.iter().find(|v| v.value == default) {
+        let ident = &v.ident;
+        return Some(quote!(Self::#ident(#value_name(#default #narrow))));
+    }
+
+    if let Some(v) = variants
+        .ranges
+        .iter()
+        .find(|v| v.start <= default && default <= v.end)
+    {
+        let ident = &v.ident;
+        let range_item_name = attr.range_value_name(ident);
+        return Some(quote!(Self::#ident(#range_item_name::new_const(#default #narrow))));
+    }
+
+    if !variants.includes.is_empty() {
+        return None;
+    }
+
+    let bits = default.to_string().parse::<u128>().unwrap_or_default();
+
+    let ident = variants
+        .masks
+        .iter()
+        .find(|v| {
+            let mask = v.mask.to_string().parse::<u128>().unwrap();
+            bits & mask == mask
+        })
+        .map(|v| &v.ident)
+        .or(variants.catchall.as_ref())?;
+
+    Some(quote!(Self::#ident(#value_name(#default #narrow))))
+}
+
+/// Generate the `From`/`TryFrom` conversions between the enum and the type given by `maps_to`.
+fn impl_external_mapping(name: &syn::Ident, attr: &AttrParams, variants: &Variants) -> TokenStream {
+    let Some(external) = &attr.maps_to_val else {
+        return TokenStream::new();
+    };
+
+    let integer = &attr.integer;
+    let mut from_cases = Vec::with_capacity(variants.mappings.len());
+    let mut try_from_cases = Vec::with_capacity(variants.mappings.len());
+    let mut seen = std::collections::HashSet::with_capacity(variants.mappings.len());
+
+    for ExternalMapping {
+        ident, path, value, ..
+    } in &variants.mappings
+    {
+        from_cases.push(quote! {
+            #name::#ident(..) => #path,
+        });
+
+        // The first variant to claim an external value wins when converting back
+        if !seen.insert(path.to_token_stream().to_string()) {
+            continue;
+        }
+
+        // Use the lowest value that belongs to the variant, or the value named for the catchall
+        let representative = value
+            .or_else(|| {
+                variants
+                    .exacts
+                    .iter()
+                    .filter(|v| &v.ident == ident)
+                    .map(|v| v.value)
+                    .min()
+            })
+            .or_else(|| {
+                variants
+                    .ranges
+                    .iter()
+                    .find(|v| &v.ident == ident)
+                    .map(|v| v.start)
+            })
+            .or_else(|| {
+                variants
+                    .masks
+                    .iter()
+                    .find(|v| &v.ident == ident)
+                    .map(|v| v.mask)
+            });
+
+        if let Some(value) = representative {
+            let value = syn::parse_str::<TokenStream>(&value.to_string()).unwrap();
+
+            try_from_cases.push(quote! {
+                #path => <#name as ClampedInteger<#integer>>::from_primitive(#value),
+            });
+        }
+    }
+
+    quote! {
+        impl From<#name> for #external {
+            #[inline(always)]
+            fn from(val: #name) -> Self {
+                match val {
+                    #(#from_cases)*
+                }
+            }
+        }
+
+        impl TryFrom<#external> for #name {
+            type Error = ::checked_rs::__private::anyhow::Error;
+
+            #[inline(always)]
+            #[allow(unreachable_patterns)]
+            fn try_from(val: #external) -> ::checked_rs::__private::anyhow::Result<Self> {
+                match val {
+                    #(#try_from_cases)*
+                    _ => ::checked_rs::__private::anyhow::bail!("no variant of `{}` maps to the given value", stringify!(#name)),
+                }
+            }
+        }
+    }
+}
+
+/// Generate the names and paths of the variants, in declaration order.
+fn impl_variant_paths(name: &syn::Ident, item: &syn::Item) -> TokenStream {
+    let syn::Item::Enum(data) = item else {
+        unreachable!()
+    };
+
+    let type_name = name.to_string();
+    let variant_idents = data.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let variant_names = variant_idents
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect::<Vec<_>>();
+
+    quote! {
+        impl #name {
+            /// The names of the variants, in declaration order.
+            #[inline(always)]
+            pub fn leaf_variants() -> &'static [&'static str] {
+                &[#(#variant_names),*]
+            }
+
+            /// The path of the variant of this value, such as `ResponseCode/NotFound`.
+            #[inline(always)]
+            pub fn path(&self) -> VariantPath {
+                match self {
+                    #(Self::#variant_idents(..) => VariantPath::new(&[#type_name, #variant_names]),)*
+                }
+            }
+
+            /// Iterate over the path of every variant depth first, in declaration order.
+            pub fn variant_paths() -> impl Iterator<Item = VariantPath> {
+                [#(VariantPath::new(&[#type_name, #variant_names])),*].into_iter()
+            }
+
+            #[inline(always)]
+            fn variant_name(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_idents(..) => #variant_names,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generate the checked state transitions described by the `#[transitions]` attribute.
+fn impl_transitions(name: &syn::Ident, variants: &Variants) -> TokenStream {
+    let Some(transitions) = &variants.transitions else {
+        return TokenStream::new();
+    };
+
+    let allowed_cases = transitions.iter().map(|Transition { from, to }| match to {
+        Some(to) => quote! { (Self::#from(..), Self::#to(..)) => true, },
+        None => quote! { (Self::#from(..), _) => true, },
+    });
+
+    quote! {
+        impl #name {
+            /// Check whether the transitions of this enum allow moving from this variant to the
+            /// variant of `next`.
+            #[inline(always)]
+            #[allow(unreachable_patterns)]
+            pub fn can_transition_to(&self, next: &Self) -> bool {
+                match (self, next) {
+                    #(#allowed_cases)*
+                    _ => false,
+                }
+            }
+
+            /// Move to `next` if the transitions of this enum allow it.
+            #[inline(always)]
+            pub fn transition(self, next: Self) -> std::result::Result<Self, TransitionError> {
+                if self.can_transition_to(&next) {
+                    Ok(next)
+                } else {
+                    Err(TransitionError {
+                        type_name: stringify!(#name),
+                        from: self.variant_name(),
+                        to: next.variant_name(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// The constructor of a variant that holds a single exact value.
+fn single_value_constructor(ident: &syn::Ident) -> syn::Ident {
+    format_ident!("new_{}", ident.to_string().to_case(Case::Snake))
+}
+
+/// Generate `from_primitive_or_alias`, which maps the legacy values given by `#[alias(..)]` onto
+/// their variants.
+fn impl_aliases(name: &syn::Ident, attr: &AttrParams, variants: &Variants) -> TokenStream {
+    if variants.aliases.is_empty() {
+        return TokenStream::new();
+    }
+
+    let integer = &attr.integer;
+    let cases = variants.aliases.iter().map(|AliasValue { ident, value }| {
+        let value = syn::parse_str::<TokenStream>(&value.to_string()).unwrap();
+        let constructor = single_value_constructor(ident);
+
+        quote! {
+            #value => Ok(Self::#constructor()),
+        }
+    });
+
+    quote! {
+        impl #name {
+            /// Create a value from `n` like `from_primitive`, but also accept the legacy values
+            /// given by `#[alias(..)]`, which map onto their variants.
+            pub fn from_primitive_or_alias(n: #integer) -> ::checked_rs::__private::anyhow::Result<Self> {
+                match n {
+                    #(#cases)*
+                    _ => <Self as ClampedInteger<#integer>>::from_primitive(n),
+                }
+            }
+        }
+    }
+}
+
+/// Generate a constant and an `is_*` predicate for each name given by `#[aka(..)]`, which stand
+/// for the value of their variant. Values are still created through the variant's own name.
+fn impl_name_aliases(name: &syn::Ident, variants: &Variants) -> TokenStream {
+    if variants.name_aliases.is_empty() {
+        return TokenStream::new();
+    }
+
+    let items = variants
+        .name_aliases
+        .iter()
+        .map(|NameAlias { ident, variant }| {
+            let const_name = format_ident!("{}", ident.to_string().to_case(Case::UpperSnake));
+            let is_method = format_ident!("is_{}", ident.to_string().to_case(Case::Snake));
+            let is_variant = format_ident!("is_{}", variant.to_string().to_case(Case::Snake));
+            let constructor = single_value_constructor(variant);
+            let doc = format!("Another name for `{}::{}`.", name, variant);
+
+            quote! {
+                #[doc = #doc]
+                pub const #const_name: Self = Self::#constructor();
+
+                #[doc = #doc]
+                #[inline(always)]
+                pub fn #is_method(&self) -> bool {
+                    self.#is_variant()
+                }
+            }
+        });
+
+    quote! {
+        impl #name {
+            #(#items)*
+        }
+    }
+}
+
+/// Implement serde for enums with the `serde_names` parameter. Variants holding a single exact
+/// value are serialized as their name and the others as the primitive, while deserializing
+/// accepts either.
+fn impl_serde_names(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    variants: &Variants,
+    item: &mut syn::Item,
+) -> TokenStream {
+    if attr.serde_names_kw.is_none() {
+        return TokenStream::new();
+    }
+
+    let syn::Item::Enum(item) = item else {
+        unreachable!()
+    };
+
+    for derive_name in ["Serialize", "Deserialize"] {
+        if let Some(path) = take_derive(&mut item.attrs, derive_name) {
+            abort!(
+                path,
+                "`serde_names` enums implement `{}` by variant name; remove the derive",
+                derive_name
+            );
+        }
+    }
+
+    let integer = &attr.integer;
+    let visitor_name = format_ident!("{}Visitor", name);
+    let idents = variants
+        .serde_names
+        .iter()
+        .map(|v| &v.ident)
+        .collect::<Vec<_>>();
+    let names = variants
+        .serde_names
+        .iter()
+        .map(|v| &v.name)
+        .collect::<Vec<_>>();
+    let accepted = variants.serde_names.iter().map(|v| {
+        let name = &v.name;
+        let aliases = &v.aliases;
+
+        quote!(#name #(| #aliases)*)
+    });
+    let constructors = idents
+        .iter()
+        .map(|ident| single_value_constructor(ident))
+        .collect::<Vec<_>>();
+    let from_number = if variants.aliases.is_empty() {
+        quote!(<#name as ClampedInteger<#integer>>::from_primitive)
+    } else {
+        quote!(#name::from_primitive_or_alias)
+    };
+
+    quote! {
+        impl ::checked_rs::__private::serde::Serialize for #name {
+            #[allow(unreachable_patterns)]
+            fn serialize<S: ::checked_rs::__private::serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                match self {
+                    #(Self::#idents(..) => serializer.serialize_str(#names),)*
+                    _ => ::checked_rs::__private::serde::Serialize::serialize(&self.into_primitive(), serializer),
+                }
+            }
+        }
+
+        impl<'de> ::checked_rs::__private::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::checked_rs::__private::serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                struct #visitor_name;
+
+                impl<'de> ::checked_rs::__private::serde::de::Visitor<'de> for #visitor_name {
+                    type Value = #name;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str(concat!("a variant name or a value of `", stringify!(#name), "`"))
+                    }
+
+                    fn visit_str<E: ::checked_rs::__private::serde::de::Error>(self, v: &str) -> std::result::Result<#name, E> {
+                        const NAMES: &[&str] = &[#(#names),*];
+
+                        match v {
+                            #(#accepted => Ok(#name::#constructors()),)*
+                            _ => Err(E::unknown_variant(v, NAMES)),
+                        }
+                    }
+
+                    fn visit_u64<E: ::checked_rs::__private::serde::de::Error>(self, v: u64) -> std::result::Result<#name, E> {
+                        self.visit_u128(v as u128)
+                    }
+
+                    fn visit_i64<E: ::checked_rs::__private::serde::de::Error>(self, v: i64) -> std::result::Result<#name, E> {
+                        self.visit_i128(v as i128)
+                    }
+
+                    fn visit_u128<E: ::checked_rs::__private::serde::de::Error>(self, v: u128) -> std::result::Result<#name, E> {
+                        <#integer as TryFrom<u128>>::try_from(v)
+                            .ok()
+                            .and_then(|n| #from_number(n).ok())
+                            .ok_or_else(|| E::custom(format_args!("{} is not a valid `{}`", v, stringify!(#name))))
+                    }
+
+                    fn visit_i128<E: ::checked_rs::__private::serde::de::Error>(self, v: i128) -> std::result::Result<#name, E> {
+                        <#integer as TryFrom<i128>>::try_from(v)
+                            .ok()
+                            .and_then(|n| #from_number(n).ok())
+                            .ok_or_else(|| E::custom(format_args!("{} is not a valid `{}`", v, stringify!(#name))))
+                    }
+                }
+
+                deserializer.deserialize_any(#visitor_name)
+            }
+        }
+    }
+}
+
+/// Implement serde as the primitive for enums with an `Invalid` variant, so that values which are
+/// not allowed survive a round trip instead of failing to deserialize.
+fn impl_invalid_serde(
+    name: &syn::Ident,
+    attr: &AttrParams,
+    variants: &Variants,
+    item: &mut syn::Item,
+) -> TokenStream {
+    if variants.invalid.is_none() {
+        return TokenStream::new();
+    }
+
+    let syn::Item::Enum(item) = item else {
+        unreachable!()
+    };
+
+    for derive_name in ["Serialize", "Deserialize"] {
+        if let Some(path) = take_derive(&mut item.attrs, derive_name) {
+            abort!(
+                path,
+                "`auto_invalid` enums implement `{}` as the primitive; remove the derive",
+                derive_name
+            );
+        }
+    }
+
+    let integer = &attr.integer;
+
+    let serialize = if attr.secret_kw.is_none() {
+        quote! {
+            impl ::checked_rs::__private::serde::Serialize for #name {
+                fn serialize<S: ::checked_rs::__private::serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                    ::checked_rs::__private::serde::Serialize::serialize(&self.into_primitive(), serializer)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #serialize
+
+        impl<'de> ::checked_rs::__private::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::checked_rs::__private::serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                <#integer as ::checked_rs::__private::serde::Deserialize>::deserialize(deserializer).map(Self::from_primitive_or_invalid)
+            }
+        }
+    }
+}
+
+/// Generate `explain`, which reports the variant a primitive maps to or the nearest value within
+/// the limits when it is outside of them.
+fn impl_explain(name: &syn::Ident, attr: &AttrParams, variants: &Variants) -> TokenStream {
+    let integer = &attr.integer;
+    let lower_limit = attr.lower_limit_token();
+    let upper_limit = attr.upper_limit_token();
+
+    let mut cases = std::collections::BTreeMap::new();
+
+    for ident in variants
+        .exacts
+        .iter()
+        .map(|v| &v.ident)
+        .chain(variants.includes.iter().map(|v| &v.ident))
+        .chain(variants.masks.iter().map(|v| &v.ident))
+        .chain(&variants.catchall)
+        .chain(&variants.invalid)
+    {
+        let variant = ident.to_string();
+
+        cases.insert(
+            variant.clone(),
+            quote! { Self::#ident(..) => (#variant, None), },
+        );
+    }
+
+    for RangeVariant { ident, start, end } in &variants.ranges {
+        let variant = ident.to_string();
+        let start = syn::parse_str::<TokenStream>(&start.to_string()).unwrap();
+        let end = syn::parse_str::<TokenStream>(&end.to_string()).unwrap();
+
+        cases.insert(
+            variant.clone(),
+            quote! { Self::#ident(..) => (#variant, Some((#start, #end))), },
+        );
+    }
+
+    let cases = cases.values();
+
+    quote! {
+        impl #name {
+            /// Explain which variant `value` maps to, or which value is the nearest one that is
+            /// allowed when it is outside of the limits.
+            pub fn explain(value: #integer) -> Explanation<#integer> {
+                let type_name = stringify!(#name);
+                let nearest = if value < #lower_limit {
+                    #lower_limit
+                } else if value > #upper_limit {
+                    #upper_limit
+                } else {
+                    match <Self as ClampedInteger<#integer>>::from_primitive(value) {
+                        Ok(this) => {
+                            let (variant, range) = match this {
+                                #(#cases)*
+                            };
+
+                            return Explanation::Variant { type_name, value, variant, range };
+                        }
+                        Err(_) => unreachable!("every value within the limits maps to a variant"),
+                    }
+                };
+
+                Explanation::Invalid { type_name, value, nearest }
+            }
+        }
+    }
+}
+
+/// The most undeclared values that are searched for `#[mask]` matches when listing the gaps.
+const MAX_MASKED_GAP_VALUES: u128 = 1 << 16;
+
+/// The spans of values within the limits that no `#[eq]` or `#[range]` variant declares, split
+/// between the `#[mask]` variants that claim them and the rest, which only `#[other]` accepts.
+struct UndeclaredSpans {
+    /// The spans claimed by each `#[mask]` variant, in declaration order.
+    masked: Vec<Vec<(NumberValue, NumberValue)>>,
+    other: Vec<(NumberValue, NumberValue)>,
+}
+
+/// Find the undeclared spans, or `None` when the values included from a const slice are only known
+/// at runtime or when there are too many undeclared values to check against the masks.
+fn undeclared_spans(attr: &AttrParams, variants: &Variants) -> Option<UndeclaredSpans> {
+    if !variants.includes.is_empty() {
+        return None;
+    }
+
+    let lower = attr.lower_limit_value();
+    let upper = attr.upper_limit_value();
+
+    let gaps = uncovered_spans(
+        lower,
+        upper,
+        variants
+            .exacts
+            .iter()
+            .map(|v| (v.value, v.value))
+            .chain(variants.ranges.iter().map(|v| (v.start, v.end))),
+    );
+
+    if variants.masks.is_empty() {
+        return Some(UndeclaredSpans {
+            masked: Vec::new(),
+            other: gaps,
+        });
+    }
+
+    let bits = |n: NumberValue| n.to_string().parse::<u128>().unwrap();
+    let masks = variants
+        .masks
+        .iter()
+        .map(|v| bits(v.mask))
+        .collect::<Vec<_>>();
+    let total = gaps
+        .iter()
+        .map(|(start, end)| start.span_len(*end))
+        .fold(0u128, u128::saturating_add);
+
+    // Splitting wide gaps around every masked value would bloat the generated code.
+    if total > MAX_MASKED_GAP_VALUES {
+        return None;
+    }
+
+    let mut masked = vec![Vec::new(); masks.len()];
+    let mut other = Vec::new();
+
+    for (start, end) in gaps {
+        for n in bits(start)..=bits(end) {
+            // the first mask that matches claims the value
+            let spans: &mut Vec<(u128, u128)> =
+                match masks.iter().position(|mask| n & mask == *mask) {
+                    Some(i) => &mut masked[i],
+                    None => &mut other,
+                };
+
+            match spans.last_mut() {
+                Some((_, last)) if *last + 1 == n => *last = n,
+                _ => spans.push((n, n)),
+            }
+        }
+    }
+
+    let kind = attr.kind();
+    let cast = |spans: Vec<(u128, u128)>| {
+        spans
+            .into_iter()
+            .map(|(a, b)| {
+                (
+                    NumberValue::U128(a).cast(kind).unwrap(),
+                    NumberValue::U128(b).cast(kind).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Some(UndeclaredSpans {
+        masked: masked.into_iter().map(cast).collect(),
+        other: cast(other),
+    })
+}
+
+/// Generate the fieldless `<Name>Kind` enum with a variant per variant of the enum, along with
+/// `kind` and `ranges_of`, which lists the values each variant accepts. `ranges_of` is left out
+/// when the spans cannot be found at compile time.
+fn impl_kinds(
+    name: &syn::Ident,
+    kind_name: &syn::Ident,
+    attr: &AttrParams,
+    variants: &Variants,
+    item: &syn::Item,
+) -> TokenStream {
+    let syn::Item::Enum(data) = item else {
+        unreachable!()
+    };
+
+    let integer = &attr.integer;
+    let variant_idents = data.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let doc = format!("The variants of [`{}`] without their values.", name);
+    let variant_count = variant_idents.len();
+
+    // the merged spans of each variant, in declaration order
+    let variant_spans = undeclared_spans(attr, variants).map(|undeclared| {
+        let mut spans =
+            std::collections::BTreeMap::<String, Vec<(NumberValue, NumberValue)>>::new();
+        let mut push =
+            |ident: &syn::Ident, span| spans.entry(ident.to_string()).or_default().push(span);
+
+        for v in &variants.exacts {
+            push(&v.ident, (v.value, v.value));
+        }
+
+        for v in &variants.ranges {
+            push(&v.ident, (v.start, v.end));
+        }
+
+        for (v, masked) in variants.masks.iter().zip(undeclared.masked) {
+            for span in masked {
+                push(&v.ident, span);
+            }
+        }
+
+        if let Some(catchall) = &variants.catchall {
+            for span in undeclared.other {
+                push(catchall, span);
+            }
+        }
+
+        variant_idents
+            .iter()
+            .map(|ident| {
+                let mut merged: Vec<(NumberValue, NumberValue)> = Vec::new();
+                let mut list = spans.remove(&ident.to_string()).unwrap_or_default();
+                list.sort();
+
+                for (start, end) in list {
+                    match merged.last_mut() {
+                        Some((_, last)) if *last + 1 == start => *last = end,
+                        _ => merged.push((start, end)),
+                    }
+                }
+
+                merged
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let ranges_of = variant_spans.as_ref().map(|variant_spans| {
+        let arms = variant_idents
+            .iter()
+            .zip(variant_spans)
+            .map(|(ident, merged)| {
+                let merged = merged.iter().map(|(start, end)| {
+                    let start = syn::parse_str::<TokenStream>(&start.to_string()).unwrap();
+                    let end = syn::parse_str::<TokenStream>(&end.to_string()).unwrap();
+
+                    quote!(#start..=#end)
+                });
+
+                quote! {
+                    #kind_name::#ident => {
+                        const SPANS: &[std::ops::RangeInclusive<#integer>] = &[#(#merged),*];
+                        SPANS
+                    }
+                }
+            });
+
+        quote! {
+            /// The spans of values that the variant of `kind` accepts, in ascending order, such
+            /// as for generating validation rules for other languages.
+            pub fn ranges_of(kind: #kind_name) -> &'static [std::ops::RangeInclusive<#integer>] {
+                match kind {
+                    #(#arms)*
+                }
+            }
+        }
+    });
+
+    let kind_codes = impl_kind_codes(
+        name,
+        kind_name,
+        attr,
+        variants,
+        &variant_idents,
+        variant_spans.as_deref(),
+    );
+
+    let kind_ranges = ranges_of.as_ref().map(|_| {
+        quote! {
+            impl KindRanges<#integer> for #kind_name {
+                #[inline(always)]
+                fn ranges(self) -> &'static [std::ops::RangeInclusive<#integer>] {
+                    #name::ranges_of(self)
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ::checked_rs::__private::serde::Serialize)]
+        #[serde(crate = "::checked_rs::__private::serde")]
+        pub enum #kind_name {
+            #(#variant_idents,)*
+        }
+
+        impl VariantKind for #kind_name {
+            const ALL: &'static [Self] = &[#(Self::#variant_idents),*];
+
+            #[inline(always)]
+            fn index(self) -> usize {
+                self as usize
+            }
+        }
+
+        #kind_ranges
+
+        #kind_codes
+
+        impl #name {
+            /// The variant of this value without its value.
+            #[inline(always)]
+            pub fn kind(&self) -> #kind_name {
+                match self {
+                    #(Self::#variant_idents(..) => #kind_name::#variant_idents,)*
+                }
+            }
+
+            #ranges_of
+
+            /// Count how many of the primitives belong to each variant, and how many to none, such
+            /// as for the status codes of a log.
+            pub fn partition<I: IntoIterator<Item = #integer>>(iter: I) -> PartitionedCounts<#kind_name, #variant_count> {
+                let mut counts = PartitionedCounts::new();
+                Self::partition_with(iter, |kind, _| counts.record(kind));
+                counts
+            }
+
+            /// Call `visit` with each primitive and the variant it belongs to, or `None` when it
+            /// belongs to no variant, without allocating.
+            pub fn partition_with<I, F>(iter: I, mut visit: F)
+            where
+                I: IntoIterator<Item = #integer>,
+                F: FnMut(Option<#kind_name>, #integer),
+            {
+                for n in iter {
+                    visit(Self::from_primitive(n).ok().map(|value| value.kind()), n);
+                }
+            }
+        }
+    }
+}
+
+/// Generate `EXACT_VALUES`, which lists the values of the `#[eq]` variants. It is left out when a
+/// variant includes the values of a const slice, since those are not known to the macro.
+/// Generate the persistent codes of the variants, which are given by `#[code(..)]` or are the
+/// lowest value of each variant, along with `kind_code` and `from_kind_code`. They are left out
+/// when the lowest value of a variant without a `#[code]` is not known at compile time.
+fn impl_kind_codes(
+    name: &syn::Ident,
+    kind_name: &syn::Ident,
+    attr: &AttrParams,
+    variants: &Variants,
+    variant_idents: &[&syn::Ident],
+    variant_spans: Option<&[Vec<(NumberValue, NumberValue)>]>,
+) -> TokenStream {
+    let mut codes = Vec::with_capacity(variant_idents.len());
+
+    for (i, ident) in variant_idents.iter().enumerate() {
+        let explicit = variants.codes.iter().find(|code| code.ident == **ident);
+        let lowest = variant_spans.and_then(|spans| spans[i].first().map(|(start, _)| *start));
+
+        match (explicit, lowest) {
+            (Some(explicit), _) => codes.push(explicit.code),
+            (None, Some(lowest)) => codes.push(lowest),
+            (None, None) if variants.codes.is_empty() => return TokenStream::new(),
+            (None, None) if variants.invalid.as_ref() == Some(*ident) => {
+                abort! {
+                    variants.codes[0].span,
+                    "The `#[code]` attribute is not supported with `auto_invalid`, since `{}` has no value within the limits to use as its code",
+                    ident
+                }
+            }
+            (None, None) => {
+                abort! {
+                    ident,
+                    "Variant `{}` needs a `#[code]` attribute, since its lowest value is not known at compile time",
+                    ident
+                }
+            }
+        }
+    }
+
+    for code in &variants.codes {
+        if let Some((owner, _)) = variant_idents
+            .iter()
+            .zip(&codes)
+            .find(|(owner, n)| ***owner != code.ident && **n == code.code)
+        {
+            emit_error! {
+                code.span,
+                "The code `{}` is already used by variant `{}`",
+                code.code,
+                owner
+            }
+        }
+    }
+
+    let integer = &attr.integer;
+    let codes = codes
+        .iter()
+        .map(|n| syn::parse_str::<TokenStream>(&n.to_string()).unwrap())
+        .collect::<Vec<_>>();
+
+    quote! {
+        impl #kind_name {
+            /// The code that identifies the variant in storage, which is given by `#[code(..)]` or
+            /// is the lowest value of the variant, so it does not change when variants are
+            /// reordered.
+            #[inline(always)]
+            pub const fn code(self) -> #integer {
+                match self {
+                    #(Self::#variant_idents => #codes,)*
+                }
+            }
+
+            /// Get the variant identified by `code`.
+            #[inline(always)]
+            pub fn from_code(code: #integer) -> Option<Self> {
+                match code {
+                    #(#codes => Some(Self::#variant_idents),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl #name {
+            /// The code of the variant of this value, such as for storing the variant in a
+            /// database column alongside the value.
+            #[inline(always)]
+            pub fn kind_code(&self) -> #integer {
+                self.kind().code()
+            }
+
+            /// Rebuild a value from the code of its variant and its primitive, failing when the
+            /// code is unknown or when the value does not belong to that variant.
+            pub fn from_kind_code(code: #integer, value: #integer) -> ::checked_rs::__private::anyhow::Result<Self> {
+                let Some(kind) = #kind_name::from_code(code) else {
+                    ::checked_rs::__private::anyhow::bail!("unknown kind code {} for `{}`", code, stringify!(#name));
+                };
+
+                let this = <Self as ClampedInteger<#integer>>::from_primitive(value)?;
+
+                if this.kind() != kind {
+                    ::checked_rs::__private::anyhow::bail!(
+                        "value {} is a `{:?}`, not a `{:?}`",
+                        value,
+                        this.kind(),
+                        kind
+                    );
+                }
+
+                Ok(this)
+            }
+        }
+    }
+}
+
+fn impl_exact_values(name: &syn::Ident, attr: &AttrParams, variants: &Variants) -> TokenStream {
+    if !variants.includes.is_empty() {
+        return TokenStream::new();
+    }
+
+    let integer = &attr.integer;
+    let values = variants.exacts.iter().map(|v| v.value);
+
+    quote! {
+        impl #name {
+            /// The values of the `#[eq]` variants, in ascending order.
+            pub const EXACT_VALUES: &'static [#integer] = &[#(#values),*];
+        }
+    }
+}
+
+/// Generate `gaps()`, listing the spans within the limits that no `#[eq]`, `#[range]` or `#[mask]`
+/// variant declares, which are the values only the `#[other]` variant accepts.
+fn impl_gaps(name: &syn::Ident, attr: &AttrParams, variants: &Variants) -> TokenStream {
+    let Some(UndeclaredSpans { other: gaps, .. }) = undeclared_spans(attr, variants) else {
+        return TokenStream::new();
+    };
+
+    let integer = &attr.integer;
+    let len = gaps.len();
+    let gaps = gaps.iter().map(|(start, end)| {
+        let start = syn::parse_str::<TokenStream>(&start.to_string()).unwrap();
+        let end = syn::parse_str::<TokenStream>(&end.to_string()).unwrap();
+
+        quote! { (#start, #end) }
+    });
+
+    quote! {
+        impl #name {
+            /// Iterate over the spans within the limits that no variant declares, in ascending
+            /// order. These are the values that only the `#[other]` variant accepts, which makes
+            /// their boundaries good probes for negative tests.
+            pub fn gaps() -> impl Iterator<Item = std::ops::RangeInclusive<#integer>> {
+                const GAPS: [(#integer, #integer); #len] = [#(#gaps),*];
+
+                GAPS.into_iter().map(|(start, end)| start..=end)
+            }
+        }
     }
 }
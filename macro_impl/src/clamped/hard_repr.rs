@@ -3,117 +3,216 @@ use quote::{format_ident, quote};
 
 use crate::{
     clamped::common_impl::{
-        define_guard, impl_binary_op, impl_conversions, impl_deref, impl_other_compare,
-        impl_other_eq, impl_self_cmp, impl_self_eq,
+        aggregate_impls, count_rejections, define_benchmark, define_delta, define_guard,
+        define_literal, doc_modify_example, doc_new_example, doc_ops_example, impl_any_clamped,
+        impl_batch, impl_binary_op, impl_bounded_integer, impl_bytemuck, impl_canonical_round_trip,
+        impl_char_conversions, impl_conversions, impl_cyclic, impl_default_array, impl_deranged,
+        impl_deref, impl_diff_display, impl_explain, impl_fingerprint, impl_fraction, impl_freeze,
+        impl_fuzz_target, impl_into_raw, impl_invalid_sampler, impl_metrics, impl_null,
+        impl_offset, impl_other_compare, impl_other_eq, impl_range_summary, impl_reflect,
+        impl_runtime_bounds, impl_scale, impl_secret, impl_self_cmp, impl_self_eq, impl_separated,
+        impl_serial, impl_ui_range, impl_validated_deserialize, module_imports, range_context,
+        FuzzedType,
     },
     params::{attr_params::AttrParams, struct_item::StructItem, BehaviorArg},
 };
 
 pub fn define_mod(attr: AttrParams, mut item: syn::Item) -> TokenStream {
     let struct_item = StructItem::from_item(&attr, &mut item);
+
+    if let syn::Item::Struct(data) = &mut item {
+        data.attrs.extend(doc_ops_example(&struct_item.name, &attr));
+    }
+
     let vis = &struct_item.vis;
     let name = &struct_item.name;
     let mod_name = &struct_item.mod_name;
 
-    let guard_name = format_ident!("{}Guard", &name);
+    let guard_name = attr.guard_name(name);
     let def_guard = define_guard(name, &guard_name, &attr);
+    let (bench_mod, bench_reexport) = define_benchmark(vis, name, mod_name, &attr, &item);
+    let (new_const, literal_macro) = define_literal(vis, name, &attr);
+    let default_value = attr.default_val.into_literal_as_tokens(attr.kind());
+    let delta = define_delta(name, &attr);
 
-    let implementations = TokenStream::from_iter(vec![
-        impl_hard_repr(name, &guard_name, &attr),
-        impl_deref(name, &attr),
-        impl_conversions(name, &attr),
-        impl_self_eq(name),
-        impl_self_cmp(name),
-        impl_other_eq(name, &attr),
-        impl_other_compare(name, &attr),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Add"),
-            format_ident!("add"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Sub"),
-            format_ident!("sub"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Mul"),
-            format_ident!("mul"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Div"),
-            format_ident!("div"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Rem"),
-            format_ident!("rem"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitAnd"),
-            format_ident!("bitand"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitOr"),
-            format_ident!("bitor"),
-            attr.behavior_type(),
-            None,
-            None,
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitXor"),
-            format_ident!("bitxor"),
-            attr.behavior_type(),
-            None,
-            None,
+    let implementations = aggregate_impls(
+        name,
+        &attr,
+        vec![
+            impl_hard_repr(name, &guard_name, &attr),
+            impl_deref(name, &attr),
+            impl_secret(name, &attr, &mut item),
+            impl_conversions(name, &attr),
+            impl_char_conversions(name, &attr, true),
+            impl_explain(name, &attr),
+            impl_diff_display(name),
+            impl_range_summary(name, &attr),
+            impl_any_clamped(name, &attr),
+            impl_canonical_round_trip(name, &attr),
+            impl_metrics(name, &attr),
+            impl_invalid_sampler(name, &attr),
+            impl_fingerprint(
+                name,
+                &attr,
+                &[(attr.lower_limit_value(), attr.upper_limit_value())],
+                &[],
+                &[],
+            ),
+            impl_reflect(
+                name,
+                &attr,
+                &[(attr.lower_limit_value(), attr.upper_limit_value())],
+                &[],
+            ),
+            impl_ui_range(name, &attr),
+            impl_fuzz_target(name, &attr, FuzzedType::Hard),
+            impl_runtime_bounds(name, &attr),
+            impl_default_array(name, Some(quote!(Self::new_const(#default_value)))),
+            impl_freeze(name),
+            impl_into_raw(name, &attr),
+            impl_offset(name, &attr),
+            impl_cyclic(name, &attr),
+            impl_serial(name, &attr),
+            impl_fraction(name, &attr),
+            impl_scale(name, &attr),
+            impl_bounded_integer(name, &attr),
+            impl_deranged(name, &attr),
+            impl_bytemuck(name, &attr, &mut item),
+            impl_batch(name, &attr, &mut item),
+            impl_null(name, &attr, &mut item),
+            impl_validated_deserialize(name, &attr, &mut item),
+            impl_separated(name, &attr),
+            impl_self_eq(name),
+            impl_self_cmp(name),
+            impl_other_eq(name, &attr),
+            impl_other_compare(name, &attr),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Add"),
+                format_ident!("add"),
+                attr.behavior_for("add"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Sub"),
+                format_ident!("sub"),
+                attr.behavior_for("sub"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Mul"),
+                format_ident!("mul"),
+                attr.behavior_for("mul"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Div"),
+                format_ident!("div"),
+                attr.behavior_for("div"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Rem"),
+                format_ident!("rem"),
+                attr.behavior_for("rem"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitAnd"),
+                format_ident!("bitand"),
+                attr.behavior_for("bitand"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitOr"),
+                format_ident!("bitor"),
+                attr.behavior_for("bitor"),
+                None,
+                None,
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitXor"),
+                format_ident!("bitxor"),
+                attr.behavior_for("bitxor"),
+                None,
+                None,
+            ),
+            // impl_binary_op(name, &attr, format_ident!("Shl"), format_ident!("shl")),
+            // impl_binary_op(name, &attr, format_ident!("Shr"), format_ident!("shr")),
+        ],
+    );
+
+    // A type or item that already has one of the names keeps it.
+    let stable_names = [(&guard_name, "Guard")]
+        .into_iter()
+        .chain(delta.as_ref().map(|(delta_name, _)| (delta_name, "Delta")))
+        .filter(|(ident, alias)| name != alias && *ident != alias)
+        .map(|(ident, alias)| {
+            let alias = format_ident!("{}", alias);
+            quote!(#ident as #alias)
+        });
+
+    let (def_delta, delta_reexport) = match &delta {
+        Some((delta_name, items)) => (
+            items.clone(),
+            quote! {
+                #[allow(unused_imports)]
+                #vis use #mod_name::#delta_name;
+            },
         ),
-        // impl_binary_op(name, &attr, format_ident!("Shl"), format_ident!("shl")),
-        // impl_binary_op(name, &attr, format_ident!("Shr"), format_ident!("shr")),
-    ]);
+        None => (TokenStream::new(), TokenStream::new()),
+    };
+
+    let imports = module_imports(&attr);
 
     quote! {
         #vis mod #mod_name {
-            use super::*;
+            #imports
 
             #item
 
             #def_guard
 
+            #def_delta
+
+            // Names that stay the same for every type, such as `port::Guard` with `module = port`.
+            pub use self::{#(#stable_names),*};
+
             #implementations
+
+            #bench_mod
+
+            #new_const
         }
 
         #vis use #mod_name::#name;
+
+        #delta_reexport
+
+        #bench_reexport
+
+        #literal_macro
     }
 }
 
@@ -123,11 +222,15 @@ fn impl_hard_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
     let lower_limit = attr.lower_limit_token();
     let upper_limit = attr.upper_limit_token();
 
+    let new_doc = doc_new_example(name, attr);
+    let modify_doc = doc_modify_example(name, attr);
+
     let mut methods = Vec::new();
 
     match attr.behavior_type() {
         BehaviorArg::Panicking(..) => {
             methods.push(quote! {
+                #(#new_doc)*
                 #[inline(always)]
                 pub fn new(value: #integer) -> Self {
                     match Self::from_primitive(value) {
@@ -137,13 +240,36 @@ fn impl_hard_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
                 }
             });
         }
-        BehaviorArg::Saturating(..) => {
+        BehaviorArg::Saturating(..) | BehaviorArg::SaturatingWithLog(..) => {
+            let (log_too_small, log_too_large) =
+                if matches!(attr.behavior_type(), BehaviorArg::SaturatingWithLog(..)) {
+                    (
+                        quote! {
+                            <StderrObserver as ClampObserver>::on_clamp(
+                                "new",
+                                &ClampError::TooSmall { val: value, min: #lower_limit },
+                            );
+                        },
+                        quote! {
+                            <StderrObserver as ClampObserver>::on_clamp(
+                                "new",
+                                &ClampError::TooLarge { val: value, max: #upper_limit },
+                            );
+                        },
+                    )
+                } else {
+                    (quote! {}, quote! {})
+                };
+
             methods.push(quote! {
+                #(#new_doc)*
                 #[inline(always)]
                 pub fn new(value: #integer) -> Self {
                     if value < #lower_limit {
+                        #log_too_small
                         Self(Self::MIN)
                     } else if value > #upper_limit {
+                        #log_too_large
                         Self(Self::MAX)
                     } else {
                         Self::from_primitive(value).unwrap()
@@ -154,6 +280,16 @@ fn impl_hard_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
     }
 
     let default_value = attr.default_val.into_literal_as_tokens(attr.kind());
+    let context = range_context(name, attr);
+    let from_primitive_body = count_rejections(
+        attr,
+        quote! {
+            Self::validate(n).map(Self).map_err(|e| {
+                let message = format!("{} {}", e, #context);
+                ::checked_rs::__private::anyhow::Error::new(e).context(message)
+            })
+        },
+    );
 
     quote! {
         impl InherentLimits<#integer> for #name {
@@ -167,10 +303,17 @@ fn impl_hard_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
 
         unsafe impl ClampedInteger<#integer> for #name {
             #[inline(always)]
-            fn from_primitive(n: #integer) -> ::anyhow::Result<Self> {
-                Ok(Self(Self::validate(n)?))
+            fn from_primitive(n: #integer) -> ::checked_rs::__private::anyhow::Result<Self> {
+                #from_primitive_body
             }
 
+            #[inline(always)]
+            fn into_primitive(&self) -> #integer {
+                self.0
+            }
+        }
+
+        impl AsPrimitive<#integer> for #name {
             #[inline(always)]
             fn as_primitive(&self) -> &#integer {
                 &self.0
@@ -192,32 +335,49 @@ fn impl_hard_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
             #[inline(always)]
             pub fn rand() -> Self {
                 loop {
-                    if let Ok(v) = Self::from_primitive(rand::random::<#integer>()) {
+                    if let Ok(v) = Self::from_primitive(::checked_rs::__private::rand::random::<#integer>()) {
                         return v;
                     }
                 }
             }
 
             #[inline(always)]
-            pub fn validate(val: #integer) -> ::anyhow::Result<#integer, ClampError<#integer>> {
-                if val < #lower_limit {
-                    Err(ClampError::TooSmall { val, min: #lower_limit })
-                } else if val > #upper_limit {
-                    Err(ClampError::TooLarge { val, max: #upper_limit })
-                } else {
-                    Ok(val)
-                }
+            pub fn validate(val: #integer) -> ::checked_rs::__private::anyhow::Result<#integer, ClampError<#integer>> {
+                try_clamp(val, #lower_limit, #upper_limit)
             }
 
             #[inline(always)]
-            pub fn set(&mut self, value: #integer) -> ::anyhow::Result<(), ClampError<#integer>> {
+            pub fn set(&mut self, value: #integer) -> ::checked_rs::__private::anyhow::Result<(), ClampError<#integer>> {
                 self.0 = Self::validate(value)?;
                 Ok(())
             }
 
+            /// Create a value without checking it against the limits. This is the only way to
+            /// build a value outside of the generated module without a check.
+            ///
+            /// # Safety
+            ///
+            /// `value` must be within the limits. Code that relies on `HardClamp`, such as the
+            /// batch operations that view slices of the type as primitives, may otherwise behave
+            /// incorrectly.
+            #[inline(always)]
+            pub const unsafe fn new_unchecked(value: #integer) -> Self {
+                Self(value)
+            }
+
+            /// Replace the value without checking it against the limits.
+            ///
+            /// # Safety
+            ///
+            /// The same as for `new_unchecked`.
             #[inline(always)]
             pub unsafe fn set_unchecked(&mut self, value: #integer) {
-                self.0 = value;
+                *self = Self::new_unchecked(value);
+            }
+
+            #[inline(always)]
+            pub fn as_primitive(&self) -> &#integer {
+                &self.0
             }
 
             #[inline(always)]
@@ -225,11 +385,18 @@ fn impl_hard_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
                 &self.0
             }
 
+            /// Get a mutable reference to the primitive, which is not checked when it is written.
+            ///
+            /// # Safety
+            ///
+            /// The primitive must be within the limits whenever the value is used again, the same
+            /// as for `new_unchecked`.
             #[inline(always)]
             pub unsafe fn get_mut(&mut self) -> &mut #integer {
                 &mut self.0
             }
 
+            #(#modify_doc)*
             #[inline(always)]
             pub fn modify<'a>(&'a mut self) -> #guard_name<'a> {
                 #guard_name::new(self)
@@ -0,0 +1,135 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+
+use crate::{
+    clamped::common_impl::{impl_range_summary, module_imports, range_context},
+    diagnostic::abort,
+    params::attr_params::AttrParams,
+};
+
+/// Generate a unit struct given `singleton = N`, the zero-sized type of the single value `N`, such
+/// as a protocol magic number or a version constant that should be distinct from other integers.
+pub fn define_mod(attr: AttrParams, mut item: syn::Item) -> TokenStream {
+    let syn::Item::Struct(data) = &mut item else {
+        abort!(
+            item,
+            "The `singleton` parameter is only supported on structs"
+        );
+    };
+
+    if let Some(field) = data.fields.iter().next() {
+        abort!(
+            field,
+            "A singleton holds no value at runtime, so it must be a unit struct"
+        );
+    }
+
+    let vis = data.vis.clone();
+    let name = data.ident.clone();
+    let mod_name = attr.module_name(&name);
+
+    data.vis = parse_quote!(pub);
+    data.fields = syn::Fields::Unit;
+
+    let integer = &attr.integer;
+    let value = attr.lower_limit_value();
+    let imports = module_imports(&attr);
+    let range_summary = impl_range_summary(&name, &attr);
+    let context = range_context(&name, &attr);
+
+    quote! {
+        #vis mod #mod_name {
+            #imports
+
+            #item
+
+            impl #name {
+                /// The value of the type, which is not stored since it is the only one allowed.
+                pub const VALUE: #integer = #value;
+
+                #[inline(always)]
+                pub const fn new() -> Self {
+                    Self
+                }
+
+                #[inline(always)]
+                pub const fn get(&self) -> #integer {
+                    Self::VALUE
+                }
+            }
+
+            impl InherentLimits<#integer> for #name {
+                const MIN: #integer = Self::VALUE;
+                const MAX: #integer = Self::VALUE;
+            }
+
+            #range_summary
+
+            impl std::ops::Deref for #name {
+                type Target = #integer;
+
+                #[inline(always)]
+                fn deref(&self) -> &Self::Target {
+                    &Self::VALUE
+                }
+            }
+
+            impl From<#name> for #integer {
+                #[inline(always)]
+                fn from(_: #name) -> Self {
+                    #name::VALUE
+                }
+            }
+
+            impl TryFrom<#integer> for #name {
+                type Error = ClampError<#integer>;
+
+                /// Only checks that the value is `VALUE`, since there is nothing to store.
+                #[inline(always)]
+                fn try_from(val: #integer) -> std::result::Result<Self, Self::Error> {
+                    try_clamp(val, Self::VALUE, Self::VALUE).map(|_| Self)
+                }
+            }
+
+            impl AnyClamped for #name {
+                #[inline(always)]
+                fn type_name(&self) -> &'static str {
+                    stringify!(#name)
+                }
+
+                #[inline(always)]
+                fn range_summary(&self) -> &'static str {
+                    Self::RANGE_SUMMARY
+                }
+
+                #[inline(always)]
+                fn as_i128(&self) -> Option<i128> {
+                    i128::try_from(Self::VALUE).ok()
+                }
+
+                #[inline(always)]
+                fn as_u128(&self) -> Option<u128> {
+                    u128::try_from(Self::VALUE).ok()
+                }
+
+                /// Only checks that the value is `VALUE`, since there is nothing to store.
+                fn set_i128(&mut self, value: i128) -> ::checked_rs::__private::anyhow::Result<()> {
+                    if i128::try_from(Self::VALUE).ok() == Some(value) {
+                        Ok(())
+                    } else {
+                        ::checked_rs::__private::anyhow::bail!("invalid value {} {}", value, #context)
+                    }
+                }
+            }
+
+            impl std::fmt::Display for #name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::Display::fmt(&Self::VALUE, f)
+                }
+            }
+        }
+
+        #vis use #mod_name::#name;
+    }
+}
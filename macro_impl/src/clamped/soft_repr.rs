@@ -3,8 +3,14 @@ use quote::{format_ident, quote};
 
 use crate::{
     clamped::common_impl::{
-        define_guard, impl_binary_op, impl_conversions, impl_deref, impl_other_compare,
-        impl_other_eq, impl_self_cmp, impl_self_eq,
+        aggregate_impls, define_benchmark, define_guard, define_literal, doc_modify_example,
+        doc_new_example, doc_ops_example, impl_any_clamped, impl_binary_op, impl_bounded_integer,
+        impl_bytemuck, impl_canonical_round_trip, impl_char_conversions, impl_conversions,
+        impl_default_array, impl_deranged, impl_deref, impl_diff_display, impl_explain,
+        impl_fingerprint, impl_fraction, impl_freeze, impl_fuzz_target, impl_into_raw,
+        impl_invalid_sampler, impl_metrics, impl_null, impl_other_compare, impl_other_eq,
+        impl_range_summary, impl_reflect, impl_runtime_bounds, impl_scale, impl_secret,
+        impl_self_cmp, impl_self_eq, impl_separated, impl_ui_range, module_imports, FuzzedType,
     },
     params::{attr_params::AttrParams, struct_item::StructItem, NumberArg},
 };
@@ -12,109 +18,176 @@ use crate::{
 pub fn define_mod(attr: AttrParams, mut item: syn::Item) -> TokenStream {
     let kind = attr.kind();
     let struct_item = StructItem::from_item(&attr, &mut item);
+
+    if let syn::Item::Struct(data) = &mut item {
+        data.attrs.extend(doc_ops_example(&struct_item.name, &attr));
+    }
+
     let vis = &struct_item.vis;
     let name = &struct_item.name;
     let mod_name = &struct_item.mod_name;
 
-    let guard_name = format_ident!("{}Guard", &name);
+    let guard_name = attr.guard_name(name);
     let def_guard = define_guard(name, &guard_name, &attr);
+    let (bench_mod, bench_reexport) = define_benchmark(vis, name, mod_name, &attr, &item);
+    let (new_const, literal_macro) = define_literal(vis, name, &attr);
+    let default_value = attr.default_val.into_literal_as_tokens(attr.kind());
+
+    let implementations = aggregate_impls(
+        name,
+        &attr,
+        vec![
+            impl_soft_repr(name, &guard_name, &attr),
+            impl_deref(name, &attr),
+            impl_secret(name, &attr, &mut item),
+            impl_conversions(name, &attr),
+            impl_char_conversions(name, &attr, false),
+            impl_explain(name, &attr),
+            impl_diff_display(name),
+            impl_range_summary(name, &attr),
+            impl_any_clamped(name, &attr),
+            impl_canonical_round_trip(name, &attr),
+            impl_metrics(name, &attr),
+            impl_invalid_sampler(name, &attr),
+            impl_fingerprint(
+                name,
+                &attr,
+                &[(attr.lower_limit_value(), attr.upper_limit_value())],
+                &[],
+                &[],
+            ),
+            impl_reflect(
+                name,
+                &attr,
+                &[(attr.lower_limit_value(), attr.upper_limit_value())],
+                &[],
+            ),
+            impl_ui_range(name, &attr),
+            impl_fuzz_target(name, &attr, FuzzedType::Soft),
+            impl_runtime_bounds(name, &attr),
+            impl_default_array(name, Some(quote!(Self::new_const(#default_value)))),
+            impl_freeze(name),
+            impl_into_raw(name, &attr),
+            impl_fraction(name, &attr),
+            impl_scale(name, &attr),
+            impl_bounded_integer(name, &attr),
+            impl_deranged(name, &attr),
+            impl_bytemuck(name, &attr, &mut item),
+            impl_null(name, &attr, &mut item),
+            impl_separated(name, &attr),
+            impl_self_eq(name),
+            impl_self_cmp(name),
+            impl_other_eq(name, &attr),
+            impl_other_compare(name, &attr),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Add"),
+                format_ident!("add"),
+                attr.behavior_for("add"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Sub"),
+                format_ident!("sub"),
+                attr.behavior_for("sub"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Mul"),
+                format_ident!("mul"),
+                attr.behavior_for("mul"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Div"),
+                format_ident!("div"),
+                attr.behavior_for("div"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("Rem"),
+                format_ident!("rem"),
+                attr.behavior_for("rem"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitAnd"),
+                format_ident!("bitand"),
+                attr.behavior_for("bitand"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitOr"),
+                format_ident!("bitor"),
+                attr.behavior_for("bitor"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            impl_binary_op(
+                name,
+                &attr,
+                format_ident!("BitXor"),
+                format_ident!("bitxor"),
+                attr.behavior_for("bitxor"),
+                Some(NumberArg::new_min_constant(kind)),
+                Some(NumberArg::new_max_constant(kind)),
+            ),
+            // impl_binary_op(name, &attr, format_ident!("Shl"), format_ident!("shl")),
+            // impl_binary_op(name, &attr, format_ident!("Shr"), format_ident!("shr")),
+        ],
+    );
+
+    // A type or guard that is already named `Guard` keeps the name.
+    let guard_alias = if name != "Guard" && guard_name != "Guard" {
+        quote!(pub use self::#guard_name as Guard;)
+    } else {
+        TokenStream::new()
+    };
 
-    let implementations = TokenStream::from_iter(vec![
-        impl_soft_repr(name, &guard_name, &attr),
-        impl_deref(name, &attr),
-        impl_conversions(name, &attr),
-        impl_self_eq(name),
-        impl_self_cmp(name),
-        impl_other_eq(name, &attr),
-        impl_other_compare(name, &attr),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Add"),
-            format_ident!("add"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Sub"),
-            format_ident!("sub"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Mul"),
-            format_ident!("mul"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Div"),
-            format_ident!("div"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("Rem"),
-            format_ident!("rem"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitAnd"),
-            format_ident!("bitand"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitOr"),
-            format_ident!("bitor"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        impl_binary_op(
-            name,
-            &attr,
-            format_ident!("BitXor"),
-            format_ident!("bitxor"),
-            attr.behavior_type(),
-            Some(NumberArg::new_min_constant(kind)),
-            Some(NumberArg::new_max_constant(kind)),
-        ),
-        // impl_binary_op(name, &attr, format_ident!("Shl"), format_ident!("shl")),
-        // impl_binary_op(name, &attr, format_ident!("Shr"), format_ident!("shr")),
-    ]);
+    let imports = module_imports(&attr);
 
     quote! {
         #vis mod #mod_name {
-            use super::*;
+            #imports
 
             #item
 
             #def_guard
 
+            // Names that stay the same for every type, such as `port::Guard` with `module = port`.
+            #guard_alias
+
             #implementations
+
+            #bench_mod
+
+            #new_const
         }
 
         #vis use #mod_name::#name;
+
+        #bench_reexport
+
+        #literal_macro
     }
 }
 
@@ -126,6 +199,9 @@ fn impl_soft_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
 
     let default_value = attr.default_val.into_literal_as_tokens(attr.kind());
 
+    let new_doc = doc_new_example(name, attr);
+    let modify_doc = doc_modify_example(name, attr);
+
     quote! {
         impl InherentLimits<#integer> for #name {
             const MIN: #integer = #lower_limit;
@@ -138,10 +214,17 @@ fn impl_soft_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
 
         unsafe impl ClampedInteger<#integer> for #name {
             #[inline(always)]
-            fn from_primitive(n: #integer) -> ::anyhow::Result<Self> {
+            fn from_primitive(n: #integer) -> ::checked_rs::__private::anyhow::Result<Self> {
                 Ok(Self(n))
             }
 
+            #[inline(always)]
+            fn into_primitive(&self) -> #integer {
+                self.0
+            }
+        }
+
+        impl AsPrimitive<#integer> for #name {
             #[inline(always)]
             fn as_primitive(&self) -> &#integer {
                 &self.0
@@ -173,6 +256,7 @@ fn impl_soft_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
         }
 
         impl #name {
+            #(#new_doc)*
             #[inline(always)]
             pub fn new(value: #integer) -> Self {
                 Self(value)
@@ -181,21 +265,15 @@ fn impl_soft_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
             #[inline(always)]
             pub fn rand() -> Self {
                 loop {
-                    if let Ok(v) = Self::validate(rand::random::<#integer>()) {
+                    if let Ok(v) = Self::validate(::checked_rs::__private::rand::random::<#integer>()) {
                         return Self::from_primitive(v).unwrap();
                     }
                 }
             }
 
             #[inline(always)]
-            pub fn validate(val: #integer) -> ::anyhow::Result<#integer, ClampError<#integer>> {
-                if val < #lower_limit {
-                    Err(ClampError::TooSmall { val, min: #lower_limit })
-                } else if val > #upper_limit {
-                    Err(ClampError::TooLarge { val, max: #upper_limit })
-                } else {
-                    Ok(val)
-                }
+            pub fn validate(val: #integer) -> ::checked_rs::__private::anyhow::Result<#integer, ClampError<#integer>> {
+                try_clamp(val, #lower_limit, #upper_limit)
             }
 
             #[inline(always)]
@@ -204,7 +282,7 @@ fn impl_soft_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
             }
 
             #[inline(always)]
-            pub fn set(&mut self, value: #integer) -> ::anyhow::Result<(), ClampError<#integer>> {
+            pub fn set(&mut self, value: #integer) -> ::checked_rs::__private::anyhow::Result<(), ClampError<#integer>> {
                 self.0 = Self::validate(value)?;
                 Ok(())
             }
@@ -214,6 +292,11 @@ fn impl_soft_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
                 self.0 = value;
             }
 
+            #[inline(always)]
+            pub fn as_primitive(&self) -> &#integer {
+                &self.0
+            }
+
             #[inline(always)]
             pub fn get(&self) -> &#integer {
                 &self.0
@@ -224,6 +307,7 @@ fn impl_soft_repr(name: &syn::Ident, guard_name: &syn::Ident, attr: &AttrParams)
                 &mut self.0
             }
 
+            #(#modify_doc)*
             #[inline(always)]
             pub fn modify<'a>(&'a mut self) -> #guard_name<'a> {
                 #guard_name::new(self)
@@ -0,0 +1,70 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+
+use crate::diagnostic::abort;
+
+/// The input of `clamped_const!`, which is the clamped type followed by the expression to fold,
+/// such as `Percent: BASE + BONUS * 2`.
+pub struct ClampedConst {
+    pub ty: syn::Type,
+    pub colon: syn::Token![:],
+    pub expr: syn::Expr,
+}
+
+impl Parse for ClampedConst {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            ty: input.parse()?,
+            colon: input.parse()?,
+            expr: input.parse()?,
+        })
+    }
+}
+
+/// Rewrite every arithmetic operation of the expression into the `const` operation of the type,
+/// so that each intermediate result is checked against the limits, and integer literals into
+/// `new_const`. Any other expression is used as is and must evaluate to the type.
+fn fold_expr(ty: &syn::Type, expr: &syn::Expr) -> TokenStream {
+    match expr {
+        syn::Expr::Binary(binary) => {
+            let method = match binary.op {
+                syn::BinOp::Add(..) => quote!(const_add),
+                syn::BinOp::Sub(..) => quote!(const_sub),
+                syn::BinOp::Mul(..) => quote!(const_mul),
+                syn::BinOp::Div(..) => quote!(const_div),
+                syn::BinOp::Rem(..) => quote!(const_rem),
+                _ => abort!(
+                    binary.op,
+                    "`clamped_const!` only supports `+`, `-`, `*`, `/` and `%`"
+                ),
+            };
+
+            let left = fold_expr(ty, &binary.left);
+            let right = fold_expr(ty, &binary.right);
+
+            quote!(<#ty>::#method(#left, #right))
+        }
+        syn::Expr::Paren(paren) => fold_expr(ty, &paren.expr),
+        syn::Expr::Group(group) => fold_expr(ty, &group.expr),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => quote!(<#ty>::new_const(#lit)),
+        expr => quote!(#expr),
+    }
+}
+
+/// Evaluate the expression at compile time, failing the build when any intermediate result is out
+/// of the limits of the type.
+pub fn clamped_const(input: ClampedConst) -> TokenStream {
+    let ty = &input.ty;
+    let folded = fold_expr(ty, &input.expr);
+
+    quote! {
+        const {
+            let value: #ty = #folded;
+            value
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Error reporting for the code generation. The `abort!`, `abort_call_site!` and `emit_error!`
+//! macros take the same arguments as the ones of `proc_macro_error`, and report through it unless
+//! they run within `catch`, which collects the errors instead so that they can be returned from
+//! outside of a procedural macro.
+
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+};
+
+use proc_macro_error::Diagnostic;
+use quote::ToTokens;
+
+thread_local! {
+    /// The errors reported within the innermost `catch`, or `None` outside of one.
+    static ERRORS: RefCell<Option<Vec<Diagnostic>>> = const { RefCell::new(None) };
+}
+
+/// The panic payload that unwinds to `catch` after an error was reported with `abort!`.
+struct AbortNow;
+
+/// Report an error and keep going. Use `emit_error!` instead.
+#[doc(hidden)]
+pub fn emit_diagnostic(diagnostic: Diagnostic) {
+    let diagnostic = ERRORS.with(|errors| match errors.borrow_mut().as_mut() {
+        Some(errors) => {
+            errors.push(diagnostic);
+            None
+        }
+        None => Some(diagnostic),
+    });
+
+    if let Some(diagnostic) = diagnostic {
+        diagnostic.emit();
+    }
+}
+
+/// Report an error and stop the expansion. Use `abort!` instead.
+#[doc(hidden)]
+pub fn abort_diagnostic(diagnostic: Diagnostic) -> ! {
+    if ERRORS.with(|errors| errors.borrow().is_none()) {
+        diagnostic.abort();
+    }
+
+    emit_diagnostic(diagnostic);
+    // Unlike `panic!`, this does not run the panic hook, so nothing is printed.
+    panic::resume_unwind(Box::new(AbortNow))
+}
+
+/// Run `f`, returning the errors it reports with `abort!` or `emit_error!` as a single
+/// `syn::Error` instead of reporting them through `proc_macro_error`, which only works within a
+/// procedural macro. Other panics are resumed.
+pub fn catch<T>(f: impl FnOnce() -> T) -> syn::Result<T> {
+    let outer = ERRORS.with(|errors| errors.replace(Some(Vec::new())));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let errors = ERRORS
+        .with(|errors| errors.replace(outer))
+        .unwrap_or_default();
+
+    match result {
+        Ok(value) if errors.is_empty() => Ok(value),
+        Err(payload) if !payload.is::<AbortNow>() => panic::resume_unwind(payload),
+        _ => Err(into_syn_error(errors)),
+    }
+}
+
+/// Convert the `compile_error!` invocations that the diagnostics expand to into a `syn::Error`
+/// with the same messages and spans.
+fn into_syn_error(errors: Vec<Diagnostic>) -> syn::Error {
+    let tokens = errors
+        .iter()
+        .map(ToTokens::to_token_stream)
+        .collect::<proc_macro2::TokenStream>();
+
+    syn::parse2::<syn::File>(tokens)
+        .into_iter()
+        .flat_map(|file| file.items)
+        .filter_map(|item| match item {
+            syn::Item::Macro(item) => {
+                let message = item.mac.parse_body::<syn::LitStr>().ok()?;
+                Some(syn::Error::new_spanned(item.mac, message.value()))
+            }
+            _ => None,
+        })
+        .reduce(|mut error, next| {
+            error.combine(next);
+            error
+        })
+        .unwrap_or_else(|| {
+            syn::Error::new(proc_macro2::Span::call_site(), "the expansion was aborted")
+        })
+}
+
+macro_rules! abort {
+    ($span:expr, $($tts:tt)*) => {
+        $crate::diagnostic::abort_diagnostic(::proc_macro_error::diagnostic!(
+            $span,
+            ::proc_macro_error::Level::Error,
+            $($tts)*
+        ))
+    };
+}
+
+macro_rules! abort_call_site {
+    ($($tts:tt)*) => {
+        $crate::diagnostic::abort!(::proc_macro2::Span::call_site(), $($tts)*)
+    };
+}
+
+macro_rules! emit_error {
+    ($span:expr, $($tts:tt)*) => {
+        $crate::diagnostic::emit_diagnostic(::proc_macro_error::diagnostic!(
+            $span,
+            ::proc_macro_error::Level::Error,
+            $($tts)*
+        ))
+    };
+}
+
+pub(crate) use abort;
+pub(crate) use abort_call_site;
+pub(crate) use emit_error;
@@ -3,8 +3,16 @@
 //! > Instead, you should use the `checked-rs` crate, which re-exports the public macros from this crate.
 //!
 pub mod clamped;
+pub mod clamped_const;
+pub mod diagnostic;
 
 // #[doc(hidden)]
 // pub mod ops;
 
 pub mod params;
+pub mod register_map;
+
+pub use clamped::{
+    expand_formatted, expand_to_string, Codegen, EnumCodegen, ExpansionFormat, HardCodegen,
+    SoftCodegen,
+};
@@ -1,11 +1,13 @@
 use std::iter::FusedIterator;
 
 use proc_macro2::TokenStream;
-use proc_macro_error::{abort, abort_call_site};
 use quote::{quote, ToTokens};
-use syn::{parse::Parse, parse_quote, spanned::Spanned};
+use syn::{parse::Parse, parse_quote, punctuated::Punctuated, spanned::Spanned};
+
+use crate::diagnostic::{abort, abort_call_site};
 
 pub mod attr_params;
+pub mod crate_config;
 pub mod enum_variants;
 pub mod struct_item;
 
@@ -15,6 +17,42 @@ mod kw {
     syn::custom_keyword!(behavior);
     syn::custom_keyword!(lower);
     syn::custom_keyword!(upper);
+    syn::custom_keyword!(store);
+    syn::custom_keyword!(maps_to);
+    syn::custom_keyword!(secret);
+    syn::custom_keyword!(literal);
+    syn::custom_keyword!(separator);
+    syn::custom_keyword!(codegen);
+    syn::custom_keyword!(table);
+    syn::custom_keyword!(auto_invalid);
+    syn::custom_keyword!(benchmark);
+    syn::custom_keyword!(metrics);
+    syn::custom_keyword!(null);
+    syn::custom_keyword!(include_values);
+    syn::custom_keyword!(SaturatingWithLog);
+    syn::custom_keyword!(div_by_zero);
+    syn::custom_keyword!(derive);
+    syn::custom_keyword!(panic);
+    syn::custom_keyword!(saturate_max);
+    syn::custom_keyword!(return_default);
+    syn::custom_keyword!(checked_only);
+    syn::custom_keyword!(skip_impls);
+    syn::custom_keyword!(cyclic);
+    syn::custom_keyword!(doc_examples);
+    syn::custom_keyword!(serial);
+    syn::custom_keyword!(serde_names);
+    syn::custom_keyword!(lookup);
+    syn::custom_keyword!(ranges);
+    syn::custom_keyword!(bitset);
+    syn::custom_keyword!(module);
+    syn::custom_keyword!(guard_drop);
+    syn::custom_keyword!(explicit_imports);
+    syn::custom_keyword!(guard_name);
+    syn::custom_keyword!(value_name);
+    syn::custom_keyword!(singleton);
+    syn::custom_keyword!(step);
+    syn::custom_keyword!(coverage);
+    syn::custom_keyword!(exhaustive);
     syn::custom_keyword!(Soft);
     syn::custom_keyword!(Hard);
     syn::custom_keyword!(Saturate);
@@ -526,11 +564,111 @@ impl NumberValue {
         }
     }
 
+    /// Convert the value into another number kind, if it can be represented by that kind.
+    pub fn cast(self, kind: NumberKind) -> Option<NumberValue> {
+        let n = self.to_string();
+
+        match kind {
+            NumberKind::U8 => n.parse().ok().map(Self::U8),
+            NumberKind::U16 => n.parse().ok().map(Self::U16),
+            NumberKind::U32 => n.parse().ok().map(Self::U32),
+            NumberKind::U64 => n.parse().ok().map(Self::U64),
+            NumberKind::U128 => n.parse().ok().map(Self::U128),
+            NumberKind::USize => n.parse().ok().map(Self::USize),
+            NumberKind::I8 => n.parse().ok().map(Self::I8),
+            NumberKind::I16 => n.parse().ok().map(Self::I16),
+            NumberKind::I32 => n.parse().ok().map(Self::I32),
+            NumberKind::I64 => n.parse().ok().map(Self::I64),
+            NumberKind::I128 => n.parse().ok().map(Self::I128),
+            NumberKind::ISize => n.parse().ok().map(Self::ISize),
+        }
+    }
+
+    /// Get the kind of number this value holds.
+    pub fn kind(&self) -> NumberKind {
+        match self {
+            Self::U8(..) => NumberKind::U8,
+            Self::U16(..) => NumberKind::U16,
+            Self::U32(..) => NumberKind::U32,
+            Self::U64(..) => NumberKind::U64,
+            Self::U128(..) => NumberKind::U128,
+            Self::USize(..) => NumberKind::USize,
+            Self::I8(..) => NumberKind::I8,
+            Self::I16(..) => NumberKind::I16,
+            Self::I32(..) => NumberKind::I32,
+            Self::I64(..) => NumberKind::I64,
+            Self::I128(..) => NumberKind::I128,
+            Self::ISize(..) => NumberKind::ISize,
+        }
+    }
+
+    /// The number of values in `self..=end`, computed in `u128` and saturating at `u128::MAX`
+    /// for the full `u128` domain, which has one more value than `u128` can count.
+    pub fn span_len(self, end: Self) -> u128 {
+        let (start, end) = (self.to_string(), end.to_string());
+        let diff = match (start.parse::<u128>(), end.parse::<u128>()) {
+            (Ok(start), Ok(end)) => end - start,
+            _ => start
+                .parse::<i128>()
+                .unwrap()
+                .abs_diff(end.parse::<i128>().unwrap()),
+        };
+
+        diff.saturating_add(1)
+    }
+
+    /// The values of `self..=end` that are a multiple of `step` away from `self`, computed in
+    /// `u128` since only unsigned kinds are accepted.
+    pub fn stepped(self, end: Self, step: u128) -> impl Iterator<Item = Self> {
+        let kind = self.kind();
+        let start = self.to_string().parse::<u128>().unwrap();
+        let end = end.to_string().parse::<u128>().unwrap();
+
+        (start..=end)
+            .step_by(usize::try_from(step).unwrap_or(usize::MAX))
+            .map(move |n| Self::U128(n).cast(kind).unwrap())
+    }
+
     pub fn range(self, end: Self) -> NumberValueIter {
-        NumberValueIter::new(self, end, 1.into())
+        let step = NumberValue::U8(1).cast(self.kind()).unwrap();
+        NumberValueIter::new(self, end, step)
     }
 }
 
+/// Find the spans within `lower..=upper` that none of the `declared` spans cover, by sorting the
+/// spans and walking over them once, so that wide domains are checked without visiting each value.
+pub fn uncovered_spans(
+    lower: NumberValue,
+    upper: NumberValue,
+    declared: impl IntoIterator<Item = (NumberValue, NumberValue)>,
+) -> Vec<(NumberValue, NumberValue)> {
+    let mut declared = declared.into_iter().collect::<Vec<_>>();
+    declared.sort();
+
+    let mut gaps = Vec::new();
+    let mut cursor = Some(lower);
+
+    for (start, end) in declared {
+        let Some(next) = cursor else {
+            break;
+        };
+
+        if next < start {
+            gaps.push((next, start - 1));
+        }
+
+        if end >= next {
+            cursor = (end < upper).then(|| end + 1);
+        }
+    }
+
+    if let Some(next) = cursor {
+        gaps.push((next, upper));
+    }
+
+    gaps
+}
+
 pub struct NumberValueIter {
     a: NumberValue,
     b: NumberValue,
@@ -541,10 +679,9 @@ impl Iterator for NumberValueIter {
     type Item = NumberValue;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.a + self.step;
-
-        if next < self.b {
-            self.a = next;
+        if self.a < self.b {
+            let next = self.a;
+            self.a = self.a + self.step;
             Some(next)
         } else {
             None
@@ -557,17 +694,15 @@ impl ExactSizeIterator for NumberValueIter {
         let diff = self.b - self.a;
         let step = self.step.into_usize();
 
-        (diff.into_usize() + step - 1) / step
+        diff.into_usize().div_ceil(step)
     }
 }
 
 impl DoubleEndedIterator for NumberValueIter {
     fn next_back(&mut self) -> Option<Self::Item> {
-        let next = self.b - self.step;
-
-        if next > self.a {
-            self.b = next;
-            Some(next)
+        if self.b > self.a {
+            self.b = self.b - self.step;
+            Some(self.b)
         } else {
             None
         }
@@ -598,7 +733,8 @@ impl NumberValueIter {
     }
 }
 
-/// Represents the number argument. It can be a literal or a the MIN/MAX constant.
+/// Represents the number argument. It can be a literal, a the MIN/MAX constant or a `const` block
+/// containing simple integer arithmetic.
 #[derive(Clone)]
 pub enum NumberArg {
     Literal(syn::LitInt),
@@ -607,12 +743,26 @@ pub enum NumberArg {
         dbl_colon: syn::Token![::],
         ident: MinOrMax,
     },
+    ConstBlock {
+        const_token: syn::Token![const],
+        block: syn::Block,
+    },
 }
 
 impl Parse for NumberArg {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         if input.peek(syn::LitInt) {
             Ok(Self::Literal(input.parse()?))
+        } else if input.peek(syn::Token![const]) {
+            let this = Self::ConstBlock {
+                const_token: input.parse()?,
+                block: input.parse()?,
+            };
+
+            // Surface evaluation errors where the block is written
+            this.base10_parse::<i128>()?;
+
+            Ok(this)
         } else {
             let kind = input.parse()?;
             let dbl_colon = input.parse()?;
@@ -644,6 +794,10 @@ impl ToTokens for NumberArg {
                     #kind #dbl_colon #ident
                 });
             }
+            Self::ConstBlock { const_token, block } => {
+                const_token.to_tokens(tokens);
+                block.to_tokens(tokens);
+            }
         }
     }
 }
@@ -731,6 +885,22 @@ impl NumberArg {
     {
         match self {
             Self::Literal(lit) => lit.base10_parse::<N>(),
+            Self::ConstBlock { block, .. } => {
+                let n = match block.stmts.as_slice() {
+                    [syn::Stmt::Expr(expr, None)] => eval_const_expr(expr)?,
+                    _ => {
+                        return Err(syn::Error::new(
+                            block.span(),
+                            "expected a single integer expression",
+                        ))
+                    }
+                };
+
+                match str::parse(&n.to_string()) {
+                    Ok(n) => Ok(n),
+                    Err(e) => Err(syn::Error::new(block.span(), e)),
+                }
+            }
             Self::Constant {
                 kind,
                 dbl_colon: _,
@@ -776,31 +946,432 @@ impl NumberArg {
     }
 }
 
-/// Represents the behavior argument. It can be `Saturating` or `Panicking`.
+/// Evaluate the integer arithmetic inside of a `const` block.
+fn eval_const_expr(expr: &syn::Expr) -> syn::Result<i128> {
+    let overflow = || syn::Error::new(expr.span(), "arithmetic overflow in const expression");
+
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse(),
+        syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => eval_const_expr(expr),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => eval_const_expr(expr)?.checked_neg().ok_or_else(overflow),
+        syn::Expr::Path(..) => syn::parse2::<NumberArg>(expr.to_token_stream())?.base10_parse(),
+        syn::Expr::Binary(syn::ExprBinary {
+            left, op, right, ..
+        }) => {
+            let lhs = eval_const_expr(left)?;
+            let rhs = eval_const_expr(right)?;
+
+            match op {
+                syn::BinOp::Add(_) => lhs.checked_add(rhs),
+                syn::BinOp::Sub(_) => lhs.checked_sub(rhs),
+                syn::BinOp::Mul(_) => lhs.checked_mul(rhs),
+                syn::BinOp::Div(_) => lhs.checked_div(rhs),
+                syn::BinOp::Rem(_) => lhs.checked_rem(rhs),
+                syn::BinOp::Shl(_) => u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shl(rhs)),
+                syn::BinOp::Shr(_) => u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shr(rhs)),
+                syn::BinOp::BitAnd(_) => Some(lhs & rhs),
+                syn::BinOp::BitOr(_) => Some(lhs | rhs),
+                syn::BinOp::BitXor(_) => Some(lhs ^ rhs),
+                _ => return Err(syn::Error::new(op.span(), "unsupported operator in const expression")),
+            }
+            .ok_or_else(overflow)
+        }
+        _ => Err(syn::Error::new(
+            expr.span(),
+            "only integer literals, `MIN`/`MAX` constants and arithmetic are supported in const expressions",
+        )),
+    }
+}
+
+/// Represents a bound inside of an `#[eq]` or `#[range]` attribute. In addition to every form of
+/// `NumberArg`, the bare `MIN` and `MAX` keywords refer to the lower and upper limits of the type.
+#[derive(Clone)]
+pub enum RangeBound {
+    Limit(MinOrMax),
+    Number(NumberArg),
+}
+
+impl Parse for RangeBound {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::MIN) || input.peek(kw::MAX) {
+            Ok(Self::Limit(input.parse()?))
+        } else {
+            Ok(Self::Number(input.parse()?))
+        }
+    }
+}
+
+impl ToTokens for RangeBound {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Limit(limit) => limit.to_tokens(tokens),
+            Self::Number(n) => n.to_tokens(tokens),
+        }
+    }
+}
+
+impl RangeBound {
+    /// Interpret the bound as a `NumberValue` of the given kind, resolving `MIN` and `MAX` to the
+    /// given limits.
+    pub fn into_value(
+        &self,
+        kind: NumberKind,
+        lower: NumberValue,
+        upper: NumberValue,
+    ) -> NumberValue {
+        match self {
+            Self::Limit(MinOrMax::Min(..)) => lower,
+            Self::Limit(MinOrMax::Max(..)) => upper,
+            Self::Number(n) => n.into_value(kind),
+        }
+    }
+}
+
+/// Represents the contents of a `#[range]` attribute. Either bound may be omitted, and it may end
+/// with `step N` to only include every `N`th value from the start.
+#[derive(Clone)]
+pub struct NumberArgRange {
+    pub start: Option<RangeBound>,
+    pub limits: syn::RangeLimits,
+    pub end: Option<RangeBound>,
+    pub step: Option<(kw::step, NumberArg)>,
+}
+
+impl Parse for NumberArgRange {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let start = if input.peek(syn::Token![..]) {
+            None
+        } else {
+            Some(input.parse()?)
+        };
+
+        let limits: syn::RangeLimits = input.parse()?;
+
+        let end = if input.is_empty() || input.peek(kw::step) {
+            None
+        } else {
+            Some(input.parse()?)
+        };
+
+        if end.is_none() && matches!(limits, syn::RangeLimits::Closed(..)) {
+            return Err(syn::Error::new(
+                limits.span(),
+                "an inclusive range must have an end bound",
+            ));
+        }
+
+        let step = if input.peek(kw::step) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            start,
+            limits,
+            end,
+            step,
+        })
+    }
+}
+
+impl ToTokens for NumberArgRange {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.start.to_tokens(tokens);
+        self.limits.to_tokens(tokens);
+        self.end.to_tokens(tokens);
+
+        if let Some((kw, step)) = &self.step {
+            kw.to_tokens(tokens);
+            step.to_tokens(tokens);
+        }
+    }
+}
+
+impl NumberArgRange {
+    /// Resolve the range into inclusive start and end values. Missing bounds default to the given
+    /// limits. Returns `None` when the range is empty.
+    pub fn into_inclusive(
+        &self,
+        kind: NumberKind,
+        lower: NumberValue,
+        upper: NumberValue,
+    ) -> Option<(NumberValue, NumberValue)> {
+        let start = self
+            .start
+            .as_ref()
+            .map_or(lower, |b| b.into_value(kind, lower, upper));
+
+        let end = match (&self.end, &self.limits) {
+            (None, _) => upper,
+            (Some(b), syn::RangeLimits::Closed(..)) => b.into_value(kind, lower, upper),
+            (Some(b), syn::RangeLimits::HalfOpen(..)) => {
+                let end = b.into_value(kind, lower, upper);
+
+                if end <= start {
+                    return None;
+                }
+
+                end - 1
+            }
+        };
+
+        if start > end {
+            None
+        } else {
+            Some((start, end))
+        }
+    }
+}
+
+/// Represents the behavior argument. It can be `Saturating`, `SaturatingWithLog` or `Panicking`.
 #[derive(Clone)]
 pub enum BehaviorArg {
     Saturating(SaturateOrSaturating),
+    SaturatingWithLog(kw::SaturatingWithLog),
     Panicking(PanicOrPanicking),
 }
 
+impl BehaviorArg {
+    /// The behavior used when none is given, which is `SaturatingWithLog` when the `never-panic`
+    /// feature forbids `Panicking`.
+    pub fn default_behavior() -> Self {
+        if cfg!(feature = "never-panic") {
+            parse_quote!(SaturatingWithLog)
+        } else {
+            parse_quote!(Panicking)
+        }
+    }
+
+    /// Whether out of bounds results saturate to the nearest limit instead of panicking.
+    pub fn is_saturating(&self) -> bool {
+        !matches!(self, Self::Panicking(..))
+    }
+}
+
 impl Parse for BehaviorArg {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         if input.peek(kw::Saturate) || input.peek(kw::Saturating) {
             Ok(Self::Saturating(input.parse()?))
+        } else if input.peek(kw::SaturatingWithLog) {
+            Ok(Self::SaturatingWithLog(input.parse()?))
         } else if input.peek(kw::Panic) || input.peek(kw::Panicking) {
-            Ok(Self::Panicking(input.parse()?))
+            let behavior: PanicOrPanicking = input.parse()?;
+
+            if cfg!(feature = "never-panic") {
+                return Err(syn::Error::new_spanned(
+                    behavior,
+                    "the `Panicking` behavior is forbidden by the `never-panic` feature; use `Saturating` or `SaturatingWithLog`",
+                ));
+            }
+
+            Ok(Self::Panicking(behavior))
+        } else {
+            Err(input.error("expected `Saturating`, `SaturatingWithLog` or `Panicking`"))
+        }
+    }
+}
+
+/// Represents the codegen argument. `match` expands a match arm for every value, while `table`
+/// looks values up in a static sorted table, which keeps the output small for large enums.
+#[derive(Clone)]
+pub enum CodegenArg {
+    Match(syn::Token![match]),
+    Table(kw::table),
+}
+
+impl Parse for CodegenArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Token![match]) {
+            Ok(Self::Match(input.parse()?))
+        } else if input.peek(kw::table) {
+            Ok(Self::Table(input.parse()?))
+        } else {
+            Err(input.error("expected `match` or `table`"))
+        }
+    }
+}
+
+impl ToTokens for CodegenArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Match(t) => t.to_tokens(tokens),
+            Self::Table(t) => t.to_tokens(tokens),
+        }
+    }
+}
+
+/// Represents the lookup argument. `ranges` checks the values of the `#[eq]` variants one by one,
+/// while `bitset` first looks the value up in a static bitset of those values.
+#[derive(Clone)]
+pub enum LookupArg {
+    Ranges(kw::ranges),
+    Bitset(kw::bitset),
+}
+
+impl Parse for LookupArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::ranges) {
+            Ok(Self::Ranges(input.parse()?))
+        } else if input.peek(kw::bitset) {
+            Ok(Self::Bitset(input.parse()?))
+        } else {
+            Err(input.error("expected `ranges` or `bitset`"))
+        }
+    }
+}
+
+impl ToTokens for LookupArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Ranges(t) => t.to_tokens(tokens),
+            Self::Bitset(t) => t.to_tokens(tokens),
+        }
+    }
+}
+
+/// Represents the coverage argument. `exhaustive` requires every value within the limits to be
+/// claimed by an `#[eq]`, `#[range]` or `#[mask]` variant instead of an `#[other]` catchall.
+#[derive(Clone)]
+pub enum CoverageArg {
+    Exhaustive(kw::exhaustive),
+}
+
+impl Parse for CoverageArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::exhaustive) {
+            Ok(Self::Exhaustive(input.parse()?))
         } else {
-            Err(input.error("expected `Saturating` or `Panicking`"))
+            Err(input.error("expected `exhaustive`"))
+        }
+    }
+}
+
+impl ToTokens for CoverageArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Exhaustive(t) => t.to_tokens(tokens),
+        }
+    }
+}
+
+/// Represents the `div_by_zero` argument, the policy for dividing by zero.
+#[derive(Clone)]
+pub enum DivByZeroArg {
+    Panic(kw::panic),
+    SaturateMax(kw::saturate_max),
+    ReturnDefault(kw::return_default),
+    CheckedOnly(kw::checked_only),
+}
+
+impl Parse for DivByZeroArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::panic) {
+            Ok(Self::Panic(input.parse()?))
+        } else if input.peek(kw::saturate_max) {
+            Ok(Self::SaturateMax(input.parse()?))
+        } else if input.peek(kw::return_default) {
+            Ok(Self::ReturnDefault(input.parse()?))
+        } else if input.peek(kw::checked_only) {
+            Ok(Self::CheckedOnly(input.parse()?))
+        } else {
+            Err(input.error("expected `panic`, `saturate_max`, `return_default` or `checked_only`"))
+        }
+    }
+}
+
+impl ToTokens for DivByZeroArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Panic(t) => t.to_tokens(tokens),
+            Self::SaturateMax(t) => t.to_tokens(tokens),
+            Self::ReturnDefault(t) => t.to_tokens(tokens),
+            Self::CheckedOnly(t) => t.to_tokens(tokens),
         }
     }
 }
 
+/// Represents the `[Name, ..]` list of generated implementations to leave out, given with
+/// `skip_impls`.
+#[derive(Clone)]
+pub struct SkipImplsArg {
+    pub bracket: syn::token::Bracket,
+    pub names: Punctuated<syn::Ident, syn::Token![,]>,
+}
+
+impl Parse for SkipImplsArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+
+        Ok(Self {
+            bracket: syn::bracketed!(content in input),
+            names: Punctuated::parse_terminated(&content)?,
+        })
+    }
+}
+
+/// The operators whose behavior can be set individually with `behavior = { op: Behavior, .. }`.
+pub const BEHAVIOR_OPS: [&str; 8] = [
+    "add", "sub", "mul", "div", "rem", "bitand", "bitor", "bitxor",
+];
+
+/// Represents a single `op: Behavior` entry of a per-operator `behavior` table. The `default`
+/// entry sets the behavior of every operator that is not listed.
+#[derive(Clone)]
+pub struct BehaviorEntry {
+    pub op: syn::Ident,
+    pub colon: syn::Token![:],
+    pub behavior: BehaviorArg,
+}
+
+impl Parse for BehaviorEntry {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let op = input.parse::<syn::Ident>()?;
+
+        if op != "default" && !BEHAVIOR_OPS.contains(&op.to_string().as_str()) {
+            return Err(syn::Error::new(
+                op.span(),
+                format!(
+                    "unknown operator `{}`; expected `default` or one of: {}",
+                    op,
+                    BEHAVIOR_OPS.join(", ")
+                ),
+            ));
+        }
+
+        Ok(Self {
+            op,
+            colon: input.parse()?,
+            behavior: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for BehaviorEntry {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.op.to_tokens(tokens);
+        self.colon.to_tokens(tokens);
+        self.behavior.to_tokens(tokens);
+    }
+}
+
 impl ToTokens for BehaviorArg {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.extend(match self {
             Self::Saturating(..) => quote! {
                 Saturating
             },
+            Self::SaturatingWithLog(..) => quote! {
+                SaturatingWithLog
+            },
             Self::Panicking(..) => quote! {
                 Panicking
             },
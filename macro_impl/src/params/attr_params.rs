@@ -1,9 +1,14 @@
+use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
-use proc_macro_error::abort;
-use quote::ToTokens;
-use syn::{parse::Parse, parse_quote, spanned::Spanned};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse::Parse, parse_quote, punctuated::Punctuated, spanned::Spanned};
 
-use super::{kw, AsSoftOrHard, BehaviorArg, NumberArg, NumberKind, NumberValue, SemiOrComma};
+use super::{
+    crate_config::CrateConfig, kw, AsSoftOrHard, BehaviorArg, BehaviorEntry, CodegenArg,
+    CoverageArg, DivByZeroArg, LookupArg, NumberArg, NumberKind, NumberValue, SemiOrComma,
+    SkipImplsArg,
+};
+use crate::diagnostic::abort;
 
 /// Represents the parameters of the `clamped` attribute.
 /// Only the `integer` and `default` parameters are required.
@@ -21,6 +26,8 @@ pub struct AttrParams {
     pub behavior_eq: syn::Token![=],
     pub behavior_val: BehaviorArg,
     pub behavior_semi: Option<SemiOrComma>,
+    /// The per-operator behaviors given with `behavior = { op: Behavior, .. }`.
+    pub behavior_ops: Vec<BehaviorEntry>,
     pub lower_kw: Option<kw::lower>,
     pub lower_eq: Option<syn::Token![=]>,
     pub lower_val: Option<NumberArg>,
@@ -29,6 +36,84 @@ pub struct AttrParams {
     pub upper_eq: Option<syn::Token![=]>,
     pub upper_val: Option<NumberArg>,
     pub upper_semi: Option<SemiOrComma>,
+    pub store_kw: Option<kw::store>,
+    pub store_eq: Option<syn::Token![=]>,
+    pub store_val: Option<syn::TypePath>,
+    pub store_semi: Option<SemiOrComma>,
+    pub maps_to_kw: Option<kw::maps_to>,
+    pub maps_to_eq: Option<syn::Token![=]>,
+    pub maps_to_val: Option<syn::TypePath>,
+    pub maps_to_semi: Option<SemiOrComma>,
+    pub secret_kw: Option<kw::secret>,
+    pub secret_semi: Option<SemiOrComma>,
+    pub literal_kw: Option<kw::literal>,
+    pub literal_eq: Option<syn::Token![=]>,
+    pub literal_val: Option<syn::Path>,
+    pub literal_semi: Option<SemiOrComma>,
+    pub separator_kw: Option<kw::separator>,
+    pub separator_eq: Option<syn::Token![=]>,
+    pub separator_val: Option<syn::LitStr>,
+    pub separator_semi: Option<SemiOrComma>,
+    pub codegen_kw: Option<kw::codegen>,
+    pub codegen_eq: Option<syn::Token![=]>,
+    pub codegen_val: Option<CodegenArg>,
+    pub codegen_semi: Option<SemiOrComma>,
+    pub auto_invalid_kw: Option<kw::auto_invalid>,
+    pub auto_invalid_semi: Option<SemiOrComma>,
+    pub benchmark_kw: Option<kw::benchmark>,
+    pub benchmark_semi: Option<SemiOrComma>,
+    pub metrics_kw: Option<kw::metrics>,
+    pub metrics_semi: Option<SemiOrComma>,
+    pub null_kw: Option<kw::null>,
+    pub null_eq: Option<syn::Token![=]>,
+    pub null_val: Option<NumberArg>,
+    pub null_semi: Option<SemiOrComma>,
+    pub div_by_zero_kw: Option<kw::div_by_zero>,
+    pub div_by_zero_eq: Option<syn::Token![=]>,
+    pub div_by_zero_val: Option<DivByZeroArg>,
+    pub div_by_zero_semi: Option<SemiOrComma>,
+    pub skip_impls_kw: Option<kw::skip_impls>,
+    pub skip_impls_eq: Option<syn::Token![=]>,
+    pub skip_impls_val: Option<SkipImplsArg>,
+    pub skip_impls_semi: Option<SemiOrComma>,
+    pub cyclic_kw: Option<kw::cyclic>,
+    pub cyclic_semi: Option<SemiOrComma>,
+    pub doc_examples_kw: Option<kw::doc_examples>,
+    pub doc_examples_semi: Option<SemiOrComma>,
+    pub serial_kw: Option<kw::serial>,
+    pub serial_semi: Option<SemiOrComma>,
+    pub serde_names_kw: Option<kw::serde_names>,
+    pub serde_names_semi: Option<SemiOrComma>,
+    pub lookup_kw: Option<kw::lookup>,
+    pub lookup_eq: Option<syn::Token![=]>,
+    pub lookup_val: Option<LookupArg>,
+    pub lookup_semi: Option<SemiOrComma>,
+    pub module_kw: Option<kw::module>,
+    pub module_eq: Option<syn::Token![=]>,
+    pub module_val: Option<syn::Ident>,
+    pub module_semi: Option<SemiOrComma>,
+    pub guard_drop_kw: Option<kw::guard_drop>,
+    pub guard_drop_eq: Option<syn::Token![=]>,
+    pub guard_drop_val: Option<syn::Path>,
+    pub guard_drop_semi: Option<SemiOrComma>,
+    pub explicit_imports_kw: Option<kw::explicit_imports>,
+    pub explicit_imports_semi: Option<SemiOrComma>,
+    pub guard_name_kw: Option<kw::guard_name>,
+    pub guard_name_eq: Option<syn::Token![=]>,
+    pub guard_name_val: Option<syn::Ident>,
+    pub guard_name_semi: Option<SemiOrComma>,
+    pub value_name_kw: Option<kw::value_name>,
+    pub value_name_eq: Option<syn::Token![=]>,
+    pub value_name_val: Option<syn::Ident>,
+    pub value_name_semi: Option<SemiOrComma>,
+    pub singleton_kw: Option<kw::singleton>,
+    pub singleton_eq: Option<syn::Token![=]>,
+    pub singleton_val: Option<NumberArg>,
+    pub singleton_semi: Option<SemiOrComma>,
+    pub coverage_kw: Option<kw::coverage>,
+    pub coverage_eq: Option<syn::Token![=]>,
+    pub coverage_val: Option<CoverageArg>,
+    pub coverage_semi: Option<SemiOrComma>,
 }
 
 impl Parse for AttrParams {
@@ -54,6 +139,7 @@ impl Parse for AttrParams {
                 behavior_eq: parse_quote!(=),
                 behavior_val: parse_quote!(Panicking),
                 behavior_semi: None,
+                behavior_ops: Vec::new(),
                 lower_kw: None,
                 lower_eq: None,
                 lower_val: None,
@@ -62,6 +148,84 @@ impl Parse for AttrParams {
                 upper_eq: None,
                 upper_val: None,
                 upper_semi: None,
+                store_kw: None,
+                store_eq: None,
+                store_val: None,
+                store_semi: None,
+                maps_to_kw: None,
+                maps_to_eq: None,
+                maps_to_val: None,
+                maps_to_semi: None,
+                secret_kw: None,
+                secret_semi: None,
+                literal_kw: None,
+                literal_eq: None,
+                literal_val: None,
+                literal_semi: None,
+                separator_kw: None,
+                separator_eq: None,
+                separator_val: None,
+                separator_semi: None,
+                codegen_kw: None,
+                codegen_eq: None,
+                codegen_val: None,
+                codegen_semi: None,
+                auto_invalid_kw: None,
+                auto_invalid_semi: None,
+                benchmark_kw: None,
+                benchmark_semi: None,
+                metrics_kw: None,
+                metrics_semi: None,
+                null_kw: None,
+                null_eq: None,
+                null_val: None,
+                null_semi: None,
+                div_by_zero_kw: None,
+                div_by_zero_eq: None,
+                div_by_zero_val: None,
+                div_by_zero_semi: None,
+                skip_impls_kw: None,
+                skip_impls_eq: None,
+                skip_impls_val: None,
+                skip_impls_semi: None,
+                cyclic_kw: None,
+                cyclic_semi: None,
+                doc_examples_kw: None,
+                doc_examples_semi: None,
+                serial_kw: None,
+                serial_semi: None,
+                serde_names_kw: None,
+                serde_names_semi: None,
+                lookup_kw: None,
+                lookup_eq: None,
+                lookup_val: None,
+                lookup_semi: None,
+                module_kw: None,
+                module_eq: None,
+                module_val: None,
+                module_semi: None,
+                guard_drop_kw: None,
+                guard_drop_eq: None,
+                guard_drop_val: None,
+                guard_drop_semi: None,
+                explicit_imports_kw: None,
+                explicit_imports_semi: None,
+                guard_name_kw: None,
+                guard_name_eq: None,
+                guard_name_val: None,
+                guard_name_semi: None,
+                value_name_kw: None,
+                value_name_eq: None,
+                value_name_val: None,
+                value_name_semi: None,
+                singleton_kw: None,
+                singleton_eq: None,
+                singleton_val: None,
+                singleton_semi: None,
+                coverage_kw: None,
+                coverage_eq: None,
+                coverage_val: None,
+                coverage_semi: None,
             });
         } else {
             integer_semi = Some(input.parse::<SemiOrComma>()?);
@@ -71,10 +235,19 @@ impl Parse for AttrParams {
         let mut default_eq = None;
         let mut default_val = None;
         let mut default_semi = None;
+        let config = CrateConfig::current()?
+            .map(|(config, _)| config)
+            .unwrap_or_default();
+        let default_behavior = config
+            .behavior
+            .clone()
+            .unwrap_or_else(BehaviorArg::default_behavior);
+
         let mut behavior_kw = None;
         let mut behavior_eq = None;
         let mut behavior_val = None;
         let mut behavior_semi = None;
+        let mut behavior_ops = Vec::<BehaviorEntry>::new();
         let mut lower_kw = None;
         let mut lower_eq = None;
         let mut lower_val = None;
@@ -83,6 +256,84 @@ impl Parse for AttrParams {
         let mut upper_eq = None;
         let mut upper_val = None;
         let mut upper_semi = None;
+        let mut store_kw = None;
+        let mut store_eq = None;
+        let mut store_val = None;
+        let mut store_semi = None;
+        let mut maps_to_kw = None;
+        let mut maps_to_eq = None;
+        let mut maps_to_val = None;
+        let mut maps_to_semi = None;
+        let mut secret_kw = None;
+        let mut secret_semi = None;
+        let mut literal_kw = None;
+        let mut literal_eq = None;
+        let mut literal_val = None;
+        let mut literal_semi = None;
+        let mut separator_kw = None;
+        let mut separator_eq = None;
+        let mut separator_val = None;
+        let mut separator_semi = None;
+        let mut codegen_kw = None;
+        let mut codegen_eq = None;
+        let mut codegen_val = None;
+        let mut codegen_semi = None;
+        let mut auto_invalid_kw = None;
+        let mut auto_invalid_semi = None;
+        let mut benchmark_kw = None;
+        let mut benchmark_semi = None;
+        let mut metrics_kw = None;
+        let mut metrics_semi = None;
+        let mut null_kw = None;
+        let mut null_eq = None;
+        let mut null_val = None;
+        let mut null_semi = None;
+        let mut div_by_zero_kw = None;
+        let mut div_by_zero_eq = None;
+        let mut div_by_zero_val = None;
+        let mut div_by_zero_semi = None;
+        let mut skip_impls_kw = None;
+        let mut skip_impls_eq = None;
+        let mut skip_impls_val = None;
+        let mut skip_impls_semi = None;
+        let mut cyclic_kw = None;
+        let mut cyclic_semi = None;
+        let mut doc_examples_kw = None;
+        let mut doc_examples_semi = None;
+        let mut serial_kw = None;
+        let mut serial_semi = None;
+        let mut serde_names_kw = None;
+        let mut serde_names_semi = None;
+        let mut lookup_kw = None;
+        let mut lookup_eq = None;
+        let mut lookup_val = None;
+        let mut lookup_semi = None;
+        let mut module_kw = None;
+        let mut module_eq = None;
+        let mut module_val = None;
+        let mut module_semi = None;
+        let mut guard_drop_kw = None;
+        let mut guard_drop_eq = None;
+        let mut guard_drop_val = None;
+        let mut guard_drop_semi = None;
+        let mut explicit_imports_kw = None;
+        let mut explicit_imports_semi = None;
+        let mut guard_name_kw = None;
+        let mut guard_name_eq = None;
+        let mut guard_name_val = None;
+        let mut guard_name_semi = None;
+        let mut value_name_kw = None;
+        let mut value_name_eq = None;
+        let mut value_name_val = None;
+        let mut value_name_semi = None;
+        let mut singleton_kw = None;
+        let mut singleton_eq = None;
+        let mut singleton_val = None;
+        let mut singleton_semi = None;
+        let mut coverage_kw = None;
+        let mut coverage_eq = None;
+        let mut coverage_val = None;
+        let mut coverage_semi = None;
 
         let mut done = false;
 
@@ -108,7 +359,40 @@ impl Parse for AttrParams {
 
                 behavior_kw = Some(input.parse::<kw::behavior>()?);
                 behavior_eq = Some(input.parse::<syn::Token![=]>()?);
-                behavior_val = Some(input.parse::<BehaviorArg>()?);
+                if input.peek(syn::token::Brace) {
+                    let content;
+                    syn::braced!(content in input);
+
+                    for entry in
+                        Punctuated::<BehaviorEntry, syn::Token![,]>::parse_terminated(&content)?
+                    {
+                        if entry.op == "default" {
+                            if behavior_val.is_some() {
+                                return Err(syn::Error::new(
+                                    entry.op.span(),
+                                    "duplicate `default` behavior",
+                                ));
+                            }
+
+                            behavior_val = Some(entry.behavior);
+                        } else {
+                            if behavior_ops.iter().any(|prev| prev.op == entry.op) {
+                                return Err(syn::Error::new(
+                                    entry.op.span(),
+                                    format!("duplicate `{}` behavior", entry.op),
+                                ));
+                            }
+
+                            behavior_ops.push(entry);
+                        }
+                    }
+
+                    if behavior_val.is_none() {
+                        behavior_val = Some(default_behavior.clone());
+                    }
+                } else {
+                    behavior_val = Some(input.parse::<BehaviorArg>()?);
+                }
                 if !input.is_empty() {
                     behavior_semi = Some(input.parse::<SemiOrComma>()?);
                     found_semi = true;
@@ -137,10 +421,298 @@ impl Parse for AttrParams {
                     upper_semi = Some(input.parse::<SemiOrComma>()?);
                     found_semi = true;
                 }
+            } else if input.peek(kw::store) {
+                if store_kw.is_some() {
+                    return Err(input.error("duplicate `store` param"));
+                }
+
+                store_kw = Some(input.parse::<kw::store>()?);
+                store_eq = Some(input.parse::<syn::Token![=]>()?);
+                store_val = Some(input.parse::<syn::TypePath>()?);
+                if !input.is_empty() {
+                    store_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::maps_to) {
+                if maps_to_kw.is_some() {
+                    return Err(input.error("duplicate `maps_to` param"));
+                }
+
+                maps_to_kw = Some(input.parse::<kw::maps_to>()?);
+                maps_to_eq = Some(input.parse::<syn::Token![=]>()?);
+                maps_to_val = Some(input.parse::<syn::TypePath>()?);
+                if !input.is_empty() {
+                    maps_to_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::secret) {
+                if secret_kw.is_some() {
+                    return Err(input.error("duplicate `secret` param"));
+                }
+
+                secret_kw = Some(input.parse::<kw::secret>()?);
+                if !input.is_empty() {
+                    secret_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::literal) {
+                if literal_kw.is_some() {
+                    return Err(input.error("duplicate `literal` param"));
+                }
+
+                literal_kw = Some(input.parse::<kw::literal>()?);
+                literal_eq = Some(input.parse::<syn::Token![=]>()?);
+                let path = input.parse::<syn::Path>()?;
+
+                // Either the name of the macro or its path from the crate root.
+                let valid = path.leading_colon.is_none()
+                    && path.segments.iter().all(|s| s.arguments.is_none())
+                    && match path.segments.len() {
+                        1 => path.segments[0].ident != "crate",
+                        _ => path.segments[0].ident == "crate",
+                    };
+
+                if !valid {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "expected the name of the macro, such as `port`, or its path from the crate root, such as `crate::net::port`",
+                    ));
+                }
+
+                literal_val = Some(path);
+                if !input.is_empty() {
+                    literal_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::separator) {
+                if separator_kw.is_some() {
+                    return Err(input.error("duplicate `separator` param"));
+                }
+
+                separator_kw = Some(input.parse::<kw::separator>()?);
+                separator_eq = Some(input.parse::<syn::Token![=]>()?);
+                separator_val = Some(input.parse::<syn::LitStr>()?);
+                if !input.is_empty() {
+                    separator_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::codegen) {
+                if codegen_kw.is_some() {
+                    return Err(input.error("duplicate `codegen` param"));
+                }
+
+                codegen_kw = Some(input.parse::<kw::codegen>()?);
+                codegen_eq = Some(input.parse::<syn::Token![=]>()?);
+                codegen_val = Some(input.parse::<CodegenArg>()?);
+                if !input.is_empty() {
+                    codegen_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::auto_invalid) {
+                if auto_invalid_kw.is_some() {
+                    return Err(input.error("duplicate `auto_invalid` param"));
+                }
+
+                auto_invalid_kw = Some(input.parse::<kw::auto_invalid>()?);
+                if !input.is_empty() {
+                    auto_invalid_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::benchmark) {
+                if benchmark_kw.is_some() {
+                    return Err(input.error("duplicate `benchmark` param"));
+                }
+
+                benchmark_kw = Some(input.parse::<kw::benchmark>()?);
+                if !input.is_empty() {
+                    benchmark_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::metrics) {
+                if metrics_kw.is_some() {
+                    return Err(input.error("duplicate `metrics` param"));
+                }
+
+                metrics_kw = Some(input.parse::<kw::metrics>()?);
+                if !input.is_empty() {
+                    metrics_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::null) {
+                if null_kw.is_some() {
+                    return Err(input.error("duplicate `null` param"));
+                }
+
+                null_kw = Some(input.parse::<kw::null>()?);
+                null_eq = Some(input.parse::<syn::Token![=]>()?);
+                null_val = Some(input.parse::<NumberArg>()?);
+                if !input.is_empty() {
+                    null_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::div_by_zero) {
+                if div_by_zero_kw.is_some() {
+                    return Err(input.error("duplicate `div_by_zero` param"));
+                }
+
+                div_by_zero_kw = Some(input.parse::<kw::div_by_zero>()?);
+                div_by_zero_eq = Some(input.parse::<syn::Token![=]>()?);
+                div_by_zero_val = Some(input.parse::<DivByZeroArg>()?);
+                if !input.is_empty() {
+                    div_by_zero_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::skip_impls) {
+                if skip_impls_kw.is_some() {
+                    return Err(input.error("duplicate `skip_impls` param"));
+                }
+
+                skip_impls_kw = Some(input.parse::<kw::skip_impls>()?);
+                skip_impls_eq = Some(input.parse::<syn::Token![=]>()?);
+                skip_impls_val = Some(input.parse::<SkipImplsArg>()?);
+                if !input.is_empty() {
+                    skip_impls_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::cyclic) {
+                if cyclic_kw.is_some() {
+                    return Err(input.error("duplicate `cyclic` param"));
+                }
+
+                cyclic_kw = Some(input.parse::<kw::cyclic>()?);
+                if !input.is_empty() {
+                    cyclic_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::doc_examples) {
+                if doc_examples_kw.is_some() {
+                    return Err(input.error("duplicate `doc_examples` param"));
+                }
+
+                doc_examples_kw = Some(input.parse::<kw::doc_examples>()?);
+                if !input.is_empty() {
+                    doc_examples_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::serial) {
+                if serial_kw.is_some() {
+                    return Err(input.error("duplicate `serial` param"));
+                }
+
+                serial_kw = Some(input.parse::<kw::serial>()?);
+                if !input.is_empty() {
+                    serial_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::serde_names) {
+                if serde_names_kw.is_some() {
+                    return Err(input.error("duplicate `serde_names` param"));
+                }
+
+                serde_names_kw = Some(input.parse::<kw::serde_names>()?);
+                if !input.is_empty() {
+                    serde_names_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::lookup) {
+                if lookup_kw.is_some() {
+                    return Err(input.error("duplicate `lookup` param"));
+                }
+
+                lookup_kw = Some(input.parse::<kw::lookup>()?);
+                lookup_eq = Some(input.parse::<syn::Token![=]>()?);
+                lookup_val = Some(input.parse::<LookupArg>()?);
+                if !input.is_empty() {
+                    lookup_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::module) {
+                if module_kw.is_some() {
+                    return Err(input.error("duplicate `module` param"));
+                }
+
+                module_kw = Some(input.parse::<kw::module>()?);
+                module_eq = Some(input.parse::<syn::Token![=]>()?);
+                module_val = Some(input.parse::<syn::Ident>()?);
+                if !input.is_empty() {
+                    module_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::guard_drop) {
+                if guard_drop_kw.is_some() {
+                    return Err(input.error("duplicate `guard_drop` param"));
+                }
+
+                guard_drop_kw = Some(input.parse::<kw::guard_drop>()?);
+                guard_drop_eq = Some(input.parse::<syn::Token![=]>()?);
+                guard_drop_val = Some(input.parse::<syn::Path>()?);
+                if !input.is_empty() {
+                    guard_drop_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::explicit_imports) {
+                if explicit_imports_kw.is_some() {
+                    return Err(input.error("duplicate `explicit_imports` param"));
+                }
+
+                explicit_imports_kw = Some(input.parse::<kw::explicit_imports>()?);
+                if !input.is_empty() {
+                    explicit_imports_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::guard_name) {
+                if guard_name_kw.is_some() {
+                    return Err(input.error("duplicate `guard_name` param"));
+                }
+
+                guard_name_kw = Some(input.parse::<kw::guard_name>()?);
+                guard_name_eq = Some(input.parse::<syn::Token![=]>()?);
+                guard_name_val = Some(input.parse::<syn::Ident>()?);
+                if !input.is_empty() {
+                    guard_name_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::value_name) {
+                if value_name_kw.is_some() {
+                    return Err(input.error("duplicate `value_name` param"));
+                }
+
+                value_name_kw = Some(input.parse::<kw::value_name>()?);
+                value_name_eq = Some(input.parse::<syn::Token![=]>()?);
+                value_name_val = Some(input.parse::<syn::Ident>()?);
+                if !input.is_empty() {
+                    value_name_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::singleton) {
+                if singleton_kw.is_some() {
+                    return Err(input.error("duplicate `singleton` param"));
+                }
+
+                singleton_kw = Some(input.parse::<kw::singleton>()?);
+                singleton_eq = Some(input.parse::<syn::Token![=]>()?);
+                singleton_val = Some(input.parse::<NumberArg>()?);
+                if !input.is_empty() {
+                    singleton_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
+            } else if input.peek(kw::coverage) {
+                if coverage_kw.is_some() {
+                    return Err(input.error("duplicate `coverage` param"));
+                }
+
+                coverage_kw = Some(input.parse::<kw::coverage>()?);
+                coverage_eq = Some(input.parse::<syn::Token![=]>()?);
+                coverage_val = Some(input.parse::<CoverageArg>()?);
+                if !input.is_empty() {
+                    coverage_semi = Some(input.parse::<SemiOrComma>()?);
+                    found_semi = true;
+                }
             }
 
             if !found_semi {
-                if default_kw.is_none() {
+                // The value of a singleton is also its default.
+                if default_kw.is_none() && singleton_kw.is_none() {
                     return Err(input.error("`default` param is required"));
                 }
 
@@ -148,6 +720,34 @@ impl Parse for AttrParams {
             }
         }
 
+        if div_by_zero_kw.is_none() {
+            div_by_zero_val = config.div_by_zero;
+        }
+
+        if guard_drop_kw.is_none() {
+            guard_drop_val = config.guard_drop;
+        }
+
+        // A singleton is the type of a single value, so it stands for both limits.
+        if let Some(singleton) = &singleton_val {
+            if let Some(kw) = &lower_kw {
+                return Err(syn::Error::new(
+                    kw.span,
+                    "The `lower` parameter cannot be combined with `singleton`",
+                ));
+            }
+
+            if let Some(kw) = &upper_kw {
+                return Err(syn::Error::new(
+                    kw.span,
+                    "The `upper` parameter cannot be combined with `singleton`",
+                ));
+            }
+
+            lower_val = Some(singleton.clone());
+            upper_val = Some(singleton.clone());
+        }
+
         if default_kw.is_none() {
             default_kw = Some(parse_quote!(default));
             default_eq = Some(parse_quote!(=));
@@ -161,7 +761,7 @@ impl Parse for AttrParams {
         if behavior_kw.is_none() {
             behavior_kw = Some(parse_quote!(behavior));
             behavior_eq = Some(parse_quote!(=));
-            behavior_val = Some(parse_quote!(Panicking));
+            behavior_val = Some(default_behavior);
         }
 
         let this = Self {
@@ -176,6 +776,7 @@ impl Parse for AttrParams {
             behavior_eq: behavior_eq.unwrap(),
             behavior_val: behavior_val.unwrap(),
             behavior_semi,
+            behavior_ops,
             lower_kw,
             lower_eq,
             lower_val,
@@ -184,6 +785,84 @@ impl Parse for AttrParams {
             upper_eq,
             upper_val,
             upper_semi,
+            store_kw,
+            store_eq,
+            store_val,
+            store_semi,
+            maps_to_kw,
+            maps_to_eq,
+            maps_to_val,
+            maps_to_semi,
+            secret_kw,
+            secret_semi,
+            literal_kw,
+            literal_eq,
+            literal_val,
+            literal_semi,
+            separator_kw,
+            separator_eq,
+            separator_val,
+            separator_semi,
+            codegen_kw,
+            codegen_eq,
+            codegen_val,
+            codegen_semi,
+            auto_invalid_kw,
+            auto_invalid_semi,
+            benchmark_kw,
+            benchmark_semi,
+            metrics_kw,
+            metrics_semi,
+            null_kw,
+            null_eq,
+            null_val,
+            null_semi,
+            div_by_zero_kw,
+            div_by_zero_eq,
+            div_by_zero_val,
+            div_by_zero_semi,
+            skip_impls_kw,
+            skip_impls_eq,
+            skip_impls_val,
+            skip_impls_semi,
+            cyclic_kw,
+            cyclic_semi,
+            doc_examples_kw,
+            doc_examples_semi,
+            serial_kw,
+            serial_semi,
+            serde_names_kw,
+            serde_names_semi,
+            lookup_kw,
+            lookup_eq,
+            lookup_val,
+            lookup_semi,
+            module_kw,
+            module_eq,
+            module_val,
+            module_semi,
+            guard_drop_kw,
+            guard_drop_eq,
+            guard_drop_val,
+            guard_drop_semi,
+            explicit_imports_kw,
+            explicit_imports_semi,
+            guard_name_kw,
+            guard_name_eq,
+            guard_name_val,
+            guard_name_semi,
+            value_name_kw,
+            value_name_eq,
+            value_name_val,
+            value_name_semi,
+            singleton_kw,
+            singleton_eq,
+            singleton_val,
+            singleton_semi,
+            coverage_kw,
+            coverage_eq,
+            coverage_val,
+            coverage_semi,
         };
 
         if !this.is_u128_or_smaller() {
@@ -253,6 +932,17 @@ impl Parse for AttrParams {
             }
         }
 
+        if let Some(store_kind) = this.store_kind() {
+            if this.lower_limit_value().cast(store_kind).is_none()
+                || this.upper_limit_value().cast(store_kind).is_none()
+            {
+                abort!(
+                    this.store_val,
+                    "the lower and upper limits must fit within the `store` type"
+                )
+            }
+        }
+
         if this.default_value() < this.lower_limit_value() {
             abort!(
                 this.default_val,
@@ -264,6 +954,40 @@ impl Parse for AttrParams {
             abort!(this.default_val, "default value exceeds upper bound value")
         }
 
+        if let Some(null) = &this.null_val {
+            let value = null.into_value(this.kind());
+
+            if value < this.lower_limit_value() || value > this.upper_limit_value() {
+                abort!(
+                    null,
+                    "the `null` value must be within the lower and upper limits"
+                )
+            }
+
+            if let Some(auto_invalid) = &this.auto_invalid_kw {
+                abort!(
+                    auto_invalid,
+                    "The `null` parameter cannot be combined with `auto_invalid`"
+                )
+            }
+        }
+
+        if let Some(serde_names) = &this.serde_names_kw {
+            if this.auto_invalid_kw.is_some() || this.null_kw.is_some() {
+                abort!(
+                    serde_names,
+                    "The `serde_names` parameter cannot be combined with `auto_invalid` or `null`"
+                )
+            }
+
+            if this.secret_kw.is_some() {
+                abort!(
+                    serde_names,
+                    "The `serde_names` parameter cannot be combined with `secret`"
+                )
+            }
+        }
+
         Ok(this)
     }
 }
@@ -294,6 +1018,22 @@ impl AttrParams {
             .unwrap_or_else(|| abort!(self.integer, "expected number type"))
     }
 
+    /// Get the number kind of the `store` type, if one was given.
+    pub fn store_kind(&self) -> Option<NumberKind> {
+        self.store_val
+            .as_ref()
+            .map(|ty| match syn::parse2(ty.to_token_stream()) {
+                Ok(kind) => kind,
+                Err(e) => abort!(ty, e.to_string()),
+            })
+    }
+
+    /// Get the type used to store the value. This is the `store` type if one was given,
+    /// otherwise it is the integer type.
+    pub fn storage_type(&self) -> &syn::TypePath {
+        self.store_val.as_ref().unwrap_or(&self.integer)
+    }
+
     /// Interpret the default value as `NumberValue`.
     pub fn default_value(&self) -> NumberValue {
         self.default_val.into_value(self.kind())
@@ -304,6 +1044,165 @@ impl AttrParams {
         &self.behavior_val
     }
 
+    /// Whether `codegen = table` was given, so lookups use a static sorted table instead of match
+    /// arms.
+    pub fn uses_table_codegen(&self) -> bool {
+        matches!(self.codegen_val, Some(CodegenArg::Table(..)))
+    }
+
+    /// Get the name of the module that holds the generated items, which is given by the `module`
+    /// parameter or else `clamped_` followed by the name of the type in snake case.
+    pub fn module_name(&self, name: &syn::Ident) -> syn::Ident {
+        match &self.module_val {
+            Some(module) if module == name => abort!(
+                module,
+                "The module must be named differently from the type, such as `{}`",
+                name.to_string().to_case(Case::Snake)
+            ),
+            Some(module) => module.clone(),
+            None => format_ident!("clamped_{}", name.to_string().to_case(Case::Snake)),
+        }
+    }
+
+    /// Get the name of the guard returned by `modify()`, which is given by the `guard_name`
+    /// parameter or else the name of the type followed by `Guard`.
+    pub fn guard_name(&self, name: &syn::Ident) -> syn::Ident {
+        match &self.guard_name_val {
+            Some(guard_name) if guard_name == name => {
+                abort!(
+                    guard_name,
+                    "The guard must be named differently from the type"
+                )
+            }
+            Some(guard_name) => guard_name.clone(),
+            None => format_ident!("{}Guard", name),
+        }
+    }
+
+    /// Get the name of the guard returned by `modify_auto()`, which is `Auto` followed by the
+    /// `guard_name` parameter when it is given, or else the name of the type followed by
+    /// `AutoGuard`.
+    pub fn auto_guard_name(&self, name: &syn::Ident) -> syn::Ident {
+        match &self.guard_name_val {
+            Some(guard_name) => format_ident!("Auto{}", guard_name),
+            None => format_ident!("{}AutoGuard", name),
+        }
+    }
+
+    /// Get the name of the wrapper held by the variants of an enum that are not `#[range]`
+    /// variants, which is given by the `value_name` parameter or else the name of the type
+    /// followed by `Value`.
+    pub fn value_name(&self, name: &syn::Ident) -> syn::Ident {
+        match &self.value_name_val {
+            Some(value_name) if value_name == name => {
+                abort!(
+                    value_name,
+                    "The value wrapper must be named differently from the type"
+                )
+            }
+            Some(value_name) => value_name.clone(),
+            None => format_ident!("{}Value", name),
+        }
+    }
+
+    /// Get the name of the struct held by a `#[range]` variant, which is the name of the variant
+    /// followed by `Value`.
+    pub fn range_value_name(&self, variant: &syn::Ident) -> syn::Ident {
+        format_ident!("{}Value", variant)
+    }
+
+    /// Get the name of the fieldless enum with a variant for each variant of an enum, which is
+    /// the name of the type followed by `Kind`.
+    pub fn kind_name(&self, name: &syn::Ident) -> syn::Ident {
+        format_ident!("{}Kind", name)
+    }
+
+    /// Get the name of the signed difference type of a hard struct, which is the name of the type
+    /// followed by `Delta`.
+    pub fn delta_name(&self, name: &syn::Ident) -> syn::Ident {
+        format_ident!("{}Delta", name)
+    }
+
+    /// Get the name of the macro given by the `literal` parameter, along with the modules from the
+    /// crate root to the type, which are empty when only the name is given.
+    pub fn literal_macro(&self) -> Option<(&syn::Ident, Vec<&syn::Ident>)> {
+        self.literal_val.as_ref().map(|path| {
+            let mut segments = path.segments.iter().map(|segment| &segment.ident);
+            let name = segments.next_back().expect("path should not be empty");
+
+            (name, segments.skip(1).collect())
+        })
+    }
+
+    /// Get the policy for guards that are dropped without calling `commit` or `discard`, which is
+    /// `WarnOnDrop` unless the `guard_drop` parameter or the crate's `configure!` gives another.
+    pub fn guard_drop_policy(&self) -> syn::Path {
+        self.guard_drop_val
+            .clone()
+            .unwrap_or_else(|| parse_quote!(WarnOnDrop))
+    }
+
+    /// Whether `lookup = bitset` was given, so the values of the `#[eq]` variants are found with a
+    /// static bitset.
+    pub fn uses_bitset_lookup(&self) -> bool {
+        matches!(self.lookup_val, Some(LookupArg::Bitset(..)))
+    }
+
+    /// Get the value marked as unset by the `null` parameter, if it was given.
+    pub fn null_value(&self) -> Option<NumberValue> {
+        self.null_val
+            .as_ref()
+            .map(|val| val.into_value(self.kind()))
+    }
+
+    /// Whether the `metrics` flag was given and the `metrics` feature is enabled, so operations
+    /// record how often they saturated, panicked or were rejected.
+    pub fn uses_metrics(&self) -> bool {
+        cfg!(feature = "metrics") && self.metrics_kw.is_some()
+    }
+
+    /// Whether the wrap-around comparisons are generated, which the `serial` flag implies.
+    pub fn uses_cyclic(&self) -> bool {
+        self.cyclic_kw.is_some() || self.serial_kw.is_some()
+    }
+
+    /// Whether `div_by_zero = checked_only` was given, so division is only offered through
+    /// `checked_div` and `checked_rem`.
+    pub fn uses_checked_division(&self) -> bool {
+        matches!(self.div_by_zero_val, Some(DivByZeroArg::CheckedOnly(..)))
+    }
+
+    /// The names of the generated implementations given with `skip_impls`.
+    pub fn skipped_impls(&self) -> Vec<&syn::Ident> {
+        self.skip_impls_val
+            .iter()
+            .flat_map(|arg| arg.names.iter())
+            .collect()
+    }
+
+    /// Get the behavior of the given operator, falling back to the default behavior when the
+    /// operator was not given its own.
+    pub fn behavior_for(&self, op: &str) -> &BehaviorArg {
+        self.behavior_ops
+            .iter()
+            .find(|entry| entry.op == op)
+            .map(|entry| &entry.behavior)
+            .unwrap_or(&self.behavior_val)
+    }
+
+    /// Get the tokens of the `behavior` param as they were given, either a single behavior or a
+    /// per-operator table.
+    pub fn behavior_tokens(&self) -> TokenStream {
+        let default = &self.behavior_val;
+
+        if self.behavior_ops.is_empty() {
+            quote! { #default }
+        } else {
+            let entries = &self.behavior_ops;
+            quote! { { #(#entries,)* default: #default } }
+        }
+    }
+
     /// Interpret the lower limit value as `NumberValue`.
     pub fn lower_limit_value(&self) -> NumberValue {
         let kind = self.kind();
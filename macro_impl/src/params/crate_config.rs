@@ -0,0 +1,227 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro2::Span;
+use syn::{parse::Parse, punctuated::Punctuated, Token};
+
+use super::{kw, BehaviorArg, DivByZeroArg};
+
+/// Represents the crate-level defaults given with `configure! { .. }`. Each entry ends with `;`.
+#[derive(Default)]
+pub struct CrateConfig {
+    pub behavior: Option<BehaviorArg>,
+    pub div_by_zero: Option<DivByZeroArg>,
+    pub guard_drop: Option<syn::Path>,
+    pub derives: Vec<syn::Path>,
+}
+
+impl Parse for CrateConfig {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut this = Self::default();
+
+        while !input.is_empty() {
+            if input.peek(kw::behavior) {
+                let kw = input.parse::<kw::behavior>()?;
+
+                if this.behavior.is_some() {
+                    return Err(syn::Error::new(kw.span, "duplicate `behavior` entry"));
+                }
+
+                input.parse::<Token![=]>()?;
+                this.behavior = Some(input.parse()?);
+            } else if input.peek(kw::div_by_zero) {
+                let kw = input.parse::<kw::div_by_zero>()?;
+
+                if this.div_by_zero.is_some() {
+                    return Err(syn::Error::new(kw.span, "duplicate `div_by_zero` entry"));
+                }
+
+                input.parse::<Token![=]>()?;
+                this.div_by_zero = Some(input.parse()?);
+            } else if input.peek(kw::guard_drop) {
+                let kw = input.parse::<kw::guard_drop>()?;
+
+                if this.guard_drop.is_some() {
+                    return Err(syn::Error::new(kw.span, "duplicate `guard_drop` entry"));
+                }
+
+                input.parse::<Token![=]>()?;
+                this.guard_drop = Some(input.parse()?);
+            } else if input.peek(kw::derive) {
+                input.parse::<kw::derive>()?;
+
+                let content;
+                syn::parenthesized!(content in input);
+
+                this.derives
+                    .extend(Punctuated::<syn::Path, Token![,]>::parse_terminated(
+                        &content,
+                    )?);
+            } else {
+                return Err(
+                    input.error("expected `behavior`, `div_by_zero`, `guard_drop` or `derive(..)`")
+                );
+            }
+
+            input.parse::<Token![;]>()?;
+        }
+
+        Ok(this)
+    }
+}
+
+/// The environment variable that names the file holding the crate's `configure!` invocation. A
+/// relative path is resolved against the directory of the crate's manifest.
+pub const CONFIG_ENV: &str = "CHECKED_RS_CONFIG";
+
+impl CrateConfig {
+    /// Get the defaults given by the `configure!` invocation in the file named by
+    /// `CHECKED_RS_CONFIG`, along with the path of that file. Every target of the crate that is
+    /// compiled with the variable set reads the same file, so the result does not depend on the
+    /// target or on the order in which the macros expand. Without the variable, there are no
+    /// defaults.
+    pub fn current() -> syn::Result<Option<(Self, PathBuf)>> {
+        let Some(value) = std::env::var_os(CONFIG_ENV) else {
+            return Ok(None);
+        };
+
+        let path = resolve_path(
+            Path::new(&value),
+            std::env::var_os("CARGO_MANIFEST_DIR")
+                .as_deref()
+                .map(Path::new),
+        );
+
+        Self::read(&path).map(|config| Some((config, path)))
+    }
+
+    /// Read the defaults from the top level `configure!` invocation of the file at `path`.
+    pub fn read(path: &Path) -> syn::Result<Self> {
+        let error = |message: String| syn::Error::new(Span::call_site(), message);
+
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            error(format!(
+                "cannot read the `{CONFIG_ENV}` file `{}`: {e}",
+                path.display()
+            ))
+        })?;
+
+        let file = syn::parse_file(&contents).map_err(|e| {
+            error(format!(
+                "cannot parse the `{CONFIG_ENV}` file `{}`: {e}",
+                path.display()
+            ))
+        })?;
+
+        let tokens = file
+            .items
+            .into_iter()
+            .find_map(|item| match item {
+                syn::Item::Macro(item)
+                    if item
+                        .mac
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "configure") =>
+                {
+                    Some(item.mac.tokens)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                error(format!(
+                    "the `{CONFIG_ENV}` file `{}` has no top level `configure!` invocation",
+                    path.display()
+                ))
+            })?;
+
+        syn::parse2::<Self>(tokens).map_err(|e| {
+            error(format!(
+                "invalid `configure!` in the `{CONFIG_ENV}` file `{}`: {e}",
+                path.display()
+            ))
+        })
+    }
+}
+
+/// Resolve the value of `CHECKED_RS_CONFIG` against the directory of the crate's manifest.
+fn resolve_path(value: &Path, manifest_dir: Option<&Path>) -> PathBuf {
+    match manifest_dir {
+        Some(dir) if value.is_relative() => dir.join(value),
+        _ => value.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("checked-rs-{}-{name}.rs", std::process::id()));
+
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read() {
+        let path = write_config(
+            "read",
+            "use std::fmt;\n\
+             checked_rs::configure! {\n\
+                 behavior = Saturating;\n\
+                 div_by_zero = saturate_max;\n\
+                 derive(Debug, Clone, Copy);\n\
+             }\n",
+        );
+
+        let config = CrateConfig::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(config.behavior.is_some());
+        assert!(config.div_by_zero.is_some());
+        assert!(config.guard_drop.is_none());
+        assert_eq!(config.derives.len(), 3);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let path = write_config(
+            "invalid",
+            "configure! { behavior = Saturating; behavior = Panicking; }",
+        );
+        let error = CrateConfig::read(&path).err().unwrap().to_string();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.contains("duplicate `behavior` entry"), "{error}");
+
+        let path = write_config("missing", "pub struct Settings;");
+        let error = CrateConfig::read(&path).err().unwrap().to_string();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.contains("no top level `configure!`"), "{error}");
+
+        let error = CrateConfig::read(Path::new("/nonexistent/checked-rs.rs"))
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("cannot read"), "{error}");
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let dir = Path::new("/work/app");
+
+        assert_eq!(
+            resolve_path(Path::new("checked-rs.rs"), Some(dir)),
+            Path::new("/work/app/checked-rs.rs")
+        );
+        assert_eq!(
+            resolve_path(Path::new("/etc/checked-rs.rs"), Some(dir)),
+            Path::new("/etc/checked-rs.rs")
+        );
+        assert_eq!(
+            resolve_path(Path::new("checked-rs.rs"), None),
+            Path::new("checked-rs.rs")
+        );
+    }
+}
@@ -1,13 +1,20 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
 
 use convert_case::{Case, Casing};
-use proc_macro_error::{abort, emit_error};
-use quote::format_ident;
-use syn::parse_quote;
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::SpanRange;
+use quote::ToTokens;
+use syn::{parse_quote, spanned::Spanned};
 
-use crate::params::{NumberArg, NumberKind};
+use crate::{
+    diagnostic::{abort, emit_error},
+    params::{NumberArgRange, RangeBound},
+};
 
-use super::{attr_params::AttrParams, NumberValue};
+use super::{attr_params::AttrParams, kw, uncovered_spans, NumberValue};
+
+/// The most values within the limits that `lookup = bitset` keeps a bit for.
+const MAX_BITSET_VALUES: i128 = 1 << 16;
 
 #[derive(Debug)]
 pub struct ExactVariant {
@@ -29,12 +36,282 @@ impl std::hash::Hash for ExactVariant {
     }
 }
 
+impl PartialOrd for ExactVariant {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExactVariant {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+/// A variant covering an inclusive range of values.
 #[derive(Debug)]
 pub struct RangeVariant {
     pub ident: syn::Ident,
-    pub start: Option<NumberValue>,
-    pub end: Option<NumberValue>,
-    pub half_open: bool,
+    pub start: NumberValue,
+    pub end: NumberValue,
+}
+
+/// A variant matching every value in a const slice given by `#[eq(include_values = path)]`, which
+/// is searched at runtime since the macro cannot read its values.
+pub struct IncludeVariant {
+    pub ident: syn::Ident,
+    pub path: syn::Path,
+}
+
+/// A variant matching every value that has all bits of `mask` set. Mask variants are tried in
+/// declaration order after the exact and range variants, so earlier ones take priority.
+#[derive(Debug)]
+pub struct MaskVariant {
+    pub ident: syn::Ident,
+    pub mask: NumberValue,
+}
+
+/// A legacy value given by `#[alias(..)]` that is mapped onto a variant, which must hold a single
+/// exact value.
+#[derive(Debug)]
+pub struct AliasValue {
+    pub ident: syn::Ident,
+    pub value: NumberValue,
+}
+
+/// The persistent code given by `#[code(..)]` to a variant, which replaces the lowest value of the
+/// variant as the code returned by `kind_code`.
+#[derive(Debug)]
+pub struct KindCode {
+    pub ident: syn::Ident,
+    pub code: NumberValue,
+    pub span: Span,
+}
+
+/// Another name given by `#[aka(..)]` to a variant holding a single exact value, such as a name
+/// that a protocol used for the value in an earlier version.
+#[derive(Debug)]
+pub struct NameAlias {
+    pub ident: syn::Ident,
+    pub variant: syn::Ident,
+}
+
+/// The external value given by `#[maps_to(..)]` to a variant. The catchall has no value of its
+/// own, so it also names the value it is converted back to.
+pub struct ExternalMapping {
+    pub ident: syn::Ident,
+    pub path: syn::Path,
+    pub value: Option<NumberValue>,
+    pub span: SpanRange,
+}
+
+/// The name a variant holding a single exact value is serialized as with the `serde_names`
+/// parameter, along with the other names accepted when deserializing.
+#[derive(Debug)]
+pub struct SerdeName {
+    pub ident: syn::Ident,
+    pub name: String,
+    pub aliases: Vec<String>,
+}
+
+/// The renaming given by `#[serde(rename = "..", alias = "..")]` on a variant.
+#[derive(Default)]
+struct SerdeRename {
+    rename: Option<String>,
+    aliases: Vec<String>,
+}
+
+/// Parse the `#[serde(..)]` attribute of a variant, which accepts `rename` and `alias`.
+fn parse_serde_rename(attr: &syn::Attribute, into: &mut SerdeRename) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("rename") {
+            into.rename = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+        } else if meta.path.is_ident("alias") {
+            into.aliases
+                .push(meta.value()?.parse::<syn::LitStr>()?.value());
+        } else {
+            return Err(meta.error(
+                "only `rename` and `alias` are supported with the `serde_names` parameter",
+            ));
+        }
+
+        Ok(())
+    })
+}
+
+/// Apply a `#[serde(rename_all = "..")]` rule to the name of a variant.
+fn apply_rename_all(rule: &syn::LitStr, ident: &syn::Ident) -> String {
+    let ident = ident.to_string();
+
+    match rule.value().as_str() {
+        "lowercase" => ident.to_lowercase(),
+        "UPPERCASE" => ident.to_uppercase(),
+        "PascalCase" => ident,
+        "camelCase" => ident.to_case(Case::Camel),
+        "snake_case" => ident.to_case(Case::Snake),
+        "SCREAMING_SNAKE_CASE" => ident.to_case(Case::UpperSnake),
+        "kebab-case" => ident.to_case(Case::Kebab),
+        "SCREAMING-KEBAB-CASE" => ident.to_case(Case::UpperKebab),
+        other => abort! {
+            rule,
+            "Unknown `rename_all` rule `{}`",
+            other;
+            help = "Use one of `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or `SCREAMING-KEBAB-CASE`"
+        },
+    }
+}
+
+/// A transition allowed by the `#[transitions]` attribute. A missing target allows any variant.
+#[derive(Debug)]
+pub struct Transition {
+    pub from: syn::Ident,
+    pub to: Option<syn::Ident>,
+}
+
+/// A value of the `#[eq]` attribute, or a small inclusive span such as `500..=504` that stands for
+/// each of its values.
+enum EqValue {
+    Single(RangeBound),
+    Span {
+        start: RangeBound,
+        dot2_eq: syn::Token![..=],
+        end: RangeBound,
+    },
+}
+
+impl syn::parse::Parse for EqValue {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let start = input.parse()?;
+
+        if input.peek(syn::Token![..=]) {
+            Ok(Self::Span {
+                start,
+                dot2_eq: input.parse()?,
+                end: input.parse()?,
+            })
+        } else {
+            Ok(Self::Single(start))
+        }
+    }
+}
+
+impl ToTokens for EqValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Single(n) => n.to_tokens(tokens),
+            Self::Span {
+                start,
+                dot2_eq,
+                end,
+            } => {
+                start.to_tokens(tokens);
+                dot2_eq.to_tokens(tokens);
+                end.to_tokens(tokens);
+            }
+        }
+    }
+}
+
+/// The arguments of the `#[eq]` attribute.
+enum EqArgs {
+    Values(Vec<EqValue>),
+    /// A file of values relative to the crate root, read when the macro expands.
+    IncludeFile(syn::LitStr),
+    /// A path to a const slice of values.
+    IncludeConst(syn::Path),
+}
+
+impl syn::parse::Parse for EqArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::include_values) {
+            input.parse::<kw::include_values>()?;
+            input.parse::<syn::Token![=]>()?;
+
+            if input.peek(syn::LitStr) {
+                Ok(Self::IncludeFile(input.parse()?))
+            } else {
+                Ok(Self::IncludeConst(input.parse()?))
+            }
+        } else {
+            Ok(Self::Values(
+                syn::punctuated::Punctuated::<EqValue, syn::Token![,]>::parse_terminated(input)?
+                    .into_iter()
+                    .collect(),
+            ))
+        }
+    }
+}
+
+/// Read the values listed in a file given by `#[eq(include_values = "...")]`. Values are separated
+/// by whitespace or commas, and everything after a `#` on a line is a comment.
+fn read_included_values(file: &syn::LitStr) -> (String, Vec<RangeBound>) {
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&root).join(file.value());
+
+    let contents = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| abort!(file, "Failed to read `{}`: {}", path.display(), e));
+
+    let mut values = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+
+        for token in line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+        {
+            match syn::parse_str::<syn::LitInt>(token) {
+                Ok(lit) => values.push(RangeBound::Number(parse_quote!(#lit))),
+                Err(_) => abort! {
+                    file,
+                    "`{}` line {}: `{}` is not an integer",
+                    file.value(),
+                    i + 1,
+                    token
+                },
+            }
+        }
+    }
+
+    if values.is_empty() {
+        abort!(file, "`{}` does not list any values", file.value());
+    }
+
+    (path.display().to_string(), values)
+}
+
+struct TransitionList(Vec<Transition>);
+
+impl syn::parse::Parse for TransitionList {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut list = Vec::new();
+
+        while !input.is_empty() {
+            let from = input.parse::<syn::Ident>()?;
+            input.parse::<syn::Token![->]>()?;
+
+            let to = if input.peek(syn::Token![*]) {
+                input.parse::<syn::Token![*]>()?;
+                None
+            } else {
+                Some(input.parse::<syn::Ident>()?)
+            };
+
+            list.push(Transition { from, to });
+
+            if input.is_empty() {
+                break;
+            }
+
+            if input.peek(syn::Token![;]) {
+                input.parse::<syn::Token![;]>()?;
+            } else {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(Self(list))
+    }
 }
 
 pub struct Variants {
@@ -42,9 +319,29 @@ pub struct Variants {
     pub name: syn::Ident,
     pub mod_name: syn::Ident,
     pub value_name: syn::Ident,
-    pub exacts: HashSet<ExactVariant>,
+    /// The exact values in ascending order, so that the generated code is the same on every build.
+    pub exacts: BTreeSet<ExactVariant>,
     pub ranges: Vec<RangeVariant>,
+    pub masks: Vec<MaskVariant>,
+    pub includes: Vec<IncludeVariant>,
+    /// The files read by `#[eq(include_values = "...")]`, so that changing them rebuilds the enum.
+    pub included_files: Vec<String>,
     pub catchall: Option<syn::Ident>,
+    /// The external values each variant maps to when the `maps_to` parameter is given.
+    pub mappings: Vec<ExternalMapping>,
+    /// The allowed transitions when the enum has a `#[transitions]` attribute.
+    pub transitions: Option<Vec<Transition>>,
+    /// The `Invalid` variant appended by the `auto_invalid` parameter.
+    pub invalid: Option<syn::Ident>,
+    /// The legacy values given by `#[alias(..)]`, in ascending order.
+    pub aliases: Vec<AliasValue>,
+    /// The other names given by `#[aka(..)]`, in declaration order.
+    pub name_aliases: Vec<NameAlias>,
+    /// The codes given by `#[code(..)]`, in declaration order.
+    pub codes: Vec<KindCode>,
+    /// The names of the variants holding a single exact value, in declaration order, when the
+    /// `serde_names` parameter is given.
+    pub serde_names: Vec<SerdeName>,
 }
 
 impl Variants {
@@ -67,18 +364,174 @@ impl Variants {
             }
         }
 
+        if let Some(literal) = &params.literal_val {
+            abort! {
+                literal,
+                "The `literal` parameter is only supported on structs"
+            }
+        }
+
+        if let Some(singleton) = &params.singleton_kw {
+            abort! {
+                singleton,
+                "The `singleton` parameter is only supported on structs"
+            }
+        }
+
+        if let Some(doc_examples) = &params.doc_examples_kw {
+            abort! {
+                doc_examples,
+                "The `doc_examples` parameter is only supported on structs"
+            }
+        }
+
+        if let Some(cyclic) = &params.cyclic_kw {
+            abort! {
+                cyclic,
+                "The `cyclic` parameter is only supported on hard structs"
+            }
+        }
+
+        if let Some(serial) = &params.serial_kw {
+            abort! {
+                serial,
+                "The `serial` parameter is only supported on hard structs"
+            }
+        }
+
+        if let (Some(lookup), true) = (&params.lookup_kw, params.uses_bitset_lookup()) {
+            if params.uses_table_codegen() {
+                abort! {
+                    lookup,
+                    "`lookup = bitset` cannot be combined with `codegen = table`"
+                }
+            }
+
+            let span = params
+                .upper_limit_value()
+                .to_string()
+                .parse::<i128>()
+                .ok()
+                .zip(params.lower_limit_value().to_string().parse::<i128>().ok())
+                .map(|(upper, lower)| upper - lower);
+
+            if span.is_none_or(|span| span >= MAX_BITSET_VALUES) {
+                abort! {
+                    lookup,
+                    "`lookup = bitset` needs at most {} values within the limits",
+                    MAX_BITSET_VALUES;
+                    help = "Narrow the limits or use a `u8` or `u16` type"
+                }
+            }
+        }
+
         let vis = data.vis.clone();
         let name = data.ident.clone();
-        let mod_name = format_ident!("clamped_{}", name.to_string().to_case(Case::Snake));
-        let value_name = format_ident!("{}Value", name);
+        let mod_name = params.module_name(&name);
+        let value_name = params.value_name(&name);
 
         data.vis = parse_quote!(pub);
 
-        let ty = &params.integer;
+        let invalid = params
+            .auto_invalid_kw
+            .map(|kw| syn::Ident::new("Invalid", kw.span));
+
+        if let Some(invalid) = &invalid {
+            if let Some(maps_to) = &params.maps_to_val {
+                abort! {
+                    maps_to,
+                    "The `maps_to` parameter cannot be combined with `auto_invalid`"
+                }
+            }
+
+            if let Some(variant) = data.variants.iter().find(|v| &v.ident == invalid) {
+                abort! {
+                    variant,
+                    "The variant `Invalid` is generated by the `auto_invalid` parameter"
+                }
+            }
+        }
+
+        let mut transitions: Option<Vec<Transition>> = None;
+
+        data.attrs.retain(|attr| {
+            if !attr.path().is_ident("transitions") {
+                return true;
+            }
+
+            match attr.parse_args::<TransitionList>() {
+                Ok(list) => transitions.get_or_insert_with(Vec::new).extend(list.0),
+                Err(e) => {
+                    emit_error! {
+                        e.span(),
+                        "The `#[transitions]` attribute must be a list of `From -> To` or `From -> *` separated by `,` or `;`: {}",
+                        e
+                    }
+                }
+            }
+
+            false
+        });
+
+        let mut rename_all = None;
+
+        if params.serde_names_kw.is_some() {
+            data.attrs.retain(|attr| {
+                if !attr.path().is_ident("serde") {
+                    return true;
+                }
+
+                let result = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename_all") {
+                        rename_all = Some(meta.value()?.parse::<syn::LitStr>()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error(
+                            "only `rename_all` is supported on the enum with the `serde_names` parameter",
+                        ))
+                    }
+                });
+
+                if let Err(e) = result {
+                    emit_error!(e.span(), "{}", e);
+                }
+
+                false
+            });
+        }
+
+        for transition in transitions.iter().flatten() {
+            for ident in std::iter::once(&transition.from).chain(&transition.to) {
+                if !data.variants.iter().any(|variant| &variant.ident == ident)
+                    && invalid.as_ref() != Some(ident)
+                {
+                    emit_error! {
+                        ident,
+                        "`{}` is not a variant of `{}`",
+                        ident,
+                        name
+                    }
+                }
+            }
+        }
+
+        let ty = params.storage_type();
+
+        let kind = params.kind();
+        let lower_limit = params.lower_limit_value();
+        let upper_limit = params.upper_limit_value();
 
-        let mut exacts = HashMap::new();
-        let mut ranges = Vec::new();
+        let mut exacts = BTreeMap::new();
+        let mut ranges: Vec<(NumberValue, NumberValue, syn::Ident, SpanRange)> = Vec::new();
+        let mut masks = Vec::new();
+        let mut includes = Vec::new();
+        let mut included_files = Vec::new();
         let mut catchall = None;
+        let mut mappings = Vec::new();
+        let mut aliases: BTreeMap<NumberValue, (syn::Ident, Span)> = BTreeMap::new();
+        let mut name_aliases: Vec<NameAlias> = Vec::new();
+        let mut codes: Vec<KindCode> = Vec::new();
+        let mut serde_renames = BTreeMap::new();
 
         for variant in &mut data.variants {
             match &variant.fields {
@@ -106,107 +559,270 @@ impl Variants {
                     "eq" => {
                         to_remove.push(i);
 
-                        struct NumberArgList(pub Vec<NumberArg>);
+                        match attr.parse_args::<EqArgs>() {
+                            Ok(EqArgs::IncludeConst(path)) => {
+                                includes.push(IncludeVariant {
+                                    ident: variant.ident.clone(),
+                                    path,
+                                });
 
-                        impl syn::parse::Parse for NumberArgList {
-                            fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-                                Ok(Self(
-                                    syn::punctuated::Punctuated::<NumberArg, syn::Token![,]>::parse_terminated(input)?
-                                    .into_iter()
-                                    .collect(),
-                                ))
+                                variant.fields = syn::Fields::Unnamed(parse_quote! {
+                                    (#value_name<#ty>)
+                                });
                             }
-                        }
+                            Ok(args) => {
+                                let list = match args {
+                                    EqArgs::IncludeFile(file) => {
+                                        let (path, values) = read_included_values(&file);
+                                        included_files.push(path);
+                                        values.into_iter().map(EqValue::Single).collect()
+                                    }
+                                    EqArgs::Values(values) => values,
+                                    EqArgs::IncludeConst(..) => unreachable!(),
+                                };
 
-                        if let Ok(list) = attr.parse_args::<NumberArgList>() {
-                            for val in list.0 {
-                                let n = val.into_value(params.kind());
+                                let mut values = Vec::new();
 
-                                if let Some(prev) = exacts.insert(n, variant.ident.clone()) {
-                                    emit_error! {
-                                        attr,
-                                        "The value `{}` is already used by variant `{}`",
-                                        n,
-                                        prev;
-                                        hint = prev.span() => "Conflicting variant here";
+                                for val in list {
+                                    match &val {
+                                        EqValue::Single(n) => values.push(n.into_value(
+                                            kind,
+                                            lower_limit,
+                                            upper_limit,
+                                        )),
+                                        EqValue::Span { start, end, .. } => {
+                                            let start =
+                                                start.into_value(kind, lower_limit, upper_limit);
+                                            let end =
+                                                end.into_value(kind, lower_limit, upper_limit);
+
+                                            if start > end {
+                                                emit_error! {
+                                                    val,
+                                                    "The span must not be empty"
+                                                }
+                                            } else if start.span_len(end) > MAX_EQ_SPAN_VALUES {
+                                                emit_error! {
+                                                    val,
+                                                    "The span has {} values, more than the {} an `#[eq]` span may expand to",
+                                                    start.span_len(end),
+                                                    MAX_EQ_SPAN_VALUES;
+                                                    hint = "Use a `#[range]` variant for wide spans";
+                                                }
+                                            } else {
+                                                values.extend(start.stepped(end, 1));
+                                            }
+                                        }
                                     }
                                 }
 
-                                params.abort_if_out_of_bounds(attr, n);
+                                for n in values {
+                                    if let Some(prev) = exacts.insert(n, variant.ident.clone()) {
+                                        emit_error! {
+                                            attr,
+                                            "The value `{}` is already used by variant `{}`",
+                                            n,
+                                            prev;
+                                            hint = prev.span() => "Conflicting variant here";
+                                        }
+                                    }
 
-                                variant.fields = syn::Fields::Unnamed(parse_quote! {
-                                    (#value_name<#ty>)
-                                });
+                                    params.abort_if_out_of_bounds(attr, n);
+
+                                    variant.fields = syn::Fields::Unnamed(parse_quote! {
+                                        (#value_name<#ty>)
+                                    });
+                                }
                             }
-                        } else {
-                            emit_error! {
-                                attr,
-                                "The `#[eq]` attribute must be one or more integer literals"
+                            Err(e) => {
+                                emit_error! {
+                                    e.span(),
+                                    "The `#[eq]` attribute must be one or more integer literals, constants or `const` blocks, or `include_values = ` followed by a file path or a path to a const slice: {}",
+                                    e
+                                }
                             }
                         }
                     }
                     "range" => {
                         to_remove.push(i);
 
-                        if let Ok(val) = attr.parse_args::<syn::ExprRange>() {
-                            let half_open = match val.limits {
-                                syn::RangeLimits::HalfOpen(_) => true,
-                                syn::RangeLimits::Closed(_) => false,
-                            };
+                        match attr.parse_args::<NumberArgRange>() {
+                            Ok(val) => {
+                                if val.start.is_none() && val.end.is_none() {
+                                    emit_error! {
+                                        attr,
+                                        "The range must have at least one bound. use `#[other]` for catchall"
+                                    }
+
+                                    continue;
+                                }
+
+                                if let Some(start) = &val.start {
+                                    params.abort_if_out_of_bounds(
+                                        start,
+                                        start.into_value(kind, lower_limit, upper_limit),
+                                    );
+                                }
 
-                            fn parse_val(
-                                val: &Option<impl AsRef<syn::Expr>>,
-                                kind: NumberKind,
-                            ) -> Option<NumberValue> {
-                                let val = match &val {
-                                    Some(v) => v.as_ref(),
-                                    None => return None,
+                                if let Some(end) = &val.end {
+                                    params.abort_if_out_of_bounds(
+                                        end,
+                                        end.into_value(kind, lower_limit, upper_limit),
+                                    );
+                                }
+
+                                let Some((start, end)) =
+                                    val.into_inclusive(kind, lower_limit, upper_limit)
+                                else {
+                                    emit_error! {
+                                        val,
+                                        "The range must not be empty"
+                                    }
+
+                                    continue;
                                 };
 
-                                let val: NumberArg = parse_quote!(#val);
-                                Some(val.into_value(kind))
-                            }
+                                // a stepped range claims its values like `#[eq]` does, so every
+                                // lookup, iteration and coverage check honors the step
+                                if let Some((_, step)) = &val.step {
+                                    let step = step
+                                        .into_value(kind)
+                                        .to_string()
+                                        .parse::<u128>()
+                                        .unwrap_or_default();
 
-                            let start = parse_val(&val.start, params.kind());
-                            let end = parse_val(&val.end, params.kind());
+                                    if step == 0 {
+                                        emit_error! {
+                                            val,
+                                            "The step must be greater than zero"
+                                        }
 
-                            if start.is_none() && end.is_none() {
-                                emit_error! {
-                                    val,
-                                    "The range must have at least one bound. use `#[other]` for catchall"
+                                        continue;
+                                    }
+
+                                    if step > 1 {
+                                        let count =
+                                            start.span_len(end).saturating_sub(1) / step + 1;
+
+                                        if count > MAX_STEPPED_VALUES {
+                                            emit_error! {
+                                                val,
+                                                "The stepped range has {} values, more than the {} it may expand to",
+                                                count,
+                                                MAX_STEPPED_VALUES;
+                                                hint = "Use a larger step or a `#[range]` without a step";
+                                            }
+
+                                            continue;
+                                        }
+
+                                        for n in start.stepped(end, step) {
+                                            if let Some(prev) =
+                                                exacts.insert(n, variant.ident.clone())
+                                            {
+                                                emit_error! {
+                                                    attr,
+                                                    "The value `{}` is already used by variant `{}`",
+                                                    n,
+                                                    prev;
+                                                    hint = prev.span() => "Conflicting variant here";
+                                                }
+                                            }
+                                        }
+
+                                        variant.fields = syn::Fields::Unnamed(parse_quote! {
+                                            (#value_name<#ty>)
+                                        });
+
+                                        continue;
+                                    }
                                 }
 
-                                continue;
-                            }
+                                if let Some((prev_start, prev_end, prev_ident, prev_span)) =
+                                    ranges.iter().find(|(prev_start, prev_end, ..)| {
+                                        start <= *prev_end && *prev_start <= end
+                                    })
+                                {
+                                    emit_overlap_error(
+                                        (*prev_start, *prev_end, prev_ident, *prev_span),
+                                        (start, end, &variant.ident, SpanRange::from_tokens(attr)),
+                                    );
+                                }
 
-                            if end.is_none() && half_open {
-                                emit_error! {
-                                    val,
-                                    "The range must be closed if it has only one bound"
+                                ranges.push((
+                                    start,
+                                    end,
+                                    variant.ident.clone(),
+                                    SpanRange::from_tokens(attr),
+                                ));
+
+                                let wrapper_name = params.range_value_name(&variant.ident);
+
+                                if wrapper_name == value_name {
+                                    emit_error! {
+                                        variant.ident,
+                                        "The struct `{}` held by this variant has the name of the value wrapper",
+                                        wrapper_name;
+                                        hint = "Rename the wrapper with the `value_name` parameter";
+                                    }
                                 }
 
-                                continue;
+                                variant.fields = syn::Fields::Unnamed(parse_quote! {
+                                    (#wrapper_name)
+                                });
                             }
-
-                            if let Some(start) = start {
-                                params.abort_if_out_of_bounds(attr, start);
+                            Err(e) => {
+                                emit_error! {
+                                    e.span(),
+                                    "The `#[range]` attribute must be a range whose bounds are integer literals, constants, `const` blocks, `MIN` or `MAX`: {}",
+                                    e
+                                }
                             }
+                        }
+                    }
+                    "mask" => {
+                        to_remove.push(i);
 
-                            if let Some(end) = end {
-                                params.abort_if_out_of_bounds(attr, end);
+                        if params.is_signed() {
+                            abort! {
+                                attr,
+                                "The `#[mask]` attribute is only supported on unsigned integer types"
                             }
+                        }
 
-                            ranges.push((start, end, half_open, variant.ident.clone()));
+                        match attr.parse_args::<RangeBound>() {
+                            Ok(val) => {
+                                let mask = val.into_value(kind, lower_limit, upper_limit);
 
-                            let wrapper_name = format_ident!("{}Value", &variant.ident);
+                                if mask == NumberValue::U8(0).cast(kind).unwrap() {
+                                    emit_error! {
+                                        attr,
+                                        "The mask must have at least one bit set"
+                                    }
+                                } else if mask > upper_limit {
+                                    emit_error! {
+                                        attr,
+                                        "The mask {} can never match since it exceeds the upper limit {}",
+                                        mask,
+                                        upper_limit
+                                    }
+                                }
 
-                            variant.fields = syn::Fields::Unnamed(parse_quote! {
-                                (#wrapper_name)
-                            });
-                        } else {
-                            emit_error! {
-                                attr,
-                                "The `#[range]` attribute must be a range literal"
+                                masks.push(MaskVariant {
+                                    ident: variant.ident.clone(),
+                                    mask,
+                                });
+
+                                variant.fields = syn::Fields::Unnamed(parse_quote! {
+                                    (#value_name<#ty>)
+                                });
+                            }
+                            Err(e) => {
+                                emit_error! {
+                                    e.span(),
+                                    "The `#[mask]` attribute must be an integer literal, constant or `const` block: {}",
+                                    e
+                                }
                             }
                         }
                     }
@@ -220,30 +836,359 @@ impl Variants {
                             }
                         }
 
+                        if params.coverage_kw.is_some() {
+                            emit_error! {
+                                attr,
+                                "`#[other]` is not allowed with `coverage = exhaustive`, which requires every value to be claimed by an `#[eq]`, `#[range]` or `#[mask]` variant"
+                            }
+                        }
+
                         catchall = Some(variant.ident.clone());
 
                         variant.fields = syn::Fields::Unnamed(parse_quote! {
                             (#value_name<#ty>)
                         });
                     }
+                    "alias" => {
+                        to_remove.push(i);
+
+                        match attr.parse_args_with(
+                            syn::punctuated::Punctuated::<RangeBound, syn::Token![,]>::parse_terminated,
+                        ) {
+                            Ok(list) => {
+                                for val in list {
+                                    let n = val.into_value(kind, lower_limit, upper_limit);
+
+                                    if let Some((prev, _)) =
+                                        aliases.insert(n, (variant.ident.clone(), attr.span()))
+                                    {
+                                        emit_error! {
+                                            attr,
+                                            "The alias `{}` is already used by variant `{}`",
+                                            n,
+                                            prev
+                                        }
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                emit_error! {
+                                    e.span(),
+                                    "The `#[alias]` attribute must be one or more integer literals, constants or `const` blocks: {}",
+                                    e
+                                }
+                            }
+                        }
+                    }
+                    "aka" => {
+                        to_remove.push(i);
+
+                        match attr.parse_args_with(
+                            syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated,
+                        ) {
+                            Ok(list) => name_aliases.extend(list.into_iter().map(|ident| NameAlias {
+                                ident,
+                                variant: variant.ident.clone(),
+                            })),
+                            Err(e) => {
+                                emit_error! {
+                                    e.span(),
+                                    "The `#[aka]` attribute must be one or more names: {}",
+                                    e
+                                }
+                            }
+                        }
+                    }
+                    "code" => {
+                        to_remove.push(i);
+
+                        match attr.parse_args::<RangeBound>() {
+                            Ok(val) => {
+                                let code = val.into_value(kind, lower_limit, upper_limit);
+
+                                if let Some(prev) = codes
+                                    .iter()
+                                    .find(|prev| prev.ident == variant.ident || prev.code == code)
+                                {
+                                    if prev.ident == variant.ident {
+                                        emit_error! {
+                                            attr,
+                                            "Only one `#[code]` attribute is allowed per variant"
+                                        }
+                                    } else {
+                                        emit_error! {
+                                            attr,
+                                            "The code `{}` is already used by variant `{}`",
+                                            code,
+                                            prev.ident
+                                        }
+                                    }
+                                }
+
+                                codes.push(KindCode {
+                                    ident: variant.ident.clone(),
+                                    code,
+                                    span: attr.span(),
+                                });
+                            }
+                            Err(e) => {
+                                emit_error! {
+                                    e.span(),
+                                    "The `#[code]` attribute must be an integer literal, constant or `const` block: {}",
+                                    e
+                                }
+                            }
+                        }
+                    }
+                    "serde" if params.serde_names_kw.is_some() => {
+                        to_remove.push(i);
+
+                        let rename = serde_renames
+                            .entry(variant.ident.to_string())
+                            .or_insert_with(|| (attr.span(), SerdeRename::default()));
+
+                        if let Err(e) = parse_serde_rename(attr, &mut rename.1) {
+                            emit_error!(e.span(), "{}", e);
+                        }
+                    }
+                    "maps_to" => {
+                        to_remove.push(i);
+
+                        if params.maps_to_val.is_none() {
+                            emit_error! {
+                                attr,
+                                "The `#[maps_to]` attribute requires the `maps_to` parameter on the `clamped` attribute"
+                            }
+                        }
+
+                        match attr.parse_args_with(|input: syn::parse::ParseStream| {
+                            let path = input.parse::<syn::Path>()?;
+                            let value = match input.parse::<Option<syn::Token![,]>>()? {
+                                Some(..) => Some(input.parse::<RangeBound>()?),
+                                None => None,
+                            };
+
+                            Ok((path, value))
+                        }) {
+                            Ok((path, value)) => mappings.push(ExternalMapping {
+                                ident: variant.ident.clone(),
+                                path,
+                                value: value
+                                    .map(|val| val.into_value(kind, lower_limit, upper_limit)),
+                                span: SpanRange::from_tokens(attr),
+                            }),
+                            Err(..) => {
+                                emit_error! {
+                                    attr,
+                                    "The `#[maps_to]` attribute must be a path to a value of the external type"
+                                }
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
 
+            if params.maps_to_val.is_some() && !mappings.iter().any(|m| m.ident == variant.ident) {
+                emit_error! {
+                    variant,
+                    "Variant `{}` is missing a `#[maps_to]` attribute",
+                    variant.ident
+                }
+            }
+
             for i in to_remove.into_iter().rev() {
                 variant.attrs.remove(i);
             }
         }
 
-        // check that all possible values between `params.lower_limit_value()` and `params.upper_limit_value()` are covered
+        // Only a variant holding a single exact value can be named or aliased, since the name
+        // or alias must stand for one value.
+        let single_values = data
+            .variants
+            .iter()
+            .filter_map(|variant| {
+                let mut values = exacts
+                    .iter()
+                    .filter(|(_, ident)| **ident == variant.ident)
+                    .map(|(n, _)| *n);
+
+                match (values.next(), values.next()) {
+                    (Some(n), None)
+                        if !ranges.iter().any(|(.., ident, _)| *ident == variant.ident)
+                            && !masks.iter().any(|v: &MaskVariant| v.ident == variant.ident)
+                            && catchall.as_ref() != Some(&variant.ident) =>
+                    {
+                        Some((variant.ident.clone(), n))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (n, (ident, span)) in &aliases {
+            if let Some(owner) = exacts.get(n).or_else(|| {
+                ranges
+                    .iter()
+                    .find(|(start, end, ..)| start <= n && n <= end)
+                    .map(|(_, _, ident, _)| ident)
+            }) {
+                emit_error! {
+                    *span,
+                    "The alias `{}` is already a value of variant `{}`",
+                    n,
+                    owner
+                }
+            }
+
+            if !single_values.iter().any(|(single, _)| single == ident) {
+                emit_error! {
+                    *span,
+                    "The `#[alias]` attribute is only supported on variants with a single `#[eq]` value"
+                }
+            }
+        }
+
+        for (i, NameAlias { ident, variant }) in name_aliases.iter().enumerate() {
+            if let Some(prev) = data
+                .variants
+                .iter()
+                .map(|v| &v.ident)
+                .chain(name_aliases[..i].iter().map(|alias| &alias.ident))
+                .find(|prev| *prev == ident)
+            {
+                emit_error! {
+                    ident,
+                    "The name `{}` is already used by `{}`",
+                    ident,
+                    prev
+                }
+            }
+
+            if !single_values.iter().any(|(single, _)| single == variant) {
+                emit_error! {
+                    ident,
+                    "The `#[aka]` attribute is only supported on variants with a single `#[eq]` value"
+                }
+            }
+        }
+
+        for mapping in &mappings {
+            let is_catchall = catchall.as_ref() == Some(&mapping.ident);
+
+            let Some(n) = mapping.value else {
+                if is_catchall {
+                    emit_error! {
+                        mapping.span,
+                        "The catchall variant has no value of its own to convert back to";
+                        hint = "Name one of its values after the external value, such as `#[maps_to(Status::Unknown, 302)]`";
+                    }
+                }
+
+                continue;
+            };
+
+            if !is_catchall {
+                emit_error! {
+                    mapping.span,
+                    "A value in `#[maps_to]` is only supported on the catchall variant, since the other variants convert back to their lowest value"
+                }
+            } else if n < lower_limit || n > upper_limit {
+                emit_error! {
+                    mapping.span,
+                    "The value `{}` is outside of the limits `{}..={}`",
+                    n,
+                    lower_limit,
+                    upper_limit
+                }
+            } else if let Some(owner) = exacts
+                .get(&n)
+                .or_else(|| {
+                    ranges
+                        .iter()
+                        .find(|(start, end, ..)| *start <= n && n <= *end)
+                        .map(|(_, _, ident, _)| ident)
+                })
+                .or_else(|| {
+                    let bits = n.to_string().parse::<u128>().ok()?;
+
+                    masks
+                        .iter()
+                        .find(|v| {
+                            let mask = v.mask.to_string().parse::<u128>().unwrap();
+                            bits & mask == mask
+                        })
+                        .map(|v| &v.ident)
+                })
+            {
+                emit_error! {
+                    mapping.span,
+                    "The value `{}` belongs to variant `{}`, not to the catchall",
+                    n,
+                    owner
+                }
+            }
+        }
+
+        let mut serde_names: Vec<SerdeName> = Vec::new();
+
+        if params.serde_names_kw.is_some() {
+            for (ident, _) in &single_values {
+                let rename = serde_renames.remove(&ident.to_string()).map(|(_, r)| r);
+                let (rename, aliases) = rename.map(|r| (r.rename, r.aliases)).unwrap_or_default();
+                let name = rename.unwrap_or_else(|| match &rename_all {
+                    Some(rule) => apply_rename_all(rule, ident),
+                    None => ident.to_string(),
+                });
+
+                for used in std::iter::once(&name).chain(&aliases) {
+                    if let Some(prev) = serde_names
+                        .iter()
+                        .find(|prev| prev.name == *used || prev.aliases.contains(used))
+                    {
+                        emit_error! {
+                            ident,
+                            "The name `{}` is already used by variant `{}`",
+                            used,
+                            prev.ident
+                        }
+                    }
+                }
+
+                serde_names.push(SerdeName {
+                    ident: ident.clone(),
+                    name,
+                    aliases,
+                });
+            }
+
+            for (ident, (span, _)) in serde_renames {
+                emit_error! {
+                    span,
+                    "`{}` is serialized as its value, so it cannot be renamed; only variants with a single `#[eq]` value are serialized by name",
+                    ident
+                }
+            }
+        }
+
+        if let Some(invalid) = &invalid {
+            let integer = &params.integer;
+
+            data.variants.push(parse_quote! {
+                #invalid(#value_name<#integer>)
+            });
+        }
+
+        if let (Some(include), None) = (includes.first(), &catchall) {
+            emit_error! {
+                include.path,
+                "The values of `include_values = {}` are only known at runtime, so the enum needs a catchall variant",
+                include.path.to_token_stream();
+                hint = "Add a catchall variant with `#[other]` attribute";
+            }
+        }
+
         let has_catchall = catchall.is_some();
-        let lower_limit = params.lower_limit_value();
-        let upper_limit = params.upper_limit_value();
-        let mut covered = if !has_catchall {
-            HashSet::with_capacity((upper_limit.clone() - lower_limit + 1).into_usize())
-        } else {
-            HashSet::new()
-        };
 
         let this = Self {
             vis,
@@ -252,78 +1197,120 @@ impl Variants {
             value_name,
             exacts: exacts
                 .into_iter()
-                .map(|(n, v)| {
-                    if !has_catchall {
-                        covered.insert(n);
-                    }
-
-                    ExactVariant { ident: v, value: n }
-                })
+                .map(|(n, v)| ExactVariant { ident: v, value: n })
                 .collect(),
             ranges: ranges
                 .into_iter()
-                .map(|(s, e, h, v)| {
-                    if !has_catchall {
-                        match (s, e) {
-                            (Some(s), Some(e)) => {
-                                if h {
-                                    for n in s.range(e) {
-                                        covered.insert(n);
-                                    }
-                                } else {
-                                    for n in s.range(e + 1) {
-                                        covered.insert(n);
-                                    }
-                                }
-                            }
-                            (Some(s), None) => {
-                                if h {
-                                    let upper_limit = upper_limit;
-                                    for n in s.range(upper_limit) {
-                                        covered.insert(n);
-                                    }
-                                } else {
-                                    let upper_limit = upper_limit;
-                                    for n in s.range(upper_limit + 1) {
-                                        covered.insert(n);
-                                    }
-                                }
-                            }
-                            (None, Some(e)) => {
-                                if h {
-                                    let lower_limit = lower_limit;
-                                    for n in lower_limit.range(e) {
-                                        covered.insert(n);
-                                    }
-                                } else {
-                                    let lower_limit = lower_limit;
-                                    for n in lower_limit.range(e + 1) {
-                                        covered.insert(n);
-                                    }
-                                }
-                            }
-                            (None, None) => unreachable!("At least one bound must be present"),
-                        }
-                    }
-
-                    RangeVariant {
-                        ident: v,
-                        start: s,
-                        end: e,
-                        half_open: h,
-                    }
-                })
+                .map(|(start, end, ident, _)| RangeVariant { ident, start, end })
                 .collect(),
+            masks,
+            includes,
+            included_files,
             catchall,
+            mappings,
+            transitions,
+            invalid,
+            aliases: aliases
+                .into_iter()
+                .map(|(value, (ident, _))| AliasValue { ident, value })
+                .collect(),
+            name_aliases,
+            codes,
+            serde_names,
         };
 
-        if !has_catchall {
-            for n in lower_limit.range(upper_limit + 1) {
-                if !covered.contains(&n) {
+        // check that every value within the limits is covered, working on spans of values so that
+        // full-width domains are checked as quickly as narrow ones
+        if let Some(coverage) = &params.coverage_kw {
+            let mask_bits = this
+                .masks
+                .iter()
+                .map(|v| v.mask.to_string().parse::<u128>().unwrap())
+                .collect::<Vec<_>>();
+            let bits = |n: NumberValue| n.to_string().parse::<u128>().unwrap_or_default();
+            let gaps = uncovered_spans(
+                lower_limit,
+                upper_limit,
+                this.exacts
+                    .iter()
+                    .map(|v| (v.value, v.value))
+                    .chain(this.ranges.iter().map(|v| (v.start, v.end))),
+            )
+            .into_iter()
+            .filter_map(|(start, end)| {
+                if mask_bits.is_empty() {
+                    Some((bits(start), bits(end)))
+                } else {
+                    // only the first value of a gap that no mask claims is known
+                    first_unmasked(bits(start), bits(end), &mask_bits).map(|n| (n, n))
+                }
+            })
+            .collect::<Vec<_>>();
+
+            if !gaps.is_empty() {
+                let mut list = gaps
+                    .iter()
+                    .take(MAX_LISTED_GAPS)
+                    .map(|(start, end)| {
+                        if start == end {
+                            start.to_string()
+                        } else {
+                            format!("{}..={}", start, end)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                if gaps.len() > MAX_LISTED_GAPS {
+                    list.push(format!("and {} more", gaps.len() - MAX_LISTED_GAPS));
+                }
+
+                emit_error! {
+                    coverage,
+                    "`coverage = exhaustive` requires every value to be claimed by a variant, but these are not: {}",
+                    list.join(", ");
+                    hint = "Add `#[eq]` or `#[range]` variants for these values";
+                }
+            }
+        } else if !has_catchall {
+            let mask_bits = this
+                .masks
+                .iter()
+                .map(|v| v.mask.to_string().parse::<u128>().unwrap())
+                .collect::<Vec<_>>();
+            let gaps = uncovered_spans(
+                lower_limit,
+                upper_limit,
+                this.exacts
+                    .iter()
+                    .map(|v| (v.value, v.value))
+                    .chain(this.ranges.iter().map(|v| (v.start, v.end))),
+            );
+
+            for (start, end) in gaps {
+                if !mask_bits.is_empty() {
+                    let bits = |n: NumberValue| n.to_string().parse::<u128>().unwrap_or_default();
+
+                    if let Some(n) = first_unmasked(bits(start), bits(end), &mask_bits) {
+                        emit_error! {
+                            item,
+                            "The value `{}` is not covered by any variant",
+                            n;
+                            hint = "Add a catchall variant with `#[other]` attribute";
+                        }
+                    }
+                } else if start == end {
                     emit_error! {
                         item,
                         "The value `{}` is not covered by any variant",
-                        n;
+                        start;
+                        hint = "Add a catchall variant with `#[other]` attribute";
+                    }
+                } else {
+                    emit_error! {
+                        item,
+                        "The values `{}..={}` are not covered by any variant",
+                        start,
+                        end;
                         hint = "Add a catchall variant with `#[other]` attribute";
                     }
                 }
@@ -333,3 +1320,95 @@ impl Variants {
         this
     }
 }
+
+/// The most gaps listed by the error of `coverage = exhaustive`.
+const MAX_LISTED_GAPS: usize = 16;
+
+/// The most values a span of the `#[eq]` attribute may expand to, since it is meant for a few
+/// neighboring values among the exact ones.
+const MAX_EQ_SPAN_VALUES: u128 = 256;
+
+/// The most values a `#[range(.. step N)]` may expand to, since each becomes an exact value with
+/// its own match arm. This is the same as for a span of the `#[eq]` attribute.
+const MAX_STEPPED_VALUES: u128 = MAX_EQ_SPAN_VALUES;
+
+/// Find the smallest value in `start..=end` that matches none of the masks. A value that matches a
+/// mask keeps matching it until the lowest bit of the mask is cleared, so the search skips to the
+/// end of that block instead of visiting every matching value.
+fn first_unmasked(mut n: u128, end: u128, masks: &[u128]) -> Option<u128> {
+    while n <= end {
+        let mut next = None;
+
+        for mask in masks.iter().filter(|mask| n & **mask == **mask) {
+            // a zero mask matches every value
+            let low_bits = (mask & mask.wrapping_neg()).checked_sub(1)?;
+            let block_end = (n | low_bits).checked_add(1)?;
+
+            next = next.max(Some(block_end));
+        }
+
+        match next {
+            Some(next) => n = next,
+            None => return Some(n),
+        }
+    }
+
+    None
+}
+
+/// Report two overlapping range variants, suggesting how to change one of them so that they no
+/// longer share any values.
+fn emit_overlap_error(
+    a: (NumberValue, NumberValue, &syn::Ident, SpanRange),
+    b: (NumberValue, NumberValue, &syn::Ident, SpanRange),
+) {
+    // order the pair so that `first` starts no later than `second`
+    let (first, second) = if a.0 <= b.0 { (a, b) } else { (b, a) };
+    let (first_start, first_end, first_ident, _) = first;
+    let (second_start, second_end, second_ident, _) = second;
+
+    let overlap_start = second_start;
+    let overlap_end = if first_end < second_end {
+        first_end
+    } else {
+        second_end
+    };
+
+    let help = if second_end > first_end {
+        format!(
+            "change `{}` to `#[range({}..={})]`",
+            second_ident,
+            first_end + 1,
+            second_end
+        )
+    } else if second_start > first_start {
+        format!(
+            "`{}` is entirely within `{}`; change `{}` to `#[range({}..={})]` and cover the rest with another variant",
+            second_ident,
+            first_ident,
+            first_ident,
+            first_start,
+            second_start - 1
+        )
+    } else {
+        format!(
+            "`{}` is entirely within `{}`; remove one of them or narrow `{}`",
+            second_ident, first_ident, first_ident
+        )
+    };
+
+    emit_error! {
+        b.3,
+        "The range of variant `{}` ({}..={}) overlaps the range of variant `{}` ({}..={}) on {}..={}",
+        b.2,
+        b.0,
+        b.1,
+        a.2,
+        a.0,
+        a.1,
+        overlap_start,
+        overlap_end;
+        hint = a.3.first => "Overlapping range of `{}` here", a.2;
+        help = help;
+    }
+}
@@ -1,9 +1,7 @@
-use convert_case::{Case, Casing};
-use proc_macro_error::abort;
-use quote::format_ident;
 use syn::parse_quote;
 
-use super::attr_params::AttrParams;
+use super::{attr_params::AttrParams, AsSoftOrHard, NumberKind};
+use crate::diagnostic::abort;
 
 pub struct StructItem {
     pub vis: syn::Visibility,
@@ -24,15 +22,116 @@ impl StructItem {
             }
         }
 
+        if let Some(store) = &params.store_val {
+            abort! {
+                store,
+                "The `store` parameter is only supported on enums"
+            }
+        }
+
+        if let Some(singleton) = &params.singleton_kw {
+            abort! {
+                singleton,
+                "The `singleton` parameter cannot be combined with `as Soft` or `as Hard`"
+            }
+        }
+
+        if let Some(value_name) = &params.value_name_val {
+            abort! {
+                value_name,
+                "The `value_name` parameter is only supported on enums"
+            }
+        }
+
+        if let Some(maps_to) = &params.maps_to_val {
+            abort! {
+                maps_to,
+                "The `maps_to` parameter is only supported on enums"
+            }
+        }
+
+        if let Some(auto_invalid) = &params.auto_invalid_kw {
+            abort! {
+                auto_invalid,
+                "The `auto_invalid` parameter is only supported on enums"
+            }
+        }
+
+        if let Some(codegen) = &params.codegen_val {
+            abort! {
+                codegen,
+                "The `codegen` parameter is only supported on enums"
+            }
+        }
+
+        if let Some(lookup) = &params.lookup_kw {
+            abort! {
+                lookup,
+                "The `lookup` parameter is only supported on enums"
+            }
+        }
+
+        if let Some(coverage) = &params.coverage_kw {
+            abort! {
+                coverage,
+                "The `coverage` parameter is only supported on enums"
+            }
+        }
+
+        if let Some(serde_names) = &params.serde_names_kw {
+            abort! {
+                serde_names,
+                "The `serde_names` parameter is only supported on enums"
+            }
+        }
+
+        if let (Some(cyclic), Some(AsSoftOrHard::Soft { .. })) =
+            (&params.cyclic_kw, &params.as_soft_or_hard)
+        {
+            abort! {
+                cyclic,
+                "The `cyclic` parameter is only supported on hard structs"
+            }
+        }
+
+        if let (Some(serial), Some(AsSoftOrHard::Soft { .. })) =
+            (&params.serial_kw, &params.as_soft_or_hard)
+        {
+            abort! {
+                serial,
+                "The `serial` parameter is only supported on hard structs"
+            }
+        }
+
+        if let Some(serial) = &params.serial_kw {
+            if !matches!(params.kind(), NumberKind::U16 | NumberKind::U32) {
+                abort! {
+                    serial,
+                    "The `serial` parameter is only supported on `u16` and `u32` types"
+                }
+            }
+        }
+
         let vis = data.vis.clone();
         let name = data.ident.clone();
-        let mod_name = format_ident!("clamped_{}", name.to_string().to_case(Case::Snake));
+        let mod_name = params.module_name(&name);
 
         let ty = &params.integer;
 
+        // Keep the attributes of a declared field, such as `#[serde(with = "..")]`, so that the
+        // helper attributes of third-party derives still apply to the generated field.
+        let field_attrs = match data.fields.iter().collect::<Vec<_>>().as_slice() {
+            [] => Vec::new(),
+            [field] => field.attrs.clone(),
+            [_, second, ..] => abort! {
+                second,
+                "Clamped structs hold a single value, so at most one field can be declared"
+            },
+        };
+
         data.vis = parse_quote!(pub);
         data.fields = syn::Fields::Unnamed(parse_quote! {
-            (#ty)
+            (#(#field_attrs)* #ty)
         });
 
         Self {
@@ -0,0 +1,191 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::diagnostic::abort;
+
+/// A field of a register map along with the bits it occupies.
+struct RegisterField {
+    ident: syn::Ident,
+    ty: syn::Type,
+    offset: u32,
+    width: u32,
+}
+
+/// Parse the `#[register(u32)]` or `#[register(u64)]` attribute of the struct.
+fn parse_register_type(input: &syn::DeriveInput) -> (syn::Ident, u32) {
+    let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("register"))
+    else {
+        abort!(
+            input.ident,
+            "`RegisterMap` requires the register type, such as `#[register(u32)]`"
+        );
+    };
+
+    let ty: syn::Ident = attr
+        .parse_args()
+        .unwrap_or_else(|e| abort!(attr, "expected `u32` or `u64`: {}", e));
+
+    let bits = match ty.to_string().as_str() {
+        "u32" => 32,
+        "u64" => 64,
+        _ => abort!(ty, "the register type must be `u32` or `u64`"),
+    };
+
+    (ty, bits)
+}
+
+/// Parse the `#[register(offset = N, width = N)]` attribute of a field.
+fn parse_field(field: &syn::Field) -> RegisterField {
+    let ident = field
+        .ident
+        .clone()
+        .unwrap_or_else(|| abort!(field, "`RegisterMap` requires named fields"));
+
+    let Some(attr) = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("register"))
+    else {
+        abort!(
+            ident,
+            "every field needs its bits, such as `#[register(offset = 0, width = 4)]`"
+        );
+    };
+
+    let mut offset = None;
+    let mut width = None;
+
+    attr.parse_nested_meta(|meta| {
+        let slot = if meta.path.is_ident("offset") {
+            &mut offset
+        } else if meta.path.is_ident("width") {
+            &mut width
+        } else {
+            return Err(meta.error("expected `offset` or `width`"));
+        };
+
+        let lit: syn::LitInt = meta.value()?.parse()?;
+        *slot = Some(lit.base10_parse::<u32>()?);
+        Ok(())
+    })
+    .unwrap_or_else(|e| abort!(attr, "{}", e));
+
+    let (Some(offset), Some(width)) = (offset, width) else {
+        abort!(attr, "both `offset` and `width` are required");
+    };
+
+    if width == 0 {
+        abort!(attr, "`width` must be at least 1");
+    }
+
+    RegisterField {
+        ident,
+        ty: field.ty.clone(),
+        offset,
+        width,
+    }
+}
+
+/// Generate `to_register` and `from_register` for a struct of clamped fields, each stored in its
+/// own bits of a `u32` or `u64` register.
+pub fn register_map(input: syn::DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let (register, register_bits) = parse_register_type(&input);
+
+    let syn::Data::Struct(data) = &input.data else {
+        abort!(name, "`RegisterMap` can only be derived for structs");
+    };
+
+    let fields = data.fields.iter().map(parse_field).collect::<Vec<_>>();
+
+    for (i, field) in fields.iter().enumerate() {
+        let end = field.offset as u64 + field.width as u64;
+
+        if end > register_bits as u64 {
+            abort!(
+                field.ident,
+                "bits {}..{} do not fit in `{}`",
+                field.offset,
+                end,
+                register
+            );
+        }
+
+        if let Some(other) = fields[..i].iter().find(|other| {
+            field.offset < other.offset + other.width && other.offset < field.offset + field.width
+        }) {
+            abort!(
+                field.ident,
+                "the bits of `{}` overlap the bits of `{}`",
+                field.ident,
+                other.ident
+            );
+        }
+    }
+
+    let mut checks = Vec::with_capacity(fields.len());
+    let mut packs = Vec::with_capacity(fields.len());
+    let mut unpacks = Vec::with_capacity(fields.len());
+
+    for RegisterField {
+        ident,
+        ty,
+        offset,
+        width,
+    } in &fields
+    {
+        let mask = if *width == 64 {
+            u64::MAX
+        } else {
+            (1u64 << width) - 1
+        };
+        let field_name = ident.to_string();
+        let message = format!("`{}` does not fit in {} bits", field_name, width);
+
+        checks.push(quote! {
+            const _: () = assert!(
+                <#ty as ::checked_rs::InherentLimits<<#ty as std::ops::Deref>::Target>>::MAX as u64 <= #mask,
+                #message,
+            );
+        });
+
+        packs.push(quote! {
+            register |= ((u64::from(*self.#ident) & #mask) << #offset) as #register;
+        });
+
+        unpacks.push(quote! {
+            #ident: {
+                let bits = (register as u64 >> #offset) & #mask;
+                let value = <<#ty as std::ops::Deref>::Target>::try_from(bits)?;
+
+                <#ty as ::checked_rs::clamp::ClampedInteger<_>>::from_primitive(value).map_err(|e| {
+                    ::checked_rs::__private::anyhow::anyhow!("field `{}`: {}", #field_name, e)
+                })?
+            },
+        });
+    }
+
+    quote! {
+        #(#checks)*
+
+        impl #name {
+            /// Pack every field into its bits of the register.
+            pub fn to_register(&self) -> #register {
+                let mut register: #register = 0;
+                #(#packs)*
+                register
+            }
+
+            /// Unpack every field from its bits of the register, failing if a field holds a value
+            /// that its type does not allow.
+            pub fn from_register(register: #register) -> ::checked_rs::__private::anyhow::Result<Self> {
+                Ok(Self {
+                    #(#unpacks)*
+                })
+            }
+        }
+    }
+}
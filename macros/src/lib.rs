@@ -5,7 +5,12 @@
 //!
 extern crate proc_macro;
 
-use checked_rs_macro_impl::{clamped::clamped as clamped_impl, params::attr_params::AttrParams};
+use checked_rs_macro_impl::{
+    clamped::clamped as clamped_impl,
+    clamped_const::{clamped_const as clamped_const_impl, ClampedConst},
+    params::{attr_params::AttrParams, crate_config::CrateConfig},
+    register_map::register_map as register_map_impl,
+};
 use proc_macro_error::proc_macro_error;
 use syn::parse_macro_input;
 
@@ -29,3 +34,31 @@ pub fn clamped(
 
     proc_macro::TokenStream::from(clamped_impl(attr, item))
 }
+
+/// Evaluate arithmetic over the constants of a clamped struct at compile time, failing the build
+/// when any intermediate result is out of bounds.
+#[proc_macro]
+#[proc_macro_error]
+pub fn clamped_const(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ClampedConst);
+
+    proc_macro::TokenStream::from(clamped_const_impl(input))
+}
+
+/// Validate the crate-level defaults, which `clamped` reads from the file named by
+/// `CHECKED_RS_CONFIG`.
+#[proc_macro]
+#[proc_macro_error]
+pub fn configure(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as CrateConfig);
+
+    proc_macro::TokenStream::new()
+}
+
+#[proc_macro_derive(RegisterMap, attributes(register))]
+#[proc_macro_error]
+pub fn register_map(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+
+    proc_macro::TokenStream::from(register_map_impl(input))
+}
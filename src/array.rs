@@ -0,0 +1,167 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
+
+use anyhow::{Context, Result};
+
+use crate::clamp::ClampedInteger;
+
+/// A fixed-size array where every element is a clamped value, such as the buckets of a bounded
+/// histogram. Arithmetic with a scalar is applied to every element and resolved with the behavior
+/// of the element type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClampedArray<T, const N: usize>([T; N]);
+
+impl<T: Default, const N: usize> Default for ClampedArray<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(std::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> ClampedArray<T, N> {
+    #[inline(always)]
+    pub fn new(items: [T; N]) -> Self {
+        Self(items)
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> [T; N] {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.0.iter_mut()
+    }
+}
+
+impl<T: Ord, const N: usize> ClampedArray<T, N> {
+    /// Get the smallest element, or `None` if the array is empty.
+    #[inline(always)]
+    pub fn min(&self) -> Option<&T> {
+        self.0.iter().min()
+    }
+
+    /// Get the largest element, or `None` if the array is empty.
+    #[inline(always)]
+    pub fn max(&self) -> Option<&T> {
+        self.0.iter().max()
+    }
+}
+
+impl<P: Copy, T: ClampedInteger<P>, const N: usize> TryFrom<[P; N]> for ClampedArray<T, N> {
+    type Error = anyhow::Error;
+
+    fn try_from(values: [P; N]) -> Result<Self> {
+        let mut items: [T; N] = std::array::from_fn(|_| T::default());
+
+        for (i, (item, value)) in items.iter_mut().zip(values).enumerate() {
+            *item =
+                T::from_primitive(value).with_context(|| format!("element {} is invalid", i))?;
+        }
+
+        Ok(Self(items))
+    }
+}
+
+impl<T, const N: usize> std::ops::Deref for ClampedArray<T, N> {
+    type Target = [T; N];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> std::ops::DerefMut for ClampedArray<T, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for ClampedArray<T, N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ClampedArray<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ClampedArray<T, N> {
+    type Item = T;
+    type IntoIter = std::array::IntoIter<T, N>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T: serde::Serialize, const N: usize> serde::Serialize for ClampedArray<T, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de>
+    for ClampedArray<T, N>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = Vec::<T>::deserialize(deserializer)?;
+        let len = items.len();
+
+        match <[T; N]>::try_from(items) {
+            Ok(items) => Ok(Self(items)),
+            Err(_) => Err(serde::de::Error::invalid_length(
+                len,
+                &format!("an array of {} elements", N).as_str(),
+            )),
+        }
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl<T: Copy + $trait<P, Output = T>, P: Copy, const N: usize> $trait<P>
+            for ClampedArray<T, N>
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $method(self, rhs: P) -> Self::Output {
+                Self(self.0.map(|item| $trait::$method(item, rhs)))
+            }
+        }
+
+        impl<T: Copy + $trait<P, Output = T>, P: Copy, const N: usize> $assign_trait<P>
+            for ClampedArray<T, N>
+        {
+            #[inline(always)]
+            fn $assign_method(&mut self, rhs: P) {
+                for item in self.0.iter_mut() {
+                    *item = $trait::$method(*item, rhs);
+                }
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign);
+impl_binary_op!(Div, div, DivAssign, div_assign);
+impl_binary_op!(Rem, rem, RemAssign, rem_assign);
@@ -0,0 +1,195 @@
+use anyhow::{Context, Result};
+
+use crate::clamp::{try_clamp, HardClamp};
+
+/// The number of elements that are checked against the limits at a time. Chunks that cannot leave
+/// the limits skip clamping entirely.
+const CHUNK_LEN: usize = 64;
+
+/// # Safety
+///
+/// Implementors must be `#[repr(transparent)]` over `T` and must allow every value in
+/// `MIN..=MAX`, so that a slice of them can be updated through a slice of `T`.
+pub unsafe trait TransparentClamp<T: Copy>: HardClamp<T> {}
+
+/// A primitive integer that batch operations and `BoundedSum` can work on.
+pub trait BatchPrimitive: Copy + Ord {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_batch_primitive {
+    ($($ty:ty),*) => {
+        $(
+            impl BatchPrimitive for $ty {
+                #[inline(always)]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, rhs)
+                }
+
+                #[inline(always)]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$ty>::saturating_add(self, rhs)
+                }
+
+                #[inline(always)]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$ty>::saturating_sub(self, rhs)
+                }
+
+                #[inline(always)]
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    <$ty>::wrapping_add(self, rhs)
+                }
+
+                #[inline(always)]
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    <$ty>::wrapping_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_batch_primitive!(u8, u16, u32, u64, u128, usize);
+
+/// View a slice of clamped values as their primitives without copying them.
+#[inline(always)]
+pub fn as_primitive_slice<C: TransparentClamp<T>, T: Copy>(items: &[C]) -> &[T] {
+    // SAFETY: `C` is `#[repr(transparent)]` over `T`, so `[C]` and `[T]` have the same size,
+    // alignment and layout, and every value of `C` is a valid `T`. The returned slice borrows
+    // `items` immutably for the same lifetime, so no invalid value can be written through it.
+    unsafe { std::slice::from_raw_parts(items.as_ptr().cast::<T>(), items.len()) }
+}
+
+/// View a slice of primitives as clamped values without copying them, failing with the position
+/// of the first primitive outside of the limits. Each chunk is checked against the
+/// limits at once, and only a chunk that fails is searched for the offending element.
+pub fn try_from_primitive_slice<C, T>(values: &[T]) -> Result<&[C]>
+where
+    C: TransparentClamp<T>,
+    T: Copy + Ord + std::fmt::Debug + std::fmt::Display + Send + Sync + 'static,
+{
+    for (i, chunk) in values.chunks(CHUNK_LEN).enumerate() {
+        let smallest = chunk.iter().copied().fold(C::MAX, Ord::min);
+        let largest = chunk.iter().copied().fold(C::MIN, Ord::max);
+
+        if smallest < C::MIN || largest > C::MAX {
+            for (j, value) in chunk.iter().enumerate() {
+                try_clamp(*value, C::MIN, C::MAX)
+                    .with_context(|| format!("element {} is invalid", i * CHUNK_LEN + j))?;
+            }
+        }
+    }
+
+    // SAFETY: `C` is `#[repr(transparent)]` over `T`, so `[T]` and `[C]` have the same size,
+    // alignment and layout. Every element was checked to be within `C::MIN..=C::MAX`, and `C`
+    // allows every value in that range, so each one is a valid `C`. The returned slice borrows
+    // `values` immutably, so the elements cannot change after they were checked.
+    Ok(unsafe { std::slice::from_raw_parts(values.as_ptr().cast::<C>(), values.len()) })
+}
+
+#[inline(always)]
+fn as_primitives_mut<C: TransparentClamp<T>, T: Copy>(items: &mut [C]) -> &mut [T] {
+    // SAFETY: `C` is `#[repr(transparent)]` over `T`, so both slices have the same layout. Callers
+    // only write values within `C::MIN..=C::MAX`, which `C` allows.
+    unsafe { std::slice::from_raw_parts_mut(items.as_mut_ptr().cast::<T>(), items.len()) }
+}
+
+/// Add `delta` to every element, saturating at the upper limit. The loops work on the primitives
+/// directly so that they can be vectorized, and each chunk is checked against the limits once.
+pub fn add_scalar_saturating<C: TransparentClamp<T>, T: BatchPrimitive>(items: &mut [C], delta: T) {
+    let max = C::MAX;
+
+    for chunk in as_primitives_mut(items).chunks_mut(CHUNK_LEN) {
+        let largest = chunk.iter().copied().fold(C::MIN, Ord::max);
+
+        // every element is within the limits, so `largest <= max`
+        if delta <= max.wrapping_sub(largest) {
+            for value in chunk.iter_mut() {
+                *value = value.wrapping_add(delta);
+            }
+        } else {
+            for value in chunk.iter_mut() {
+                *value = value.saturating_add(delta).min(max);
+            }
+        }
+    }
+}
+
+/// Subtract `delta` from every element, saturating at the lower limit. The loops work on the
+/// primitives directly so that they can be vectorized, and each chunk is checked against the
+/// limits once.
+pub fn sub_scalar_saturating<C: TransparentClamp<T>, T: BatchPrimitive>(items: &mut [C], delta: T) {
+    let min = C::MIN;
+
+    for chunk in as_primitives_mut(items).chunks_mut(CHUNK_LEN) {
+        let smallest = chunk.iter().copied().fold(C::MAX, Ord::min);
+
+        // every element is within the limits, so `smallest >= min`
+        if delta <= smallest.wrapping_sub(min) {
+            for value in chunk.iter_mut() {
+                *value = value.wrapping_sub(delta);
+            }
+        } else {
+            for value in chunk.iter_mut() {
+                *value = value.saturating_sub(delta).max(min);
+            }
+        }
+    }
+}
+
+// These are also run under miri with `make miri`.
+#[cfg(test)]
+mod tests {
+    use checked_rs_macros::clamped;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[clamped(u16 as Hard, default = 10, lower = 10, upper = 1000)]
+    #[derive(Debug, Clone, Copy)]
+    struct Reading;
+
+    #[test]
+    fn test_primitive_slices() {
+        let values = (10..=1000u16).collect::<Vec<_>>();
+        let readings = Reading::try_from_primitive_slice(&values).unwrap();
+        assert_eq!(readings.len(), values.len());
+        assert_eq!(*readings[0], 10);
+        assert_eq!(*readings[990], 1000);
+
+        let primitives = Reading::as_primitive_slice(readings);
+        assert_eq!(primitives, values.as_slice());
+        assert_eq!(primitives.as_ptr(), values.as_ptr());
+
+        let mut values = values;
+        values[200] = 1001;
+        let err = Reading::try_from_primitive_slice(&values).unwrap_err();
+        assert_eq!(err.to_string(), "element 200 is invalid");
+        assert!(matches!(
+            err.downcast_ref::<ClampError<u16>>(),
+            Some(ClampError::TooLarge { val: 1001, .. })
+        ));
+
+        values[200] = 9;
+        assert!(try_from_primitive_slice::<Reading, _>(&values).is_err());
+
+        let empty: &[Reading] = Reading::try_from_primitive_slice(&[]).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_scalar_ops_through_primitives() {
+        let mut readings = vec![Reading::default(); 130];
+        Reading::add_scalar_saturating(&mut readings, 995);
+        assert!(readings.iter().all(|r| **r == 1000));
+        Reading::sub_scalar_saturating(&mut readings, 500);
+        assert!(Reading::as_primitive_slice(&readings)
+            .iter()
+            .all(|r| *r == 500));
+    }
+}
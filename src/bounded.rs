@@ -0,0 +1,228 @@
+use std::{
+    marker::PhantomData,
+    num,
+    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Sub},
+};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    clamp::{try_clamp, ClampError, Panicking},
+    Behavior,
+};
+
+/// Limits that are only known at runtime, such as those loaded from a database or user settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(
+    try_from = "ClampSpecRepr<T>",
+    bound(deserialize = "T: Copy + Ord + serde::Deserialize<'de>")
+)]
+pub struct ClampSpec<T: Copy> {
+    min: T,
+    max: T,
+}
+
+#[derive(serde::Deserialize)]
+struct ClampSpecRepr<T> {
+    min: T,
+    max: T,
+}
+
+impl<T: Copy + Ord> TryFrom<ClampSpecRepr<T>> for ClampSpec<T> {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: ClampSpecRepr<T>) -> Result<Self> {
+        Self::new(repr.min, repr.max)
+    }
+}
+
+impl<T: Copy + Ord> ClampSpec<T> {
+    #[inline(always)]
+    pub fn new(min: T, max: T) -> Result<Self> {
+        if min > max {
+            bail!("`min` must not be greater than `max`");
+        }
+
+        Ok(Self { min, max })
+    }
+
+    #[inline(always)]
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    #[inline(always)]
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, value: T) -> bool {
+        self.validate(value).is_ok()
+    }
+
+    #[inline(always)]
+    pub fn validate(&self, value: T) -> Result<T, ClampError<T>> {
+        try_clamp(value, self.min, self.max)
+    }
+}
+
+/// A value that carries its own limits alongside it, for cases where the limits are not known when
+/// the type is defined. Arithmetic is resolved with the behavior `B`, exactly as it is for the
+/// types generated by the `clamped` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(
+    try_from = "BoundedValueRepr<T>",
+    bound(deserialize = "T: Copy + Ord + std::fmt::Display + serde::Deserialize<'de>")
+)]
+pub struct BoundedValue<T: Copy, B: Behavior = Panicking> {
+    value: T,
+    spec: ClampSpec<T>,
+    #[serde(skip)]
+    behavior: PhantomData<B>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(bound = "T: Copy + Ord + serde::Deserialize<'de>")]
+struct BoundedValueRepr<T: Copy> {
+    value: T,
+    spec: ClampSpec<T>,
+}
+
+impl<T: Copy + Ord + std::fmt::Display, B: Behavior> TryFrom<BoundedValueRepr<T>>
+    for BoundedValue<T, B>
+{
+    type Error = ClampError<T>;
+
+    fn try_from(repr: BoundedValueRepr<T>) -> Result<Self, ClampError<T>> {
+        Self::new(repr.value, repr.spec)
+    }
+}
+
+impl<T: Copy + Ord, B: Behavior> BoundedValue<T, B> {
+    #[inline(always)]
+    pub fn new(value: T, spec: ClampSpec<T>) -> Result<Self, ClampError<T>> {
+        Ok(Self {
+            value: spec.validate(value)?,
+            spec,
+            behavior: PhantomData,
+        })
+    }
+
+    #[inline(always)]
+    pub fn saturating(value: T, spec: ClampSpec<T>) -> Self {
+        Self {
+            value: crate::clamp_saturating(value, spec.min, spec.max),
+            spec,
+            behavior: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> T {
+        self.value
+    }
+
+    #[inline(always)]
+    pub fn spec(&self) -> &ClampSpec<T> {
+        &self.spec
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, value: T) -> Result<(), ClampError<T>> {
+        self.value = self.spec.validate(value)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Copy, B: Behavior> std::ops::Deref for BoundedValue<T, B> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: Copy + std::fmt::Display, B: Behavior> std::fmt::Display for BoundedValue<T, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident) => {
+        impl<T: Copy + $trait<Output = T> + Eq + Ord, B: Behavior> $trait<T> for BoundedValue<T, B>
+        where
+            num::Saturating<T>: $trait<Output = num::Saturating<T>>,
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $method(self, rhs: T) -> Self::Output {
+                Self {
+                    value: B::$method(self.value, rhs, self.spec.min, self.spec.max),
+                    spec: self.spec,
+                    behavior: PhantomData,
+                }
+            }
+        }
+
+        impl<T: Copy + $trait<Output = T> + Eq + Ord, B: Behavior> $trait<BoundedValue<T, B>>
+            for BoundedValue<T, B>
+        where
+            num::Saturating<T>: $trait<Output = num::Saturating<T>>,
+        {
+            type Output = Self;
+
+            /// The limits of the left hand side are kept.
+            #[inline(always)]
+            fn $method(self, rhs: BoundedValue<T, B>) -> Self::Output {
+                $trait::$method(self, rhs.value)
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add);
+impl_binary_op!(Sub, sub);
+impl_binary_op!(Mul, mul);
+impl_binary_op!(Div, div);
+impl_binary_op!(Rem, rem);
+impl_binary_op!(BitAnd, bitand);
+impl_binary_op!(BitOr, bitor);
+impl_binary_op!(BitXor, bitxor);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clamp::Saturating;
+
+    #[test]
+    fn test_bounded_value() -> Result<()> {
+        let spec = ClampSpec::new(10u8, 100)?;
+        assert!(ClampSpec::new(100u8, 10).is_err());
+
+        let val = BoundedValue::<u8, Saturating>::new(50, spec)?;
+        assert_eq!(*(val + 100), 100);
+        assert_eq!(*(val - 45), 10);
+        assert_eq!(*(val * val), 100);
+
+        assert!(matches!(
+            BoundedValue::<u8>::new(5, spec),
+            Err(ClampError::TooSmall { val: 5, min: 10 })
+        ));
+        assert_eq!(*BoundedValue::<u8>::saturating(5, spec), 10);
+
+        let mut val = BoundedValue::<u8>::new(50, spec)?;
+        assert!(val.set(101).is_err());
+        assert_eq!(val.get(), 50);
+        assert!(std::panic::catch_unwind(|| val + 51).is_err());
+        Ok(())
+    }
+}
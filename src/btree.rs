@@ -0,0 +1,45 @@
+use std::{
+    collections::BTreeMap,
+    ops::{Bound, RangeInclusive},
+};
+
+use crate::{iter::VariantKind, InherentLimits};
+
+/// The kind enum of a clamped enum whose variants know the primitive values they accept, which is
+/// implemented for every generated `<Name>Kind` enum that has `ranges_of`.
+pub trait KindRanges<T>: VariantKind {
+    /// The spans of values that the variant accepts, in ascending order.
+    fn ranges(self) -> &'static [RangeInclusive<T>];
+}
+
+/// Get the bounds of the limits of `C`, such as for `BTreeMap::range` over an index keyed by the
+/// primitives of `C`.
+#[inline(always)]
+pub fn limit_bounds<C: InherentLimits<T>, T>() -> (Bound<T>, Bound<T>) {
+    (Bound::Included(C::MIN), Bound::Included(C::MAX))
+}
+
+/// Iterate in ascending order over the entries of `map` whose keys are within the limits of `C`.
+/// For the wrapper of a `#[range]` variant, these are the keys of that variant.
+pub fn btree_range_of<C, T, V>(map: &BTreeMap<T, V>) -> impl Iterator<Item = (&T, &V)>
+where
+    C: InherentLimits<T>,
+    T: Ord,
+{
+    map.range(limit_bounds::<C, T>())
+}
+
+/// Iterate in ascending order over the entries of `map` whose keys the variant `kind` accepts,
+/// with one range query per span of the variant instead of a scan of every entry.
+pub fn btree_range_of_variant<K, T, V>(
+    map: &BTreeMap<T, V>,
+    kind: K,
+) -> impl Iterator<Item = (&T, &V)>
+where
+    K: KindRanges<T>,
+    T: Ord + Clone + 'static,
+{
+    kind.ranges()
+        .iter()
+        .flat_map(move |span| map.range(span.clone()))
+}
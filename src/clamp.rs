@@ -1,26 +1,46 @@
 use std::{
     num,
-    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Sub},
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref, Div,
+        DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign,
+    },
 };
 
-use crate::{InherentBehavior, InherentLimits};
+use crate::{batch::BatchPrimitive, InherentBehavior, InherentLimits};
 use anyhow::Result;
 
+/// # Safety
+///
+/// Implementors must only produce values from `from_primitive` that satisfy the type's limits.
 pub unsafe trait ClampedInteger<T: Copy>:
     'static + Default + Eq + Ord + InherentLimits<T>
 {
     fn from_primitive(value: T) -> Result<Self>;
-    fn as_primitive(&self) -> &T;
 
-    fn into_primitive(&self) -> T {
-        *self.as_primitive()
-    }
+    #[allow(clippy::wrong_self_convention)]
+    fn into_primitive(&self) -> T;
+}
+
+/// A clamped integer that holds its value as `T`, so that it can hand out a reference to it.
+/// Enums that store the value in a narrower type with `store` have no `T` to reference, so they
+/// only implement `ClampedInteger`.
+pub trait AsPrimitive<T: Copy>: ClampedInteger<T> {
+    fn as_primitive(&self) -> &T;
 }
 
+/// # Safety
+///
+/// Implementors must uphold the requirements of `ClampedInteger`.
 pub unsafe trait SoftClamp<T: Copy>: ClampedInteger<T> + InherentBehavior {}
 
+/// # Safety
+///
+/// Implementors must never hold a value outside of their limits.
 pub unsafe trait HardClamp<T: Copy>: ClampedInteger<T> + InherentBehavior {}
 
+/// # Safety
+///
+/// Implementors must never hold a value that is not covered by one of their variants.
 pub unsafe trait ClampedEnum<T: Copy>: ClampedInteger<T> + InherentBehavior {}
 
 #[derive(Debug, Clone, Copy, thiserror::Error)]
@@ -31,6 +51,130 @@ pub enum ClampError<T: Copy> {
     TooLarge { val: T, max: T },
 }
 
+/// What dividing a clamped type by zero results in, as chosen by the `div_by_zero` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DivByZero {
+    /// Panic like primitive division does.
+    Panic,
+    /// Saturate to the upper limit.
+    SaturateMax,
+    /// Return the default value of the type.
+    ReturnDefault,
+}
+
+/// The error returned when parsing a clamped type from a string fails, either because the input is
+/// not a number or because the number is not allowed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid value {input:?} for `{type_name}`: {reason} (expected {allowed})")]
+pub struct ParseClampedError {
+    /// The name of the type being parsed.
+    pub type_name: &'static str,
+    /// The string that failed to parse.
+    pub input: String,
+    /// Why the input was rejected.
+    pub reason: String,
+    /// A human-readable summary of the allowed values.
+    pub allowed: &'static str,
+}
+
+/// The error returned when a clamped enum is asked to move to a state that its `#[transitions]`
+/// table does not allow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("`{type_name}` cannot transition from `{from}` to `{to}`")]
+pub struct TransitionError {
+    /// The name of the enum.
+    pub type_name: &'static str,
+    /// The variant the value was in.
+    pub from: &'static str,
+    /// The variant the value was asked to move to.
+    pub to: &'static str,
+}
+
+/// Check that `value` is within `min..=max` without defining a clamped type.
+#[inline(always)]
+pub fn try_clamp<T: Copy + Ord>(value: T, min: T, max: T) -> Result<T, ClampError<T>> {
+    debug_assert!(min <= max, "`min` must not be greater than `max`");
+
+    if value < min {
+        Err(ClampError::TooSmall { val: value, min })
+    } else if value > max {
+        Err(ClampError::TooLarge { val: value, max })
+    } else {
+        Ok(value)
+    }
+}
+
+/// A primitive integer with its own limits, which tell in which direction an operation on it
+/// overflowed.
+pub trait PrimitiveBounds: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+macro_rules! impl_primitive_bounds {
+    ($($ty:ty),*) => {
+        $(
+            impl PrimitiveBounds for $ty {
+                const MIN: Self = <$ty>::MIN;
+                const MAX: Self = <$ty>::MAX;
+            }
+        )*
+    };
+}
+
+impl_primitive_bounds!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Check the outcome of a primitive operation against `min..=max`. `checked` is the result of the
+/// checked operation and `saturated` is the result of the saturating one, which stands in for the
+/// value when the operation overflowed the primitive type.
+#[inline(always)]
+pub fn try_clamp_op<T: PrimitiveBounds>(
+    checked: Option<T>,
+    saturated: T,
+    min: T,
+    max: T,
+) -> Result<T, ClampError<T>> {
+    match checked {
+        Some(val) => try_clamp(val, min, max),
+        // An overflow saturates at the upper limit of the primitive and an underflow at the lower
+        // one, which tells them apart even when `min` and `max` are the same limit.
+        None if saturated == T::MAX => Err(ClampError::TooLarge {
+            val: saturated,
+            max,
+        }),
+        None => Err(ClampError::TooSmall {
+            val: saturated,
+            min,
+        }),
+    }
+}
+
+/// Keep the result of a checked operation on primitives only if it is within `min..=max`, which is
+/// how the generated `checked_<op>` methods fail regardless of the behavior of the type.
+#[inline(always)]
+pub fn checked_within<T: Copy + Ord>(checked: Option<T>, min: T, max: T) -> Option<T> {
+    checked.filter(|val| min <= *val && *val <= max)
+}
+
+/// Clamp `value` to `min..=max`, saturating at the nearest limit when it is out of bounds.
+#[inline(always)]
+pub fn clamp_saturating<T: Copy + Ord>(value: T, min: T, max: T) -> T {
+    match try_clamp(value, min, max) {
+        Ok(val) => val,
+        Err(ClampError::TooSmall { min, .. }) => min,
+        Err(ClampError::TooLarge { max, .. }) => max,
+    }
+}
+
+/// Clamp `value` to `min..=max`, panicking when it is out of bounds.
+#[inline(always)]
+pub fn clamp_panicking<T: Copy + Ord + std::fmt::Display>(value: T, min: T, max: T) -> T {
+    match try_clamp(value, min, max) {
+        Ok(val) => val,
+        Err(e) => panic!("{}", e),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Panicking {}
 
@@ -216,6 +360,10 @@ impl crate::Behavior for Panicking {
         }
         val
     }
+
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        result
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -425,6 +573,736 @@ impl crate::Behavior for Saturating {
             val
         }
     }
+
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        match result {
+            Err(ClampError::TooSmall { min, .. }) => Ok(min),
+            Err(ClampError::TooLarge { max, .. }) => Ok(max),
+            ok => ok,
+        }
+    }
+}
+
+/// Observes the results that fall outside of the limits before a `Logged` behavior resolves them.
+pub trait ClampObserver: Copy + 'static {
+    /// Called with the name of the operation, such as `"add"`, and how the result missed the
+    /// limits. The result itself has already been saturated to the primitive type.
+    fn on_clamp<T: Copy>(op: &'static str, error: &ClampError<T>);
+}
+
+/// A behavior that reports every out of bounds result to `F` and then resolves it with `B`, such
+/// as `Logged<Saturating, MyLogger>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Logged<B, F>(std::marker::PhantomData<(B, F)>);
+
+/// A behavior that resolves out of bounds results with `B1` and falls back to `B2` for those that
+/// `B1` rejects, which is what `B1::resolve` returning an error signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fallback<B1, B2>(std::marker::PhantomData<(B1, B2)>);
+
+/// A `ClampObserver` that writes a line to standard error for every out of bounds result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StderrObserver {}
+
+impl ClampObserver for StderrObserver {
+    fn on_clamp<T: Copy>(op: &'static str, error: &ClampError<T>) {
+        let direction = match error {
+            ClampError::TooSmall { .. } => "below the lower limit",
+            ClampError::TooLarge { .. } => "above the upper limit",
+        };
+
+        eprintln!("checked-rs: `{op}` result was {direction} and has been saturated");
+    }
+}
+
+/// Saturates like `Saturating` but reports every saturated result on standard error. This is the
+/// default behavior when the `never-panic` feature is enabled.
+pub type SaturatingWithLog = Logged<Saturating, StderrObserver>;
+
+/// Compute the result of an operation on the saturating wrapper, so that results beyond the
+/// primitive type are still reported against the limits.
+macro_rules! saturated_outcome {
+    ($lhs:ident $op:tt $rhs:ident, $min:ident, $max:ident) => {{
+        let num::Saturating(val) = num::Saturating($lhs) $op num::Saturating($rhs);
+        try_clamp(val, $min, $max)
+    }};
+    ($op:tt $value:ident, $min:ident, $max:ident) => {{
+        let num::Saturating(val) = $op num::Saturating($value);
+        try_clamp(val, $min, $max)
+    }};
+}
+
+macro_rules! impl_combinator_binary_op {
+    ($trait:ident, $method:ident, $op:tt, |$lhs:ident, $rhs:ident, $min:ident, $max:ident, $outcome:ident| $body:expr) => {
+        fn $method<T: $trait<Output = T> + Copy>(
+            $lhs: T,
+            $rhs: T,
+            $min: T::Output,
+            $max: T::Output,
+        ) -> T::Output
+        where
+            T::Output: Eq + Ord,
+            num::Saturating<T>: $trait<Output = num::Saturating<T>>,
+        {
+            let $outcome = saturated_outcome!($lhs $op $rhs, $min, $max);
+            $body
+        }
+    };
+}
+
+macro_rules! impl_combinator_unary_op {
+    ($trait:ident, $method:ident, $op:tt, |$value:ident, $min:ident, $max:ident, $outcome:ident| $body:expr) => {
+        fn $method<T: std::ops::$trait<Output = T> + Copy>(
+            $value: T,
+            $min: T::Output,
+            $max: T::Output,
+        ) -> T::Output
+        where
+            T::Output: Eq + Ord,
+            num::Saturating<T>: std::ops::$trait<Output = num::Saturating<T>>,
+        {
+            let $outcome = saturated_outcome!($op $value, $min, $max);
+            $body
+        }
+    };
+}
+
+macro_rules! impl_logged_binary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_binary_op!($trait, $method, $op, |lhs, rhs, min, max, outcome| {
+            if let Err(e) = outcome {
+                F::on_clamp(stringify!($method), &e);
+            }
+
+            B::$method(lhs, rhs, min, max)
+        });
+    };
+}
+
+macro_rules! impl_logged_unary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_unary_op!($trait, $method, $op, |value, min, max, outcome| {
+            if let Err(e) = outcome {
+                F::on_clamp(stringify!($method), &e);
+            }
+
+            B::$method(value, min, max)
+        });
+    };
+}
+
+impl<B: crate::Behavior, F: ClampObserver> crate::Behavior for Logged<B, F> {
+    impl_logged_binary_op!(Add, add, +);
+    impl_logged_binary_op!(Sub, sub, -);
+    impl_logged_binary_op!(Mul, mul, *);
+    impl_logged_binary_op!(Div, div, /);
+    impl_logged_binary_op!(Rem, rem, %);
+    impl_logged_binary_op!(BitAnd, bitand, &);
+    impl_logged_binary_op!(BitOr, bitor, |);
+    impl_logged_binary_op!(BitXor, bitxor, ^);
+    impl_logged_unary_op!(Neg, neg, -);
+    impl_logged_unary_op!(Not, not, !);
+
+    fn div_by_zero<T: Copy>(policy: DivByZero, max: T, default: T) -> T {
+        B::div_by_zero(policy, max, default)
+    }
+
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        if let Err(e) = &result {
+            F::on_clamp("resolve", e);
+        }
+
+        B::resolve(result)
+    }
+}
+
+macro_rules! impl_fallback_binary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_binary_op!($trait, $method, $op, |lhs, rhs, min, max, outcome| {
+            match outcome {
+                Ok(val) => val,
+                Err(e) => match B1::resolve(Err(e)) {
+                    Ok(_) => B1::$method(lhs, rhs, min, max),
+                    Err(_) => B2::$method(lhs, rhs, min, max),
+                },
+            }
+        });
+    };
+}
+
+macro_rules! impl_fallback_unary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_unary_op!($trait, $method, $op, |value, min, max, outcome| {
+            match outcome {
+                Ok(val) => val,
+                Err(e) => match B1::resolve(Err(e)) {
+                    Ok(_) => B1::$method(value, min, max),
+                    Err(_) => B2::$method(value, min, max),
+                },
+            }
+        });
+    };
+}
+
+impl<B1: crate::Behavior, B2: crate::Behavior> crate::Behavior for Fallback<B1, B2> {
+    impl_fallback_binary_op!(Add, add, +);
+    impl_fallback_binary_op!(Sub, sub, -);
+    impl_fallback_binary_op!(Mul, mul, *);
+    impl_fallback_binary_op!(Div, div, /);
+    impl_fallback_binary_op!(Rem, rem, %);
+    impl_fallback_binary_op!(BitAnd, bitand, &);
+    impl_fallback_binary_op!(BitOr, bitor, |);
+    impl_fallback_binary_op!(BitXor, bitxor, ^);
+    impl_fallback_unary_op!(Neg, neg, -);
+    impl_fallback_unary_op!(Not, not, !);
+
+    fn div_by_zero<T: Copy>(policy: DivByZero, max: T, default: T) -> T {
+        B1::div_by_zero(policy, max, default)
+    }
+
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        match B1::resolve(result) {
+            Err(e) => B2::resolve(Err(e)),
+            ok => ok,
+        }
+    }
+}
+
+/// The name and declared range of a clamped type, which the generated code puts into its panic and
+/// error messages so that they can be understood without the source of the type.
+pub trait RangeSummary: 'static {
+    const TYPE_NAME: &'static str;
+    /// The values the type allows, such as `"1024..=65535"`.
+    const RANGE_SUMMARY: &'static str;
+}
+
+/// An object-safe view of any generated type, so that values of different clamped types can be
+/// kept together as `Box<dyn AnyClamped>`, such as the fields of a settings panel. Values are
+/// widened to `i128` or `u128`, and setting one validates it against the limits of the type.
+pub trait AnyClamped: 'static {
+    fn type_name(&self) -> &'static str;
+
+    /// The values the type allows, such as `"1024..=65535"`.
+    fn range_summary(&self) -> &'static str;
+
+    /// The value as an `i128`, or `None` if it does not fit.
+    fn as_i128(&self) -> Option<i128>;
+
+    /// The value as a `u128`, or `None` if it is negative.
+    fn as_u128(&self) -> Option<u128>;
+
+    /// Replace the value, failing without changing it if the type does not allow `value`.
+    fn set_i128(&mut self, value: i128) -> Result<()>;
+}
+
+/// The generic form of a clamped type with a single range of values, which is the `deranged` type
+/// with the same bounds, such as `RangedU16<1, 9999>`. It lets generic numeric code accept every
+/// generated type through one bound, such as `T: GenericForm<Generic = RangedU8<0, 100>>`, and is
+/// implemented for hard structs and enums, which always hold a valid value.
+#[cfg(feature = "deranged")]
+pub trait GenericForm: Sized {
+    type Generic;
+
+    fn into_generic(self) -> Self::Generic;
+
+    fn from_generic(value: Self::Generic) -> Self;
+}
+
+/// A behavior that resolves with `B` and, when `B` rejects an out of bounds result, panics with a
+/// message that names the type `C` and its range, such as
+/// "Addition overflow for `Port` (valid: 1024..=65535)". Generated types use it for their
+/// operators.
+pub struct Described<B, C>(std::marker::PhantomData<(B, C)>);
+
+impl<B, C> Clone for Described<B, C> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B, C> Copy for Described<B, C> {}
+
+impl<B: crate::Behavior, C: RangeSummary> Described<B, C> {
+    #[inline(always)]
+    fn check<T: Copy>(op: &'static str, outcome: Result<T, ClampError<T>>) {
+        if let Err(e) = outcome {
+            if B::resolve(Err(e)).is_err() {
+                let direction = match e {
+                    ClampError::TooSmall { .. } => "underflow",
+                    ClampError::TooLarge { .. } => "overflow",
+                };
+
+                panic!(
+                    "{} {} for `{}` (valid: {})",
+                    op,
+                    direction,
+                    C::TYPE_NAME,
+                    C::RANGE_SUMMARY
+                );
+            }
+        }
+    }
+}
+
+macro_rules! impl_described_binary_op {
+    ($trait:ident, $method:ident, $op:tt, $name:literal) => {
+        impl_combinator_binary_op!($trait, $method, $op, |lhs, rhs, min, max, outcome| {
+            Self::check($name, outcome);
+            B::$method(lhs, rhs, min, max)
+        });
+    };
+}
+
+macro_rules! impl_described_unary_op {
+    ($trait:ident, $method:ident, $op:tt, $name:literal) => {
+        impl_combinator_unary_op!($trait, $method, $op, |value, min, max, outcome| {
+            Self::check($name, outcome);
+            B::$method(value, min, max)
+        });
+    };
+}
+
+impl<B: crate::Behavior, C: RangeSummary> crate::Behavior for Described<B, C> {
+    impl_described_binary_op!(Add, add, +, "Addition");
+    impl_described_binary_op!(Sub, sub, -, "Subtraction");
+    impl_described_binary_op!(Mul, mul, *, "Multiplication");
+    impl_described_binary_op!(Div, div, /, "Division");
+    impl_described_binary_op!(Rem, rem, %, "Remainder");
+    impl_described_binary_op!(BitAnd, bitand, &, "Bitwise AND");
+    impl_described_binary_op!(BitOr, bitor, |, "Bitwise OR");
+    impl_described_binary_op!(BitXor, bitxor, ^, "Bitwise XOR");
+    impl_described_unary_op!(Neg, neg, -, "Negation");
+    impl_described_unary_op!(Not, not, !, "Bitwise NOT");
+
+    fn div_by_zero<T: Copy>(policy: DivByZero, max: T, default: T) -> T {
+        B::div_by_zero(policy, max, default)
+    }
+
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        B::resolve(result)
+    }
+}
+
+/// A behavior that records in the counters of `M` whether each out of bounds result was saturated
+/// or panicked, and then resolves it with `B`. Types given the `metrics` flag use it for their
+/// operators.
+#[cfg(feature = "metrics")]
+pub struct Metered<B, M>(std::marker::PhantomData<(B, M)>);
+
+#[cfg(feature = "metrics")]
+impl<B, M> Clone for Metered<B, M> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<B, M> Copy for Metered<B, M> {}
+
+#[cfg(feature = "metrics")]
+macro_rules! impl_metered_binary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_binary_op!($trait, $method, $op, |lhs, rhs, min, max, outcome| {
+            if let Err(e) = outcome {
+                let _ = Self::record(e);
+            }
+
+            B::$method(lhs, rhs, min, max)
+        });
+    };
+}
+
+#[cfg(feature = "metrics")]
+macro_rules! impl_metered_unary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_unary_op!($trait, $method, $op, |value, min, max, outcome| {
+            if let Err(e) = outcome {
+                let _ = Self::record(e);
+            }
+
+            B::$method(value, min, max)
+        });
+    };
+}
+
+#[cfg(feature = "metrics")]
+impl<B: crate::Behavior, M: crate::metrics::MetricsSource> Metered<B, M> {
+    /// Resolve an out of bounds result with `B` and count how it was resolved.
+    #[inline(always)]
+    fn record<T: Copy>(error: ClampError<T>) -> Result<T, ClampError<T>> {
+        let result = B::resolve(Err(error));
+
+        match result {
+            Ok(_) => M::metrics().record_saturated(),
+            Err(_) => M::metrics().record_panicked(),
+        }
+
+        result
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<B: crate::Behavior, M: crate::metrics::MetricsSource> crate::Behavior for Metered<B, M> {
+    impl_metered_binary_op!(Add, add, +);
+    impl_metered_binary_op!(Sub, sub, -);
+    impl_metered_binary_op!(Mul, mul, *);
+    impl_metered_binary_op!(Div, div, /);
+    impl_metered_binary_op!(Rem, rem, %);
+    impl_metered_binary_op!(BitAnd, bitand, &);
+    impl_metered_binary_op!(BitOr, bitor, |);
+    impl_metered_binary_op!(BitXor, bitxor, ^);
+    impl_metered_unary_op!(Neg, neg, -);
+    impl_metered_unary_op!(Not, not, !);
+
+    fn div_by_zero<T: Copy>(policy: DivByZero, max: T, default: T) -> T {
+        B::div_by_zero(policy, max, default)
+    }
+
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        match result {
+            Err(e) => Self::record(e),
+            ok => ok,
+        }
+    }
+}
+
+/// A behavior for chaos testing that resolves with `B`, and also fails the operations chosen by the
+/// `FaultPlan` of the current thread even when their result is within the limits, so that the
+/// saturation and panic paths of an application can be tested without crafting boundary inputs.
+/// An injected fault is an overflow at the upper limit, so `Saturating` results in the upper limit
+/// and `Panicking` panics. Generated types use it for their operators when the `testing` feature
+/// is enabled, and nothing fails until a plan is set.
+#[cfg(feature = "testing")]
+pub struct FaultInjecting<B>(std::marker::PhantomData<B>);
+
+#[cfg(feature = "testing")]
+impl<B> Clone for FaultInjecting<B> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<B> Copy for FaultInjecting<B> {}
+
+#[cfg(feature = "testing")]
+impl<B: crate::Behavior> FaultInjecting<B> {
+    /// Resolve an overflow at `max` with `B`, panicking if `B` rejects it.
+    fn inject<T: Copy>(op: &'static str, max: T) -> T {
+        match B::resolve(Err(ClampError::TooLarge { val: max, max })) {
+            Ok(val) => val,
+            Err(_) => panic!("Injected fault in `{}`", op),
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+macro_rules! impl_fault_injecting_binary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_binary_op!($trait, $method, $op, |lhs, rhs, min, max, outcome| {
+            if crate::testing::take_fault(outcome.is_ok()) {
+                Self::inject(stringify!($method), max)
+            } else {
+                B::$method(lhs, rhs, min, max)
+            }
+        });
+    };
+}
+
+#[cfg(feature = "testing")]
+macro_rules! impl_fault_injecting_unary_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl_combinator_unary_op!($trait, $method, $op, |value, min, max, outcome| {
+            if crate::testing::take_fault(outcome.is_ok()) {
+                Self::inject(stringify!($method), max)
+            } else {
+                B::$method(value, min, max)
+            }
+        });
+    };
+}
+
+#[cfg(feature = "testing")]
+impl<B: crate::Behavior> crate::Behavior for FaultInjecting<B> {
+    impl_fault_injecting_binary_op!(Add, add, +);
+    impl_fault_injecting_binary_op!(Sub, sub, -);
+    impl_fault_injecting_binary_op!(Mul, mul, *);
+    impl_fault_injecting_binary_op!(Div, div, /);
+    impl_fault_injecting_binary_op!(Rem, rem, %);
+    impl_fault_injecting_binary_op!(BitAnd, bitand, &);
+    impl_fault_injecting_binary_op!(BitOr, bitor, |);
+    impl_fault_injecting_binary_op!(BitXor, bitxor, ^);
+    impl_fault_injecting_unary_op!(Neg, neg, -);
+    impl_fault_injecting_unary_op!(Not, not, !);
+
+    fn div_by_zero<T: Copy>(policy: DivByZero, max: T, default: T) -> T {
+        B::div_by_zero(policy, max, default)
+    }
+
+    /// Previews are not failed, since they do not change the value.
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        B::resolve(result)
+    }
+}
+
+/// A running total of primitives that always stays within the limits of the hard clamped type
+/// `T`, such as the amount spent from a budget. Unlike adding with operators, it reports how much
+/// headroom is left before the upper limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundedSum<T> {
+    total: T,
+}
+
+impl<T> Default for BoundedSum<T>
+where
+    T: HardClamp<<T as Deref>::Target> + Deref,
+    T::Target: BatchPrimitive + PrimitiveBounds,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BoundedSum<T>
+where
+    T: HardClamp<<T as Deref>::Target> + Deref,
+    T::Target: BatchPrimitive + PrimitiveBounds,
+{
+    /// Start a sum at the lower limit of `T`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::starting_at(T::from_primitive(T::MIN).expect("value should be within bounds"))
+    }
+
+    #[inline(always)]
+    pub fn starting_at(total: T) -> Self {
+        Self { total }
+    }
+
+    #[inline(always)]
+    pub fn total(&self) -> &T {
+        &self.total
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.total
+    }
+
+    /// Get how much can still be added before the total reaches the upper limit of `T`.
+    #[inline(always)]
+    pub fn remaining(&self) -> T::Target {
+        T::MAX.wrapping_sub(*self.total)
+    }
+
+    /// Add `value` to the total, leaving it unchanged and failing if the result would be above the
+    /// upper limit of `T`.
+    pub fn try_add(&mut self, value: T::Target) -> Result<&T, ClampError<T::Target>> {
+        let total = try_clamp_op(
+            self.total.checked_add(value),
+            self.total.saturating_add(value),
+            T::MIN,
+            T::MAX,
+        )?;
+
+        self.total = T::from_primitive(total).expect("value should be within bounds");
+        Ok(&self.total)
+    }
+
+    /// Add `value` to the total, saturating at the upper limit of `T`.
+    pub fn saturating_add(&mut self, value: T::Target) -> &T {
+        let total = clamp_saturating(self.total.saturating_add(value), T::MIN, T::MAX);
+
+        self.total = T::from_primitive(total).expect("value should be within bounds");
+        &self.total
+    }
+}
+
+/// A clamped value that remembers the lowest and highest values it has held, such as the peak
+/// depth of a bounded queue for capacity planning. Operators are forwarded to `T` and resolved
+/// with its behavior, and every result updates the marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Watermarked<T> {
+    value: T,
+    low: T,
+    high: T,
+}
+
+impl<T: Copy + Ord + Default> Default for Watermarked<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Copy + Ord> Watermarked<T> {
+    /// Start tracking at `value`, which is both the low and the high mark.
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            low: value,
+            high: value,
+        }
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> T {
+        self.value
+    }
+
+    /// The lowest value held since the last reset.
+    #[inline(always)]
+    pub fn low(&self) -> T {
+        self.low
+    }
+
+    /// The highest value held since the last reset.
+    #[inline(always)]
+    pub fn high(&self) -> T {
+        self.high
+    }
+
+    /// Replace the value, updating the marks.
+    #[inline(always)]
+    pub fn set(&mut self, value: T) {
+        self.value = value;
+        self.low = self.low.min(value);
+        self.high = self.high.max(value);
+    }
+
+    /// Forget the marks, so both start again from the current value.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.low = self.value;
+        self.high = self.value;
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Watermarked<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Watermarked<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+macro_rules! impl_watermarked_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl<T: Copy + Ord + $trait<Rhs, Output = T>, Rhs> $trait<Rhs> for Watermarked<T> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $method(mut self, rhs: Rhs) -> Self::Output {
+                self.set($trait::$method(self.value, rhs));
+                self
+            }
+        }
+
+        impl<T: Copy + Ord + $trait<Rhs, Output = T>, Rhs> $assign_trait<Rhs> for Watermarked<T> {
+            #[inline(always)]
+            fn $assign_method(&mut self, rhs: Rhs) {
+                self.set($trait::$method(self.value, rhs));
+            }
+        }
+    };
+}
+
+impl_watermarked_binary_op!(Add, add, AddAssign, add_assign);
+impl_watermarked_binary_op!(Sub, sub, SubAssign, sub_assign);
+impl_watermarked_binary_op!(Mul, mul, MulAssign, mul_assign);
+impl_watermarked_binary_op!(Div, div, DivAssign, div_assign);
+impl_watermarked_binary_op!(Rem, rem, RemAssign, rem_assign);
+impl_watermarked_binary_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_watermarked_binary_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_watermarked_binary_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+/// A clamped value that decays towards its lower limit as time passes, such as the tokens of a
+/// leaky bucket or a reputation score. Decay and boosts saturate at the limits like `Saturating`
+/// does, and the time left over from a partial period is carried into the next `tick`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decaying<T> {
+    value: T,
+    rate: u64,
+    period: std::time::Duration,
+    carry: std::time::Duration,
+}
+
+impl<T> Decaying<T>
+where
+    T: ClampedInteger<<T as Deref>::Target> + Deref,
+    T::Target: Copy + Ord + Add<Output = T::Target> + Sub<Output = T::Target> + TryFrom<u128>,
+    num::Saturating<T::Target>:
+        Add<Output = num::Saturating<T::Target>> + Sub<Output = num::Saturating<T::Target>>,
+{
+    /// Decay `value` by `rate` every `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn new(value: T, rate: u64, period: std::time::Duration) -> Self {
+        assert!(!period.is_zero(), "the decay period must not be zero");
+
+        Self {
+            value,
+            rate,
+            period,
+            carry: std::time::Duration::ZERO,
+        }
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Decay the value for `elapsed`, which may be shorter than a period.
+    pub fn tick(&mut self, elapsed: std::time::Duration) -> &T {
+        let total = self.carry.saturating_add(elapsed);
+        let periods = total.as_nanos() / self.period.as_nanos();
+        let amount = periods.saturating_mul(self.rate as u128);
+
+        self.carry =
+            std::time::Duration::from_nanos((total.as_nanos() % self.period.as_nanos()) as u64);
+
+        let value = match T::Target::try_from(amount) {
+            Ok(amount) => <Saturating as crate::Behavior>::sub(*self.value, amount, T::MIN, T::MAX),
+            // More than the primitive holds is more than the whole range.
+            Err(_) => T::MIN,
+        };
+
+        self.value = T::from_primitive(value).expect("value should be within the limits");
+        &self.value
+    }
+
+    /// Raise the value by `amount`, saturating at the upper limit.
+    pub fn boost(&mut self, amount: T::Target) -> &T {
+        let value = <Saturating as crate::Behavior>::add(*self.value, amount, T::MIN, T::MAX);
+
+        self.value = T::from_primitive(value).expect("value should be within the limits");
+        &self.value
+    }
 }
 
 #[cfg(test)]
@@ -432,8 +1310,10 @@ mod tests {
     use checked_rs_macros::clamped;
 
     use super::*;
+    use crate::bounded::{BoundedValue, ClampSpec};
     use crate::prelude::*;
 
+    #[cfg(not(feature = "never-panic"))]
     #[test]
     fn test_define() {
         #[clamped(u8; default = 1; behavior = Panicking)]
@@ -459,4 +1339,171 @@ mod tests {
 
         assert!(d.is_nil());
     }
+
+    #[test]
+    fn test_watermarked() {
+        #[clamped(u8 as Hard, default = 10, lower = 0, upper = 100, behavior = Saturating)]
+        #[derive(Debug, Clone, Copy)]
+        struct QueueDepth;
+
+        let mut depth = Watermarked::new(QueueDepth::default());
+        depth += 30;
+        depth -= 35;
+        let depth_after = depth + 200;
+        assert_eq!(*depth_after.high(), 100);
+        depth = depth_after;
+        assert_eq!(**depth, 100);
+        assert_eq!(*depth.low(), 5);
+        assert_eq!(*depth.high(), 100);
+
+        depth -= 50;
+        depth.reset();
+        assert_eq!(*depth.low(), 50);
+        assert_eq!(*depth.high(), 50);
+        assert_eq!(depth.to_string(), "50");
+    }
+
+    #[test]
+    fn test_decaying() {
+        use std::time::Duration;
+
+        #[clamped(u8, default = 50, lower = 10, upper = 100)]
+        #[derive(Debug, Clone, Copy)]
+        enum Reputation {
+            #[range(10..=29)]
+            Poor,
+            #[range(30..=69)]
+            Fair,
+            #[range(70..=100)]
+            Good,
+        }
+
+        let mut score = Decaying::new(Reputation::default(), 2, Duration::from_secs(1));
+        assert!(score.boost(30).is_good());
+        assert_eq!(**score.boost(200), 100);
+
+        // Half a period is carried over instead of being lost.
+        score.tick(Duration::from_millis(1500));
+        assert_eq!(**score.get(), 98);
+        score.tick(Duration::from_millis(500));
+        assert_eq!(**score.get(), 96);
+
+        assert!(score.tick(Duration::from_secs(20)).is_fair());
+        assert!(score.tick(Duration::from_secs(3600)).is_poor());
+        assert_eq!(**score.get(), 10);
+        assert_eq!(score.tick(Duration::MAX).into_primitive(), 10);
+    }
+
+    #[test]
+    fn test_free_functions() {
+        assert_eq!(clamp_saturating(150u8, 10, 100), 100);
+        assert_eq!(clamp_saturating(5u8, 10, 100), 10);
+        assert_eq!(clamp_panicking(50u8, 10, 100), 50);
+        assert_eq!(
+            try_clamp_op(5u8.checked_sub(3), 5u8.saturating_sub(3), 0, 10).unwrap(),
+            2
+        );
+        assert!(matches!(
+            try_clamp_op(5u8.checked_sub(6), 5u8.saturating_sub(6), 0, 10),
+            Err(ClampError::TooSmall { val: 0, min: 0 })
+        ));
+        assert!(matches!(
+            try_clamp_op(0u8.checked_sub(1), 0u8.saturating_sub(1), 0, 0),
+            Err(ClampError::TooSmall { val: 0, min: 0 })
+        ));
+        assert!(matches!(
+            try_clamp_op(
+                i8::MIN.checked_mul(2),
+                i8::MIN.saturating_mul(2),
+                i8::MIN,
+                i8::MIN
+            ),
+            Err(ClampError::TooSmall {
+                val: -128,
+                min: -128
+            })
+        ));
+        assert!(matches!(
+            try_clamp_op(u8::MAX.checked_add(1), u8::MAX.saturating_add(1), 255, 255),
+            Err(ClampError::TooLarge { val: 255, max: 255 })
+        ));
+        assert!(matches!(
+            try_clamp(-5i32, 0, 10),
+            Err(ClampError::TooSmall { val: -5, min: 0 })
+        ));
+        assert!(std::panic::catch_unwind(|| clamp_panicking(101u8, 10, 100)).is_err());
+    }
+
+    #[test]
+    fn test_behavior_combinators() -> anyhow::Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CLAMPED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone, Copy)]
+        struct Counter;
+
+        impl ClampObserver for Counter {
+            fn on_clamp<T: Copy>(op: &'static str, error: &ClampError<T>) {
+                assert_eq!(op, "add");
+                assert!(matches!(error, ClampError::TooLarge { .. }));
+                CLAMPED.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let spec = ClampSpec::new(10u8, 100)?;
+
+        let val = BoundedValue::<u8, Logged<Saturating, Counter>>::new(50, spec)?;
+        assert_eq!(*(val + 10), 60);
+        assert_eq!(CLAMPED.load(Ordering::SeqCst), 0);
+        assert_eq!(*(val + 250), 100);
+        assert_eq!(CLAMPED.load(Ordering::SeqCst), 1);
+
+        let val = BoundedValue::<u8, Fallback<Panicking, Saturating>>::new(50, spec)?;
+        assert_eq!(*(val * 3), 100);
+        assert_eq!(*(val - 45), 10);
+        assert_eq!(*(val / 5), 10);
+
+        assert!(std::panic::catch_unwind(|| {
+            <Logged<Panicking, Counter> as Behavior>::add(90u8, 20, 10, 100)
+        })
+        .is_err());
+        assert_eq!(CLAMPED.load(Ordering::SeqCst), 2);
+
+        assert_eq!(
+            <Fallback<Panicking, Saturating> as Behavior>::resolve(Err(ClampError::TooSmall {
+                val: 1u8,
+                min: 10
+            }))?,
+            10
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bounded_sum() -> anyhow::Result<()> {
+        #[clamped(u16 as Hard, default = 0, upper = 1000)]
+        #[derive(Debug, Clone, Copy)]
+        struct Budget;
+
+        let mut spent = BoundedSum::<Budget>::new();
+        assert_eq!(spent.remaining(), 1000);
+
+        assert_eq!(**spent.try_add(600)?, 600);
+        assert_eq!(spent.remaining(), 400);
+
+        assert!(matches!(
+            spent.try_add(401),
+            Err(ClampError::TooLarge {
+                val: 1001,
+                max: 1000
+            })
+        ));
+        assert_eq!(**spent.total(), 600);
+
+        assert!(spent.try_add(u16::MAX).is_err());
+        assert_eq!(**spent.saturating_add(u16::MAX), 1000);
+        assert_eq!(spent.remaining(), 0);
+        Ok(())
+    }
 }
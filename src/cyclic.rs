@@ -0,0 +1,91 @@
+use crate::scale::RangeOffset;
+
+/// Get the number of steps forward from `from` to `to` on the circle `min..=max`, where the upper
+/// limit is followed by the lower limit, as it is for angles and sequence numbers.
+#[inline(always)]
+pub fn cyclic_distance<T: RangeOffset>(from: T, to: T, min: T, max: T) -> u128 {
+    let from = from.offset_from(min);
+    let to = to.offset_from(min);
+
+    if to >= from {
+        to - from
+    } else {
+        // ordered so that it cannot overflow when the limits cover every `u128`
+        (max.offset_from(min) - from) + to + 1
+    }
+}
+
+/// Check whether `a` comes before `b` on the circle `min..=max`, which is when `b` is less than
+/// half of the circle ahead of `a`, as in RFC 1982 serial number arithmetic. When `b` is exactly
+/// half of the circle away, neither comes before the other.
+#[inline(always)]
+pub fn cyclic_lt<T: RangeOffset>(a: T, b: T, min: T, max: T) -> bool {
+    let distance = cyclic_distance(a, b, min, max);
+
+    // `2 * distance < len` without overflowing
+    distance > 0 && distance <= max.offset_from(min) / 2
+}
+
+/// Check whether walking forward on the circle `min..=max` from `start` to `end` passes `value`,
+/// including both ends.
+#[inline(always)]
+pub fn cyclic_is_between<T: RangeOffset>(value: T, start: T, end: T, min: T, max: T) -> bool {
+    cyclic_distance(start, value, min, max) <= cyclic_distance(start, end, min, max)
+}
+
+/// Get the value `n` steps forward from `value` on the circle `min..=max`.
+#[inline(always)]
+pub fn cyclic_add<T: RangeOffset>(value: T, n: u128, min: T, max: T) -> T {
+    let offset = value.offset_from(min);
+
+    let offset = match max.offset_from(min).checked_add(1) {
+        Some(len) => {
+            let n = n % len;
+
+            // ordered so that it cannot overflow
+            if n >= len - offset {
+                n - (len - offset)
+            } else {
+                offset + n
+            }
+        }
+        // the limits cover every `u128`, which wraps on its own
+        None => offset.wrapping_add(n),
+    };
+
+    min.offset_by(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cyclic() {
+        // degrees of a compass
+        assert_eq!(cyclic_distance(350u16, 10, 0, 359), 20);
+        assert_eq!(cyclic_distance(10u16, 350, 0, 359), 340);
+        assert_eq!(cyclic_distance(10u16, 10, 0, 359), 0);
+
+        assert!(cyclic_lt(350u16, 10, 0, 359));
+        assert!(!cyclic_lt(10u16, 350, 0, 359));
+        assert!(!cyclic_lt(0u16, 180, 0, 359));
+        assert!(!cyclic_lt(180u16, 0, 0, 359));
+
+        assert!(cyclic_is_between(0u16, 350, 10, 0, 359));
+        assert!(!cyclic_is_between(180u16, 350, 10, 0, 359));
+        assert!(cyclic_is_between(180u16, 10, 350, 0, 359));
+
+        assert_eq!(cyclic_add(350u16, 20, 0, 359), 10);
+        assert_eq!(cyclic_add(350u16, 9, 0, 359), 359);
+        assert_eq!(cyclic_add(10u16, 720, 0, 359), 10);
+        assert_eq!(cyclic_add(u8::MAX, 1, 0, u8::MAX), 0);
+        assert_eq!(cyclic_add(u128::MAX, 2, 0, u128::MAX), 1);
+
+        // the full domain of the primitive
+        assert_eq!(cyclic_distance(u8::MAX, 0, 0, u8::MAX), 1);
+        assert!(cyclic_lt(u8::MAX, 0, 0, u8::MAX));
+        assert!(cyclic_lt(u128::MAX, 5, 0, u128::MAX));
+        assert_eq!(cyclic_distance(u128::MAX, 5, 0, u128::MAX), 6);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::prelude::*;
+
+/// The status code of an HTTP response, with a variant per well-known code, a variant per class
+/// of errors and a catch-all for the rest of the valid codes.
+///
+/// ```rust
+/// use checked_rs::{examples::HttpStatus, prelude::*};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let status = HttpStatus::from_primitive(503)?;
+/// assert!(status.is_server_error());
+/// assert!(HttpStatus::from_primitive(600).is_err());
+/// assert_eq!(*HttpStatus::default(), 200);
+/// # Ok(())
+/// # }
+/// ```
+#[clamped(u16, default = 200, lower = 100, upper = 599)]
+#[derive(Debug, Clone, Copy)]
+pub enum HttpStatus {
+    #[eq(200)]
+    Ok,
+    #[eq(201)]
+    Created,
+    #[eq(204)]
+    NoContent,
+    #[eq(301)]
+    MovedPermanently,
+    #[eq(304)]
+    NotModified,
+    #[eq(400)]
+    BadRequest,
+    #[eq(401)]
+    Unauthorized,
+    #[eq(403)]
+    Forbidden,
+    #[eq(404)]
+    NotFound,
+    #[range(400..=499)]
+    ClientError,
+    #[range(500..=599)]
+    ServerError,
+    #[other]
+    Other,
+}
+
+/// A TCP or UDP port that a service can listen on, which excludes the reserved port `0`. Literals
+/// are checked at compile time with `examples::port!`.
+///
+/// ```rust
+/// use checked_rs::{
+///     examples::{port, Port},
+///     prelude::*,
+/// };
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let port = Port::from_primitive(8080)?;
+/// assert_eq!(*port, 8080);
+/// assert!(Port::from_primitive(0).is_err());
+/// assert_eq!(*Port::default(), 80);
+///
+/// const HTTPS: Port = port!(443);
+/// assert_eq!(*HTTPS, 443);
+/// # Ok(())
+/// # }
+/// ```
+#[clamped(u16 as Hard, default = 80, lower = 1, literal = crate::examples::port, doc_examples)]
+#[derive(Debug, Clone, Copy)]
+pub struct Port;
+
+/// A whole percentage, where arithmetic saturates at `0` and `100` instead of failing.
+///
+/// ```rust
+/// use checked_rs::{examples::Percent, prelude::*};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let progress = Percent::from_primitive(90)? + 25;
+/// assert_eq!(*progress, 100);
+/// assert_eq!(*Percent::new(250), 100);
+/// # Ok(())
+/// # }
+/// ```
+#[clamped(u8 as Hard, default = 0, upper = 100, behavior = Saturating, doc_examples)]
+#[derive(Debug, Clone, Copy)]
+pub struct Percent;
+
+/// A compass heading in whole degrees, which wraps from `359` back to `0` when turning.
+///
+/// ```rust
+/// use checked_rs::{examples::Angle, prelude::*};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let heading = Angle::from_primitive(350)?.wrapping_add(20);
+/// assert_eq!(*heading, 10);
+/// assert!(Angle::from_primitive(350)?.cyclic_lt(&heading));
+/// # Ok(())
+/// # }
+/// ```
+#[clamped(u16 as Hard, default = 0, upper = 359, behavior = Saturating, serial)]
+#[derive(Debug, Clone, Copy)]
+pub struct Angle;
@@ -0,0 +1,241 @@
+use std::fmt;
+
+/// How a primitive maps onto a clamped type, as reported by the generated `explain` function.
+/// Its `Display` output is meant for error messages and debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub enum Explanation<T> {
+    /// The value is within the limits of a struct type.
+    Valid {
+        type_name: &'static str,
+        value: T,
+        min: T,
+        max: T,
+    },
+    /// The value maps to a variant of an enum, along with the bounds of the variant when it was
+    /// declared with `#[range]`.
+    Variant {
+        type_name: &'static str,
+        value: T,
+        variant: &'static str,
+        range: Option<(T, T)>,
+    },
+    /// The value is not allowed, along with the closest value that is.
+    Invalid {
+        type_name: &'static str,
+        value: T,
+        nearest: T,
+    },
+}
+
+impl<T: Copy> Explanation<T> {
+    #[inline(always)]
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Valid { type_name, .. }
+            | Self::Variant { type_name, .. }
+            | Self::Invalid { type_name, .. } => type_name,
+        }
+    }
+
+    /// The primitive that was explained.
+    #[inline(always)]
+    pub fn value(&self) -> T {
+        match self {
+            Self::Valid { value, .. }
+            | Self::Variant { value, .. }
+            | Self::Invalid { value, .. } => *value,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_valid(&self) -> bool {
+        !matches!(self, Self::Invalid { .. })
+    }
+
+    /// The path of the variant the value maps to, such as `"ResponseCode::NotFound"`.
+    pub fn variant_path(&self) -> Option<String> {
+        match self {
+            Self::Variant {
+                type_name, variant, ..
+            } => Some(format!("{}::{}", type_name, variant)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Explanation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Valid {
+                type_name,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "{} is a valid `{}` ({}..={})",
+                value, type_name, min, max
+            ),
+            Self::Variant {
+                type_name,
+                value,
+                variant,
+                range,
+            } => {
+                write!(f, "{} is `{}::{}`", value, type_name, variant)?;
+
+                if let Some((start, end)) = range {
+                    write!(f, " ({}..={})", start, end)?;
+                }
+
+                Ok(())
+            }
+            Self::Invalid {
+                type_name,
+                value,
+                nearest,
+            } => write!(
+                f,
+                "{} is not a valid `{}`, the nearest valid value is {}",
+                value, type_name, nearest
+            ),
+        }
+    }
+}
+
+/// A value that describes itself along with its variant or range in the failure message of
+/// `assert_clamped_eq!`, such as `503 (ResponseCode::ServerError)` or `42 (Percent, valid: 0..=100)`.
+/// It is implemented for every generated struct and enum.
+pub trait DiffDisplay {
+    fn diff_display(&self) -> String;
+}
+
+/// Describe an explained primitive for `DiffDisplay`, given the allowed values of its type.
+pub fn diff_display_of<T: fmt::Display>(explanation: &Explanation<T>, summary: &str) -> String {
+    match explanation {
+        Explanation::Valid {
+            type_name, value, ..
+        } => format!("{} ({}, valid: {})", value, type_name, summary),
+        Explanation::Variant {
+            type_name,
+            value,
+            variant,
+            ..
+        } => format!("{} ({}::{})", value, type_name, variant),
+        Explanation::Invalid {
+            type_name, value, ..
+        } => format!("{} ({}, invalid, valid: {})", value, type_name, summary),
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_clamped_eq_failed<L: DiffDisplay, R: DiffDisplay>(
+    left: &L,
+    right: &R,
+    args: Option<fmt::Arguments<'_>>,
+) -> ! {
+    let message = match args {
+        Some(args) => format!("assertion `left == right` failed: {}", args),
+        None => "assertion `left == right` failed".to_string(),
+    };
+
+    panic!(
+        "{}\n  left: {}\n right: {}",
+        message,
+        left.diff_display(),
+        right.diff_display()
+    )
+}
+
+/// Assert that two clamped values are equal like `assert_eq!`, but print them with their variant
+/// or range on failure, such as `left: 503 (ResponseCode::ServerError)`, rather than with `Debug`.
+///
+/// ```rust
+/// use checked_rs::{assert_clamped_eq, prelude::*};
+///
+/// #[clamped(u8, default = 0, upper = 9)]
+/// #[derive(Debug, Clone, Copy)]
+/// enum Digit {
+///     #[eq(0)]
+///     Zero,
+///     #[range(1..=9)]
+///     NonZero,
+/// }
+///
+/// # fn main() -> anyhow::Result<()> {
+/// assert_clamped_eq!(Digit::from_primitive(3)?, Digit::from_primitive(3)?);
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_clamped_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::explain::assert_clamped_eq_failed(left, right, None);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::explain::assert_clamped_eq_failed(
+                        left,
+                        right,
+                        Some(format_args!($($arg)+)),
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explanation_display() {
+        let valid = Explanation::Valid {
+            type_name: "Port",
+            value: 80u16,
+            min: 1,
+            max: 9999,
+        };
+        assert_eq!(valid.to_string(), "80 is a valid `Port` (1..=9999)");
+        assert!(valid.is_valid());
+        assert_eq!(valid.variant_path(), None);
+
+        let variant = Explanation::Variant {
+            type_name: "ResponseCode",
+            value: 503u16,
+            variant: "ServerError",
+            range: Some((500, 599)),
+        };
+        assert_eq!(
+            variant.to_string(),
+            "503 is `ResponseCode::ServerError` (500..=599)"
+        );
+        assert_eq!(
+            variant.variant_path().as_deref(),
+            Some("ResponseCode::ServerError")
+        );
+
+        let invalid = Explanation::Invalid {
+            type_name: "Port",
+            value: 0u16,
+            nearest: 1,
+        };
+        assert_eq!(
+            invalid.to_string(),
+            "0 is not a valid `Port`, the nearest valid value is 1"
+        );
+        assert!(!invalid.is_valid());
+        assert_eq!(invalid.value(), 0);
+        assert_eq!(invalid.type_name(), "Port");
+    }
+}
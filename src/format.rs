@@ -0,0 +1,197 @@
+use std::fmt::Display;
+
+/// Format an integer with `separator` between every group of three digits, such as `1,234,567`.
+pub fn separated_string<T: Display>(value: T, separator: &str) -> String {
+    let digits = value.to_string();
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", digits.as_str()),
+    };
+
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 * separator.len() + 1);
+    out.push_str(sign);
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push_str(separator);
+        }
+
+        out.push(c);
+    }
+
+    out
+}
+
+/// Displays an integer with a separator between every group of three digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplaySeparated<T> {
+    value: T,
+    separator: &'static str,
+}
+
+impl<T> DisplaySeparated<T> {
+    #[inline(always)]
+    pub fn new(value: T, separator: &'static str) -> Self {
+        Self { value, separator }
+    }
+}
+
+impl<T: Display> Display for DisplaySeparated<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&separated_string(&self.value, self.separator))
+    }
+}
+
+/// Check whether `s` is an integer in canonical form: base 10, with an optional leading `-`, no
+/// `+`, no leading zeros, no separators or whitespace, and never `-0`. Every integer has exactly
+/// one canonical form, and it is what `to_string` produces regardless of locale.
+pub fn is_canonical_integer(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+
+    match digits.as_bytes() {
+        [] => false,
+        [b'0'] => digits.len() == s.len(),
+        [first, rest @ ..] => {
+            first.is_ascii_digit() && *first != b'0' && rest.iter().all(u8::is_ascii_digit)
+        }
+    }
+}
+
+/// A compact textual encoding of a `u128`, for 128-bit ids whose decimal form is unwieldy in URLs
+/// and logs. Both encodings only use characters that are safe in URLs without percent-encoding.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompactEncoding {
+    /// The digits `0-9`, `A-Z` and `a-z`, which fits any `u128` in 22 characters.
+    #[default]
+    Base62,
+    /// Lowercase hexadecimal digits, which fits any `u128` in 32 characters.
+    Base16,
+}
+
+const BASE62_DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE16_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+impl CompactEncoding {
+    #[inline(always)]
+    fn radix(self) -> u128 {
+        match self {
+            Self::Base62 => 62,
+            Self::Base16 => 16,
+        }
+    }
+
+    #[inline(always)]
+    fn digit_value(self, c: u8) -> Option<u128> {
+        let value = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' if self == Self::Base16 => c - b'a' + 10,
+            b'A'..=b'Z' if self == Self::Base62 => c - b'A' + 10,
+            b'a'..=b'z' if self == Self::Base62 => c - b'a' + 36,
+            _ => return None,
+        };
+
+        Some(value as u128)
+    }
+}
+
+/// Encode `value` with the digits of `encoding`, without leading zeros.
+pub fn encode_compact(mut value: u128, encoding: CompactEncoding) -> String {
+    let radix = encoding.radix();
+    let mut digits = Vec::new();
+
+    loop {
+        let digit = (value % radix) as usize;
+        digits.push(match encoding {
+            CompactEncoding::Base62 => BASE62_DIGITS[digit],
+            CompactEncoding::Base16 => BASE16_DIGITS[digit],
+        });
+        value /= radix;
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    digits.reverse();
+    String::from_utf8(digits).expect("digits should be ASCII")
+}
+
+/// Decode a value written by `encode_compact`. Leading zeros and characters outside of the
+/// alphabet of `encoding` are rejected, so that every value has exactly one encoding.
+pub fn decode_compact(s: &str, encoding: CompactEncoding) -> Result<u128, String> {
+    match s.as_bytes() {
+        [] => return Err("empty string".to_string()),
+        [b'0', _, ..] => return Err("leading zeros".to_string()),
+        _ => {}
+    }
+
+    s.bytes().try_fold(0u128, |acc, c| {
+        let digit = encoding
+            .digit_value(c)
+            .ok_or_else(|| format!("invalid {:?} digit {:?}", encoding, c as char))?;
+
+        acc.checked_mul(encoding.radix())
+            .and_then(|acc| acc.checked_add(digit))
+            .ok_or_else(|| "number too large to fit in target type".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separated_string() {
+        assert_eq!(separated_string(0u8, ","), "0");
+        assert_eq!(separated_string(999u16, ","), "999");
+        assert_eq!(separated_string(1000u16, ","), "1,000");
+        assert_eq!(separated_string(1234567u32, "_"), "1_234_567");
+        assert_eq!(separated_string(-123456i32, "\u{2009}"), "-123\u{2009}456");
+        assert_eq!(
+            format!("{:>8}", DisplaySeparated::new(12345u32, ",")),
+            "  12,345"
+        );
+    }
+
+    #[test]
+    fn test_compact_encoding() {
+        for value in [0, 9, 61, 62, 255, u64::MAX as u128, u128::MAX] {
+            for encoding in [CompactEncoding::Base62, CompactEncoding::Base16] {
+                assert_eq!(
+                    decode_compact(&encode_compact(value, encoding), encoding),
+                    Ok(value)
+                );
+            }
+        }
+
+        assert_eq!(encode_compact(61, CompactEncoding::Base62), "z");
+        assert_eq!(encode_compact(62, CompactEncoding::Base62), "10");
+        assert_eq!(encode_compact(255, CompactEncoding::Base16), "ff");
+        assert_eq!(encode_compact(u128::MAX, CompactEncoding::Base62).len(), 22);
+
+        assert!(decode_compact("", CompactEncoding::Base62).is_err());
+        assert!(decode_compact("01", CompactEncoding::Base62).is_err());
+        assert!(decode_compact("FF", CompactEncoding::Base16).is_err());
+        assert!(decode_compact("a-b", CompactEncoding::Base62).is_err());
+        assert!(decode_compact(&"z".repeat(23), CompactEncoding::Base62).is_err());
+    }
+
+    #[test]
+    fn test_is_canonical_integer() {
+        for s in [
+            "0",
+            "7",
+            "-7",
+            "1234567",
+            "-170141183460469231731687303715884105728",
+        ] {
+            assert!(is_canonical_integer(s), "{s}");
+        }
+
+        for s in [
+            "", "-", "-0", "+7", "007", "-07", " 7", "7 ", "1,000", "1_000", "0x10",
+        ] {
+            assert!(!is_canonical_integer(s), "{s}");
+        }
+    }
+}
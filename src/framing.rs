@@ -0,0 +1,284 @@
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    marker::PhantomData,
+    ops::Deref,
+};
+
+use crate::clamp::{try_clamp, ClampError, ClampedInteger};
+
+/// A primitive that is framed as a fixed number of big-endian bytes.
+pub trait WirePrimitive: Copy + Ord + fmt::Debug + fmt::Display + 'static {
+    /// The number of bytes a value occupies.
+    const SIZE: usize;
+
+    /// Decode a value from exactly `SIZE` bytes.
+    fn from_be_slice(bytes: &[u8]) -> Self;
+
+    /// Encode the value into exactly `SIZE` bytes.
+    fn write_be_slice(self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_wire_primitive {
+    ($($ty:ty),*) => {
+        $(
+            impl WirePrimitive for $ty {
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                #[inline(always)]
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    <$ty>::from_be_bytes(bytes.try_into().expect("slice should be `SIZE` bytes"))
+                }
+
+                #[inline(always)]
+                fn write_be_slice(self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_be_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_wire_primitive!(u8, u16, u32, u64, u128);
+
+/// The largest `SIZE` of a `WirePrimitive`.
+const MAX_SIZE: usize = 16;
+
+/// The error returned by `ClampedReader` and `ClampedWriter`, along with the offset of the first
+/// byte of the value that failed.
+#[derive(Debug, thiserror::Error)]
+pub enum FrameError<T: WirePrimitive> {
+    #[error("I/O error at byte {offset}: {source}")]
+    Io {
+        offset: u64,
+        #[source]
+        source: io::Error,
+    },
+    /// The stream ended partway through a value.
+    #[error("truncated value at byte {offset}: got {read} of {expected} bytes")]
+    Truncated {
+        offset: u64,
+        read: usize,
+        expected: usize,
+    },
+    /// The value is not allowed by the clamped type.
+    #[error("invalid value at byte {offset}: {source}")]
+    Invalid {
+        offset: u64,
+        #[source]
+        source: ClampError<T>,
+    },
+}
+
+impl<T: WirePrimitive> FrameError<T> {
+    /// The offset of the first byte of the value that failed.
+    #[inline(always)]
+    pub fn offset(&self) -> u64 {
+        match self {
+            Self::Io { offset, .. }
+            | Self::Truncated { offset, .. }
+            | Self::Invalid { offset, .. } => *offset,
+        }
+    }
+}
+
+/// Check a primitive against the limits of `T` and convert it.
+fn validate<T, P>(value: P, offset: u64) -> Result<T, FrameError<P>>
+where
+    T: ClampedInteger<P>,
+    P: WirePrimitive,
+{
+    try_clamp(value, T::MIN, T::MAX)
+        .map(|value| T::from_primitive(value).expect("value should be within bounds"))
+        .map_err(|source| FrameError::Invalid { offset, source })
+}
+
+/// Reads a sequence of clamped values, each framed as the big-endian bytes of its primitive, and
+/// validates every value as it is read. It is also an iterator over the values, which ends when
+/// the stream ends on a value boundary.
+pub struct ClampedReader<R, T> {
+    reader: R,
+    offset: u64,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T> ClampedReader<R, T>
+where
+    T: Deref + ClampedInteger<T::Target>,
+    T::Target: WirePrimitive,
+{
+    #[inline(always)]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            offset: 0,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of bytes read so far.
+    #[inline(always)]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    #[inline(always)]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the next value, or `None` if the stream ended before its first byte.
+    pub fn read_value(&mut self) -> Result<Option<T>, FrameError<T::Target>> {
+        let size = <T::Target as WirePrimitive>::SIZE;
+        let start = self.offset;
+        let mut buf = [0u8; MAX_SIZE];
+        let mut read = 0;
+
+        while read < size {
+            match self.reader.read(&mut buf[read..size]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => {
+                    return Err(FrameError::Truncated {
+                        offset: start,
+                        read,
+                        expected: size,
+                    })
+                }
+                Ok(n) => {
+                    read += n;
+                    self.offset += n as u64;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(source) => {
+                    return Err(FrameError::Io {
+                        offset: start,
+                        source,
+                    })
+                }
+            }
+        }
+
+        let value = <T::Target as WirePrimitive>::from_be_slice(&buf[..size]);
+
+        validate(value, start).map(Some)
+    }
+}
+
+impl<R: Read, T> Iterator for ClampedReader<R, T>
+where
+    T: Deref + ClampedInteger<T::Target>,
+    T::Target: WirePrimitive,
+{
+    type Item = Result<T, FrameError<T::Target>>;
+
+    /// Yield the next value. An invalid value does not end the iteration since the stream is
+    /// still aligned, but an I/O error or a truncated value does.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.read_value().transpose();
+
+        if matches!(
+            result,
+            None | Some(Err(FrameError::Io { .. } | FrameError::Truncated { .. }))
+        ) {
+            self.done = true;
+        }
+
+        result
+    }
+}
+
+/// Writes a sequence of clamped values, each framed as the big-endian bytes of its primitive, and
+/// validates every value before it is written.
+pub struct ClampedWriter<W, T> {
+    writer: W,
+    offset: u64,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<W: Write, T> ClampedWriter<W, T>
+where
+    T: Deref + ClampedInteger<T::Target>,
+    T::Target: WirePrimitive,
+{
+    #[inline(always)]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            offset: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of bytes written so far.
+    #[inline(always)]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    #[inline(always)]
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write a value, failing without writing anything if it is outside of the limits of `T`.
+    #[inline(always)]
+    pub fn write_value(&mut self, value: &T) -> Result<(), FrameError<T::Target>> {
+        self.write_primitive(value.into_primitive())
+    }
+
+    /// Write a primitive, failing without writing anything if `T` does not allow it.
+    pub fn write_primitive(&mut self, value: T::Target) -> Result<(), FrameError<T::Target>> {
+        let size = <T::Target as WirePrimitive>::SIZE;
+        let value = validate::<T, _>(value, self.offset)?.into_primitive();
+        let mut buf = [0u8; MAX_SIZE];
+
+        value.write_be_slice(&mut buf[..size]);
+
+        self.writer
+            .write_all(&buf[..size])
+            .map_err(|source| FrameError::Io {
+                offset: self.offset,
+                source,
+            })?;
+        self.offset += size as u64;
+
+        Ok(())
+    }
+
+    /// Write every value in order, stopping at the first one that fails.
+    pub fn write_all<'a>(
+        &mut self,
+        values: impl IntoIterator<Item = &'a T>,
+    ) -> Result<(), FrameError<T::Target>>
+    where
+        T: 'a,
+    {
+        values
+            .into_iter()
+            .try_for_each(|value| self.write_value(value))
+    }
+
+    #[inline(always)]
+    pub fn flush(&mut self) -> Result<(), FrameError<T::Target>> {
+        self.writer.flush().map_err(|source| FrameError::Io {
+            offset: self.offset,
+            source,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+/// A primitive integer that can be decoded from the bytes of a fuzz input.
+pub trait FuzzPrimitive: Copy {
+    /// Decode the value from little-endian bytes, padding missing bytes with zeroes.
+    fn from_fuzz_bytes(bytes: &[u8]) -> Self;
+
+    const SIZE: usize;
+}
+
+macro_rules! impl_fuzz_primitive {
+    ($($ty:ty),*) => {
+        $(
+            impl FuzzPrimitive for $ty {
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                #[inline(always)]
+                fn from_fuzz_bytes(bytes: &[u8]) -> Self {
+                    let mut buf = [0u8; std::mem::size_of::<$ty>()];
+                    let len = bytes.len().min(buf.len());
+
+                    buf[..len].copy_from_slice(&bytes[..len]);
+                    <$ty>::from_le_bytes(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_fuzz_primitive!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The bytes of a fuzz input, which are consumed as primitives in order. Once the bytes run out,
+/// no more primitives are decoded, so every input ends the target after a bounded number of steps.
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzInput<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> FuzzInput<'a> {
+    #[inline(always)]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Decode the next primitive, or `None` when the input is exhausted. The last primitive may be
+    /// decoded from fewer bytes than its size.
+    pub fn take<P: FuzzPrimitive>(&mut self) -> Option<P> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let (bytes, rest) = self.data.split_at(P::SIZE.min(self.data.len()));
+        self.data = rest;
+
+        Some(P::from_fuzz_bytes(bytes))
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// A generated type with a fuzz target, which is implemented for every type when the `fuzz`
+/// feature is enabled and the crate is built with `--cfg fuzzing`, as `cargo fuzz` does, or for
+/// its own tests. The target decodes primitives from the input, builds values with
+/// `from_primitive`, applies the operators and panics when an invariant of the type is broken.
+pub trait FuzzTarget {
+    fn fuzz(data: &[u8]);
+}
+
+/// Run the fuzz target of `T` on `data`, such as from the body of `fuzz_target!`.
+#[inline(always)]
+pub fn fuzz_type<T: FuzzTarget>(data: &[u8]) {
+    T::fuzz(data)
+}
+
+/// Run one of several fuzz targets on `data`, chosen by its first byte, so that a single
+/// `cargo fuzz` target covers every clamped type of a crate:
+///
+/// ```rust,ignore
+/// fuzz_target!(|data: &[u8]| {
+///     checked_rs::fuzz::fuzz_dispatch(data, &[fuzz_type::<Port>, fuzz_type::<Percent>]);
+/// });
+/// ```
+pub fn fuzz_dispatch(data: &[u8], targets: &[fn(&[u8])]) {
+    if let (Some((selector, data)), false) = (data.split_first(), targets.is_empty()) {
+        targets[*selector as usize % targets.len()](data);
+    }
+}
@@ -4,6 +4,54 @@ use std::{
     mem::{ManuallyDrop, MaybeUninit},
 };
 
+/// Decides what happens when a guard is dropped without calling `commit` or `discard` first, as
+/// chosen per type with the `guard_drop` parameter. Implement it to forward the event to a logging
+/// facade such as `log` or `tracing`.
+pub trait GuardDropPolicy: 'static {
+    /// Called with the name of the guarded type, such as `"Port"`.
+    fn on_unfinished_drop(type_name: &'static str);
+}
+
+/// Writes a line to standard error in debug builds. This is the default policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarnOnDrop {}
+
+impl GuardDropPolicy for WarnOnDrop {
+    fn on_unfinished_drop(type_name: &'static str) {
+        if cfg!(debug_assertions) {
+            eprintln!(
+                "A guard of `{}` was dropped without calling `commit` or `discard` first",
+                type_name
+            );
+        }
+    }
+}
+
+/// Does nothing, such as for code that drops guards on purpose when it returns early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SilentOnDrop {}
+
+impl GuardDropPolicy for SilentOnDrop {
+    #[inline(always)]
+    fn on_unfinished_drop(_: &'static str) {}
+}
+
+/// Panics in debug builds, unless the thread is already panicking, to find guards whose changes
+/// are lost by mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PanicOnDrop {}
+
+impl GuardDropPolicy for PanicOnDrop {
+    fn on_unfinished_drop(type_name: &'static str) {
+        if cfg!(debug_assertions) && !std::thread::panicking() {
+            panic!(
+                "A guard of `{}` was dropped without calling `commit` or `discard` first",
+                type_name
+            );
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Guard<'a, T: 'static, E, U: Validator<Item = T, Error = E>>(
     pub(crate) MaybeUninit<T>,
@@ -43,10 +91,7 @@ impl<'a, T, E, U: Validator<Item = T, Error = E>> AsMut<T> for Guard<'a, T, E, U
 
 impl<'a, T, E, U: Validator<Item = T, Error = E>> Drop for Guard<'a, T, E, U> {
     fn drop(&mut self) {
-        #[cfg(debug_assertions)]
-        {
-            eprintln!("A `Guard` was dropped without calling `commit` or `discard` first");
-        }
+        WarnOnDrop::on_unfinished_drop("Guard");
     }
 }
 
@@ -108,3 +153,59 @@ macro_rules! commit_or_bail {
         }
     };
 }
+
+/// Fail to compile unless the limits of a clamped type are the given range, and optionally unless
+/// the values of its `#[eq]` variants are the given ones in ascending order. This pins the contract
+/// of a clamped type from another crate, so that an upgrade that changes it is noticed.
+///
+/// ```rust
+/// use checked_rs::prelude::*;
+///
+/// #[clamped(u8 as Hard, default = 0, upper = 100)]
+/// #[derive(Debug, Clone, Copy)]
+/// struct Percent;
+///
+/// checked_rs::static_assert_range!(Percent, 0..=100);
+///
+/// fn main() {}
+/// ```
+#[macro_export]
+macro_rules! static_assert_range {
+    ($ty:ty, $min:literal ..= $max:literal) => {
+        const _: () = assert!(
+            <$ty as $crate::InherentLimits<_>>::MIN == $min
+                && <$ty as $crate::InherentLimits<_>>::MAX == $max,
+            concat!(
+                "the limits of `",
+                stringify!($ty),
+                "` are not ",
+                stringify!($min..=$max)
+            ),
+        );
+    };
+    ($ty:ty, $min:literal ..= $max:literal, exact = [$($exact:literal),+ $(,)?]) => {
+        $crate::static_assert_range!($ty, $min..=$max);
+
+        const _: () = {
+            let actual = <$ty>::EXACT_VALUES;
+            let expected = [$($exact),+];
+            let mut matches = actual.len() == expected.len();
+            let mut i = 0;
+
+            while matches && i < actual.len() {
+                matches = actual[i] == expected[i];
+                i += 1;
+            }
+
+            assert!(
+                matches,
+                concat!(
+                    "the exact values of `",
+                    stringify!($ty),
+                    "` are not ",
+                    stringify!([$($exact),+])
+                ),
+            );
+        };
+    };
+}
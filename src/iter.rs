@@ -0,0 +1,98 @@
+use crate::clamp::{clamp_saturating, try_clamp, ClampError, ClampedInteger};
+
+/// Adapters that convert a stream of primitives into clamped values.
+pub trait IteratorExt: Iterator + Sized
+where
+    Self::Item: Copy + Ord,
+{
+    /// Convert each item into `T`, yielding an error for every item outside of `T`'s limits.
+    #[allow(clippy::type_complexity)]
+    fn validate_clamped<T: ClampedInteger<Self::Item>>(
+        self,
+    ) -> std::iter::Map<Self, fn(Self::Item) -> Result<T, ClampError<Self::Item>>> {
+        self.map(|item| {
+            let item = try_clamp(item, T::MIN, T::MAX)?;
+            Ok(T::from_primitive(item).expect("value should be within bounds"))
+        })
+    }
+
+    /// Convert each item into `T`, saturating items outside of `T`'s limits to the nearest limit.
+    fn clamp_saturating<T: ClampedInteger<Self::Item>>(
+        self,
+    ) -> std::iter::Map<Self, fn(Self::Item) -> T> {
+        self.map(|item| {
+            T::from_primitive(clamp_saturating(item, T::MIN, T::MAX))
+                .expect("value should be within bounds")
+        })
+    }
+}
+
+impl<I: Iterator> IteratorExt for I where I::Item: Copy + Ord {}
+
+/// A fieldless enum whose variants can be counted, such as the `<Name>Kind` enum generated for
+/// every clamped enum.
+pub trait VariantKind: Copy + 'static {
+    /// Every variant, in declaration order.
+    const ALL: &'static [Self];
+
+    /// The position of the variant in `ALL`.
+    fn index(self) -> usize;
+}
+
+/// The number of primitives in a stream that belong to each variant of a clamped enum, along with
+/// those that belong to none, as returned by the generated `partition`. `N` is the number of
+/// variants, and the counts live inline so that counting never allocates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PartitionedCounts<K, const N: usize> {
+    counts: [u64; N],
+    invalid: u64,
+    marker: std::marker::PhantomData<K>,
+}
+
+impl<K: VariantKind, const N: usize> Default for PartitionedCounts<K, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: VariantKind, const N: usize> PartitionedCounts<K, N> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            counts: [0; N],
+            invalid: 0,
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Count one primitive of the variant `kind`, or one that belongs to no variant for `None`.
+    #[inline(always)]
+    pub fn record(&mut self, kind: Option<K>) {
+        match kind {
+            Some(kind) => self.counts[kind.index()] += 1,
+            None => self.invalid += 1,
+        }
+    }
+
+    #[inline(always)]
+    pub fn get(&self, kind: K) -> u64 {
+        self.counts[kind.index()]
+    }
+
+    /// The number of primitives that belong to no variant.
+    #[inline(always)]
+    pub fn invalid(&self) -> u64 {
+        self.invalid
+    }
+
+    /// The number of primitives counted, including those that belong to no variant.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum::<u64>() + self.invalid
+    }
+
+    /// Iterate over every variant and its count, in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (K, u64)> + '_ {
+        K::ALL.iter().copied().zip(self.counts.iter().copied())
+    }
+}
@@ -18,16 +18,61 @@
 //! - `int`: The integer type to use for the clamped value.
 //!
 //! The macro accepts the following arguments _(in any order)_:
-//! - `behavior`: The behavior to use when the value overflows the limits. The default behavior is `Panicking`.
+//! - `behavior`: The behavior to use when the value overflows the limits. It can be `Panicking`, `Saturating` or `SaturatingWithLog`, which saturates and also reports each saturated result on standard error. The default behavior is `Panicking`, or `SaturatingWithLog` with the `never-panic` feature.
+//!   - Operators can be given their own behavior with a table such as `behavior = { add: Saturating, mul: Panicking, default: Panicking }`. The operators are `add`, `sub`, `mul`, `div`, `rem`, `bitand`, `bitor` and `bitxor`, and any operator without an entry uses `default`, which is `Panicking` when omitted.
+//! - `div_by_zero`: What dividing by zero with `/` or `%` results in, since it panics regardless of the behavior otherwise. It can be `panic` _(the default)_, `saturate_max` to saturate to the upper limit, `return_default` to return the default value, or `checked_only`, which replaces the `Div` and `Rem` implementations with `checked_div(int)` and `checked_rem(int)` returning `None` for a zero divisor. The policy is resolved by the behavior through `Behavior::div_by_zero`, which wrapping behaviors forward to the behavior they wrap.
 //! - `default`: The default value to use when the value is not provided. The default default value is zero _(if possible)_ or the minimum value.
 //! - `lower`: The lower limit of the clamped value. The default lower limit is the minimum value of `int`.
 //! - `upper`: The upper limit of the clamped value. The default upper limit is the maximum value of `int`.
+//! - `store`: _(enums only)_ A narrower unsigned integer type used to store the value. The public API still uses `int` and values are widened when read. The limits must fit within the `store` type. Since there is no `int` to reference, the enum has no `as_primitive` and does not implement `AsPrimitive`, so generic code reads the value with `ClampedInteger::into_primitive`.
+//! - `maps_to`: _(enums only)_ An external type that the enum converts to and from. Every variant must then have a `#[maps_to(...)]` attribute naming the external value it corresponds to. This generates `From<Enum> for External` and `TryFrom<External> for Enum`, where the latter uses the lowest value of the first matching variant. The catchall has no value of its own, so its attribute also names the value it converts back to, such as `#[maps_to(Status::Unknown, 302)]`, which must not belong to another variant.
+//! - `secret`: A flag (no value) that marks the type as sensitive. A derived `Debug` prints `Name(<redacted>)` instead of the value and deriving `Serialize` is rejected. With the `zeroize` feature enabled, `zeroize::Zeroize` is implemented by overwriting the value with the default.
+//! - `literal`: _(structs only)_ The name of a companion macro, such as `literal = port`, that turns an integer literal into a value checked at compile time. `port!(8080)` expands to `const { $crate::Port::new_const(8080) }`, so an out of bounds literal fails to compile. The macro is exported from the crate and re-exported next to the type with the visibility of the type, so it can be used from other modules and crates, such as `net::port!(8080)`. Since it refers to the type by its path from the crate root, a type outside of the crate root gives that path along with the name, such as `literal = crate::net::port`.
+//! - `separator`: A string, such as `"_"` or `"\u{2009}"`, placed between every group of three digits by `separated()`. Defaults to `","`.
+//! - `benchmark`: A flag (no value) that generates criterion benchmarks for `new` _(structs only)_, `from_primitive`, `add` and `sub`, each next to the same operation on `int` as a baseline. They are compiled only when the `bench` feature of `checked-rs` is enabled, such as by a feature of the defining crate that enables `checked-rs/bench`, so the defining crate does not need a `bench` feature of its own. They are run with `criterion_group!(benches, <type>_bench::benches)`. The type must derive `Copy`.
+//! - `metrics`: A flag (no value) that counts how often operators saturated or panicked because their result was outside of the limits, and how often `from_primitive` rejected a value, when the `metrics` feature is enabled. The counters are read with `<type>::metrics()`, which returns a `&'static ClampMetrics`, and `registered_metrics()` lists a snapshot for every type that has recorded anything. Without the feature the flag does nothing.
+//! - `auto_invalid`: _(enums only)_ A flag (no value) that appends an `Invalid(int)` variant for forward-compatible decoding. `from_primitive` still rejects values that are not allowed, while `from_primitive_or_invalid(int) -> Self` captures them in `Invalid`. The enum gains `is_invalid()` and `into_valid() -> Result<Self>`, and implements `serde::Serialize` and `serde::Deserialize` as the primitive so unknown values survive a round trip. It cannot be combined with `maps_to` or a derived `Serialize`/`Deserialize`.
+//! - `null`: A value within the limits that marks the type as unset, such as `null = 0`, for wire protocols that use a sentinel for "not present". The type gains a `NULL` constant, `unset()`, `is_unset()`, `get_set() -> Option<Self>` and `set_or_default()`, and implements `serde::Serialize` and `serde::Deserialize` with the unset value as `null`. It cannot be combined with `auto_invalid` or a derived `Serialize`/`Deserialize`.
+//! - `codegen`: _(enums only)_ Either `match` _(the default)_ or `table`. With `match`, `from_primitive` expands one match arm per exact value and range. With `table`, the values are stored in a static sorted table and looked up with a binary search, so the generated code grows with the number of variants rather than the number of values. This keeps compile times down for enums with hundreds of exact values, at the cost of a binary search instead of a jump table at runtime. Only `from_primitive` changes, since `as_primitive`, `into_primitive` and the `new_<variant>` factory methods already expand to one match arm or method per variant, however many values it declares.
+//! - `lookup`: _(enums only)_ Either `ranges` _(the default)_ or `bitset`. With `bitset`, the values of the `#[eq]` variants are kept in a static bitset with one bit per value within the limits, so `contains_exact(int) -> bool` is a single lookup and `from_primitive` only checks the `#[eq]` variants for values that one of them declares. This speeds up tight parsers of sparse exact values. The limits may span at most 65 536 values, which always holds for `u8` and `u16` types, and it cannot be combined with `codegen = table`.
+//! - `module`: The name of the module that holds the generated items, such as `module = port`. It defaults to `clamped_` followed by the name of the type in snake case, and has the same visibility as the type. Besides the type itself, the module has names that are the same for every type so other crates can mention them in signatures: `Guard` for the guard returned by `modify()`, `Delta` for the delta type of hard structs, and for enums `Kind` for the kind enum and `Value` for the wrapper held by variants that are not `#[range]` variants. Each `#[range]` variant holds a `<Variant>Value` struct from the same module.
+//! - `guard_name`: The name of the guard returned by `modify()`, such as `guard_name = PortLock`, for crates that already have an item with the default name. It defaults to the name of the type followed by `Guard`. The guard returned by `modify_auto()` is named `Auto` followed by it, and defaults to the name of the type followed by `AutoGuard`. The module still names the guard `Guard`.
+//! - `value_name`: _(enums only)_ The name of the wrapper held by the variants that are not `#[range]` variants, such as `value_name = LaneRaw`. It defaults to the name of the type followed by `Value`, and the module still names it `Value`. The naming scheme is otherwise fixed: the kind enum is the name of the type followed by `Kind`, the delta type of hard structs is the name of the type followed by `Delta`, and the struct held by a `#[range]` variant is the name of the variant followed by `Value`, which must not be the name of the wrapper.
+//! - `singleton`: _(unit structs only)_ The only value of the type, such as `singleton = 42`, which makes it a zero-sized type with a `VALUE` constant. It stands for `lower`, `upper` and `default`, and the struct is declared without `as Soft` or `as Hard`. See [Singletons](#singletons).
+//! - `guard_drop`: What happens when a guard is dropped without calling `commit` or `discard` first, such as `guard_drop = SilentOnDrop`. The default, `WarnOnDrop`, writes a line to standard error in debug builds, while `PanicOnDrop` panics in debug builds. Any type that implements `GuardDropPolicy` can be given, such as one that forwards to `log` or `tracing`. A crate-wide default can be set with a `guard_drop = ..;` entry in `configure!`.
+//! - `explicit_imports`: Import only the names of `checked_rs` that the generated code uses, instead of everything in the enclosing module as well. The generated module then does not depend on what is in scope where the type is declared, and leaves no unused imports for `#![deny(unused_imports)]` to reject. This suits library crates that re-export their clamped types. Paths given in the attribute and on the type, such as a custom behavior or a `maps_to` target, are resolved inside the generated module, so items of the enclosing module are named with `super::` or `crate::` paths.
+//! - `skip_impls`: A list of generated trait implementations to leave out, such as `skip_impls = [FromStr, PartialOrdPrimitive]`, so they do not conflict with ones written by hand. Each is named after its trait, with a `Primitive` suffix when `int` or another primitive integer is one side of it, so `PartialOrd` is the comparison with the type itself and `PartialOrdPrimitive` the one with `int`. An unknown name is an error that lists the names available for the type. Unsafe traits, `InherentLimits` and `InherentBehavior` cannot be skipped.
+//! - `doc_examples`: _(structs only)_ A flag (no value) that gives the type, `new` and `modify` an `# Examples` doc section built from the actual limits, default and behavior. Each example declares an equivalent type in hidden lines, so it runs as a doctest of the crate that defines the type, as it does for `examples::Port` and `examples::Percent`.
+//! - `cyclic`: _(hard structs only)_ A flag (no value) for wrap-around domains such as angles and sequence numbers, where `Ord` is misleading because the upper limit is followed by the lower limit. The type gains `cyclic_distance(&other) -> int`, the number of steps forward to `other`, `cyclic_lt(&other)`, which follows RFC 1982 serial number arithmetic so that `other` is ahead when it is less than half of the range away, and `is_between(&start, &end)`. Arithmetic still follows the type's behavior. The same comparisons are available for plain integers through `cyclic_distance`, `cyclic_lt` and `cyclic_is_between`.
+//! - `serial`: _(hard `u16` and `u32` structs only)_ A flag (no value) for sequence counters such as DNS serials, TCP-like sequence numbers and game ticks, which follow RFC 1982 serial number arithmetic within the limits. It implies `cyclic`, and adds `is_newer_than(&other)`, `wrapping_add(int) -> Self` and `wrapping_increment()`, which wrap from the upper limit to the lower limit regardless of the behavior. The value is serialized as the plain primitive like any other type.
+//! - `serde_names`: _(enums only)_ A flag (no value) that implements `serde::Serialize` and `serde::Deserialize` by variant name. Variants holding a single `#[eq]` value are serialized as their name and every other value as the primitive, while deserializing accepts either from a self-describing format such as JSON. A variant's name can be changed with `#[serde(rename = "ok")]` and extra names accepted with `#[serde(alias = "success")]`, and `#[serde(rename_all = "snake_case")]` on the enum applies to every variant that is not renamed. It cannot be combined with `auto_invalid`, `null`, `secret` or a derived `Serialize`/`Deserialize`.
+//! - `coverage`: _(enums only)_ Given `coverage = exhaustive`, every value within the limits must be claimed by an `#[eq]`, `#[range]` or `#[mask]` variant. An `#[other]` variant is rejected, and any values left unclaimed are listed in the compile error.
 //!
 //! The transformed type will have the following inherent implementations:
 //! - `new(value: int) -> Self`: A constructor that creates a new clamped value from the provided value.
 //! - `rand() -> Self`: A method that generates a random value within the clamped range.
+//! - `arbitrary_invalid<R: Rng>(rng: &mut R) -> Option<int>`: A method for negative tests and fuzzers that picks a random primitive outside of the limits, which `validate` rejects, or `None` when the limits cover every value of the primitive. With a seeded `rng` the values are the same on every run. Values inside the limits that only an enum's `#[other]` variant accepts are listed by `gaps()` instead.
 //! - `validate(value: int) -> Result<int, Error>`: A method that validates the provided value and returns the value if it is within the clamped range.
+//! - `separated(&self) -> DisplaySeparated<int>`: A method that displays the value with the `separator` between every group of three digits. The same logic is available for plain integers through `separated_string`.
+//! - `to_canonical_string(&self) -> String` and `from_canonical_str(&str) -> Result<Self, ParseClampedError>`: Methods that write and read the value as a plain base 10 integer with no separators, independent of platform and locale. Every valid value round-trips, and `from_canonical_str` rejects any input that is not in canonical form, such as `+5`, `05` or `-0`.
+//! - `to_compact_string(&self, CompactEncoding) -> String` and `from_compact_str(&str, CompactEncoding) -> Result<Self, ParseClampedError>`: Generated for `u128` types only. Methods that write and read the value in base 62 or lowercase base 16, which are shorter than base 10 for 128-bit ids in URLs and logs. Leading zeros and characters outside of the alphabet are rejected, so every value has exactly one encoding. The same encodings are available for any `u128` as `encode_compact` and `decode_compact`.
+//! - `RANGE_FINGERPRINT: u64`: A constant hash of the values the type allows and the behavior of each operator, which ignores the default and the names of enum variants. Storing it alongside persisted values lets a loader detect that they were written under different bounds, such as by an older version of the crate, and migrate them.
+//! - `RANGE_SUMMARY: &'static str`: A constant summary of the limits, such as `1024..=65535`. Every generated panic and error message ends with it and the name of the type, such as "Addition overflow for `Port` (valid: 1024..=65535)", so runtime failures can be understood without the source of the type. Operators get this from the `Described<B, T>` behavior, which wraps the behavior of the type and reads the `RangeSummary` trait that every type implements. The errors of `from_primitive` keep their original error as the source, so the `ClampError` of a struct can still be downcast.
+//! - `AnyClamped`: An object-safe trait that every generated type implements, so values of different clamped types can be kept together as `Box<dyn AnyClamped>`, such as the fields of a settings panel. It gives the `type_name()` and `range_summary()` of the value, reads it with `as_i128()` or `as_u128()`, and `set_i128()` replaces it after checking it against the limits of the type, leaving it unchanged on failure.
+//! - `DEFAULT: Self` and `default_array<const N: usize>() -> [Self; N]`: The default value as a constant, and an array filled with it, for fixed-size buffers such as telemetry ring buffers. Neither requires `Copy` and both are usable in `const` contexts. For an enum whose default is not declared by an `#[eq]` or `#[range]` variant and that includes the values of a const with `include_values`, the variant is only known at runtime, so `DEFAULT` is not generated and `default_array` is not `const`.
+//! - `freeze(self) -> Frozen<Self>`: A method that makes the value read-only, for configuration that must not change after startup. `Frozen` dereferences to the value and can be compared, hashed and serialized as it, but has no operators or guards.
+//! - `into_raw(self) -> Unvalidated<T>`: The escape hatch for taking the primitive out of the validated domain. `Unvalidated` is `#[must_use]` and has no `Deref` or operators, so the primitive is only reachable through `into_inner`, `map` or `validate::<U>()`, which checks it against the limits of `U` again. Prefer it over dereferencing when a value is taken out to be changed, so that review can find every place that leaves the validated domain.
+//! - `fraction(&self) -> f64`, `to_percent_of_range(&self) -> f64` and `from_fraction(f64) -> Self`: _(structs only)_ Methods that place the value within the limits, for gauges and progress bars. `scale_to::<U, _>(self) -> U` maps the value proportionally onto the limits of another clamped type using integer arithmetic only.
+//! - `cast::<U, _>(self) -> Result<U, ClampError<u128>>`: Converts the value into another clamped type, which may store a different primitive, and validates it against the limits of `U`. The value and the limits in the error are widened to `u128`, since the value may not fit in the primitive of `U`.
+//! - `scale(self, num: u32, den: u32, rounding: Rounding) -> Self`: _(structs only)_ A method that multiplies the value by `num / den` for percent-of and fee computations. The product is widened so it never overflows the primitive, the quotient is rounded with `Rounding::Floor`, `Ceil` or `HalfEven`, and a result outside of the limits saturates or panics like `*` does. The same arithmetic is available for plain integers through `scale_ratio`.
+//! - `percent_of(self, pct: impl Into<u32>) -> Self` and `as_percent_of(&self, whole: Self) -> u8`: _(structs only)_ Methods for progress and quota computations. `percent_of` is `scale(pct, 100, Rounding::Floor)`, so it resolves results outside of the limits like `*` does. `as_percent_of` gives the value as a percentage of `whole`, rounding down, with values above `whole` giving `100` and a `whole` of zero giving `0`.
+//! - `diff(self, other: Self) -> <Name>Delta`: _(hard structs only)_ A method that gives the signed difference between two values, so it is not forced back into the limits of the type. The delta type is defined next to the type, holds the narrowest signed primitive that fits both `MAX - MIN` and `MIN - MAX`, and is left out when that would not fit an `i128`. Adding or subtracting a delta with `+` and `-` resolves a result outside of the limits like the operators do, while `checked_add_delta` and `checked_sub_delta` fail with a `ClampError` instead.
+//! - `checked_<op>(self, rhs: T) -> Option<Self>`: A method per operator (`checked_add`, `checked_sub`, `checked_mul`, `checked_div`, `checked_rem`, `checked_bitand`, `checked_bitor` and `checked_bitxor`) that returns `None` instead of saturating, wrapping or panicking when the result is outside of the limits, regardless of the behavior of the operator. For soft types, which hold any primitive, only an overflow of the primitive is `None`. These methods shadow the ones of the primitive reached through `Deref`. With `div_by_zero = checked_only`, `checked_div` and `checked_rem` keep that mode's meaning. The `checked_within` helper applies the same check to a checked primitive result.
+//! - `offset(&self) -> int` and `from_offset(int) -> Result<Self>`: _(hard structs only)_ Methods that convert between the value and its zero-based distance above the lower limit, so IDs whose range starts at `1000` can index a dense array. `SPAN: int` is the number of values within the limits, which is left out when it does not fit in `int`.
 //! - `modify<'a>(&'a mut self) -> Guard<'a>`: A method that returns a guard that can be used to stage _(potentially out-of-bounds)_ changes to the clamped value and either commit or discard the changes.
+//! - `modify_auto(&mut self) -> AutoGuard<'_>`: A method that returns a guard that commits the staged value when it is dropped, for quick imperative code where the explicit `commit` is overkill. An invalid staged value is resolved with the type's behavior, so it saturates to the nearest limit or panics. `discard()` drops the staged value instead.
+//!   - The guard exposes `will_be_valid()` to check the staged value. For enums it also exposes `staged()` and a `staged_is_*` predicate per variant describing where the staged value would land.
+//!   - `preview_add`, `preview_sub` and `preview_mul` return what the operation on the staged value would yield once the type's behavior is applied, as a `Result<int, ClampError<int>>`, without changing it. `apply_add`, `apply_sub` and `apply_mul` stage that result when it is valid.
 //!
 //! The transformed type will have the following custom traits implemented:
 //! - `InherentLimits<int>`: A trait that defines the minimum and maximum values of the clamped range.
@@ -37,6 +82,8 @@
 //! The transformed type will have the following standard traits implemented:
 //! - `Default`, `Deref`, `AsRef`, `FromStr`, `PartialEq`, `PartialOrd`, `Eq`, `Ord`, `Add`, `AddAssign`, `Sub`, `SubAssign`, `Mul`, `MulAssign`, `Div`, `DivAssign`, `Rem`, `RemAssign`, `Neg`, `Not`, `BitAnd`, `BitAndAssign`, `BitOr`, `BitOrAssign`, `BitXor`, `BitXorAssign`.
 //! - `From` implementations are provided to support conversions for the same machine integer types as `int`.
+//! - `TryFrom<char>`, and `From<Self> for char` or `TryFrom<Self> for char`: _(`u32` only)_ Conversions for code-point classes whose upper limit is at most `char::MAX`, such as an `Ascii` type with `upper = 127`. Converting into `char` is infallible when every value the type can hold is a Unicode scalar value. It is fallible when the limits span the surrogates `0xD800..=0xDFFF`, or when the type can hold values outside of its limits, as soft structs and enums with `auto_invalid` can.
+//! - `FromStr` and `TryFrom<&str>` fail with a `ParseClampedError`, which carries the type name, the input, the reason it was rejected and a summary of the allowed values, such as `a value in 1..=9999`.
 //!
 //! > **NOTE**: The `std::cmp` and `std::ops` traits support `rhs` values of the clamped type or `int`.
 //!
@@ -66,7 +113,7 @@
 //!
 //! Hard clamps are clamped types that **_DO_** enforce the limits on the value. The value is clamped when it is created and any operations that would cause the value to overflow the limits will be handled according to the specified behavior.
 //!
-//! > **UNSAFE NOTE**: The `set_unchecked` and `as_mut` methods are available but marked unsafe because they can be used to assign an out-of-bounds value.
+//! > **UNSAFE NOTE**: `new_unchecked`, `set_unchecked` and `get_mut` are the only ways to put a value into a hard clamp without checking it, and they are marked unsafe because they can be used to assign an out-of-bounds value. The field of a hard clamp and of the value wrappers held by enum variants is private to the generated module, and a derived `serde::Deserialize` checks the value against the limits, and for enums against the variant it was deserialized as.
 //!
 //! ```ignore
 //! use checked_rs::prelude::*;
@@ -76,6 +123,19 @@
 //! struct Available;
 //! ```
 //!
+//! #### Singletons
+//!
+//! A unit struct given `singleton = N` instead of the limits is the type of the single value `N`, such as a protocol magic number or a version constant that should not be mixed up with other integers. It is zero-sized since the value is implied by the type, which provides it as the `VALUE` constant, through `Deref`, `get()` and `From<Answer> for u8`. `TryFrom<u8>` only checks that the value is `N`. `as Soft` and `as Hard` do not apply to singletons.
+//!
+//! ```rust
+//! use checked_rs::prelude::*;
+//!
+//! #[clamped(u8, singleton = 42)]
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//! struct Answer;
+//! # fn main() {}
+//! ```
+//!
 //! ### Enum Usage
 //!
 //! Each variant of the enum will either represent a specific value within the overall clamped range, a hard clamped sub-range or a special variant that represents any value that is not explicitly handled. The variants will have corresponding methods that can be used to create a new instances of that variant or check if the contained value is that variant.
@@ -83,6 +143,36 @@
 //! > **NOTE**: The enum must account for all possible values within the clamped range. This can be done by using the `#[eq]` and `#[range]` attributes on the variants.
 //! > The `#[other]` attribute can be used to account for any values that are not explicitly handled.
 //!
+//! The bounds of `#[eq]` and `#[range]` accept integer literals, constants such as `u16::MAX`, `const` blocks containing simple integer arithmetic (e.g. `const { 2 * 100 }`) and the bare `MIN`/`MAX` keywords, which refer to the `lower` and `upper` limits of the type. Ranges may be inclusive (`a..=b`), half-open (`a..b`) or omit a bound (`..b`, `a..`), in which case the missing bound is the corresponding limit.
+//!
+//! The `#[range]` of an enum variant can end with `step N`, such as `#[range(0..=100 step 2)]`, to only include every `N`th value counting from its start. Its values are then claimed like the values of `#[eq]`, so the variant holds the `Value` wrapper rather than a `<Variant>Value` struct, lookups reject the values between the steps unless another variant claims them, and `EXACT_VALUES` and `ranges_of()` list every value it includes. Since every value becomes its own match arm, a stepped range may include at most 256 values, like a span of `#[eq]`. Steps are only supported on enum variants, since a struct is limited by `lower` and `upper` alone.
+//!
+//! The values of `#[eq]` can include small inclusive spans, such as `#[eq(408, 425, 429, 500..=504)]`, which expand to one exact value each, so a variant can mix single values and neighboring ones while its values are still matched as literals. A span may expand to at most 256 values; use `#[range]` for wider spans.
+//!
+//! For status-register style values on unsigned types, `#[mask(0b0000_0001)]` matches every value that has all bits of the mask set and keeps the full value, which `as_primitive` returns. Values are resolved by `#[eq]` first, then `#[range]`, then the `#[mask]` variants in declaration order, so an earlier mask takes priority over a later one, and finally `#[other]`.
+//!
+//! Long lists of exact values, such as currency or country codes, can be kept out of the macro body with `#[eq(include_values = "codes.txt")]`, which reads the values from a file relative to the crate root when the macro expands. Values in the file are separated by whitespace or commas and everything after a `#` on a line is a comment. They are treated exactly like values listed in `#[eq]`. `#[eq(include_values = path::CODES)]` instead matches the values of a const array or slice, such as one generated by a build script. Since the macro cannot read those values, they are checked against the limits at compile time and searched at runtime after the `#[eq]` and `#[range]` variants and before the `#[mask]` variants. An enum with such a variant needs an `#[other]` variant.
+//!
+//! A variant holding a single `#[eq]` value can also claim legacy values that are no longer in use with `#[alias(410)]`. `from_primitive` is unchanged, while `from_primitive_or_alias(int)` maps each alias onto the variant's current value before falling back to `from_primitive`, and the `serde_names` deserializer does the same. An alias cannot be a value that an `#[eq]` or `#[range]` variant declares, but may be one that `#[other]` accepts, in which case the alias wins.
+//!
+//! A variant holding a single `#[eq]` value can also be given other names with `#[aka(Success200, Fine)]`, such as names that a protocol used for the value in earlier versions. Each name gets a constant, such as `Reply::SUCCESS_200`, and an `is_*` predicate, such as `is_success_200()`, that both stand for the value of the variant. Values are still created and matched through the variant itself, so the names add no variants and do not affect the coverage check. A name cannot be used by another variant or name.
+//!
+//! Enums also provide `gaps()`, which iterates in ascending order over the `RangeInclusive<int>` spans within the limits that no `#[eq]`, `#[range]` or `#[mask]` variant declares. These are the values that only the `#[other]` variant accepts, so their boundaries make good probes for negative tests. `gaps()` is not generated when `#[mask]` variants would have to be checked against more than 65 536 undeclared values, or when a variant includes the values of a const with `include_values`.
+//!
+//! Each enum also gets a fieldless `<Name>Kind` enum with one variant per variant, such as `ResponseCodeKind::NotFound`, which is re-exported next to the enum and returned by `kind()`. `ranges_of(kind) -> &'static [RangeInclusive<int>]` lists the values each variant accepts in ascending order, with adjacent values merged, so code can answer which raw values a variant accepts at runtime, such as to generate validation rules for a frontend. The `#[other]` variant accepts the spans that `gaps()` lists, and `ranges_of` is left out in the same cases as `gaps()`.
+//!
+//! Indexes keyed by primitives, such as a `BTreeMap<u16, Stats>` of status codes, can be queried by the structure of the type. `btree_range_of_variant(&map, ResponseCodeKind::ServerError)` iterates over the entries whose keys a variant accepts, with one range query per span from `ranges_of`, through the `KindRanges` trait that the kind enum implements whenever `ranges_of` is generated. `btree_range_of::<T, _, _>(&map)` does the same for the limits of any clamped type, such as the `<Variant>Value` struct of a `#[range]` variant, and `limit_bounds::<T, int>()` returns those limits as a `(Bound, Bound)` pair for other range APIs.
+//!
+//! Variants can be stored by a code that survives reordering them. `kind.code()` is the value given by a `#[code(N)]` attribute on the variant, or else the lowest value the variant accepts, and `Kind::from_code(code)` reverses it. `kind_code()` returns the code of a value, and `from_kind_code(code, value)` rebuilds a value that was stored as the pair, failing when the code is unknown or the value does not belong to that variant. Codes must be unique, and they are left out when the lowest value of a variant without `#[code]` is not known at compile time, such as with `include_values`.
+//!
+//! `partition(iter) -> PartitionedCounts<<Name>Kind, N>` counts how many primitives of a stream belong to each variant and how many to none, such as the status codes of a log. The counts are kept inline, read with `get(kind)`, `invalid()`, `total()` and `iter()`, and the kind enum implements `VariantKind` to index them. `partition_with(iter, |kind, n| ..)` calls a closure with each primitive and its kind instead, without allocating.
+//!
+//! For functional-style code, `map_value(f)` calls `f` with the primitive value whichever variant holds it, and each variant gets an `if_*` combinator, such as `if_client_error(f) -> Option<R>`, which calls `f` with the primitive only when the value is that variant.
+//!
+//! Each enum also gets `leaf_variants()`, the names of its variants in declaration order, and `path()`, which returns a `VariantPath` that displays as `ResponseCode/NotFound` for use as a metrics or log label. `variant_paths()` iterates over the path of every variant. Clamped enums are flat, so their paths have two segments.
+//!
+//! Every generated type provides `explain(int) -> Explanation<int>`, which reports how a primitive maps onto the type for error messages and debugging data feeds. For enums it names the variant the value maps to, along with its bounds for `#[range]` variants, and `variant_path()` returns a path such as `"ResponseCode::NotFound"`. For structs it reports that the value is within the limits. Values outside of the limits are explained with the nearest valid value. `Explanation` implements `Display` with a sentence such as "503 is `ResponseCode::ServerError` (500..=599)".
+//!
 //! ```ignore
 //! use checked_rs::prelude::*;
 //!
@@ -109,6 +199,205 @@
 //!
 //! ```
 //!
+//! Each `#[eq]` variant gets a `const fn new_<variant>() -> Self`, so `ResponseCode::new_not_found()` can initialize a `const`. Each `#[range]` variant gets a `const fn try_new_<variant>(int) -> Result<Self, ClampError<int>>` that rejects values outside of its range. With the `anyhow-compat` feature, range variants also get `new_<variant>(int) -> anyhow::Result<Self>`.
+//!
+//! Enums that model protocol states can restrict which variants may follow each other with a `#[transitions(...)]` attribute. Each entry is `From -> To` or `From -> *` (any variant), separated by `,` or `;`. This generates `can_transition_to(&self, next: &Self) -> bool` and `transition(self, next: Self) -> Result<Self, TransitionError>`, where the error names the two variants.
+//!
+//! ```rust
+//! use checked_rs::prelude::*;
+//!
+//! #[clamped(u8, default = 0, upper = 2)]
+//! #[transitions(Idle -> Running; Running -> *)]
+//! #[derive(Debug, Clone, Copy)]
+//! enum Task {
+//!     #[eq(0)]
+//!     Idle,
+//!     #[eq(1)]
+//!     Running,
+//!     #[eq(2)]
+//!     Done,
+//! }
+//!
+//! fn main() {
+//!     let task = Task::new_idle();
+//!     assert!(task.transition(Task::new_done()).is_err());
+//!     assert!(task.transition(Task::new_running()).is_ok());
+//! }
+//! ```
+//!
+//! ### Crate-level Defaults
+//!
+//! A crate with many clamped types can declare shared defaults once in a file that holds a `checked_rs::configure!` invocation, named by the `CHECKED_RS_CONFIG` environment variable. A relative path is resolved against the directory of the crate's manifest. The invocation accepts `behavior = ...;`, `div_by_zero = ...;`, `guard_drop = ...;` and `derive(...);` entries. Every `clamped` type compiled with the variable set, in any target of the crate, uses the `behavior`, `div_by_zero` and `guard_drop` defaults unless it gives its own, and gets each listed derive it does not already have. Since the expansion order of macros is unspecified, `clamped` reads the defaults from the file on every expansion instead of from an invocation in the code, which only checks that its entries are valid. The file is included in the build with `include_bytes!`, so editing it rebuilds the types, and invalid entries in it are reported by `clamped`.
+//!
+//! ```toml
+//! # .cargo/config.toml
+//! [env]
+//! CHECKED_RS_CONFIG = { value = "checked-rs.rs", relative = true }
+//! ```
+//!
+//! ```ignore
+//! // checked-rs.rs
+//! checked_rs::configure! {
+//!     behavior = Saturating;
+//!     derive(Debug, Clone, Copy);
+//! }
+//! ```
+//!
+//! Third-party derives work on clamped types along with their helper attributes. Attributes on the enum or struct and on each variant are kept as written, and a struct may declare its single field to give it attributes, such as `struct Rating(#[serde(serialize_with = "as_string")] u8);`.
+//!
+//! ### Free Functions
+//!
+//! For one-off checks that don't warrant a dedicated type, `clamp_saturating`, `clamp_panicking` and `try_clamp` apply the same semantics to plain primitives.
+//!
+//! ```rust
+//! use checked_rs::prelude::*;
+//!
+//! assert_eq!(clamp_saturating(150u8, 10, 100), 100);
+//! assert!(try_clamp(5u8, 10, 100).is_err());
+//! ```
+//!
+//! `static_assert_range!(Type, min..=max)` fails to compile unless the limits of a clamped type are exactly the given range. For enums, `static_assert_range!(Type, min..=max, exact = [..])` also checks the values of the `#[eq]` variants in ascending order, which enums expose as `EXACT_VALUES` unless a variant includes a const slice. This pins the contract of a clamped type from another crate, so that an upgrade that changes it fails the build instead of going unnoticed.
+//!
+//! `clamped_const!(Type: expr)` evaluates arithmetic over the constants of a clamped struct at compile time, such as `const TOTAL: Percent = clamped_const!(Percent: BASE + BONUS * 2);`. Each `+`, `-`, `*`, `/` and `%` becomes a call to the `const` operation of the type, such as `const_add`, and integer literals become `new_const`, so the build fails when any intermediate result leaves the limits, and not only the final one. This makes it possible to precompute tables of clamped values with compile-time guarantees.
+//!
+//! `assert_clamped_eq!(left, right)` asserts that two clamped values are equal like `assert_eq!`, but prints them through the `DiffDisplay` trait on failure, which every generated type implements, so each value is shown with its variant or range, such as `left: 503 (ResponseCode::ServerError)` and `right: 404 (ResponseCode::NotFound)`. Like `assert_eq!`, it accepts a format string and arguments after the values.
+//!
+//! ### Composing Behaviors
+//!
+//! `Logged<B, F>` and `Fallback<B1, B2>` implement `Behavior` by wrapping other behaviors, so cross-cutting concerns can be added to `Panicking` and `Saturating` without rewriting them. `Logged` reports every out of bounds result to a `ClampObserver` before resolving it with `B`. `Fallback` resolves with `B1` and falls back to `B2` for results that `B1::resolve` rejects. They can be used anywhere a behavior type is accepted, such as `BoundedValue<u8, Logged<Saturating, MyLogger>>`.
+//!
+//! ### Iterator Adapters
+//!
+//! `IteratorExt` converts streams of primitives into clamped values. `validate_clamped::<T>()` yields a `Result<T, ClampError<int>>` per item and `clamp_saturating::<T>()` yields `T` by saturating each item to the nearest limit.
+//!
+//! ```rust
+//! use checked_rs::{iter::IteratorExt, prelude::*};
+//!
+//! #[clamped(u8 as Hard, default = 0, upper = 100)]
+//! #[derive(Debug, Clone, Copy)]
+//! struct Percent;
+//!
+//! fn main() {
+//!     let values: Vec<Percent> = [5u8, 150].into_iter().clamp_saturating::<Percent>().collect();
+//!     assert_eq!(*values[1], 100);
+//! }
+//! ```
+//!
+//! ### Optional Features
+//!
+//! - `zeroize`: Implements `zeroize::Zeroize` for types marked `secret`.
+//! - `anyhow-compat`: Generates `new_<variant>(int) -> anyhow::Result<Self>` for the `#[range]` variants of enums, alongside `try_new_<variant>`.
+//! - `bench`: Re-exports `criterion` for the benchmarks generated by the `benchmark` flag. The crate's own benchmarks run with `cargo bench --features bench`.
+//! - `deranged`: Implements conversions between every generated type and the `deranged` type with the same bounds, such as `RangedU8<1, 10>`. Converting to or from a `deranged` type with different bounds fails to compile. Soft types only convert into the `deranged` type with `TryFrom`, since they may hold an invalid value. Hard structs and enums also implement `GenericForm`, whose `into_generic()` and `from_generic(value)` convert to and from that type, so generic numeric code can accept every generated type through one bound, such as `T: GenericForm<Generic = RangedU16<MIN, MAX>>`, instead of a trait per type.
+//! - `bounded-integer`: Implements the same conversions as `deranged` for the `bounded-integer` type with the same bounds, such as `BoundedU8<1, 10>`, without `GenericForm`.
+//! - `examples`: Adds the `examples` module with fully worked types to copy or import: `HttpStatus`, an enum over HTTP status codes, `Port`, `Percent`, which saturates at its limits, and `Angle`, a heading in degrees that wraps around. Each type has a doctest that shows how it is used, and `Port` and `Percent` also have the doctests generated by `doc_examples`.
+//! - `fuzz`: Implements `FuzzTarget` for every generated type in builds with `--cfg fuzzing`, as `cargo fuzz` makes them, and in tests. `T::fuzz(data)` decodes primitives from the bytes, checks that `from_primitive` accepts exactly the values within the limits and that they round-trip, and for hard structs applies `+`, `-` and `*` with the next primitive whenever the preview of the guard says the behavior resolves them, checking the result against the preview. `fuzz_type::<T>` runs one target, and `fuzz_dispatch(data, &[fuzz_type::<Port>, fuzz_type::<Percent>])` chooses one by the first byte, so a single `fuzz_target!` covers every type of a crate.
+//! - `metrics`: Enables the counters of types given the `metrics` flag, along with the `Metered<B, M>` behavior that records how `B` resolved each out of bounds result in the counters of `M`.
+//! - `never-panic`: Rejects the `Panicking` behavior with a compile error, both as the type behavior and in behavior tables, and makes `SaturatingWithLog` the default behavior instead. Since Cargo unifies the features of a dependency across the build, enabling it in any crate makes `Panicking` a compile error in every crate of the build that uses checked-rs, including dependencies that were written for it. Libraries should leave it to the final binary, and enable it there only when no dependency declares a `Panicking` type. The tests of `generate_kind_matrix_tests!` expect the default behavior to be `Panicking` and fail with it enabled.
+//! - `reflect`: Generates a static `ClampedTypeDescriptor` for every type, with its name, primitive type, limits, declared ranges and exact values, default and behavior. It is returned by `<type>::descriptor()` and the `Reflect` trait, and registered with `inventory` so that `registered_descriptors()` iterates over every clamped type linked into the binary and `find_descriptor(name)` looks one up by name. The descriptor also lists the `conversions` generated for the type, such as `From<u16> for Port`, with `may_panic` set on the `From` conversions from primitives that have values outside of the limits, so the code that relies on them can be found and moved to `TryFrom`. `write_conversion_manifest(out)` writes that list for every registered type, which is all a small audit binary needs to call.
+//! - `ui`: Implements the `UiRange` trait for every generated struct, with the limits and step of the type as `f64` values and a `from_f64` that rounds and clamps, so that the sliders of GUI toolkits such as `egui` can be bound directly to clamped values. The docs of `UiRange` include an adapter for the `Slider` of `egui`.
+//! - `bytemuck`: Replaces the `bytemuck` derives of a struct, such as `#[derive(bytemuck::Zeroable, bytemuck::NoUninit, bytemuck::CheckedBitPattern)]`, with implementations that respect the limits, and makes the struct `#[repr(transparent)]` for zero-copy casts of slices. Structs that derive none of them are left as they are, so they may implement the traits by hand. `Zeroable` can only be derived when zero is allowed, and `Pod`, `AnyBitPattern` and `TransparentWrapper` only by types that allow every value, including all soft types. Other types derive `CheckedBitPattern`, so `bytemuck::checked` validates each value. Deriving a `bytemuck` trait that the limits make unsound, or any `bytemuck` trait on an enum, is rejected by the macro.
+//! - `roundtrip-tests`: Generates a test alongside every type that checks its limits, default and a sample of random values round-trip through `to_canonical_string` and `from_canonical_str`. Enable it in `dev-dependencies` to run the tests with `cargo test`.
+//! - `testing`: Wraps the behavior of every generated type in `FaultInjecting`, which fails the operations chosen by the `FaultPlan` of the current thread even when their result is within the limits, so that applications can test how they handle clamp events without crafting boundary inputs. A plan either fails each operation with a probability or follows a schedule, and is set with `set_fault_plan`, or with `with_fault_plan` for the duration of a closure. An injected fault is an overflow at the upper limit, so `Saturating` results in the upper limit and `Panicking` panics. Nothing fails until a plan is set, and `injected_fault_count()` tells how many faults were injected. It is meant for dev-dependencies.
+//! - `tokio`: Adds `ClampedWatch<T>`, a `tokio::sync::watch` sender of clamped values for propagating bounded settings, such as a rate limit, to the tasks of an async service. `send(int)` and `send_modify(|int| ..)` validate the primitive before publishing it and fail without notifying the receivers when it is out of bounds, and `subscribe()` returns a `ClampedReceiver<T>` whose `get()` and `changed().await` yield the clamped values.
+//!
+//! ### `BoundedValue`
+//!
+//! When the limits are only known at runtime, such as those loaded from a database or user settings, `BoundedValue<T, B>` carries a `ClampSpec<T>` alongside the value. Its operators are resolved with the behavior `B` just like the generated types. Every generated type also provides `runtime_spec()` and `with_runtime_bounds(spec)` to bridge into it.
+//!
+//! ```rust
+//! use checked_rs::{
+//!     bounded::{BoundedValue, ClampSpec},
+//!     prelude::*,
+//! };
+//!
+//! let spec = ClampSpec::new(10u8, 100)?;
+//! let val = BoundedValue::<u8, Saturating>::new(50, spec)?;
+//!
+//! assert_eq!(*(val + 100), 100);
+//! assert!(BoundedValue::<u8>::new(5, spec).is_err());
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! ### `BoundedSum`
+//!
+//! `BoundedSum<T>` accumulates primitives into a running total that always stays within the limits of the hard struct type `T`, such as the amount spent from a budget or quota. `try_add` fails and leaves the total unchanged when the result would be above the upper limit, `saturating_add` stops at the upper limit, and `remaining()` reports the headroom left before it.
+//!
+//! ### `Watermarked`
+//!
+//! `Watermarked<T>` wraps a clamped value and remembers the lowest and highest values it has held, such as the peak depth of a bounded queue for capacity planning. Its operators are forwarded to `T` and resolved with the behavior of `T`, and every result updates the marks. `low()` and `high()` read the marks, and `reset()` starts them again from the current value.
+//!
+//! ### `Decaying`
+//!
+//! `Decaying<T>` wraps a clamped value that decays towards its lower limit as time passes, such as the tokens of a leaky bucket or a reputation score. It is created with a rate and a period, such as `Decaying::new(score, 2, Duration::from_secs(1))`. `tick(elapsed)` lowers the value by the rate for every whole period elapsed and carries the rest of the time into the next tick, while `boost(n)` raises it. Both saturate at the limits with `Saturating`, which also works for enums whose `#[range]` variants split the limits.
+//!
+//! ### `ClampedArray`
+//!
+//! `ClampedArray<T, N>` holds a fixed number of clamped values, such as the buckets of a bounded histogram. It is built from an array of primitives with `try_from`, applies arithmetic with a scalar to every element using the element type's behavior, and provides `min()` and `max()` queries. It serializes as a sequence of its elements.
+//!
+//! ### `ClampedReader` and `ClampedWriter`
+//!
+//! `ClampedReader<R, T>` and `ClampedWriter<W, T>` frame a sequence of clamped values over `io::Read` and `io::Write`, so that binary protocol parsers validate values while reading them instead of in a second pass. Each value is the big-endian bytes of its primitive, which is `u8` through `u128`. The reader is an iterator over `Result<T, FrameError<int>>` that ends when the stream ends on a value boundary, and the writer refuses values that `T` does not allow. Every `FrameError` records the byte offset of the value that failed and whether it was invalid, truncated or an I/O error. Iteration continues past an invalid value but stops after a truncated value or an I/O error.
+//!
+//! ### Batch Operations
+//!
+//! `add_scalar_saturating` and `sub_scalar_saturating` adjust every value in a `&mut [T]` of a hard struct type at once, such as the stats of thousands of game entities each tick, saturating at the limits. Hard structs are `#[repr(transparent)]` and implement `TransparentClamp`, so the loops run over the primitives in chunks that the compiler can vectorize, and each chunk is checked against the limits once instead of every element. The same operations are generated as associated functions, such as `Health::add_scalar_saturating(&mut stats, 5)`.
+//!
+//! For serialization hot paths, `as_primitive_slice(&[T]) -> &[int]` views a slice of a hard struct type as its primitives without copying, and `try_from_primitive_slice(&[int]) -> Result<&[T]>` checks every primitive against the limits before viewing the slice as `T`, failing with the position of the first invalid one. Both are sound because `TransparentClamp` guarantees the layout and that every value within the limits is allowed, and their tests also run under miri with `make miri`.
+//!
+//! ### `ClampedSlice`
+//!
+//! `ClampedSlice<'a, T, int>` is a read-only view over a slice of primitives, such as a large telemetry buffer, that reports which elements are valid values of `T` without building a `Result` for each one. `ClampedSlice::new` validates elements as they are read, while `ClampedSlice::eager` validates them all up front and records the invalid positions in a bitmap. It provides `get`, `is_valid`, `iter_valid()`, `invalid_positions()`, `first_invalid()` and `to_owned_validated()`, which fails with the position of the first invalid element.
+//!
+//! ### `ClampedVec`
+//!
+//! `ClampedVec<T, int>` is a growable buffer of clamped values that stores their primitives contiguously, such as the state of many entities in a simulation. Elements are validated when they are added with `push`, `try_push_raw`, `extend` or `TryFrom<Vec<int>>`, and read back as `T` with `get`, `pop` and `iter()`. The bulk operations `saturating_add_all` and `saturating_sub_all` work on the primitives directly so that they can be vectorized, checking each chunk against the limits once. It serializes as a sequence of primitives and deserializing it validates every element.
+//!
+//! ### `ShardRouter`
+//!
+//! `ShardRouter<S>` maps arbitrary keys to the shard ids allowed by a `u32` clamped type `S`, hashing their bytes with FNV-1a so that every node of a cluster agrees on where a key lives, whatever its platform or compiler version. Keys are given as bytes, such as a `&str` or the `to_le_bytes()` of an integer, since the standard `Hash` implementations depend on the platform and may change between compiler versions. Keys are spread uniformly over the allowed ids, which `without(spans)` can narrow, such as `without(ShardId::gaps())` to skip the values an enum only accepts through `#[other]`. The mapping of a hash onto a set of ids with gaps is available on its own as `nth_in_spans`.
+//!
+//! ### `RegisterMap`
+//!
+//! `#[derive(RegisterMap)]` packs a struct of clamped fields into a single `u32` or `u64` register, as hardware registers lay out their fields. The register type is given with `#[register(u32)]` on the struct and the bits of each field with `#[register(offset = N, width = N)]`. It generates `to_register()` and `from_register(register)`, which unpacks every field and fails with the name of the first field holding a value its type does not allow. Fields that overlap or do not fit in the register are rejected by the macro, and a field type whose upper limit does not fit in its width fails to compile.
+//!
+//! ```rust
+//! use checked_rs::prelude::*;
+//!
+//! #[clamped(u8 as Hard, default = 0, upper = 7)]
+//! #[derive(Debug, Clone, Copy)]
+//! struct Mode;
+//!
+//! #[clamped(u16 as Hard, default = 1, lower = 1, upper = 1000)]
+//! #[derive(Debug, Clone, Copy)]
+//! struct Divider;
+//!
+//! #[derive(RegisterMap)]
+//! #[register(u32)]
+//! struct Control {
+//!     #[register(offset = 0, width = 3)]
+//!     mode: Mode,
+//!     #[register(offset = 8, width = 10)]
+//!     divider: Divider,
+//! }
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let control = Control::from_register(0x0000_6405)?;
+//!     assert_eq!(*control.mode, 5);
+//!     assert_eq!(*control.divider, 100);
+//!     assert_eq!(control.to_register(), 0x0000_6405);
+//!     assert!(Control::from_register(0).is_err());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Expansion Snapshots
+//!
+//! Workspaces with many clamped types can snapshot what the attribute generates for them in CI, to notice when an upgrade of checked-rs changes the code behind their types. `checked_rs_macro_impl::expand_to_string(attr, item, format)` expands the attribute for the given tokens and returns the code as a string. `ExpansionFormat::Stable` formats it with `prettyplease`, with trailing whitespace removed and runs of blank lines collapsed, while `ExpansionFormat::Tokens` gives the tokens on a single line. Neither keeps spans, so the output only changes when the generated code does. Add `checked-rs-macro-impl` as a dev-dependency at the same version as `checked-rs` to use it.
+//!
+//! Procedural macro crates that build clamped types from their own syntax can reuse the code generation as a library through the `checked_rs_macro_impl::Codegen` trait, which `SoftCodegen`, `HardCodegen` and `EnumCodegen` implement. `Codegen::expand(attr, item)` takes the parsed `AttrParams` and item and returns the generated tokens. Its signature only changes in a major release, while the generated code may change in any release. It must be called from a macro annotated with `#[proc_macro_error]`, and it does not add the derives of `configure!`.
+//! Forks of the macros can check their code generation with `checked_rs::generate_kind_matrix_tests!()`, invoked inside a test module. It expands hard and soft structs, single and multi-range enums and full width types with both `Panicking` and `Saturating` behaviors for every unsigned primitive, along with tests of their boundary values. Signed primitives are not covered yet, since `#[clamped]` does not accept them.
+//!
 //! ### `View`
 //!
 //! The `View` struct is a wrapper around a value that encodes it's validation logic into the wrapper. The `Validator` trait is used to define the validation logic for a `View`.
@@ -153,15 +442,134 @@ use std::{
     num,
     ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Sub},
 };
+
+use clamp::{ClampError, DivByZero};
+
+// Lets the code generated by `clamped` refer to this crate as `::checked_rs` from within it.
+extern crate self as checked_rs;
+
+pub mod array;
+pub mod batch;
+pub mod bounded;
+pub mod btree;
 pub mod clamp;
+pub mod cyclic;
+#[cfg(feature = "examples")]
+pub mod examples;
+pub mod explain;
+pub mod format;
+pub mod framing;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 pub mod guard;
+pub mod iter;
+mod matrix;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod path;
+#[cfg(feature = "reflect")]
+pub mod reflect;
+pub mod scale;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "ui")]
+pub mod ui;
 pub mod view;
 
+pub use checked_rs_macros::configure;
+pub use clamp::{clamp_panicking, clamp_saturating, try_clamp};
+
+/// Expand to the given items only when the `bench` feature of this crate is enabled, so that the
+/// benchmarks generated by the `benchmark` flag do not depend on the features of the crate that
+/// defines the type. This is not part of the public API.
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_bench {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "bench"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_bench {
+    ($($item:item)*) => {};
+}
+
+/// The crates used by the code that `clamped` generates, so that it compiles even when they are
+/// not dependencies of the crate using it. This is not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use anyhow;
+    #[cfg(feature = "bounded-integer")]
+    pub use bounded_integer;
+    #[cfg(feature = "bytemuck")]
+    pub use bytemuck;
+    #[cfg(feature = "bench")]
+    pub use criterion;
+    #[cfg(feature = "deranged")]
+    pub use deranged;
+    #[cfg(feature = "reflect")]
+    pub use inventory;
+    pub use rand;
+    pub use serde;
+    #[cfg(feature = "zeroize")]
+    pub use zeroize;
+
+    pub use crate::__if_bench as if_bench;
+
+    /// The names that the generated code uses without a path, which it imports along with the
+    /// items of the enclosing module.
+    pub mod codegen {
+        pub use crate::prelude::*;
+
+        pub use crate::array::*;
+        pub use crate::batch::*;
+        pub use crate::bounded::*;
+        pub use crate::btree::*;
+        pub use crate::cyclic::*;
+        pub use crate::explain::*;
+        pub use crate::format::*;
+        pub use crate::framing::*;
+        #[cfg(feature = "fuzz")]
+        pub use crate::fuzz::*;
+        pub use crate::guard::{GuardDropPolicy, PanicOnDrop, SilentOnDrop, WarnOnDrop};
+        pub use crate::iter::*;
+        #[cfg(feature = "metrics")]
+        pub use crate::metrics::*;
+        pub use crate::path::*;
+        #[cfg(feature = "reflect")]
+        pub use crate::reflect::*;
+        pub use crate::scale::*;
+        #[cfg(feature = "testing")]
+        pub use crate::testing::*;
+        #[cfg(feature = "ui")]
+        pub use crate::ui::*;
+    }
+}
+
 mod reexports {
     #[doc(hidden)]
     pub use anyhow::{anyhow, bail, ensure, format_err, Chain, Context, Error, Result};
+    #[cfg(feature = "bounded-integer")]
+    #[doc(hidden)]
+    pub use bounded_integer;
+    #[cfg(feature = "bytemuck")]
+    #[doc(hidden)]
+    pub use bytemuck;
+    #[cfg(feature = "bench")]
+    #[doc(hidden)]
+    pub use criterion;
+    #[cfg(feature = "deranged")]
+    #[doc(hidden)]
+    pub use deranged;
     #[doc(hidden)]
     pub use serde;
+    #[cfg(feature = "zeroize")]
+    #[doc(hidden)]
+    pub use zeroize;
 }
 
 pub mod prelude {
@@ -171,60 +579,104 @@ pub mod prelude {
     pub use crate::commit_or_bail;
     pub use crate::view::*;
     pub use crate::{Behavior, InherentBehavior, InherentLimits};
-    pub use checked_rs_macros::clamped;
+    pub use checked_rs_macros::{clamped, clamped_const, RegisterMap};
 }
 
 pub trait Behavior: Copy + 'static {
     // Binary Ops
-    fn add<T: Add<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn add<T: Add<Output = T> + Copy>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: Add<Output = num::Saturating<T>>;
-    fn sub<T: Sub<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn sub<T: Sub<Output = T> + Copy>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: Sub<Output = num::Saturating<T>>;
-    fn mul<T: Mul<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn mul<T: Mul<Output = T> + Copy>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: Mul<Output = num::Saturating<T>>;
-    fn div<T: Div<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn div<T: Div<Output = T> + Copy>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: Div<Output = num::Saturating<T>>;
-    fn rem<T: Rem<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn rem<T: Rem<Output = T> + Copy>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: Rem<Output = num::Saturating<T>>;
-    fn bitand<T: BitAnd<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn bitand<T: BitAnd<Output = T> + Copy>(
+        lhs: T,
+        rhs: T,
+        min: T::Output,
+        max: T::Output,
+    ) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: BitAnd<Output = num::Saturating<T>>;
-    fn bitor<T: BitOr<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn bitor<T: BitOr<Output = T> + Copy>(
+        lhs: T,
+        rhs: T,
+        min: T::Output,
+        max: T::Output,
+    ) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: BitOr<Output = num::Saturating<T>>;
-    fn bitxor<T: BitXor<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    fn bitxor<T: BitXor<Output = T> + Copy>(
+        lhs: T,
+        rhs: T,
+        min: T::Output,
+        max: T::Output,
+    ) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: BitXor<Output = num::Saturating<T>>;
-    // fn shl<T: Shl<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    // fn shl<T: Shl<Output = T> + Copy>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
     // where
     //     T::Output: Eq + Ord,
     //     num::Saturating<T>: Shl<Output = num::Saturating<T>>;
-    // fn shr<T: Shr<Output = T>>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
+    // fn shr<T: Shr<Output = T> + Copy>(lhs: T, rhs: T, min: T::Output, max: T::Output) -> T::Output
     // where
     //     T::Output: Eq + Ord,
     //     num::Saturating<T>: Shr<Output = num::Saturating<T>>;
     // Unary Ops
-    fn neg<T: std::ops::Neg<Output = T>>(value: T, min: T::Output, max: T::Output) -> T::Output
+    fn neg<T: std::ops::Neg<Output = T> + Copy>(
+        value: T,
+        min: T::Output,
+        max: T::Output,
+    ) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: std::ops::Neg<Output = num::Saturating<T>>;
-    fn not<T: std::ops::Not<Output = T>>(value: T, min: T::Output, max: T::Output) -> T::Output
+    fn not<T: std::ops::Not<Output = T> + Copy>(
+        value: T,
+        min: T::Output,
+        max: T::Output,
+    ) -> T::Output
     where
         T::Output: Eq + Ord,
         num::Saturating<T>: std::ops::Not<Output = num::Saturating<T>>;
+    /// Decide what dividing by zero results in under the `div_by_zero` policy, given the upper
+    /// limit and the default value of the type.
+    fn div_by_zero<T: Copy>(policy: DivByZero, max: T, default: T) -> T {
+        match policy {
+            DivByZero::Panic => panic!("attempt to divide by zero"),
+            DivByZero::SaturateMax => max,
+            DivByZero::ReturnDefault => default,
+        }
+    }
+    // Previews
+    /// Decide what an out of bounds result becomes under this behavior without panicking. The
+    /// default implementation asks `add` and `sub` how they resolve a result past either limit of
+    /// a `u8` range, and resolves to the limit when they saturate, or leaves the error as is when
+    /// they do anything else. Behaviors whose `add` or `sub` panic must override it.
+    fn resolve<T: Copy>(result: Result<T, ClampError<T>>) -> Result<T, ClampError<T>> {
+        match result {
+            Err(ClampError::TooSmall { min, .. }) if Self::sub(10u8, 5, 8, 20) == 8 => Ok(min),
+            Err(ClampError::TooLarge { max, .. }) if Self::add(15u8, 10, 8, 20) == 20 => Ok(max),
+            result => result,
+        }
+    }
 }
 
 pub trait InherentLimits<T>: 'static {
@@ -239,6 +691,19 @@ pub trait InherentBehavior: 'static {
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
+    use crate::{
+        array::ClampedArray,
+        assert_clamped_eq,
+        batch::sub_scalar_saturating,
+        bounded::ClampSpec,
+        btree::{btree_range_of, btree_range_of_variant, limit_bounds},
+        explain::{DiffDisplay, Explanation},
+        format::CompactEncoding,
+        framing::{ClampedReader, ClampedWriter, FrameError},
+        iter::IteratorExt,
+        scale::Rounding,
+        static_assert_range,
+    };
 
     #[clamped(u16, default = 600, behavior = Saturating, lower = 100, upper = 600)]
     #[derive(Debug, Clone, Copy)]
@@ -283,10 +748,2487 @@ mod tests {
         assert!(code.is_unknown());
     }
 
+    #[test]
+    fn test_const_factories() {
+        const NOT_FOUND: ResponseCode = ResponseCode::new_not_found();
+        const BAD_GATEWAY: Result<ResponseCode, ClampError<u16>> =
+            ResponseCode::try_new_server_error(502);
+
+        assert!(NOT_FOUND.is_not_found());
+        assert_eq!(BAD_GATEWAY.unwrap().into_primitive(), 502);
+        assert!(matches!(
+            ResponseCode::try_new_server_error(404),
+            Err(ClampError::TooSmall { val: 404, min: 500 })
+        ));
+        assert!(matches!(
+            ResponseCode::try_new_server_error(600),
+            Err(ClampError::TooLarge { val: 600, max: 599 })
+        ));
+
+        #[cfg(feature = "anyhow-compat")]
+        assert!(ResponseCode::new_server_error(404).is_err());
+    }
+
+    #[test]
+    fn test_staged_predicates() {
+        let mut code = ResponseCode::new_success();
+        let mut g = code.modify();
+
+        *g = 404;
+        assert!(g.will_be_valid());
+        assert!(g.staged_is_not_found());
+        assert!(!g.staged_is_success());
+
+        *g = 550;
+        assert!(g.staged_is_server_error());
+
+        *g = 50;
+        assert!(!g.will_be_valid());
+        assert!(!g.staged_is_unknown());
+
+        g.discard();
+        assert!(code.is_success());
+    }
+
+    #[cfg(not(feature = "never-panic"))]
+    #[test]
+    fn test_guard_preview() -> Result<()> {
+        let mut code = ResponseCode::new_success();
+        let mut g = code.modify();
+
+        assert_eq!(g.preview_add(50).unwrap(), 250);
+        assert_eq!(g.preview_add(1000).unwrap(), 600);
+        assert_eq!(g.preview_sub(500).unwrap(), 100);
+        assert_eq!(g.preview_mul(u16::MAX).unwrap(), 600);
+        assert_eq!(*g, 200);
+
+        assert_eq!(g.apply_add(300).unwrap(), 500);
+        assert!(g.staged_is_server_error());
+        assert!(g.commit().is_ok());
+
+        let mut pin = Pin::from_primitive(9000)?;
+        let mut g = pin.modify();
+
+        assert!(matches!(
+            g.preview_add(1000),
+            Err(ClampError::TooLarge {
+                val: 10000,
+                max: 9999
+            })
+        ));
+        assert!(matches!(
+            g.apply_sub(9001),
+            Err(ClampError::TooSmall { val: 0, min: 0 })
+        ));
+        assert_eq!(*g, 9000);
+        g.discard();
+        Ok(())
+    }
+
+    #[cfg(not(feature = "never-panic"))]
+    #[test]
+    fn test_auto_guard() -> Result<()> {
+        let mut code = ResponseCode::new_success();
+        *code.modify_auto() += 204;
+        assert!(code.is_not_found());
+
+        {
+            let mut g = code.modify_auto();
+            *g = 1000;
+            assert!(g.is_changed() && !g.will_be_valid());
+        }
+        assert_eq!(code.into_primitive(), 600);
+
+        let mut g = code.modify_auto();
+        *g = 100;
+        g.discard();
+        assert_eq!(code.into_primitive(), 600);
+
+        let mut port = port!(8080);
+        *port.modify_auto() = 443;
+        assert_eq!(port, 443);
+
+        assert!(std::panic::catch_unwind(move || {
+            *port.modify_auto() = 0;
+        })
+        .is_err());
+        Ok(())
+    }
+
+    #[clamped(usize, default = 0, store = u16, upper = 60_000, separator = "_")]
+    #[derive(Debug, Clone, Copy)]
+    enum Slot {
+        #[eq(0)]
+        Empty,
+        #[range(1..=59_999)]
+        Used,
+        #[other]
+        Full,
+    }
+
+    #[test]
+    fn test_separated() {
+        assert_eq!(
+            Slot::from_primitive(54321).unwrap().separated().to_string(),
+            "54_321"
+        );
+        assert_eq!(port!(8080).separated().to_string(), "8,080");
+    }
+
+    #[test]
+    fn test_narrow_store() {
+        assert!(std::mem::size_of::<Slot>() < std::mem::size_of::<usize>());
+
+        let mut slot = Slot::new_empty();
+        assert_eq!(slot.into_primitive(), 0usize);
+
+        slot += 59_999usize;
+        assert!(slot.is_used());
+        assert_eq!(usize::from(slot), 59_999);
+
+        slot += 1usize;
+        assert!(slot.is_full());
+        assert!(Slot::from_primitive(60_001).is_err());
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Status {
+        Fine,
+        Missing,
+        Broken,
+        Unknown,
+    }
+
+    #[clamped(u16, default = 200, lower = 100, upper = 599, maps_to = Status)]
+    #[derive(Debug, Clone, Copy)]
+    enum HttpCode {
+        #[eq(200)]
+        #[maps_to(Status::Fine)]
+        Okay,
+        #[eq(404, 410)]
+        #[maps_to(Status::Missing)]
+        Gone,
+        #[range(500..=599)]
+        #[maps_to(Status::Broken)]
+        Server,
+        #[other]
+        #[maps_to(Status::Unknown, 302)]
+        Other,
+    }
+
+    #[test]
+    fn test_maps_to() -> Result<()> {
+        assert_eq!(Status::from(HttpCode::new_okay()), Status::Fine);
+        assert_eq!(Status::from(HttpCode::from_primitive(503)?), Status::Broken);
+        assert_eq!(
+            Status::from(HttpCode::from_primitive(302)?),
+            Status::Unknown
+        );
+
+        assert_eq!(HttpCode::try_from(Status::Missing)?, 404);
+        assert_eq!(HttpCode::try_from(Status::Broken)?, 500);
+        assert_eq!(HttpCode::try_from(Status::Unknown)?, 302);
+        assert!(HttpCode::try_from(Status::Unknown)?.is_other());
+        Ok(())
+    }
+
+    #[clamped(u8, default = 0, upper = 200)]
+    #[derive(Debug, Clone, Copy)]
+    enum Bounds {
+        #[range(..1)]
+        Zero,
+        #[range(1..10)]
+        Low,
+        #[range(10..=const { 2 * 10 })]
+        Mid,
+        #[range(const { 20 + 1 }..const { 10 * 10 })]
+        High,
+        #[range(100..MAX)]
+        Higher,
+        #[range(MAX..)]
+        Top,
+    }
+
+    #[clamped(u8, default = 5, lower = 5, upper = 50)]
+    #[derive(Debug, Clone, Copy)]
+    enum Limits {
+        #[range(MIN..=const { u8::MIN + 10 })]
+        Early,
+        #[range(const { u8::MIN + 11 }..MAX)]
+        Late,
+        #[eq(MAX)]
+        Last,
+    }
+
+    #[clamped(u64, default = 0)]
+    #[derive(Debug, Clone, Copy)]
+    enum Offset {
+        #[eq(0)]
+        Start,
+        #[range(1..MAX)]
+        Inside,
+        #[eq(MAX)]
+        End,
+    }
+
+    #[clamped(u128, default = 0)]
+    #[derive(Debug, Clone, Copy)]
+    enum Nonce {
+        #[eq(0)]
+        Unset,
+        #[range(1..)]
+        Set,
+    }
+
+    #[clamped(u32, default = 0, upper = 7)]
+    #[derive(Debug, Clone, Copy)]
+    enum Nibble {
+        #[range(0..4)]
+        Low,
+        #[mask(4)]
+        High,
+    }
+
+    #[test]
+    fn test_full_domain_coverage() -> Result<()> {
+        assert!(Offset::from_primitive(0)?.is_start());
+        assert!(Offset::from_primitive(u64::MAX - 1)?.is_inside());
+        assert!(Offset::from_primitive(u64::MAX)?.is_end());
+        assert!(Nonce::from_primitive(0)?.is_unset());
+        assert!(Nonce::from_primitive(u128::MAX)?.is_set());
+        assert!(Nibble::from_primitive(3)?.is_low());
+        assert!(Nibble::from_primitive(7)?.is_high());
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_bounds() -> Result<()> {
+        let check = |n: u8| Bounds::from_primitive(n);
+
+        assert!(check(0)?.is_zero());
+        assert!(check(1)?.is_low() && check(9)?.is_low());
+        assert!(check(10)?.is_mid() && check(20)?.is_mid());
+        assert!(check(21)?.is_high() && check(99)?.is_high());
+        assert!(check(100)?.is_higher() && check(199)?.is_higher());
+        assert!(check(200)?.is_top());
+        assert!(check(201).is_err());
+
+        assert!(Limits::from_primitive(5)?.is_early());
+        assert!(Limits::from_primitive(10)?.is_early());
+        assert!(Limits::from_primitive(11)?.is_late());
+        assert!(Limits::from_primitive(49)?.is_late());
+        assert!(Limits::new_last().is_last());
+        assert!(Limits::from_primitive(4).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() -> Result<()> {
         let code: ResponseCode = "200".parse()?;
         assert!(code.is_success());
+        assert!(ResponseCode::try_from("404")?.is_not_found());
+
+        let err = "abc".parse::<ResponseCode>().unwrap_err();
+        assert_eq!(err.type_name, "ResponseCode");
+        assert_eq!(err.input, "abc");
+        assert_eq!(err.allowed, "a value in 100..=600");
+
+        let err = Port::try_from("10000").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value \"10000\" for `Port`: Value too large: 10000 (max: 9999) (expected a value in 1..=9999)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonical_string() -> Result<()> {
+        assert_eq!(Port::from_primitive(80)?.to_canonical_string(), "80");
+        assert_eq!(Port::from_canonical_str("443")?, 443);
+        assert!(ResponseCode::from_canonical_str("404")?.is_not_found());
+
+        for s in ["+443", "0443", "-0", " 443", "4_43", ""] {
+            let err = Port::from_canonical_str(s).unwrap_err();
+            assert_eq!(err.reason, "not in canonical form");
+        }
+
+        assert!(Port::from_canonical_str("0").is_err());
+        assert!(ResponseCode::from_canonical_str("99").is_err());
+
+        for n in 1..=9999 {
+            let value = Port::from_primitive(n)?;
+            assert_eq!(
+                Port::from_canonical_str(&value.to_canonical_string())?,
+                value
+            );
+        }
         Ok(())
     }
+
+    #[clamped(u8 as Hard, default = 0, behavior = Saturating, upper = 100, benchmark)]
+    #[derive(Debug, Clone, Copy)]
+    struct Percent;
+
+    #[cfg(feature = "bench")]
+    #[test]
+    fn test_benchmark() {
+        let _: fn(&mut criterion::Criterion) = percent_bench::benches;
+    }
+
+    #[test]
+    fn test_iterator_adapters() {
+        let raw = [5u8, 150, 100];
+
+        let validated = raw
+            .into_iter()
+            .validate_clamped::<Percent>()
+            .collect::<Vec<_>>();
+        assert_eq!(*validated[0].unwrap(), 5);
+        assert!(matches!(
+            validated[1],
+            Err(ClampError::TooLarge { val: 150, max: 100 })
+        ));
+
+        let saturated = raw
+            .into_iter()
+            .clamp_saturating::<Percent>()
+            .map(|n| *n)
+            .collect::<Vec<_>>();
+        assert_eq!(saturated, [5, 100, 100]);
+
+        let codes = [404u16, 50, 700]
+            .into_iter()
+            .clamp_saturating::<ResponseCode>()
+            .collect::<Vec<_>>();
+        assert!(codes[0].is_not_found());
+        assert!(codes[1].is_continue());
+        assert!(codes[2].is_invalid());
+    }
+
+    #[test]
+    fn test_fraction() {
+        let val = Percent::new(25);
+        assert_eq!(val.fraction(), 0.25);
+        assert_eq!(val.to_percent_of_range(), 25.0);
+        assert_eq!(*Percent::from_fraction(0.255), 26);
+        assert_eq!(*Percent::from_fraction(-1.0), 0);
+
+        let port: Port = Percent::new(50).scale_to();
+        assert_eq!(*port, 5000);
+        assert_eq!(*port!(9999).scale_to::<Percent, _>(), 100);
+        assert_eq!(*port!(1).scale_to::<Percent, _>(), 0);
+    }
+
+    #[test]
+    fn test_cast() {
+        let percent: Percent = port!(80).cast().unwrap();
+        assert_eq!(*percent, 80);
+        assert_eq!(*Percent::new(100).cast::<Port, _>().unwrap(), 100);
+        assert!(matches!(
+            port!(9999).cast::<Percent, _>(),
+            Err(ClampError::TooLarge {
+                val: 9999,
+                max: 100
+            })
+        ));
+        assert!(matches!(
+            Percent::new(0).cast::<Port, _>(),
+            Err(ClampError::TooSmall { val: 0, min: 1 })
+        ));
+
+        let code: ResponseCode = port!(404).cast().unwrap();
+        assert!(code.is_not_found());
+        assert_eq!(*code.cast::<Port, _>().unwrap(), 404);
+        assert!(port!(99).cast::<ResponseCode, _>().is_err());
+    }
+
+    #[cfg(not(feature = "never-panic"))]
+    #[test]
+    fn test_scale() {
+        let percent = Percent::new(45);
+        assert_eq!(*percent.scale(1, 2, Rounding::Floor), 22);
+        assert_eq!(*percent.scale(1, 2, Rounding::Ceil), 23);
+        assert_eq!(*percent.scale(1, 2, Rounding::HalfEven), 22);
+        assert_eq!(*percent.scale(3, 1, Rounding::Floor), 100);
+        assert_eq!(*percent.scale(u32::MAX, 1, Rounding::Floor), 100);
+
+        let port = port!(9000);
+        assert_eq!(port.scale(101, 1000, Rounding::HalfEven), 909);
+        assert!(std::panic::catch_unwind(|| port.scale(2, 1, Rounding::Floor)).is_err());
+        assert!(std::panic::catch_unwind(|| port.scale(0, 1, Rounding::Floor)).is_err());
+        assert!(std::panic::catch_unwind(|| port.scale(1, 0, Rounding::Floor)).is_err());
+    }
+
+    #[test]
+    fn test_percent_of() {
+        let percent = Percent::new(45);
+        assert_eq!(*percent.percent_of(50u8), 22);
+        assert_eq!(*percent.percent_of(300u16), 100);
+        assert_eq!(percent.as_percent_of(Percent::new(90)), 50);
+        assert_eq!(Percent::new(90).as_percent_of(percent), 100);
+        assert_eq!(percent.as_percent_of(Percent::new(0)), 0);
+
+        assert_eq!(*port!(9000).percent_of(50u8), 4500);
+        assert_eq!(port!(2500).as_percent_of(port!(9999)), 25);
+    }
+
+    #[cfg(not(feature = "never-panic"))]
+    #[test]
+    fn test_delta() {
+        let low = Percent::new(20);
+        let high = Percent::new(90);
+        let delta: PercentDelta = low.diff(high);
+        assert_eq!(delta.get(), -70);
+        assert_eq!(*high.diff(low), 70i8);
+
+        assert_eq!(*(high + delta), 20);
+        assert_eq!(*(low + -delta), 90);
+        assert_eq!(*(low - -delta), 0);
+        assert!(matches!(
+            low.checked_add_delta(delta),
+            Err(ClampError::TooSmall { val: 0, min: 0 })
+        ));
+        assert_eq!(*low.checked_sub_delta(delta).unwrap(), 90);
+
+        let span: PortDelta = port!(9999).diff(port!(1));
+        assert_eq!(span.to_string(), "9998");
+        assert!(std::panic::catch_unwind(|| port!(2) + span).is_err());
+    }
+
+    #[test]
+    fn test_clamped_array() -> Result<()> {
+        let mut gauges = ClampedArray::<Percent, 3>::try_from([10, 50, 90])?;
+        assert!(ClampedArray::<Percent, 3>::try_from([10, 101, 90]).is_err());
+
+        gauges += 20;
+        assert_eq!(gauges.map(|n| *n), [30, 70, 100]);
+        assert_eq!(**gauges.min().unwrap(), 30);
+        assert_eq!(**gauges.max().unwrap(), 100);
+
+        let gauges = gauges - 50;
+        assert_eq!(gauges.map(|n| *n), [0, 20, 50]);
+        assert!(ClampedArray::<Percent, 0>::default().max().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clamped_slice() -> Result<()> {
+        let buffer = [10u8, 101, 50, 255, 100];
+
+        for view in [
+            ClampedSlice::<Percent, _>::new(&buffer),
+            ClampedSlice::<Percent, _>::eager(&buffer),
+        ] {
+            assert_eq!(view.len(), 5);
+            assert!(view.is_valid(0) && !view.is_valid(1) && !view.is_valid(5));
+            assert_eq!(view.get(2).map(|n| *n), Some(50));
+            assert!(view.get(3).is_none());
+            assert_eq!(
+                view.iter_valid().map(|n| *n).collect::<Vec<_>>(),
+                [10, 50, 100]
+            );
+            assert_eq!(view.invalid_positions().collect::<Vec<_>>(), [1, 3]);
+            assert_eq!(view.first_invalid(), Some((1, 101)));
+
+            let err = view.to_owned_validated().unwrap_err();
+            assert_eq!(err.to_string(), "element 1 is invalid");
+        }
+
+        let view = ClampedSlice::<Percent, _>::eager(&buffer[2..]);
+        assert!(view.first_invalid().is_some());
+        assert_eq!(
+            ClampedSlice::<Percent, _>::eager(&[0, 100])
+                .to_owned_validated()?
+                .len(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_clamped_vec() -> Result<()> {
+        let mut levels = ClampedVec::<Percent, _>::with_capacity(200);
+        levels.push(Percent::new(10));
+        levels.try_push_raw(95)?;
+        assert!(levels.try_push_raw(101).is_err());
+        levels.extend((0..198).map(|_| Percent::new(50)));
+
+        assert_eq!(levels.len(), 200);
+        assert_eq!(levels.get(1).map(|n| *n), Some(95));
+        assert_eq!(levels.set(0, Percent::new(20)).map(|n| *n), Some(10));
+
+        levels.saturating_add_all(10);
+        assert_eq!(levels.as_primitives()[..3], [30, 100, 60]);
+        levels.saturating_sub_all(40);
+        assert_eq!(
+            levels.iter().take(3).map(|n| *n).collect::<Vec<_>>(),
+            [0, 60, 20]
+        );
+        assert!(levels.iter().skip(2).all(|n| *n == 20));
+
+        let json = serde_json::to_string(&levels)?;
+        assert_eq!(
+            serde_json::from_str::<ClampedVec<Percent, _>>(&json)?,
+            levels
+        );
+        let err = serde_json::from_str::<ClampedVec<Percent, u8>>("[1, 200]").unwrap_err();
+        assert!(err.to_string().contains("element 1 is invalid"));
+
+        let codes: ClampedVec<ResponseCode, _> =
+            [ResponseCode::new_success()].into_iter().collect();
+        assert!(codes.get(0).unwrap().is_success());
+        assert!(ClampedVec::<ResponseCode, u16>::try_from(vec![200, 700]).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "deranged")]
+    #[test]
+    fn test_deranged() -> Result<()> {
+        use deranged::{RangedU16, RangedU8};
+
+        let ranged: RangedU8<0, 100> = Percent::new(42).into();
+        assert_eq!(ranged.get(), 42);
+        assert_eq!(*Percent::from(RangedU8::<0, 100>::new_static::<7>()), 7);
+
+        let ranged: RangedU16<1, 9999> = port!(443).into();
+        assert_eq!(*Port::from(ranged), 443);
+
+        let code: RangedU16<100, 600> = ResponseCode::from_primitive(404)?.into();
+        assert!(ResponseCode::from(code).is_not_found());
+        Ok(())
+    }
+
+    #[cfg(feature = "bounded-integer")]
+    #[test]
+    fn test_bounded_integer() -> Result<()> {
+        use bounded_integer::{BoundedU16, BoundedU8};
+
+        let bounded: BoundedU8<0, 100> = Percent::new(42).into();
+        assert_eq!(bounded.get(), 42);
+        assert_eq!(*Percent::from(BoundedU8::<0, 100>::new(7).unwrap()), 7);
+
+        let bounded: BoundedU16<1, 9999> = port!(443).into();
+        assert_eq!(*Port::from(bounded), 443);
+
+        let code: BoundedU16<100, 600> = ResponseCode::from_primitive(404)?.into();
+        assert!(ResponseCode::from(code).is_not_found());
+
+        let dimmer = Dimmer::new(42);
+        assert!(BoundedU8::<1, 10>::try_from(dimmer).is_err());
+        assert_eq!(BoundedU8::<1, 10>::try_from(Dimmer::new(3))?.get(), 3);
+        Ok(())
+    }
+
+    #[cfg(feature = "deranged")]
+    #[test]
+    fn test_generic_form() -> Result<()> {
+        use deranged::RangedU16;
+
+        fn widen<T: GenericForm<Generic = RangedU16<MIN, MAX>>, const MIN: u16, const MAX: u16>(
+            value: T,
+        ) -> u32 {
+            value.into_generic().get() as u32
+        }
+
+        assert_eq!(widen(port!(443)), 443);
+        assert_eq!(widen(ResponseCode::from_primitive(503)?), 503);
+
+        let ranged = Percent::new(42).into_generic();
+        assert_eq!(*Percent::from_generic(ranged), 42);
+        Ok(())
+    }
+
+    #[clamped(u16, default = 100, lower = 100, upper = 599)]
+    #[transitions(Continue -> Okay, Continue -> Client; Okay -> Redirect, Okay -> *; Redirect -> Client)]
+    #[derive(Debug, Clone, Copy)]
+    enum Exchange {
+        #[range(100..=199)]
+        Continue,
+        #[eq(200)]
+        Okay,
+        #[range(300..=399)]
+        Redirect,
+        #[range(400..=499)]
+        Client,
+        #[other]
+        Server,
+    }
+
+    #[test]
+    fn test_transitions() -> Result<()> {
+        let start = Exchange::default();
+        let okay = Exchange::new_okay();
+        let client = Exchange::from_primitive(404)?;
+        let server = Exchange::from_primitive(500)?;
+
+        assert!(start.can_transition_to(&okay));
+        assert!(!start.can_transition_to(&server));
+        assert!(okay.can_transition_to(&server));
+        assert!(!client.can_transition_to(&okay));
+
+        assert!(start.transition(client)?.is_client());
+
+        let err = client.transition(okay).unwrap_err();
+        assert_eq!(
+            err,
+            TransitionError {
+                type_name: "Exchange",
+                from: "Client",
+                to: "Okay"
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "`Exchange` cannot transition from `Client` to `Okay`"
+        );
+        Ok(())
+    }
+
+    #[clamped(u16, default = 1, lower = 1, upper = 31, codegen = table)]
+    #[derive(Debug, Clone, Copy)]
+    enum MonthDay {
+        #[eq(1, 2, 3, 4, 5, 6, 7)]
+        FirstWeek,
+        #[range(8..=28)]
+        Middle,
+        #[eq(29, 30, 31)]
+        Tail,
+    }
+
+    #[clamped(u8, default = 0, codegen = table)]
+    #[derive(Debug, Clone, Copy)]
+    enum Opcode {
+        #[eq(0x00)]
+        Nop,
+        #[eq(0x10, 0x11)]
+        Load,
+        #[range(0x20..=0x2f)]
+        Jump,
+        #[other]
+        Unknown,
+    }
+
+    #[test]
+    fn test_table_codegen() -> Result<()> {
+        assert!(MonthDay::from_primitive(1)?.is_first_week());
+        assert!(MonthDay::from_primitive(7)?.is_first_week());
+        assert!(MonthDay::from_primitive(8)?.is_middle());
+        assert!(MonthDay::from_primitive(31)?.is_tail());
+        assert!(MonthDay::from_primitive(0).is_err());
+        assert!(MonthDay::from_primitive(32).is_err());
+        assert_eq!(MonthDay::from_primitive(30)?.into_primitive(), 30);
+        assert_eq!(*MonthDay::new_first_week().as_primitive(), 1);
+        assert_eq!(MonthDay::new_tail().into_primitive(), 29);
+        assert!(MonthDay::try_new_middle(12)?.is_middle());
+
+        assert!(Opcode::default().is_nop());
+        assert!(Opcode::from_primitive(0x11)?.is_load());
+        assert!(Opcode::from_primitive(0x2a)?.is_jump());
+        assert!(Opcode::from_primitive(0x12)?.is_unknown());
+        assert_eq!(*Opcode::from_primitive(0xff)?, 0xff);
+        Ok(())
+    }
+
+    #[clamped(u16, default = 1000, lower = 1000, upper = 9999, lookup = bitset)]
+    #[derive(Debug, Clone, Copy)]
+    enum PostalRegion {
+        #[eq(1010, 2000, 3001, 4500, 4501, 9999)]
+        Metro,
+        #[eq(5000, 7777)]
+        Coastal,
+        #[range(8000..=8999)]
+        Alpine,
+        #[other]
+        Rural,
+    }
+
+    #[test]
+    fn test_bitset_lookup() -> Result<()> {
+        assert!(PostalRegion::contains_exact(4501));
+        assert!(PostalRegion::contains_exact(7777));
+        assert!(!PostalRegion::contains_exact(4502));
+        assert!(!PostalRegion::contains_exact(8500));
+        assert!(!PostalRegion::contains_exact(0));
+
+        assert!(PostalRegion::from_primitive(9999)?.is_metro());
+        assert!(PostalRegion::from_primitive(5000)?.is_coastal());
+        assert!(PostalRegion::from_primitive(8123)?.is_alpine());
+        assert!(PostalRegion::from_primitive(1011)?.is_rural());
+        assert!(PostalRegion::from_primitive(999).is_err());
+        Ok(())
+    }
+
+    #[clamped(u8 as Hard, default = 5, lower = 1, upper = 10, module = loudness)]
+    #[derive(Debug, Clone, Copy)]
+    struct Loudness;
+
+    #[clamped(u8, default = 1, lower = 1, upper = 9, module = signal)]
+    #[derive(Debug, Clone, Copy)]
+    enum Signal {
+        #[eq(1)]
+        Weak,
+        #[range(2..=9)]
+        Strong,
+    }
+
+    fn turn_up(guard: &mut loudness::Guard<'_>) {
+        **guard += 1;
+    }
+
+    fn strongest(kinds: &[signal::Kind]) -> Option<signal::Kind> {
+        kinds.iter().copied().max()
+    }
+
+    #[test]
+    fn test_module_layout() -> Result<()> {
+        let mut loudness = Loudness::default();
+        let mut guard = loudness.modify();
+        turn_up(&mut guard);
+        assert!(guard.commit().is_ok());
+        assert_eq!(*loudness, 6);
+
+        let kinds = [Signal::new_weak().kind(), Signal::from_primitive(4)?.kind()];
+        assert_eq!(strongest(&kinds), Some(signal::Kind::Strong));
+        let _: fn(signal::Value<u8>) -> Signal = Signal::Weak;
+        Ok(())
+    }
+
+    #[test]
+    fn test_partition() {
+        let counts = Signal::partition([0, 1, 2, 5, 9, 10, 1]);
+        assert_eq!(counts.get(SignalKind::Weak), 2);
+        assert_eq!(counts.get(SignalKind::Strong), 3);
+        assert_eq!(counts.invalid(), 2);
+        assert_eq!(counts.total(), 7);
+        assert_eq!(
+            counts.iter().collect::<Vec<_>>(),
+            [(SignalKind::Weak, 2), (SignalKind::Strong, 3)]
+        );
+
+        let mut strongest = 0;
+        Signal::partition_with([3, 8, 12], |kind, n| {
+            if kind == Some(SignalKind::Strong) {
+                strongest = strongest.max(n);
+            }
+        });
+        assert_eq!(strongest, 8);
+    }
+
+    static_assert_range!(Loudness, 1..=10);
+    static_assert_range!(Signal, 1..=9, exact = [1]);
+    static_assert_range!(
+        ResponseCode,
+        100..=600,
+        exact = [100, 200, 300, 400, 404, 600]
+    );
+
+    #[test]
+    fn test_static_assert_range() {
+        assert_eq!(Signal::EXACT_VALUES, &[1]);
+        assert_eq!(ResponseCode::EXACT_VALUES, &[100, 200, 300, 400, 404, 600]);
+    }
+
+    #[clamped(u128, default = 1, lower = 1)]
+    #[derive(Debug, Clone, Copy)]
+    enum ObjectId {
+        #[eq(1)]
+        Root,
+        #[other]
+        Child,
+    }
+
+    #[test]
+    fn test_compact_string() -> Result<()> {
+        let id = ObjectId::from_primitive(u128::MAX)?;
+        let encoded = id.to_compact_string(CompactEncoding::Base62);
+        assert_eq!(encoded, "7n42DGM5Tflk9n8mt7Fhc7");
+        assert_eq!(
+            ObjectId::from_compact_str(&encoded, CompactEncoding::Base62)?.into_primitive(),
+            u128::MAX
+        );
+        assert_eq!(
+            ObjectId::new_root().to_compact_string(CompactEncoding::Base16),
+            "1"
+        );
+
+        let err = ObjectId::from_compact_str("0", CompactEncoding::Base16).unwrap_err();
+        assert_eq!(err.type_name, "ObjectId");
+        assert!(ObjectId::from_compact_str("zz", CompactEncoding::Base16).is_err());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "never-panic"))]
+    #[test]
+    fn test_range_summary() {
+        assert_eq!(Port::RANGE_SUMMARY, "1..=9999");
+        assert_eq!(ResponseCode::RANGE_SUMMARY, "100..=600");
+
+        let panic = std::panic::catch_unwind(|| port!(9000) + 1000).unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().map(String::as_str),
+            Some("Addition overflow for `Port` (valid: 1..=9999)")
+        );
+
+        let err = Port::from_primitive(0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Value too small: 0 (min: 1) for `Port` (valid: 1..=9999)"
+        );
+        assert!(err.downcast_ref::<ClampError<u16>>().is_some());
+
+        let err = ResponseCode::from_primitive(700).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value 700 for `ResponseCode` (valid: 100..=600)"
+        );
+    }
+
+    #[clamped(u16, default = 200, lower = 200, upper = 299)]
+    #[derive(Debug, Clone, Copy)]
+    enum Reply {
+        #[eq(200)]
+        #[aka(Success200, Fine)]
+        Okay,
+        #[eq(204)]
+        NoContent,
+        #[other]
+        Other,
+    }
+
+    #[test]
+    fn test_name_aliases() -> Result<()> {
+        let reply = Reply::from_primitive(200)?;
+        assert!(reply.is_okay());
+        assert!(reply.is_success_200());
+        assert!(reply.is_fine());
+        assert_eq!(Reply::SUCCESS_200.into_primitive(), 200);
+        assert!(Reply::FINE.is_okay());
+        assert!(!Reply::new_no_content().is_success_200());
+        Ok(())
+    }
+
+    #[clamped(u8 as Hard, default = 1, lower = 1, upper = 5, module = gain, guard_name = GainLock)]
+    #[derive(Debug, Clone, Copy)]
+    struct Gain;
+
+    #[clamped(u8, default = 0, upper = 3, value_name = LaneRaw)]
+    #[derive(Debug, Clone, Copy)]
+    enum Lane {
+        #[eq(0)]
+        Left,
+        #[range(1..=2)]
+        Middle,
+        #[eq(3)]
+        Right,
+    }
+
+    #[test]
+    fn test_naming_params() -> Result<()> {
+        let mut level = Gain::default();
+        let mut lock: gain::GainLock<'_> = level.modify();
+        *lock = 4;
+        assert!(lock.commit().is_ok());
+        assert_eq!(*level, 4);
+
+        let auto: gain::AutoGainLock<'_> = level.modify_auto();
+        drop(auto);
+
+        let _: fn(&mut Gain) -> gain::Guard<'_> = Gain::modify;
+        let _: fn(clamped_lane::LaneRaw<u8>) -> Lane = Lane::Left;
+        let _: fn(clamped_lane::MiddleValue) -> Lane = Lane::Middle;
+        let _: fn(clamped_lane::Value<u8>) -> Lane = Lane::Right;
+        Ok(())
+    }
+
+    #[clamped(u8, singleton = 42)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Answer;
+
+    #[clamped(u32, singleton = 3_405_691_582, module = magic)]
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct Magic;
+
+    #[test]
+    fn test_singleton() {
+        assert_eq!(std::mem::size_of::<Answer>(), 0);
+        assert_eq!(Answer::VALUE, 42);
+        assert_eq!(*Answer::new(), 42);
+        assert_eq!(u8::from(Answer), 42);
+        assert_eq!(Answer::try_from(42).unwrap(), Answer);
+        assert!(matches!(
+            Answer::try_from(43),
+            Err(ClampError::TooLarge { val: 43, max: 42 })
+        ));
+        assert_eq!(Answer.to_string(), "42");
+        assert_eq!(Answer::RANGE_SUMMARY, "42");
+        assert_eq!(<Magic as InherentLimits<u32>>::MAX, 0xCAFE_BABE);
+        assert_eq!(magic::Magic.get(), 0xCAFE_BABE);
+    }
+
+    #[test]
+    fn test_clamped_reader_writer() -> Result<()> {
+        let mut writer = ClampedWriter::<_, ResponseCode>::new(Vec::new());
+        writer.write_all(&[
+            ResponseCode::new_not_found(),
+            ResponseCode::from_primitive(503)?,
+        ])?;
+        assert!(matches!(
+            writer.write_primitive(50),
+            Err(FrameError::Invalid { offset: 4, .. })
+        ));
+        assert_eq!(writer.offset(), 4);
+
+        let mut bytes = writer.into_inner();
+        assert_eq!(bytes, [0x01, 0x94, 0x01, 0xf7]);
+
+        bytes.extend([0x00, 0x05, 0x00, 0xc8, 0x02]);
+        let mut reader = ClampedReader::<_, ResponseCode>::new(bytes.as_slice());
+        assert!(reader.next().unwrap()?.is_not_found());
+        assert!(reader.next().unwrap()?.is_server_error());
+
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.offset(), 4);
+        assert!(matches!(
+            err,
+            FrameError::Invalid {
+                source: ClampError::TooSmall { val: 5, .. },
+                ..
+            }
+        ));
+
+        assert!(reader.next().unwrap()?.is_success());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(FrameError::Truncated {
+                offset: 8,
+                read: 1,
+                expected: 2
+            }))
+        ));
+        assert!(reader.next().is_none());
+        assert_eq!(reader.offset(), 9);
+
+        let levels =
+            ClampedReader::<_, Loudness>::new(&[1u8, 10][..]).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(levels.iter().map(|l| **l).collect::<Vec<_>>(), [1, 10]);
+        Ok(())
+    }
+
+    #[clamped(u8 as Soft, default = 5, lower = 1, upper = 10, guard_drop = SilentOnDrop)]
+    #[derive(Debug, Clone, Copy)]
+    struct Dimmer;
+
+    #[clamped(u8 as Soft, default = 5, lower = 1, upper = 10, guard_drop = PanicOnDrop)]
+    #[derive(Debug, Clone, Copy)]
+    struct Contrast;
+
+    #[test]
+    fn test_guard_drop_policy() {
+        let mut dimmer = Dimmer::default();
+        {
+            let mut guard = dimmer.modify();
+            *guard = 7;
+        }
+        assert_eq!(*dimmer, 5);
+
+        let mut contrast = Contrast::default();
+        let mut guard = contrast.modify();
+        *guard = 7;
+        assert!(guard.commit().is_ok());
+        assert_eq!(*contrast, 7);
+
+        let result = std::panic::catch_unwind(|| {
+            let mut contrast = Contrast::default();
+            let _guard = contrast.modify();
+        });
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+    }
+
+    // Declared without the prelude in scope, as a library would in a public module.
+    mod library {
+        #![deny(unused_imports)]
+
+        #[checked_rs_macros::clamped(u8 as Hard, default = 3, lower = 1, upper = 5, explicit_imports)]
+        #[derive(Debug, Clone, Copy)]
+        pub struct Stars;
+
+        #[checked_rs_macros::clamped(u8, default = 0, upper = 9, explicit_imports)]
+        #[derive(Debug, Clone, Copy)]
+        pub enum Channel {
+            #[eq(0)]
+            Off,
+            #[range(1..=9)]
+            On,
+        }
+    }
+
+    pub use library::{Channel, Stars};
+
+    #[test]
+    fn test_explicit_imports() -> Result<()> {
+        let mut stars = Stars::default();
+        stars += 2;
+        assert_eq!(*stars, 5);
+
+        let channel = Channel::from_primitive(4)?;
+        assert!(channel.is_on());
+        assert_eq!(*Channel::default(), 0);
+        Ok(())
+    }
+
+    #[clamped(u16, default = 1, lower = 1, upper = 3, store = u8, auto_invalid)]
+    #[derive(Debug, Clone, Copy)]
+    enum Frame {
+        #[eq(1)]
+        Data,
+        #[eq(2)]
+        Ping,
+        #[eq(3)]
+        Close,
+    }
+
+    #[test]
+    fn test_auto_invalid() -> Result<()> {
+        use serde::{de::value, Deserialize};
+
+        assert!(Frame::from_primitive(4).is_err());
+
+        let unknown = Frame::from_primitive_or_invalid(700);
+        assert!(unknown.is_invalid());
+        assert_eq!(unknown.into_primitive(), 700);
+        assert!(unknown.into_valid().is_err());
+
+        let ping = Frame::from_primitive_or_invalid(2);
+        assert!(ping.is_ping());
+        assert!(ping.into_valid()?.is_ping());
+
+        let de = Frame::deserialize(value::U16Deserializer::<value::Error>::new(700))?;
+        assert!(de.is_invalid());
+        assert_eq!(de.into_primitive(), 700);
+        assert!(Frame::deserialize(value::U16Deserializer::<value::Error>::new(3))?.is_close());
+        Ok(())
+    }
+
+    #[clamped(u8, default = 0)]
+    #[derive(Debug, Clone, Copy)]
+    enum Register {
+        #[eq(0)]
+        Idle,
+        #[mask(0b1000_0000)]
+        Fault,
+        #[mask(0b0000_0011)]
+        Armed,
+        #[mask(0b0000_0001)]
+        Ready,
+        #[other]
+        Busy,
+    }
+
+    #[clamped(u8, default = 0, upper = 3, codegen = table)]
+    #[derive(Debug, Clone, Copy)]
+    enum Flags {
+        #[eq(0)]
+        Clear,
+        #[mask(0b10)]
+        High,
+        #[mask(0b01)]
+        Low,
+    }
+
+    #[test]
+    fn test_mask_variants() -> Result<()> {
+        assert!(Register::from_primitive(0)?.is_idle());
+        assert!(Register::from_primitive(0b1000_0011)?.is_fault());
+        assert!(Register::from_primitive(0b0000_0111)?.is_armed());
+        assert!(Register::from_primitive(0b0000_0101)?.is_ready());
+        assert!(Register::from_primitive(0b0000_0100)?.is_busy());
+        assert_eq!(
+            *Register::from_primitive(0b1000_0011)?.as_primitive(),
+            0b1000_0011
+        );
+
+        assert!(Flags::from_primitive(0)?.is_clear());
+        assert!(Flags::from_primitive(0b11)?.is_high());
+        assert!(Flags::from_primitive(0b01)?.is_low());
+        assert_eq!(Flags::from_primitive(0b11)?.into_primitive(), 0b11);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gaps() {
+        assert_eq!(
+            ResponseCode::gaps().collect::<Vec<_>>(),
+            [101..=199, 201..=299, 301..=399, 401..=403, 405..=499]
+        );
+        assert_eq!(Flags::gaps().count(), 0);
+        assert_eq!(
+            Register::gaps().take(3).collect::<Vec<_>>(),
+            [2..=2, 4..=4, 6..=6]
+        );
+        assert_eq!(Register::gaps().last(), Some(126..=126));
+    }
+
+    #[cfg(not(feature = "never-panic"))]
+    #[clamped(u8 as Hard, default = 0, behavior = { add: Saturating, sub: Saturating, default: Panicking }, upper = 100)]
+    #[derive(Debug, Clone, Copy)]
+    struct Volume;
+
+    #[cfg(not(feature = "never-panic"))]
+    #[test]
+    fn test_behavior_per_operator() -> Result<()> {
+        let val = Volume::new(90);
+        assert_eq!(*(val + 20), 100);
+        assert_eq!(*(Volume::new(10) - 20), 0);
+        assert!(std::panic::catch_unwind(|| val * 2).is_err());
+
+        let mut val = Volume::new(90);
+        let g = val.modify();
+        assert_eq!(g.preview_add(20)?, 100);
+        assert!(g.preview_mul(2).is_err());
+        g.discard();
+        Ok(())
+    }
+
+    #[clamped(u16 as Hard, default = 0, upper = 9999, null = 0)]
+    #[derive(Debug, Clone, Copy)]
+    struct SessionId;
+
+    #[clamped(u8, default = 1, lower = 1, upper = 64, null = 64)]
+    #[derive(Debug, Clone, Copy)]
+    enum Hops {
+        #[range(1..=63)]
+        Count,
+        #[eq(64)]
+        Unknown,
+    }
+
+    #[test]
+    fn test_null_value() -> Result<()> {
+        let id = SessionId::default();
+        assert!(id.is_unset());
+        assert!(id.get_set().is_none());
+        assert_eq!(serde_json::to_string(&id)?, "null");
+        assert!(serde_json::from_str::<SessionId>("null")?.is_unset());
+
+        let id: SessionId = serde_json::from_str("42")?;
+        assert_eq!(id.get_set().map(|id| *id), Some(42));
+        assert_eq!(serde_json::to_string(&id)?, "42");
+        assert!(serde_json::from_str::<SessionId>("10000").is_err());
+
+        let hops = Hops::unset();
+        assert!(hops.is_unknown() && hops.is_unset());
+        assert_eq!(hops.set_or_default().into_primitive(), 1);
+        assert_eq!(serde_json::to_string(&hops)?, "null");
+        assert_eq!(serde_json::from_str::<Hops>("7")?.into_primitive(), 7);
+        assert_eq!(Hops::NULL, 64);
+        Ok(())
+    }
+
+    fn as_string<S: serde::Serializer>(value: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    #[clamped(u8 as Hard, default = 3, lower = 1, upper = 5)]
+    #[derive(Debug, Clone, Copy, serde::Serialize)]
+    struct Rating(#[serde(serialize_with = "as_string")] u8);
+
+    #[clamped(u8, default = 1, lower = 1, upper = 3)]
+    #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Tier {
+        #[eq(1)]
+        #[serde(rename = "basic")]
+        Free,
+        #[range(2..=3)]
+        PaidPlan,
+    }
+
+    #[test]
+    fn test_derive_helper_attributes() -> Result<()> {
+        assert_eq!(serde_json::to_string(&Rating::new(4))?, "\"4\"");
+        assert_eq!(
+            serde_json::to_string(&Tier::from_primitive(1)?)?,
+            r#"{"basic":1}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Tier::from_primitive(3)?)?,
+            r#"{"paid_plan":3}"#
+        );
+        Ok(())
+    }
+
+    #[clamped(u8 as Hard, default = 3, lower = 1, upper = 5)]
+    #[derive(Debug, Clone, Copy, serde::Deserialize)]
+    #[serde(transparent)]
+    struct Grade(u8);
+
+    #[test]
+    fn test_derived_deserialize_is_checked() -> Result<()> {
+        assert_eq!(*serde_json::from_str::<Grade>("4")?, 4);
+        assert!(serde_json::from_str::<Grade>("9").is_err());
+
+        assert!(serde_json::from_str::<Tier>(r#"{"paid_plan":3}"#)?.is_paid_plan());
+        assert!(serde_json::from_str::<Tier>(r#"{"paid_plan":7}"#).is_err());
+        assert!(serde_json::from_str::<Tier>(r#"{"basic":2}"#).is_err());
+        Ok(())
+    }
+
+    #[clamped(u16, default = 200, lower = 200, upper = 599, serde_names)]
+    #[derive(Debug, Clone, Copy)]
+    #[serde(rename_all = "snake_case")]
+    enum HttpStatus {
+        #[eq(200)]
+        #[serde(rename = "ok", alias = "success")]
+        Ok,
+        #[eq(404)]
+        #[alias(410)]
+        NotFound,
+        #[eq(500)]
+        #[alias(503, 504)]
+        ServerError,
+        #[other]
+        Other,
+    }
+
+    #[test]
+    fn test_serde_names() -> Result<()> {
+        assert_eq!(serde_json::to_string(&HttpStatus::new_ok())?, r#""ok""#);
+        assert_eq!(
+            serde_json::to_string(&HttpStatus::from_primitive(404)?)?,
+            r#""not_found""#
+        );
+        assert_eq!(
+            serde_json::to_string(&HttpStatus::from_primitive(301)?)?,
+            "301"
+        );
+
+        assert!(serde_json::from_str::<HttpStatus>(r#""success""#)?.is_ok());
+        assert!(serde_json::from_str::<HttpStatus>(r#""server_error""#)?.is_server_error());
+        assert!(serde_json::from_str::<HttpStatus>("404")?.is_not_found());
+        assert!(serde_json::from_str::<HttpStatus>(r#""Ok""#).is_err());
+        assert!(serde_json::from_str::<HttpStatus>("600").is_err());
+
+        // legacy values are mapped onto the current variants
+        assert!(serde_json::from_str::<HttpStatus>("410")?.is_not_found());
+        assert_eq!(
+            serde_json::from_str::<HttpStatus>("503")?.into_primitive(),
+            500
+        );
+        assert!(HttpStatus::from_primitive(503)?.is_other());
+        assert!(HttpStatus::from_primitive_or_alias(504)?.is_server_error());
+        assert!(HttpStatus::from_primitive_or_alias(600).is_err());
+        Ok(())
+    }
+
+    #[clamped(u16 as Hard, default = 1013, lower = 870, upper = 1085)]
+    #[derive(Debug)]
+    struct Pressure;
+
+    #[test]
+    fn test_default_array() -> Result<()> {
+        // `Pressure` is not `Copy`, and zero is outside of its limits
+        const READINGS: [Pressure; 4] = Pressure::default_array();
+        assert!(READINGS.iter().all(|p| **p == 1013));
+
+        let codes = ResponseCode::default_array::<3>();
+        assert!(codes.iter().all(|code| code.into_primitive() == 600));
+        assert_eq!(
+            HttpStatus::DEFAULT.into_primitive(),
+            HttpStatus::default().into_primitive()
+        );
+        assert!(Hops::DEFAULT.is_count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_freeze() -> Result<()> {
+        let port = Port::new(8080).freeze();
+        assert_eq!(**port, 8080);
+        assert_eq!(port, Port::new(8080).freeze());
+        assert!(port > Port::new(80).freeze());
+
+        let status = HttpStatus::new_not_found().freeze();
+        let json = serde_json::to_string(&status)?;
+        assert_eq!(json, r#""not_found""#);
+        assert_eq!(serde_json::from_str::<Frozen<HttpStatus>>(&json)?, status);
+
+        let code = ResponseCode::new_not_found().freeze();
+        assert!(code.is_not_found());
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_raw() {
+        let raw = Port::new(8080).into_raw();
+        assert_eq!(raw.to_string(), "8080");
+        assert_eq!(*raw.map(|n| n + 1).validate::<Port>().unwrap(), 8081);
+        assert!(matches!(
+            raw.map(|n| n * 2).validate::<Port>(),
+            Err(ClampError::TooLarge {
+                val: 16160,
+                max: 9999
+            })
+        ));
+        assert_eq!(raw.into_inner(), 8080);
+
+        let raw = ResponseCode::new_not_found().into_raw();
+        assert!(raw.validate::<ResponseCode>().unwrap().is_not_found());
+        assert!(raw.map(|_| 99).validate::<ResponseCode>().is_err());
+    }
+
+    #[clamped(u8 as Hard, default = 50, behavior = Saturating, upper = 100, metrics)]
+    #[derive(Debug, Clone, Copy)]
+    struct Throttle;
+
+    #[cfg(not(feature = "never-panic"))]
+    #[clamped(u8, default = 1, behavior = Panicking, lower = 1, upper = 3, metrics)]
+    #[derive(Debug, Clone, Copy)]
+    enum Gear {
+        #[eq(1)]
+        Low,
+        #[eq(2)]
+        Mid,
+        #[eq(3)]
+        High,
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        use crate::metrics::registered_metrics;
+
+        let throttle = Throttle::default();
+        assert_eq!(*(throttle + 10), 60);
+        assert_eq!(*(throttle + 60), 100);
+        assert_eq!(*(throttle * 3), 100);
+        assert!(Throttle::from_primitive(101).is_err());
+        assert!("200".parse::<Throttle>().is_err());
+
+        // Other tests may convert values concurrently, so rejections are only bounded below.
+        let metrics = Throttle::metrics().snapshot();
+        assert_eq!((metrics.saturated, metrics.panicked), (2, 0));
+        assert!(metrics.rejected >= 2);
+        assert!(registered_metrics()
+            .iter()
+            .any(|m| m.type_name == "Throttle"));
+    }
+
+    #[cfg(all(feature = "metrics", not(feature = "never-panic")))]
+    #[test]
+    fn test_metrics_panicked() {
+        use crate::metrics::registered_metrics;
+
+        assert!(std::panic::catch_unwind(|| Gear::new_high() + Gear::new_low()).is_err());
+        assert!(Gear::from_primitive(0).is_err());
+        assert_eq!(Gear::metrics().panicked(), 1);
+        assert!(Gear::metrics().rejected() >= 1);
+        assert!(registered_metrics().iter().any(|m| m.type_name == "Gear"));
+    }
+
+    #[cfg(feature = "reflect")]
+    #[test]
+    fn test_reflect() {
+        use crate::reflect::{find_descriptor, registered_descriptors, PrimitiveKind, Reflect};
+
+        let port = Port::descriptor();
+        assert_eq!(port.type_name, "Port");
+        assert_eq!(port.primitive, PrimitiveKind::U16);
+        assert_eq!((port.min, port.max), (1, 9999));
+        assert_eq!(port.ranges, &[(1, 9999)]);
+        assert!(port.exacts.is_empty());
+
+        let code = <ResponseCode as Reflect>::descriptor();
+        assert_eq!(code.ranges, &[(500, 599)]);
+        assert_eq!(code.exacts, &[100, 200, 300, 400, 404, 600]);
+        assert_eq!(code.default, 600);
+        assert_eq!(code.behavior, "Saturating");
+
+        assert!(registered_descriptors().any(|d| d.type_name == "Port"));
+        assert_eq!(find_descriptor("ResponseCode"), Some(code));
+        assert_eq!(find_descriptor("Missing"), None);
+    }
+
+    #[cfg(feature = "reflect")]
+    #[test]
+    fn test_conversion_manifest() -> Result<()> {
+        use crate::reflect::write_conversion_manifest;
+
+        let listed: Vec<_> = Port::descriptor()
+            .conversions
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert!(listed.contains(&"Deref<Target = u16> for Port".to_string()));
+        assert!(listed.contains(&"From<Port> for u32".to_string()));
+        assert!(listed.contains(&"From<u16> for Port (may panic)".to_string()));
+        assert!(listed.contains(&"TryFrom<&str> for Port".to_string()));
+
+        let full_width = Sequence::descriptor().conversions;
+        assert!(full_width
+            .iter()
+            .any(|c| c.trait_name == "From" && c.from == "u16" && !c.may_panic));
+
+        let mut manifest = Vec::new();
+        write_conversion_manifest(&mut manifest)?;
+        let manifest = String::from_utf8(manifest)?;
+        assert!(manifest.contains("Port:\n    Deref<Target = u16> for Port\n"));
+        assert!(manifest.contains("    From<u8> for Port (may panic)\n"));
+        Ok(())
+    }
+
+    // The derive macros of `bytemuck` are not a dependency here. `clamped` replaces the derives
+    // before they would run, so these only need to resolve.
+    #[cfg(feature = "bytemuck")]
+    mod bytemuck_derive {
+        pub use serde::Serialize as Zeroable;
+        pub use serde::Serialize as Pod;
+        pub use serde::Serialize as TransparentWrapper;
+        pub use serde::Serialize as NoUninit;
+        pub use serde::Serialize as CheckedBitPattern;
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[clamped(u8 as Soft, default = 0, upper = 100)]
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        bytemuck_derive::Zeroable,
+        bytemuck_derive::Pod,
+        bytemuck_derive::TransparentWrapper,
+    )]
+    struct Level;
+
+    #[cfg(feature = "bytemuck")]
+    #[clamped(u8 as Hard, default = 0, upper = 100)]
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        bytemuck_derive::Zeroable,
+        bytemuck_derive::NoUninit,
+        bytemuck_derive::CheckedBitPattern,
+    )]
+    struct Gauge;
+
+    // Only the derived traits are implemented, so this one may be implemented by hand.
+    #[cfg(feature = "bytemuck")]
+    #[clamped(u8 as Hard, default = 0, upper = 100)]
+    #[derive(Debug, Clone, Copy)]
+    struct HandWritten;
+
+    #[cfg(feature = "bytemuck")]
+    unsafe impl bytemuck::Zeroable for HandWritten {}
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck() {
+        use bytemuck::{checked, TransparentWrapper, Zeroable};
+
+        let raw = [10u8, 50, 100];
+        let gauges: &[Gauge] = checked::try_cast_slice(&raw).unwrap();
+        assert_eq!(*gauges[2], 100);
+        assert!(checked::try_cast_slice::<u8, Gauge>(&[10, 101]).is_err());
+        assert_eq!(*Gauge::zeroed(), 0);
+        assert_eq!(bytemuck::cast_slice::<Gauge, u8>(gauges), raw);
+        assert_eq!(*HandWritten::zeroed(), 0);
+
+        let levels: &[Level] = bytemuck::cast_slice(&[1u8, 200]);
+        assert!(!levels[1].is_valid());
+        assert_eq!(**Level::wrap_ref(&7), 7);
+    }
+
+    #[clamped(u16 as Hard, default = 80, lower = 1, upper = 9999, literal = crate::tests::port)]
+    #[derive(Debug, Clone, Copy)]
+    struct Port;
+
+    const HTTP: Port = port!(80);
+
+    #[test]
+    fn test_runtime_bounds() -> Result<()> {
+        assert_eq!(Port::runtime_spec(), ClampSpec::new(1, 9999)?);
+
+        let val = HTTP.with_runtime_bounds(ClampSpec::new(1, 1024)?)?;
+        assert_eq!(*val, 80);
+        assert!(port!(8080)
+            .with_runtime_bounds(ClampSpec::new(1, 1024)?)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_literal_macro() {
+        assert_eq!(*HTTP, 80);
+        assert_eq!(*port!(8080), 8080);
+        assert_eq!(Port::new_const(1), Port::MIN);
+    }
+
+    #[clamped(u16 as Hard, default = 0, upper = 9999, secret)]
+    #[derive(Debug, Clone, Copy)]
+    struct Pin;
+
+    #[clamped(u8, default = 0, secret)]
+    #[derive(Debug, Clone, Copy)]
+    enum KeyIndex {
+        #[eq(0)]
+        Primary,
+        #[other]
+        Backup,
+    }
+
+    #[test]
+    fn test_secret() -> Result<()> {
+        let pin = Pin::from_primitive(1234)?;
+        assert_eq!(format!("{:?}", pin), "Pin(<redacted>)");
+        assert_eq!(*pin, 1234);
+
+        let index = KeyIndex::from_primitive(7)?;
+        assert_eq!(format!("{:?}", index), "KeyIndex(<redacted>)");
+        assert!(index.is_backup());
+        Ok(())
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_secret_zeroize() -> Result<()> {
+        use zeroize::Zeroize;
+
+        let mut pin = Pin::from_primitive(1234)?;
+        pin.zeroize();
+        assert_eq!(*pin, 0);
+
+        let mut index = KeyIndex::from_primitive(7)?;
+        index.zeroize();
+        assert!(index.is_primary());
+        Ok(())
+    }
+
+    #[clamped(u8 as Hard, default = 5, behavior = SaturatingWithLog, lower = 1, upper = 10)]
+    #[derive(Debug, Clone, Copy)]
+    struct Brightness;
+
+    #[test]
+    fn test_saturating_with_log() {
+        assert_eq!(*Brightness::new(0), 1);
+        assert_eq!(*Brightness::new(200), 10);
+        assert_eq!(*(Brightness::new(8) + 5), 10);
+        assert_eq!(*(Brightness::new(2) - 5), 1);
+        assert_eq!(*(Brightness::new(2) * 3), 6);
+    }
+
+    #[clamped(u8 as Hard, default = 0, upper = 3)]
+    #[derive(Debug, Clone, Copy)]
+    struct Prescaler;
+
+    #[clamped(u8, default = 0, upper = 15)]
+    #[derive(Debug, Clone, Copy)]
+    enum ClockSource {
+        #[eq(0)]
+        Internal,
+        #[eq(1)]
+        External,
+        #[range(2..=15)]
+        Reserved,
+    }
+
+    #[derive(Debug, RegisterMap)]
+    #[register(u64)]
+    struct ClockConfig {
+        #[register(offset = 0, width = 2)]
+        prescaler: Prescaler,
+        #[register(offset = 4, width = 4)]
+        source: ClockSource,
+        #[register(offset = 32, width = 16)]
+        port: Port,
+    }
+
+    #[test]
+    fn test_register_map() -> Result<()> {
+        let config = ClockConfig::from_register(0x1F90_0000_0013)?;
+        assert_eq!(*config.prescaler, 3);
+        assert!(config.source.is_external());
+        assert_eq!(*config.port, 8080);
+        assert_eq!(config.to_register(), 0x1F90_0000_0013);
+
+        let err = ClockConfig::from_register(0x0000_0000_0013).unwrap_err();
+        assert!(err.to_string().contains("field `port`"));
+        Ok(())
+    }
+
+    const PRECIOUS_METALS: [u16; 4] = [959, 961, 962, 964];
+    const FUND_CODES: &[u16] = &[970, 979, 984];
+
+    #[clamped(u16, default = 0, upper = 999)]
+    #[derive(Debug, Clone, Copy)]
+    enum Currency {
+        #[eq(include_values = "testdata/currency_codes.txt")]
+        Major,
+        #[eq(include_values = PRECIOUS_METALS)]
+        Metal,
+        #[eq(include_values = FUND_CODES)]
+        Fund,
+        #[eq(0)]
+        Unset,
+        #[other]
+        Minor,
+    }
+
+    #[test]
+    fn test_include_values() -> Result<()> {
+        assert!(Currency::from_primitive(840)?.is_major());
+        assert!(Currency::from_primitive(36)?.is_major());
+        assert!(Currency::from_primitive(959)?.is_metal());
+        assert!(Currency::from_primitive(984)?.is_fund());
+        assert!(Currency::from_primitive(0)?.is_unset());
+        assert!(Currency::from_primitive(578)?.is_minor());
+        assert_eq!(Currency::new_major(), Currency::from_primitive(36)?);
+        assert_eq!(*Currency::from_primitive(962)?, 962);
+        assert!(Currency::from_primitive(1000).is_err());
+        Ok(())
+    }
+
+    #[clamped(u8 as Hard, default = 1, upper = 100, div_by_zero = saturate_max)]
+    #[derive(Debug, Clone, Copy)]
+    struct Ratio;
+
+    #[clamped(u8 as Soft, default = 7, behavior = Saturating, lower = 1, upper = 50, div_by_zero = return_default)]
+    #[derive(Debug, Clone, Copy)]
+    struct Divisor;
+
+    #[clamped(u16 as Hard, default = 0, upper = 1000, div_by_zero = checked_only)]
+    #[derive(Debug, Clone, Copy)]
+    struct Share;
+
+    #[test]
+    fn test_div_by_zero() {
+        let ratio = Ratio::new(40);
+        assert_eq!(*(ratio / 4), 10);
+        assert_eq!(*(ratio / 0), 100);
+        assert_eq!(*(ratio % 0), 100);
+        assert_eq!(*(ratio / Ratio::new(0)), 100);
+
+        let mut divisor = Divisor::new(30);
+        divisor /= 0;
+        assert_eq!(*divisor, 7);
+        assert_eq!(*(Divisor::new(30) % 0), 7);
+
+        let share = Share::new(900);
+        assert_eq!(share.checked_div(3).map(|v| *v), Some(300));
+        assert_eq!(share.checked_rem(7).map(|v| *v), Some(4));
+        assert!(share.checked_div(0).is_none());
+        assert!(share.checked_rem(0).is_none());
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            Port::explain(8080),
+            Explanation::Valid {
+                type_name: "Port",
+                value: 8080,
+                min: 1,
+                max: 9999
+            }
+        );
+        assert_eq!(
+            Port::explain(0).to_string(),
+            "0 is not a valid `Port`, the nearest valid value is 1"
+        );
+
+        let explanation = ResponseCode::explain(404);
+        assert_eq!(
+            explanation.variant_path().as_deref(),
+            Some("ResponseCode::NotFound")
+        );
+        assert_eq!(
+            ResponseCode::explain(503).to_string(),
+            "503 is `ResponseCode::ServerError` (500..=599)"
+        );
+        assert_eq!(
+            ResponseCode::explain(150).to_string(),
+            "150 is `ResponseCode::Unknown`"
+        );
+        assert_eq!(
+            ResponseCode::explain(42),
+            Explanation::Invalid {
+                type_name: "ResponseCode",
+                value: 42,
+                nearest: 100
+            }
+        );
+    }
+
+    #[test]
+    fn test_batch_ops() {
+        let mut ports = (0..150u16)
+            .map(|i| Port::from_primitive(1 + i * 50).unwrap())
+            .collect::<Vec<_>>();
+        ports[130] = Port::from_primitive(Port::MAX).unwrap();
+
+        Port::add_scalar_saturating(&mut ports, 100);
+        assert_eq!(*ports[0], 101);
+        assert_eq!(*ports[99], 5051);
+        assert_eq!(*ports[130], 9999);
+        assert_eq!(*ports[149], 7551);
+
+        sub_scalar_saturating(&mut ports, 200);
+        assert_eq!(*ports[0], 1);
+        assert_eq!(*ports[99], 4851);
+        assert_eq!(*ports[130], 9799);
+
+        Port::add_scalar_saturating(&mut ports, u16::MAX);
+        assert!(ports.iter().all(|port| **port == Port::MAX));
+
+        Port::sub_scalar_saturating(&mut ports, u16::MAX);
+        assert!(ports.iter().all(|port| **port == Port::MIN));
+    }
+
+    #[test]
+    fn test_value_combinators() -> Result<()> {
+        let code = ResponseCode::from_primitive(503)?;
+        assert_eq!(code.map_value(|n| n / 100), 5);
+        assert_eq!(code.if_server_error(|n| n - 500), Some(3));
+        assert_eq!(code.if_not_found(|n| n), None);
+
+        let code = ResponseCode::from_primitive(150)?;
+        assert_eq!(code.if_unknown(|n| n * 2), Some(300));
+        Ok(())
+    }
+
+    #[test]
+    fn test_variant_paths() -> Result<()> {
+        let code = ResponseCode::from_primitive(404)?;
+        assert_eq!(code.path().to_string(), "ResponseCode/NotFound");
+        assert_eq!(code.path().leaf(), Some("NotFound"));
+        assert_eq!(ResponseCode::from_primitive(150)?.path().depth(), 2);
+
+        assert_eq!(
+            ResponseCode::leaf_variants(),
+            &[
+                "Continue",
+                "Success",
+                "Redirection",
+                "BadRequest",
+                "NotFound",
+                "ServerError",
+                "Unknown",
+                "Invalid"
+            ]
+        );
+        assert_eq!(
+            ResponseCode::variant_paths()
+                .map(|path| path.to_string())
+                .last()
+                .as_deref(),
+            Some("ResponseCode/Invalid")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ranges_of() -> Result<()> {
+        let code = ResponseCode::from_primitive(503)?;
+        assert_eq!(code.kind(), ResponseCodeKind::ServerError);
+        assert_eq!(ResponseCode::ranges_of(code.kind()), &[500..=599]);
+        assert_eq!(
+            ResponseCode::ranges_of(ResponseCodeKind::NotFound),
+            &[404..=404]
+        );
+        assert_eq!(
+            ResponseCode::ranges_of(ResponseCodeKind::Unknown),
+            &[101..=199, 201..=299, 301..=399, 401..=403, 405..=499]
+        );
+
+        assert_eq!(Register::ranges_of(RegisterKind::Idle), &[0..=0]);
+        assert_eq!(Register::ranges_of(RegisterKind::Fault), &[128..=255]);
+        assert_eq!(
+            Register::ranges_of(RegisterKind::Armed)[..2],
+            [3..=3, 7..=7]
+        );
+        assert_eq!(
+            Register::ranges_of(RegisterKind::Ready)[..2],
+            [1..=1, 5..=5]
+        );
+        assert_eq!(Register::ranges_of(RegisterKind::Busy)[..2], [2..=2, 4..=4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_fingerprint() {
+        #[clamped(u16 as Hard, default = 80, lower = 1, upper = 9999)]
+        #[derive(Debug, Clone, Copy)]
+        struct SamePort;
+
+        #[clamped(u16 as Hard, default = 80, lower = 1, upper = 9998)]
+        #[derive(Debug, Clone, Copy)]
+        struct NarrowerPort;
+
+        assert_eq!(Port::RANGE_FINGERPRINT, SamePort::RANGE_FINGERPRINT);
+        assert_ne!(Port::RANGE_FINGERPRINT, NarrowerPort::RANGE_FINGERPRINT);
+        assert_ne!(Port::RANGE_FINGERPRINT, Pin::RANGE_FINGERPRINT);
+        assert_ne!(ResponseCode::RANGE_FINGERPRINT, Port::RANGE_FINGERPRINT);
+    }
+
+    #[clamped(u32 as Hard, default = 0x41, upper = 127)]
+    #[derive(Debug, Clone, Copy)]
+    struct Ascii;
+
+    #[clamped(u32, default = 0x1F600, lower = 0xD000, upper = 0x1F64F)]
+    #[derive(Debug, Clone, Copy)]
+    enum CodePoint {
+        #[range(0x1F600..=0x1F64F)]
+        Emoji,
+        #[other]
+        Other,
+    }
+
+    #[test]
+    fn test_char_conversions() -> Result<()> {
+        let a = Ascii::try_from('a')?;
+        assert_eq!(char::from(a), 'a');
+        assert!(Ascii::try_from('é').is_err());
+
+        let smile = CodePoint::try_from('😀')?;
+        assert!(smile.is_emoji());
+        assert_eq!(char::try_from(smile)?, '😀');
+
+        // the limits span the surrogates, so the conversion into `char` can fail
+        let surrogate = CodePoint::from_primitive(0xD800)?;
+        assert!(char::try_from(surrogate).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arbitrary_invalid() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+
+        for _ in 0..1000 {
+            let n = Port::arbitrary_invalid(&mut rng).unwrap();
+            assert!(Port::validate(n).is_err());
+
+            let n = ResponseCode::arbitrary_invalid(&mut rng).unwrap();
+            assert!(ResponseCode::from_primitive(n).is_err());
+
+            let n = Divisor::arbitrary_invalid(&mut rng).unwrap();
+            assert!(Divisor::validate(n).is_err());
+        }
+
+        assert_eq!(Opcode::arbitrary_invalid(&mut rng), None);
+    }
+
+    #[test]
+    fn test_shard_router() -> Result<()> {
+        let router = ShardRouter::<Ascii>::new();
+        assert_eq!(router.shard_count(), 128);
+        assert_eq!(*router.route("user:42")?, *router.route("user:42")?);
+
+        // The shards are part of the contract with other nodes, so they must never change
+        assert_eq!(*router.route("user:42")?, 54);
+        assert_eq!(*router.route("order:7")?, 111);
+        assert_eq!(*router.route(7u32.to_le_bytes())?, 54);
+        assert_eq!(*router.route_hash(0)?, 0);
+        assert_eq!(*router.route_hash(u64::MAX)?, 127);
+
+        let mut counts = [0u32; 128];
+        for key in 0..12_800u32 {
+            counts[*router.route(key.to_le_bytes())? as usize] += 1;
+        }
+        assert!(counts.iter().all(|count| *count > 50));
+
+        let router = ShardRouter::<CodePoint>::new().without(CodePoint::gaps());
+        assert_eq!(router.spans(), [0x1F600..=0x1F64F]);
+        assert_eq!(*router.route("user:42")?, 0x1F621);
+        for key in 0..1000u32 {
+            assert!(router.route(key.to_le_bytes())?.is_emoji());
+        }
+
+        let router = ShardRouter::<Ascii>::new().without([0..=127]);
+        assert!(router.route("user:42").is_err());
+        Ok(())
+    }
+
+    #[clamped(u32 as Hard, default = 1, lower = 1, upper = 100, skip_impls = [FromStr, PartialOrdPrimitive])]
+    #[derive(Debug, Clone, Copy)]
+    struct Priority;
+
+    impl std::str::FromStr for Priority {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self> {
+            match s {
+                "low" => Self::from_primitive(1),
+                "high" => Self::from_primitive(100),
+                _ => Self::from_primitive(s.parse()?),
+            }
+        }
+    }
+
+    impl PartialOrd<u32> for Priority {
+        fn partial_cmp(&self, other: &u32) -> Option<std::cmp::Ordering> {
+            Some((**self).cmp(other))
+        }
+    }
+
+    impl PartialOrd<Priority> for u32 {
+        fn partial_cmp(&self, other: &Priority) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(&**other))
+        }
+    }
+
+    #[test]
+    fn test_skip_impls() -> Result<()> {
+        assert_eq!(*"high".parse::<Priority>()?, 100);
+        assert_eq!(*"42".parse::<Priority>()?, 42);
+        assert!(Priority::from_primitive(50)? < 60u32);
+        assert!(Priority::from_primitive(50)? + 10u32 == 60u32);
+        Ok(())
+    }
+
+    #[clamped(u16 as Hard, default = 1000, lower = 1000, upper = 1099)]
+    #[derive(Debug, Clone, Copy)]
+    struct TicketId;
+
+    #[test]
+    fn test_offset() -> Result<()> {
+        assert_eq!(TicketId::SPAN, 100);
+
+        let mut seats = [false; TicketId::SPAN as usize];
+        let id = TicketId::from_primitive(1042)?;
+        seats[id.offset() as usize] = true;
+        assert!(seats[42]);
+
+        assert_eq!(*TicketId::from_offset(42)?, 1042);
+        assert_eq!(*TicketId::from_offset(99)?, 1099);
+        assert!(TicketId::from_offset(100).is_err());
+        assert!(TicketId::from_offset(u16::MAX).is_err());
+        Ok(())
+    }
+
+    #[clamped(u16 as Hard, default = 0, upper = 359, behavior = Saturating, cyclic)]
+    #[derive(Debug, Clone, Copy)]
+    struct Heading;
+
+    #[test]
+    fn test_cyclic() -> Result<()> {
+        let north_west = Heading::from_primitive(350)?;
+        let north_east = Heading::from_primitive(10)?;
+        let south = Heading::from_primitive(180)?;
+
+        assert!(north_west > north_east);
+        assert!(north_west.cyclic_lt(&north_east));
+        assert!(!north_east.cyclic_lt(&north_west));
+        assert_eq!(north_west.cyclic_distance(&north_east), 20);
+
+        assert!(Heading::default().is_between(&north_west, &north_east));
+        assert!(!south.is_between(&north_west, &north_east));
+        Ok(())
+    }
+
+    #[clamped(u16 as Hard, default = 0, serial)]
+    #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+    struct Sequence;
+
+    #[test]
+    fn test_serial() -> Result<()> {
+        let mut seq = Sequence::from_primitive(u16::MAX - 1)?;
+        let before = seq;
+
+        seq.wrapping_increment();
+        seq.wrapping_increment();
+        assert_eq!(*seq, 0);
+        assert!(seq.is_newer_than(&before));
+        assert!(!before.is_newer_than(&seq));
+        assert_eq!(*seq.wrapping_add(5), 5);
+
+        let json = serde_json::to_string(&seq)?;
+        assert_eq!(json, "0");
+        assert_eq!(*serde_json::from_str::<Sequence>(&json)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_any_clamped() {
+        let mut fields: Vec<Box<dyn AnyClamped>> = vec![
+            Box::new(Port::default()),
+            Box::new(Dimmer::new(5)),
+            Box::new(ResponseCode::default()),
+            Box::new(Answer),
+        ];
+
+        let names: Vec<_> = fields.iter().map(|field| field.type_name()).collect();
+        assert_eq!(names, ["Port", "Dimmer", "ResponseCode", "Answer"]);
+        assert_eq!(fields[0].range_summary(), "1..=9999");
+        assert_eq!(fields[0].as_u128(), Some(80));
+        assert_eq!(fields[2].as_i128(), Some(600));
+
+        fields[0].set_i128(8080).unwrap();
+        assert_eq!(fields[0].as_u128(), Some(8080));
+        assert!(fields[0].set_i128(10_000).is_err());
+        assert!(fields[0].set_i128(-1).is_err());
+        assert_eq!(fields[0].as_u128(), Some(8080));
+
+        assert!(fields[1].set_i128(11).is_err());
+        fields[2].set_i128(404).unwrap();
+        assert_eq!(fields[2].as_u128(), Some(404));
+
+        assert!(fields[3].set_i128(42).is_ok());
+        assert!(fields[3].set_i128(43).is_err());
+    }
+
+    #[cfg(feature = "ui")]
+    #[test]
+    fn test_ui_range() {
+        use crate::ui::UiRange;
+
+        assert_eq!(Port::ui_min(), 1.0);
+        assert_eq!(Port::ui_max(), 9999.0);
+        assert_eq!(Port::ui_step(), 1.0);
+        assert_eq!(Port::default().to_f64(), 80.0);
+        assert_eq!(*Port::from_f64(442.6), 443);
+        assert_eq!(*Port::from_f64(-5.0), 1);
+        assert_eq!(*Port::from_f64(1e12), 9999);
+        assert_eq!(*Port::from_f64(f64::NAN), 1);
+        assert_eq!(*Dimmer::from_f64(10.4), 10);
+        assert_eq!(Dimmer::ui_max(), 10.0);
+    }
+
+    #[cfg(all(feature = "testing", not(feature = "never-panic")))]
+    #[test]
+    fn test_fault_injection() -> Result<()> {
+        use crate::testing::{
+            clear_fault_plan, injected_fault_count, set_fault_plan, with_fault_plan, FaultPlan,
+        };
+
+        let gauge = Percent::from_primitive(10)?;
+
+        let results = with_fault_plan(FaultPlan::Schedule(vec![false, true]), || {
+            [*(gauge + 1), *(gauge + 1), *(gauge + 1)]
+        });
+        assert_eq!(results, [11, 100, 11]);
+        assert_eq!(injected_fault_count(), 1);
+
+        let outcome =
+            std::panic::catch_unwind(|| with_fault_plan(FaultPlan::Probability(1.0), || HTTP + 1));
+        assert!(outcome.is_err());
+        assert_eq!(*(HTTP + 1), 81);
+        assert_eq!(injected_fault_count(), 2);
+
+        set_fault_plan(FaultPlan::Probability(0.0));
+        assert_eq!(*(gauge - 10), 0);
+        clear_fault_plan();
+        Ok(())
+    }
+
+    #[clamped(u8, default = 0, upper = 100)]
+    #[derive(Debug, Clone, Copy)]
+    enum Parity {
+        #[range(0..=100 step 2)]
+        Even,
+        #[range(1..100 step 2)]
+        Odd,
+    }
+
+    #[clamped(u16, default = 0, upper = 1000)]
+    #[derive(Debug, Clone, Copy)]
+    enum Dial {
+        #[range(0.. step 250)]
+        Quarter,
+        #[other]
+        Between,
+    }
+
+    #[test]
+    fn test_stepped_ranges() -> Result<()> {
+        assert!(Parity::from_primitive(0)?.is_even());
+        assert!(Parity::from_primitive(57)?.is_odd());
+        assert!(Parity::from_primitive(100)?.is_even());
+        assert!(Parity::from_primitive(101).is_err());
+        assert_eq!(Parity::EXACT_VALUES.len(), 101);
+        assert_eq!(Parity::ranges_of(ParityKind::Even).len(), 51);
+        assert_eq!(Parity::ranges_of(ParityKind::Odd)[1], 3..=3);
+
+        let quarters: Vec<_> = (0..=1000)
+            .filter(|&n| Dial::from_primitive(n).unwrap().is_quarter())
+            .collect();
+        assert_eq!(quarters, [0, 250, 500, 750, 1000]);
+        assert!(Dial::from_primitive(251)?.is_between());
+        assert_eq!(Dial::from_primitive(500)?.into_primitive(), 500);
+        Ok(())
+    }
+
+    #[test]
+    fn test_btree_range_queries() {
+        use std::collections::BTreeMap;
+
+        let hits: BTreeMap<u16, u32> = [(100, 1), (404, 7), (450, 2), (503, 9), (599, 4), (600, 3)]
+            .into_iter()
+            .collect();
+
+        let server_errors: Vec<_> =
+            btree_range_of_variant(&hits, ResponseCodeKind::ServerError).collect();
+        assert_eq!(server_errors, [(&503, &9), (&599, &4)]);
+
+        let unknown: Vec<_> = btree_range_of_variant(&hits, ResponseCodeKind::Unknown)
+            .map(|(code, _)| *code)
+            .collect();
+        assert_eq!(unknown, [450]);
+
+        let wrapped: Vec<_> =
+            btree_range_of::<clamped_response_code::ServerErrorValue, _, _>(&hits)
+                .map(|(code, _)| *code)
+                .collect();
+        assert_eq!(wrapped, [503, 599]);
+
+        let ports: BTreeMap<u16, &str> = [(0, "unused"), (80, "http"), (10_000, "high")]
+            .into_iter()
+            .collect();
+        assert_eq!(ports.range(limit_bounds::<Port, u16>()).count(), 1);
+    }
+
+    #[clamped(u8, default = 0, upper = 7, coverage = exhaustive)]
+    #[derive(Debug, Clone, Copy)]
+    enum Instruction {
+        #[eq(0)]
+        Nop,
+        #[range(1..=3)]
+        Load,
+        #[range(4..=7 step 2)]
+        Store,
+        #[eq(5, 7)]
+        Jump,
+    }
+
+    #[test]
+    fn test_exhaustive_coverage() -> Result<()> {
+        assert!(Instruction::from_primitive(0)?.is_nop());
+        assert!(Instruction::from_primitive(3)?.is_load());
+        assert!(Instruction::from_primitive(6)?.is_store());
+        assert!(Instruction::from_primitive(7)?.is_jump());
+        assert!(Instruction::from_primitive(8).is_err());
+        assert_eq!(Instruction::gaps().count(), 0);
+        Ok(())
+    }
+
+    const BASE_PERCENT: Percent = Percent::new_const(40);
+    const PERCENT_TABLE: [Percent; 3] = [
+        clamped_const!(Percent: BASE_PERCENT / 2),
+        clamped_const!(Percent: BASE_PERCENT + 10 * 3),
+        clamped_const!(Percent: (BASE_PERCENT - 15) % 7),
+    ];
+
+    #[test]
+    fn test_clamped_const() {
+        let values: Vec<u8> = PERCENT_TABLE.iter().map(|p| **p).collect();
+        assert_eq!(values, [20, 70, 4]);
+    }
+
+    #[clamped(u8, default = 0, upper = 9)]
+    #[derive(Debug, Clone, Copy)]
+    enum Outcome {
+        #[range(5..=9)]
+        #[code(20)]
+        Failure,
+        #[eq(0)]
+        Success,
+        #[other]
+        Pending,
+    }
+
+    #[test]
+    fn test_kind_codes() -> Result<()> {
+        let failure = Outcome::from_primitive(7)?;
+        assert_eq!(failure.kind_code(), 20);
+        assert_eq!(Outcome::from_primitive(0)?.kind_code(), 0);
+        assert_eq!(Outcome::from_primitive(3)?.kind_code(), 1);
+        assert_eq!(OutcomeKind::from_code(1), Some(OutcomeKind::Pending));
+        assert_eq!(OutcomeKind::from_code(5), None);
+
+        assert_eq!(*Outcome::from_kind_code(20, 7)?, 7);
+        assert!(Outcome::from_kind_code(20, 3).is_err());
+        assert!(Outcome::from_kind_code(5, 7).is_err());
+
+        assert_eq!(ResponseCode::from_primitive(503)?.kind_code(), 500);
+        assert_eq!(ResponseCodeKind::Unknown.code(), 101);
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_clamped_watch() -> Result<()> {
+        let (watch, mut receiver) = ClampedWatch::channel(Percent::new(10));
+        assert!(!receiver.has_changed()?);
+
+        watch.send(40u8)?;
+        assert!(receiver.has_changed()?);
+        assert_eq!(*receiver.get_and_update(), 40);
+
+        assert!(watch.send(150u8).is_err());
+        assert!(watch.send_modify(|value: &mut u8| *value += 70).is_err());
+        assert!(!receiver.has_changed()?);
+        assert_eq!(*watch.get(), 40);
+
+        watch.send_modify(|value: &mut u8| *value += 5)?;
+        assert_eq!(*receiver.get_and_update(), 45);
+
+        drop(watch);
+        assert!(receiver.has_changed().is_err());
+        Ok(())
+    }
+
+    #[clamped(u16, default = 200, lower = 100, upper = 599)]
+    #[derive(Debug, Clone, Copy)]
+    enum Retry {
+        #[eq(408, 425, 429, 500..=504)]
+        Retryable,
+        #[eq(200..=201)]
+        Done,
+        #[other]
+        Fatal,
+    }
+
+    #[test]
+    fn test_eq_spans() -> Result<()> {
+        for n in [408, 425, 429, 500, 502, 504] {
+            assert!(Retry::from_primitive(n)?.is_retryable(), "{}", n);
+        }
+
+        assert!(Retry::from_primitive(505)?.is_fatal());
+        assert!(Retry::from_primitive(201)?.is_done());
+        assert_eq!(
+            Retry::ranges_of(RetryKind::Retryable),
+            [408..=408, 425..=425, 429..=429, 500..=504]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_fuzz_targets() {
+        use crate::fuzz::{fuzz_dispatch, fuzz_type, FuzzInput};
+
+        let bytes = (0..=255u8).chain((0..=255u8).rev()).collect::<Vec<_>>();
+        let targets: [fn(&[u8]); 4] = [
+            fuzz_type::<Percent>,
+            fuzz_type::<Port>,
+            fuzz_type::<Dimmer>,
+            fuzz_type::<ResponseCode>,
+        ];
+
+        for target in targets {
+            target(&bytes);
+            target(&[0xff; 7]);
+        }
+
+        for selector in 0..4u8 {
+            fuzz_dispatch(&[selector, 0x10, 0x27, 0x01], &targets);
+        }
+
+        fuzz_dispatch(&[], &targets);
+        assert_eq!(FuzzInput::new(&[1, 2, 3]).take::<u16>(), Some(0x0201));
+    }
+
+    #[test]
+    fn test_assert_clamped_eq() -> Result<()> {
+        assert_clamped_eq!(Percent::new(42), Percent::new(42));
+        assert_eq!(
+            Percent::new(42).diff_display(),
+            "42 (Percent, valid: 0..=100)"
+        );
+        assert_eq!(
+            Dimmer::from_primitive(20)?.diff_display(),
+            "20 (Dimmer, invalid, valid: 1..=10)"
+        );
+
+        let left = ResponseCode::from_primitive(503)?;
+        let right = ResponseCode::from_primitive(404)?;
+        let panic = std::panic::catch_unwind(|| assert_clamped_eq!(left, right, "for {}", "GET"))
+            .expect_err("the values differ");
+
+        assert_eq!(
+            panic.downcast_ref::<String>().map(String::as_str),
+            Some(
+                "assertion `left == right` failed: for GET\n  left: 503 (ResponseCode::ServerError)\n right: 404 (ResponseCode::NotFound)"
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_arithmetic() -> Result<()> {
+        let percent = Percent::new(60);
+        assert_eq!(*(percent + 50), 100);
+        assert!(percent.checked_add(50).is_none());
+        assert_eq!(percent.checked_add(40).map(|p| *p), Some(100));
+        assert!(percent.checked_sub(61).is_none());
+        assert_eq!(percent.checked_div(7).map(|p| *p), Some(8));
+        assert!(percent.checked_rem(0).is_none());
+        assert_eq!(percent.checked_bitor(4).map(|p| *p), Some(60));
+        assert!(percent.checked_bitxor(0x80).is_none());
+
+        assert!(HTTP.checked_sub(80).is_none());
+        assert!(HTTP.checked_mul(1000).is_none());
+        assert_eq!(HTTP.checked_mul(100).map(|p| *p), Some(8000));
+
+        let dimmer = Dimmer::new(5).checked_mul(3).unwrap();
+        assert_eq!(*dimmer, 15);
+        assert!(!dimmer.is_valid());
+        assert!(Dimmer::new(5).checked_mul(100).is_none());
+
+        let code = ResponseCode::from_primitive(404)?;
+        assert!(code
+            .checked_add(100)
+            .is_some_and(|code| code.is_server_error()));
+        assert!(code.checked_add(300).is_none());
+        Ok(())
+    }
+
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    struct Manual(u8);
+
+    impl InherentLimits<u8> for Manual {
+        const MIN: u8 = 0;
+        const MAX: u8 = 9;
+    }
+
+    unsafe impl ClampedInteger<u8> for Manual {
+        fn from_primitive(value: u8) -> Result<Self> {
+            anyhow::ensure!(value <= 9, "invalid value: {}", value);
+            Ok(Self(value))
+        }
+
+        fn into_primitive(&self) -> u8 {
+            self.0
+        }
+    }
+
+    impl AsPrimitive<u8> for Manual {
+        fn as_primitive(&self) -> &u8 {
+            &self.0
+        }
+    }
+
+    fn primitive_ref<T: AsPrimitive<P>, P: Copy>(value: &T) -> P {
+        *value.as_primitive()
+    }
+
+    #[test]
+    fn test_as_primitive_compat() -> Result<()> {
+        let manual = Manual::from_primitive(7)?;
+        assert_eq!(manual.into_primitive(), 7);
+        assert_eq!(primitive_ref(&manual), 7);
+
+        assert_eq!(primitive_ref(&Percent::new(60)), 60);
+        assert_eq!(primitive_ref(&Dimmer::new(5)), 5);
+        assert_eq!(primitive_ref(&ResponseCode::from_primitive(404)?), 404);
+        Ok(())
+    }
+
+    macro_rules! define_delegating_behavior {
+        ($name:ident, |$val:ident, $min:ident, $max:ident| $resolve:expr) => {
+            #[derive(Debug, Clone, Copy)]
+            enum $name {}
+
+            define_delegating_behavior!(@impl $name, |$val, $min, $max| $resolve,
+                add: Add, sub: Sub, mul: Mul, div: Div, rem: Rem,
+                bitand: BitAnd, bitor: BitOr, bitxor: BitXor);
+        };
+        (@impl $name:ident, |$val:ident, $min:ident, $max:ident| $resolve:expr,
+            $($method:ident: $trait:ident),*) => {
+            impl Behavior for $name {
+                $(
+                    fn $method<T: std::ops::$trait<Output = T> + Copy>(
+                        lhs: T,
+                        rhs: T,
+                        min: T::Output,
+                        max: T::Output,
+                    ) -> T::Output
+                    where
+                        T::Output: Eq + Ord,
+                        std::num::Saturating<T>: std::ops::$trait<Output = std::num::Saturating<T>>,
+                    {
+                        let ($val, $min, $max) = (
+                            std::ops::$trait::$method(std::num::Saturating(lhs), std::num::Saturating(rhs)).0,
+                            min,
+                            max,
+                        );
+                        $resolve
+                    }
+                )*
+
+                fn neg<T: std::ops::Neg<Output = T> + Copy>(
+                    value: T,
+                    min: T::Output,
+                    max: T::Output,
+                ) -> T::Output
+                where
+                    T::Output: Eq + Ord,
+                    std::num::Saturating<T>: std::ops::Neg<Output = std::num::Saturating<T>>,
+                {
+                    let ($val, $min, $max) = ((-std::num::Saturating(value)).0, min, max);
+                    $resolve
+                }
+
+                fn not<T: std::ops::Not<Output = T> + Copy>(
+                    value: T,
+                    min: T::Output,
+                    max: T::Output,
+                ) -> T::Output
+                where
+                    T::Output: Eq + Ord,
+                    std::num::Saturating<T>: std::ops::Not<Output = std::num::Saturating<T>>,
+                {
+                    let ($val, $min, $max) = ((!std::num::Saturating(value)).0, min, max);
+                    $resolve
+                }
+            }
+        };
+    }
+
+    // Out of bounds results saturate at the nearest limit.
+    define_delegating_behavior!(Clipping, |val, min, max| val.clamp(min, max));
+    // Out of bounds results are reset to the lower limit.
+    define_delegating_behavior!(Resetting, |val, min, max| if (min..=max).contains(&val) {
+        val
+    } else {
+        min
+    });
+
+    #[test]
+    fn test_default_resolve() {
+        use crate::clamp::ClampError;
+
+        let too_small = || Err::<u16, _>(ClampError::TooSmall { val: 1, min: 5 });
+        let too_large = || Err::<u16, _>(ClampError::TooLarge { val: 90, max: 50 });
+
+        assert_eq!(Clipping::resolve(too_small()).unwrap(), 5);
+        assert_eq!(Clipping::resolve(too_large()).unwrap(), 50);
+        assert_eq!(Clipping::resolve(Ok(7u16)).unwrap(), 7);
+
+        assert_eq!(Resetting::resolve(too_small()).unwrap(), 5);
+        assert!(matches!(
+            Resetting::resolve(too_large()),
+            Err(ClampError::TooLarge { val: 90, max: 50 })
+        ));
+    }
+
+    // The re-export only checks the entries, since `clamped` reads the defaults from the file named
+    // by `CHECKED_RS_CONFIG`.
+    crate::configure! {
+        behavior = Saturating;
+        div_by_zero = saturate_max;
+        guard_drop = SilentOnDrop;
+        derive(Debug, Clone, Copy);
+    }
+
+    mod literal_elsewhere {
+        #[test]
+        fn test_literal_from_another_module() {
+            const HTTPS: crate::tests::Port = crate::tests::port!(443);
+
+            assert_eq!(*HTTPS, 443);
+            assert_eq!(*super::port!(8080), 8080);
+        }
+    }
 }
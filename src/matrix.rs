@@ -0,0 +1,190 @@
+/// Expand a matrix of clamped definitions for every supported primitive kind, along with boundary
+/// value tests for each of them. Each kind gets its own module holding hard and soft structs, a
+/// single range and a multi-range enum, and a full width struct, with both `Panicking` and
+/// `Saturating` behaviors, so that a codegen regression that only affects one kind is caught in
+/// one place.
+///
+/// This is meant for the tests of this crate and of forks of its macros. Only the unsigned kinds
+/// are covered, since those are the kinds `#[clamped]` accepts.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_kind_matrix_tests {
+    () => {
+        $crate::generate_kind_matrix_tests! {
+            u8_matrix: u8,
+            u16_matrix: u16,
+            u32_matrix: u32,
+            u64_matrix: u64,
+            u128_matrix: u128,
+            usize_matrix: usize,
+        }
+    };
+    ($($module:ident: $kind:ident),+ $(,)?) => {
+        $(
+            $crate::generate_kind_matrix_tests!(@kind $module, $kind);
+        )+
+    };
+    (@kind $module:ident, $kind:ident) => {
+        // The aliases each type re-exports, such as `Guard`, are not used by the tests.
+        #[allow(dead_code, unused_imports)]
+        mod $module {
+            use $crate::prelude::*;
+
+            #[clamped($kind as Hard, default = 10, lower = 10, upper = 200)]
+            #[derive(Debug, Clone, Copy)]
+            struct HardPanicking;
+
+            #[clamped($kind as Hard, default = 10, behavior = Saturating, lower = 10, upper = 200)]
+            #[derive(Debug, Clone, Copy)]
+            struct HardSaturating;
+
+            #[clamped($kind as Soft, default = 10, lower = 10, upper = 200)]
+            #[derive(Debug, Clone, Copy)]
+            struct SoftPanicking;
+
+            #[clamped($kind as Soft, default = 10, behavior = Saturating, lower = 10, upper = 200)]
+            #[derive(Debug, Clone, Copy)]
+            struct SoftSaturating;
+
+            #[clamped($kind as Hard, default = 0, behavior = Saturating)]
+            #[derive(Debug, Clone, Copy)]
+            struct FullWidth;
+
+            #[clamped($kind, default = 10, lower = 10, upper = 200)]
+            #[derive(Debug, Clone, Copy)]
+            enum SingleRange {
+                #[range(10..=200)]
+                All,
+            }
+
+            #[clamped($kind, default = 10, behavior = Saturating, lower = 10, upper = 200)]
+            #[derive(Debug, Clone, Copy)]
+            enum MultiRange {
+                #[eq(10)]
+                Bottom,
+                #[range(11..100)]
+                Low,
+                #[range(100..200)]
+                High,
+                #[eq(200)]
+                Top,
+            }
+
+            #[clamped($kind, default = 0)]
+            #[derive(Debug, Clone, Copy)]
+            enum FullWidthEnum {
+                #[eq(0)]
+                Zero,
+                #[other]
+                Rest,
+            }
+
+            #[test]
+            fn limits() {
+                assert_eq!(<HardPanicking as InherentLimits<$kind>>::MIN, 10);
+                assert_eq!(<HardPanicking as InherentLimits<$kind>>::MAX, 200);
+                assert_eq!(<SoftSaturating as InherentLimits<$kind>>::MIN, 10);
+                assert_eq!(<SoftSaturating as InherentLimits<$kind>>::MAX, 200);
+                assert_eq!(<MultiRange as InherentLimits<$kind>>::MIN, 10);
+                assert_eq!(<MultiRange as InherentLimits<$kind>>::MAX, 200);
+                assert_eq!(<FullWidth as InherentLimits<$kind>>::MIN, $kind::MIN);
+                assert_eq!(<FullWidth as InherentLimits<$kind>>::MAX, $kind::MAX);
+                assert_eq!(<FullWidthEnum as InherentLimits<$kind>>::MAX, $kind::MAX);
+            }
+
+            #[test]
+            fn from_primitive_boundaries() {
+                assert_eq!(*HardPanicking::from_primitive(10).unwrap(), 10);
+                assert_eq!(*HardPanicking::from_primitive(200).unwrap(), 200);
+                assert!(HardPanicking::from_primitive(9).is_err());
+                assert!(HardPanicking::from_primitive(201).is_err());
+
+                assert!(SoftPanicking::from_primitive(200).unwrap().is_valid());
+                assert!(!SoftPanicking::from_primitive(201).unwrap().is_valid());
+                assert!(!SoftPanicking::new(9).is_valid());
+
+                assert!(SingleRange::from_primitive(10).unwrap().is_all());
+                assert!(SingleRange::from_primitive(200).unwrap().is_all());
+                assert!(SingleRange::from_primitive(9).is_err());
+                assert!(SingleRange::from_primitive(201).is_err());
+
+                assert!(MultiRange::from_primitive(10).unwrap().is_bottom());
+                assert!(MultiRange::from_primitive(11).unwrap().is_low());
+                assert!(MultiRange::from_primitive(99).unwrap().is_low());
+                assert!(MultiRange::from_primitive(100).unwrap().is_high());
+                assert!(MultiRange::from_primitive(199).unwrap().is_high());
+                assert!(MultiRange::from_primitive(200).unwrap().is_top());
+                assert!(MultiRange::from_primitive(201).is_err());
+
+                assert_eq!(*FullWidth::from_primitive($kind::MAX).unwrap(), $kind::MAX);
+                assert!(FullWidthEnum::from_primitive(0).unwrap().is_zero());
+                assert!(FullWidthEnum::from_primitive($kind::MAX).unwrap().is_rest());
+            }
+
+            #[test]
+            fn default_values() {
+                assert_eq!(*HardPanicking::default(), 10);
+                assert_eq!(*SoftSaturating::default(), 10);
+                assert_eq!(*FullWidth::default(), 0);
+                assert!(MultiRange::default().is_bottom());
+                assert!(FullWidthEnum::default().is_zero());
+            }
+
+            #[test]
+            fn saturating_arithmetic() {
+                let top = HardSaturating::from_primitive(200).unwrap();
+                let bottom = HardSaturating::from_primitive(10).unwrap();
+                assert_eq!(*(top + 1), 200);
+                assert_eq!(*(bottom - 1), 10);
+
+                // Soft types leave arithmetic unchecked, whatever their behavior.
+                let top = SoftSaturating::from_primitive(200).unwrap();
+                assert!(!(top + 1).is_valid());
+
+                let full = FullWidth::from_primitive($kind::MAX).unwrap();
+                assert_eq!(*(full + 1), $kind::MAX);
+                assert_eq!(*(FullWidth::default() - 1), 0);
+
+                let mut value = MultiRange::from_primitive(199).unwrap();
+                value += 1;
+                assert!(value.is_top());
+                value += 1;
+                assert!(value.is_top());
+                value -= $kind::MAX;
+                assert!(value.is_bottom());
+            }
+
+            #[test]
+            fn panicking_arithmetic() {
+                let top = HardPanicking::from_primitive(200).unwrap();
+                let bottom = HardPanicking::from_primitive(10).unwrap();
+                assert_eq!(*(bottom + 190), 200);
+                assert!(std::panic::catch_unwind(move || top + 1).is_err());
+                assert!(std::panic::catch_unwind(move || bottom - 1).is_err());
+
+                let top = SoftPanicking::from_primitive(200).unwrap();
+                assert_eq!(*(top + 1), 201);
+            }
+
+            #[test]
+            fn string_round_trip() {
+                for value in [10, 11, 99, 100, 199, 200] {
+                    let text = value.to_string();
+                    assert_eq!(*text.parse::<HardPanicking>().unwrap(), value);
+                    assert_eq!(text.parse::<MultiRange>().unwrap().into_primitive(), value);
+                }
+
+                let max = $kind::MAX.to_string();
+                assert_eq!(*max.parse::<FullWidth>().unwrap(), $kind::MAX);
+                assert!("9".parse::<HardPanicking>().is_err());
+                assert!("201".parse::<MultiRange>().is_err());
+            }
+        }
+    };
+}
+
+// The matrix relies on the default `Panicking` behavior, which `never-panic` forbids.
+#[cfg(all(test, not(feature = "never-panic")))]
+mod tests {
+    crate::generate_kind_matrix_tests!();
+}
@@ -0,0 +1,157 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Mutex,
+};
+
+/// Every set of counters that has been used, in the order they were first used.
+static REGISTRY: Mutex<Vec<&'static ClampMetrics>> = Mutex::new(Vec::new());
+
+/// A type that records how its operations resolved, which is implemented for types given the
+/// `metrics` flag.
+pub trait MetricsSource: 'static {
+    fn metrics() -> &'static ClampMetrics;
+}
+
+/// Counters for how often the operations of a type left its limits, for tuning the limits in
+/// production. Every counter is updated atomically, so they can be read from any thread.
+#[derive(Debug)]
+pub struct ClampMetrics {
+    type_name: &'static str,
+    saturated: AtomicU64,
+    panicked: AtomicU64,
+    rejected: AtomicU64,
+    registered: AtomicBool,
+}
+
+/// The counters of a type at a single point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub struct MetricsSnapshot {
+    pub type_name: &'static str,
+    pub saturated: u64,
+    pub panicked: u64,
+    pub rejected: u64,
+}
+
+impl ClampMetrics {
+    #[inline(always)]
+    pub const fn new(type_name: &'static str) -> Self {
+        Self {
+            type_name,
+            saturated: AtomicU64::new(0),
+            panicked: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            registered: AtomicBool::new(false),
+        }
+    }
+
+    #[inline(always)]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// How many operations had a result outside of the limits that was saturated.
+    #[inline(always)]
+    pub fn saturated(&self) -> u64 {
+        self.saturated.load(Ordering::Relaxed)
+    }
+
+    /// How many operations had a result outside of the limits that panicked.
+    #[inline(always)]
+    pub fn panicked(&self) -> u64 {
+        self.panicked.load(Ordering::Relaxed)
+    }
+
+    /// How many primitives were rejected when converting them into the type.
+    #[inline(always)]
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            type_name: self.type_name,
+            saturated: self.saturated(),
+            panicked: self.panicked(),
+            rejected: self.rejected(),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.saturated.store(0, Ordering::Relaxed);
+        self.panicked.store(0, Ordering::Relaxed);
+        self.rejected.store(0, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn record_saturated(&'static self) {
+        self.register();
+        self.saturated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn record_panicked(&'static self) {
+        self.register();
+        self.panicked.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn record_rejected(&'static self) {
+        self.register();
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Add the counters to the registry the first time they are used.
+    #[inline(always)]
+    pub fn register(&'static self) {
+        if !self.registered.swap(true, Ordering::Relaxed) {
+            REGISTRY
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(self);
+        }
+    }
+}
+
+/// Get a snapshot of the counters of every type that has recorded anything so far.
+pub fn registered_metrics() -> Vec<MetricsSnapshot> {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|metrics| metrics.snapshot())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_metrics() {
+        static METRICS: ClampMetrics = ClampMetrics::new("Test");
+
+        METRICS.record_saturated();
+        METRICS.record_saturated();
+        METRICS.record_rejected();
+
+        assert_eq!(
+            METRICS.snapshot(),
+            MetricsSnapshot {
+                type_name: "Test",
+                saturated: 2,
+                panicked: 0,
+                rejected: 1,
+            }
+        );
+        assert_eq!(
+            registered_metrics()
+                .iter()
+                .filter(|m| m.type_name == "Test")
+                .count(),
+            1
+        );
+
+        METRICS.reset();
+        assert_eq!(METRICS.saturated(), 0);
+    }
+}
@@ -0,0 +1,69 @@
+/// The path from an enum to one of its variants, such as `ResponseCode/NotFound`, for labels in
+/// metrics and logs. It is displayed with its segments separated by `/`. Clamped enums are flat,
+/// so the paths they generate have two segments, while longer paths can describe enums that wrap
+/// other enums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub struct VariantPath {
+    segments: &'static [&'static str],
+}
+
+impl VariantPath {
+    #[inline(always)]
+    pub const fn new(segments: &'static [&'static str]) -> Self {
+        Self { segments }
+    }
+
+    #[inline(always)]
+    pub fn segments(&self) -> &'static [&'static str] {
+        self.segments
+    }
+
+    /// The number of segments in the path.
+    #[inline(always)]
+    pub fn depth(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// The last segment of the path, which names the variant itself.
+    #[inline(always)]
+    pub fn leaf(&self) -> Option<&'static str> {
+        self.segments.last().copied()
+    }
+
+    /// Check whether this path starts with every segment of `prefix`.
+    #[inline(always)]
+    pub fn starts_with(&self, prefix: &[&str]) -> bool {
+        self.segments.len() >= prefix.len() && self.segments.iter().zip(prefix).all(|(a, b)| a == b)
+    }
+}
+
+impl std::fmt::Display for VariantPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+
+            f.write_str(segment)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_path() {
+        let path = VariantPath::new(&["Error", "Client", "NotFound"]);
+
+        assert_eq!(path.to_string(), "Error/Client/NotFound");
+        assert_eq!(path.depth(), 3);
+        assert_eq!(path.leaf(), Some("NotFound"));
+        assert!(path.starts_with(&["Error", "Client"]));
+        assert!(!path.starts_with(&["Error", "Server"]));
+        assert_eq!(VariantPath::new(&[]).to_string(), "");
+    }
+}
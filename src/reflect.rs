@@ -0,0 +1,121 @@
+use std::{fmt, io};
+
+/// The primitive type that a clamped type stores its value in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub enum PrimitiveKind {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl PrimitiveKind {
+    /// The name of the primitive type, such as `"u16"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::U128 => "u128",
+            Self::Usize => "usize",
+        }
+    }
+}
+
+/// A description of a clamped type that is available at runtime, for tooling such as admin
+/// dashboards that list every clamped type in a binary. Values are widened to `u128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub struct ClampedTypeDescriptor {
+    pub type_name: &'static str,
+    pub primitive: PrimitiveKind,
+    /// The lower limit of the type.
+    pub min: u128,
+    /// The upper limit of the type.
+    pub max: u128,
+    /// The inclusive ranges of values the type declares. This is the limits for structs and the
+    /// `#[range]` variants for enums.
+    pub ranges: &'static [(u128, u128)],
+    /// The values of the `#[eq]` variants of enums, in ascending order.
+    pub exacts: &'static [u128],
+    pub default: u128,
+    /// The name of the behavior used by operators without their own entry in a behavior table.
+    pub behavior: &'static str,
+    /// The conversion traits that were generated for the type.
+    pub conversions: &'static [Conversion],
+}
+
+/// A conversion trait that was generated for a clamped type, such as `From<u32> for Port`. Types
+/// are named as they were written by the macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub struct Conversion {
+    /// The trait, which is `From`, `TryFrom`, `FromStr`, `Deref` or `AsRef`.
+    pub trait_name: &'static str,
+    pub from: &'static str,
+    pub to: &'static str,
+    /// Whether the conversion panics for some values, which is the case for `From` a primitive
+    /// that has values outside of the limits. Those are the ones to migrate to `TryFrom`.
+    pub may_panic: bool,
+}
+
+impl fmt::Display for Conversion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.trait_name {
+            "FromStr" => write!(f, "FromStr for {}", self.to)?,
+            "Deref" => write!(f, "Deref<Target = {}> for {}", self.to, self.from)?,
+            "AsRef" => write!(f, "AsRef<{}> for {}", self.to, self.from)?,
+            _ => write!(f, "{}<{}> for {}", self.trait_name, self.from, self.to)?,
+        }
+
+        if self.may_panic {
+            f.write_str(" (may panic)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A type that describes itself with a `ClampedTypeDescriptor`, which is implemented for every
+/// generated type when the `reflect` feature is enabled.
+pub trait Reflect: 'static {
+    fn descriptor() -> &'static ClampedTypeDescriptor;
+}
+
+/// The entry that registers a descriptor with `inventory`. This is not part of the public API.
+#[doc(hidden)]
+pub struct DescriptorEntry(pub &'static ClampedTypeDescriptor);
+
+inventory::collect!(DescriptorEntry);
+
+/// Iterate over the descriptor of every clamped type linked into the binary, in no particular
+/// order.
+pub fn registered_descriptors() -> impl Iterator<Item = &'static ClampedTypeDescriptor> {
+    inventory::iter::<DescriptorEntry>
+        .into_iter()
+        .map(|entry| entry.0)
+}
+
+/// Write the conversions of every clamped type linked into the binary, sorted by type name, one
+/// per line such as `From<u32> for Port (may panic)`. A helper binary that audits a code base only
+/// needs to call it with standard output.
+pub fn write_conversion_manifest(mut out: impl io::Write) -> io::Result<()> {
+    let mut descriptors = registered_descriptors().collect::<Vec<_>>();
+    descriptors.sort_by_key(|descriptor| descriptor.type_name);
+
+    for descriptor in descriptors {
+        writeln!(out, "{}:", descriptor.type_name)?;
+
+        for conversion in descriptor.conversions {
+            writeln!(out, "    {}", conversion)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the descriptor of the clamped type with the given name.
+pub fn find_descriptor(type_name: &str) -> Option<&'static ClampedTypeDescriptor> {
+    registered_descriptors().find(|descriptor| descriptor.type_name == type_name)
+}
@@ -0,0 +1,247 @@
+/// A primitive integer whose distance from a lower value can be measured as an unsigned offset,
+/// which lets values be mapped between ranges of different integer types.
+pub trait RangeOffset: Copy + Ord {
+    /// Get the distance of `self` above `min`, which must not be greater than `self`.
+    fn offset_from(self, min: Self) -> u128;
+
+    /// Get the value `offset` above `self`, which must be representable.
+    fn offset_by(self, offset: u128) -> Self;
+}
+
+macro_rules! impl_range_offset_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl RangeOffset for $ty {
+                #[inline(always)]
+                fn offset_from(self, min: Self) -> u128 {
+                    (self - min) as u128
+                }
+
+                #[inline(always)]
+                fn offset_by(self, offset: u128) -> Self {
+                    (self as u128 + offset) as Self
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_range_offset_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl RangeOffset for $ty {
+                #[inline(always)]
+                fn offset_from(self, min: Self) -> u128 {
+                    (self as i128).wrapping_sub(min as i128) as u128
+                }
+
+                #[inline(always)]
+                fn offset_by(self, offset: u128) -> Self {
+                    (self as i128).wrapping_add(offset as i128) as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_range_offset_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_range_offset_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Get the position of `value` within `min..=max` as a fraction from `0.0` to `1.0`. A range with
+/// a single value is always at `0.0`.
+#[inline(always)]
+pub fn range_fraction<T: RangeOffset>(value: T, min: T, max: T) -> f64 {
+    let span = max.offset_from(min);
+
+    if span == 0 {
+        0.0
+    } else {
+        value.offset_from(min) as f64 / span as f64
+    }
+}
+
+/// Get the value at `fraction` of the way through `min..=max`, rounded to the nearest value. The
+/// fraction is clamped to `0.0..=1.0` and `NaN` is treated as `0.0`.
+#[inline(always)]
+pub fn value_at_fraction<T: RangeOffset>(fraction: f64, min: T, max: T) -> T {
+    let span = max.offset_from(min);
+    let fraction = if fraction.is_nan() {
+        0.0
+    } else {
+        fraction.clamp(0.0, 1.0)
+    };
+
+    min.offset_by(((fraction * span as f64).round() as u128).min(span))
+}
+
+/// Map `value` from `min..=max` onto `to_min..=to_max` proportionally, rounding down. This only
+/// uses integer arithmetic, so no precision is lost for wide integer types.
+#[inline(always)]
+pub fn scale_between<T: RangeOffset, U: RangeOffset>(
+    value: T,
+    min: T,
+    max: T,
+    to_min: U,
+    to_max: U,
+) -> U {
+    let span = max.offset_from(min);
+    let to_span = to_max.offset_from(to_min);
+
+    if span == 0 {
+        to_min
+    } else {
+        to_min.offset_by(mul_div(value.offset_from(min), to_span, span))
+    }
+}
+
+/// How the result of a division that does not come out even is rounded.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum Rounding {
+    /// Round towards zero.
+    #[default]
+    Floor,
+    /// Round away from zero.
+    Ceil,
+    /// Round to the nearest value, with ties going to the even value.
+    HalfEven,
+}
+
+/// Compute `value * num / den` rounded with `rounding`, widening so the product never overflows.
+/// Returns `None` when the result does not fit in a `u128`.
+///
+/// # Panics
+///
+/// Panics if `den` is zero.
+pub fn scale_ratio(value: u128, num: u32, den: u32, rounding: Rounding) -> Option<u128> {
+    assert!(den != 0, "attempt to divide by zero");
+
+    const LOW: u128 = u64::MAX as u128;
+
+    let (num, den) = (num as u128, den as u128);
+
+    // The product is at most 160 bits wide, split into a high half and a low half.
+    let lo = (value & LOW) * num;
+    let mid = (value >> 64) * num + (lo >> 64);
+    let (hi, lo) = (mid >> 64, (mid << 64) | (lo & LOW));
+
+    // Long division one 64-bit digit at a time, which cannot overflow since `den` is 32 bits.
+    let q_hi = hi / den;
+    let r = hi % den;
+    let cur = (r << 64) | (lo >> 64);
+    let q_mid = cur / den;
+    let r = cur % den;
+    let cur = (r << 64) | (lo & LOW);
+    let q_lo = cur / den;
+    let rem = cur % den;
+
+    if q_hi != 0 || q_mid > LOW {
+        return None;
+    }
+
+    let quotient = (q_mid << 64) | q_lo;
+
+    let round_up = match rounding {
+        Rounding::Floor => false,
+        Rounding::Ceil => rem != 0,
+        Rounding::HalfEven => rem * 2 > den || (rem * 2 == den && quotient & 1 == 1),
+    };
+
+    if round_up {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Compute `a * b / c` rounded down without overflowing, where `a <= c` so the result fits.
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product / c;
+    }
+
+    const LOW: u128 = u64::MAX as u128;
+
+    let (a_hi, a_lo) = (a >> 64, a & LOW);
+    let (b_hi, b_lo) = (b >> 64, b & LOW);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let cross = (lo_lo >> 64) + (hi_lo & LOW) + a_lo * b_hi;
+
+    let lo = (cross << 64) | (lo_lo & LOW);
+    let hi = a_hi * b_hi + (hi_lo >> 64) + (cross >> 64);
+
+    let mut quotient = 0u128;
+    let mut remainder = 0u128;
+
+    for i in (0..256).rev() {
+        let bit = if i >= 128 {
+            (hi >> (i - 128)) & 1
+        } else {
+            (lo >> i) & 1
+        };
+        let carry = remainder >> 127;
+
+        remainder = (remainder << 1) | bit;
+        quotient <<= 1;
+
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_between() {
+        assert_eq!(range_fraction(150u16, 100, 200), 0.5);
+        assert_eq!(range_fraction(-128i8, -128, 127), 0.0);
+        assert_eq!(range_fraction(5u8, 5, 5), 0.0);
+
+        assert_eq!(value_at_fraction(0.5, 100u16, 200), 150);
+        assert_eq!(value_at_fraction(2.0, 100u16, 200), 200);
+        assert_eq!(value_at_fraction(f64::NAN, -10i32, 10), -10);
+
+        assert_eq!(scale_between(500u16, 0, 1000, 0u8, 100), 50);
+        assert_eq!(scale_between(0i8, -128, 127, 0u8, 255), 128);
+        assert_eq!(scale_between(u128::MAX, 0, u128::MAX, 0u8, 100), 100);
+        assert_eq!(
+            scale_between(u128::MAX / 2, 0, u128::MAX, 0u128, u128::MAX - 1),
+            u128::MAX / 2 - 1
+        );
+        assert_eq!(scale_between(7u8, 7, 7, 1u8, 9), 1);
+    }
+
+    #[test]
+    fn test_scale_ratio() {
+        assert_eq!(scale_ratio(7, 1, 2, Rounding::Floor), Some(3));
+        assert_eq!(scale_ratio(7, 1, 2, Rounding::Ceil), Some(4));
+        assert_eq!(scale_ratio(5, 1, 2, Rounding::HalfEven), Some(2));
+        assert_eq!(scale_ratio(7, 1, 2, Rounding::HalfEven), Some(4));
+        assert_eq!(scale_ratio(10, 1, 3, Rounding::HalfEven), Some(3));
+        assert_eq!(scale_ratio(6, 3, 3, Rounding::Ceil), Some(6));
+
+        assert_eq!(
+            scale_ratio(u128::MAX, u32::MAX, u32::MAX, Rounding::Floor),
+            Some(u128::MAX)
+        );
+        assert_eq!(
+            scale_ratio(u128::MAX, 3, 4, Rounding::Ceil),
+            Some(u128::MAX / 4 * 3 + 3)
+        );
+        assert_eq!(scale_ratio(u128::MAX, 2, 1, Rounding::Floor), None);
+        assert_eq!(
+            scale_ratio(u128::MAX, 1, 1, Rounding::Ceil),
+            Some(u128::MAX)
+        );
+        assert!(std::panic::catch_unwind(|| scale_ratio(1, 1, 0, Rounding::Floor)).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use std::cell::{Cell, RefCell};
+
+use rand::Rng;
+
+/// Which operations of a `FaultInjecting` behavior fail on the current thread. Every operation
+/// takes one step of the plan, whether or not its result is within the limits.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FaultPlan {
+    /// Fail each operation with the given probability, which is clamped to `0.0..=1.0`.
+    Probability(f64),
+    /// Fail the operations at the `true` entries, in the order they run after the plan is set.
+    /// Operations past the end of the schedule do not fail.
+    Schedule(Vec<bool>),
+}
+
+thread_local! {
+    /// The plan of the current thread and the number of steps taken from it.
+    static PLAN: RefCell<Option<(FaultPlan, usize)>> = const { RefCell::new(None) };
+    static INJECTED: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Start failing the operations of `FaultInjecting` behaviors on the current thread according to
+/// `plan`, replacing the previous plan.
+pub fn set_fault_plan(plan: FaultPlan) {
+    PLAN.with(|cell| *cell.borrow_mut() = Some((plan, 0)));
+}
+
+/// Stop failing operations on the current thread.
+pub fn clear_fault_plan() {
+    PLAN.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// Run `f` with `plan` set on the current thread, restoring the previous plan afterwards, even if
+/// `f` panics.
+pub fn with_fault_plan<R>(plan: FaultPlan, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<(FaultPlan, usize)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            PLAN.with(|cell| *cell.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(PLAN.with(|cell| cell.borrow_mut().replace((plan, 0))));
+
+    f()
+}
+
+/// The number of faults injected on the current thread so far, which only counts operations
+/// whose result was within the limits.
+pub fn injected_fault_count() -> u64 {
+    INJECTED.with(Cell::get)
+}
+
+/// Take the next step of the plan of the current thread, returning whether the operation fails.
+/// `in_range` tells whether the result of the operation was within the limits, since only those
+/// are counted as injected.
+pub(crate) fn take_fault(in_range: bool) -> bool {
+    let fault = PLAN.with(|cell| {
+        let mut plan = cell.borrow_mut();
+        let Some((plan, step)) = plan.as_mut() else {
+            return false;
+        };
+
+        let fault = match plan {
+            FaultPlan::Probability(p) => rand::thread_rng().gen_bool(p.clamp(0.0, 1.0)),
+            FaultPlan::Schedule(schedule) => schedule.get(*step).copied().unwrap_or(false),
+        };
+
+        *step += 1;
+        fault
+    });
+
+    if fault && in_range {
+        INJECTED.with(|count| count.set(count.get() + 1));
+    }
+
+    fault && in_range
+}
@@ -0,0 +1,40 @@
+/// A clamped type with a single range of values that the sliders of GUI toolkits, which work with
+/// `f64` values, can be bound to. It is implemented for every generated struct when the `ui`
+/// feature is enabled. Values are converted to `f64`, which is exact up to 2^53.
+///
+/// An adapter for the `Slider` of `egui` takes a few lines, since the limits and the step come
+/// from the type and `from_f64` keeps every value the slider produces within the limits:
+///
+/// ```rust,ignore
+/// fn clamped_slider<T: UiRange>(value: &mut T) -> egui::Slider<'_> {
+///     egui::Slider::from_get_set(T::ui_min()..=T::ui_max(), move |new| {
+///         if let Some(new) = new {
+///             *value = T::from_f64(new);
+///         }
+///
+///         value.to_f64()
+///     })
+///     .step_by(T::ui_step())
+/// }
+///
+/// ui.add(clamped_slider(&mut settings.volume));
+/// ```
+pub trait UiRange: Sized {
+    /// The lower limit of the type.
+    fn ui_min() -> f64;
+
+    /// The upper limit of the type.
+    fn ui_max() -> f64;
+
+    /// The distance between neighboring values, which is `1.0` for integers.
+    #[inline(always)]
+    fn ui_step() -> f64 {
+        1.0
+    }
+
+    fn to_f64(&self) -> f64;
+
+    /// Round `value` to the nearest integer and clamp it to the limits of the type. `NaN` becomes
+    /// the lower limit.
+    fn from_f64(value: f64) -> Self;
+}
@@ -1,6 +1,13 @@
-use anyhow::Result;
+use std::{marker::PhantomData, ops::RangeInclusive};
 
-use crate::guard::Guard;
+use anyhow::{Context, Result};
+
+use crate::{
+    batch::BatchPrimitive,
+    clamp::{try_clamp, ClampError, ClampedInteger},
+    guard::Guard,
+    scale::RangeOffset,
+};
 
 pub trait Validator: 'static + Copy {
     type Item;
@@ -59,6 +66,714 @@ impl<T, E, U: Validator<Item = T, Error = E>> View<T, E, U> {
     }
 }
 
+/// A value that can no longer change, such as configuration that is fixed after startup, created
+/// with the generated `freeze` method. It can be read through `Deref`, compared, hashed and
+/// serialized as the inner value, but offers no operators, guards or way to take the value back
+/// out by value.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Frozen<T>(T);
+
+impl<T> Frozen<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    #[inline(always)]
+    pub const fn get(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::Deref for Frozen<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Frozen<T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Frozen<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A primitive that has left the validated domain of a clamped type through the generated
+/// `into_raw` method. It has no `Deref` and no operators, so it can only be taken out with
+/// `into_inner`, changed with `map` or brought back with `validate`, which keeps every place that
+/// works with unvalidated values visible in review.
+#[must_use = "an unvalidated value should be validated or taken out with `into_inner`"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Unvalidated<T>(T);
+
+impl<T: Copy> Unvalidated<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Take the primitive out, accepting that nothing checks it anymore.
+    #[inline(always)]
+    pub const fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Change the primitive without leaving the unvalidated domain.
+    #[inline(always)]
+    pub fn map(self, f: impl FnOnce(T) -> T) -> Self {
+        Self(f(self.0))
+    }
+
+    /// Check the primitive against the limits of `U` and convert it.
+    #[inline(always)]
+    pub fn validate<U: ClampedInteger<T>>(self) -> Result<U, ClampError<T>>
+    where
+        T: Ord,
+    {
+        try_clamp(self.0, U::MIN, U::MAX)
+            .map(|value| U::from_primitive(value).expect("value should be within bounds"))
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Unvalidated<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A read-only view over a slice of primitives that are expected to be valid values of `T`, such
+/// as a large telemetry buffer. Each element is validated when it is read, or all of them are
+/// validated up front with `eager`, which records the invalid positions in a bitmap so they can be
+/// found again without revalidating.
+#[derive(Debug, Clone)]
+pub struct ClampedSlice<'a, T, P> {
+    items: &'a [P],
+    invalid: Option<Vec<u64>>,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<'a, T: ClampedInteger<P>, P: Copy + Ord> ClampedSlice<'a, T, P> {
+    /// Create a view that validates each element when it is read.
+    #[inline(always)]
+    pub fn new(items: &'a [P]) -> Self {
+        Self {
+            items,
+            invalid: None,
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Create a view that validates every element now, recording the invalid positions.
+    pub fn eager(items: &'a [P]) -> Self {
+        let mut invalid = vec![0u64; items.len().div_ceil(64)];
+
+        for (i, item) in items.iter().enumerate() {
+            if !Self::check(*item) {
+                invalid[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        Self {
+            items,
+            invalid: Some(invalid),
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the underlying primitives, including any invalid ones.
+    #[inline(always)]
+    pub fn as_primitives(&self) -> &'a [P] {
+        self.items
+    }
+
+    /// Check whether the element at `index` is a valid value. Out of bounds indices are invalid.
+    #[inline(always)]
+    pub fn is_valid(&self, index: usize) -> bool {
+        match (&self.invalid, self.items.get(index)) {
+            (_, None) => false,
+            (Some(invalid), Some(_)) => invalid[index / 64] & (1 << (index % 64)) == 0,
+            (None, Some(item)) => Self::check(*item),
+        }
+    }
+
+    /// Get the element at `index`, or `None` if it is out of bounds or invalid.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<T> {
+        if self.is_valid(index) {
+            T::from_primitive(self.items[index]).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the valid elements, skipping any invalid ones.
+    pub fn iter_valid(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.items.len()).filter_map(|i| self.get(i))
+    }
+
+    /// Iterate over the positions of the invalid elements.
+    pub fn invalid_positions(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.items.len()).filter(|i| !self.is_valid(*i))
+    }
+
+    /// Get the position and value of the first invalid element, if there is one.
+    #[inline(always)]
+    pub fn first_invalid(&self) -> Option<(usize, P)> {
+        self.invalid_positions().next().map(|i| (i, self.items[i]))
+    }
+
+    /// Copy every element into a `Vec`, failing with the position of the first invalid element.
+    pub fn to_owned_validated(&self) -> Result<Vec<T>> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                T::from_primitive(*item).with_context(|| format!("element {} is invalid", i))
+            })
+            .collect()
+    }
+
+    /// Values outside of the limits are rejected without building an error.
+    #[inline(always)]
+    fn check(item: P) -> bool {
+        item >= T::MIN && item <= T::MAX && T::from_primitive(item).is_ok()
+    }
+}
+
+impl<'a, T, P> AsRef<[P]> for ClampedSlice<'a, T, P> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[P] {
+        self.items
+    }
+}
+
+/// The number of elements that bulk operations of `ClampedVec` check against the limits at a time.
+const CHUNK_LEN: usize = 64;
+
+/// A growable buffer of clamped values that stores their primitives contiguously, such as the
+/// state of many entities in a simulation. Elements are validated when they are added and read
+/// back as `T`, while the bulk operations work on the primitives directly so that they can be
+/// vectorized.
+pub struct ClampedVec<T, P> {
+    items: Vec<P>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T, P> Default for ClampedVec<T, P> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Clone> Clone for ClampedVec<T, P> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, P: std::fmt::Debug> std::fmt::Debug for ClampedVec<T, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.items).finish()
+    }
+}
+
+impl<T, P: PartialEq> PartialEq for ClampedVec<T, P> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T, P: Eq> Eq for ClampedVec<T, P> {}
+
+impl<T, P> ClampedVec<T, P>
+where
+    T: ClampedInteger<P>,
+    P: BatchPrimitive + std::fmt::Debug + std::fmt::Display + Send + Sync + 'static,
+{
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn push(&mut self, value: T) {
+        self.items.push(value.into_primitive());
+    }
+
+    /// Add a primitive, failing without adding it if it is not a valid value of `T`.
+    pub fn try_push_raw(&mut self, value: P) -> Result<()> {
+        let value = T::from_primitive(try_clamp(value, T::MIN, T::MAX)?)?;
+
+        self.push(value);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop().map(Self::read)
+    }
+
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.items.get(index).copied().map(Self::read)
+    }
+
+    /// Replace the element at `index`, returning the previous one, or `None` if it is out of
+    /// bounds.
+    #[inline(always)]
+    pub fn set(&mut self, index: usize, value: T) -> Option<T> {
+        let slot = self.items.get_mut(index)?;
+
+        Some(Self::read(std::mem::replace(slot, value.into_primitive())))
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = T> + '_ {
+        self.items.iter().copied().map(Self::read)
+    }
+
+    #[inline(always)]
+    pub fn as_primitives(&self) -> &[P] {
+        &self.items
+    }
+
+    #[inline(always)]
+    pub fn into_primitives(self) -> Vec<P> {
+        self.items
+    }
+
+    /// Add `delta` to every element, saturating at the upper limit. Each chunk is checked against
+    /// the limits once, and only a chunk that would leave them is clamped element by element.
+    pub fn saturating_add_all(&mut self, delta: P) {
+        let (min, max) = (T::MIN, T::MAX);
+
+        for chunk in self.items.chunks_mut(CHUNK_LEN) {
+            let (smallest, largest) = Self::bounds(chunk);
+
+            if smallest >= min && largest <= max && delta <= max.wrapping_sub(largest) {
+                for value in chunk.iter_mut() {
+                    *value = value.wrapping_add(delta);
+                }
+            } else {
+                for value in chunk.iter_mut() {
+                    *value = value.saturating_add(delta).clamp(min, max);
+                }
+            }
+        }
+    }
+
+    /// Subtract `delta` from every element, saturating at the lower limit. Each chunk is checked
+    /// against the limits once, and only a chunk that would leave them is clamped element by
+    /// element.
+    pub fn saturating_sub_all(&mut self, delta: P) {
+        let (min, max) = (T::MIN, T::MAX);
+
+        for chunk in self.items.chunks_mut(CHUNK_LEN) {
+            let (smallest, largest) = Self::bounds(chunk);
+
+            if smallest >= min && largest <= max && delta <= smallest.wrapping_sub(min) {
+                for value in chunk.iter_mut() {
+                    *value = value.wrapping_sub(delta);
+                }
+            } else {
+                for value in chunk.iter_mut() {
+                    *value = value.saturating_sub(delta).clamp(min, max);
+                }
+            }
+        }
+    }
+
+    /// Soft types may hold values outside of their limits, so `T::from_primitive` is only
+    /// expected to fail if an element was never validated.
+    #[inline(always)]
+    fn read(value: P) -> T {
+        T::from_primitive(value).expect("element should have been validated")
+    }
+
+    #[inline(always)]
+    fn bounds(chunk: &[P]) -> (P, P) {
+        chunk
+            .iter()
+            .fold((T::MAX, T::MIN), |(smallest, largest), value| {
+                (smallest.min(*value), largest.max(*value))
+            })
+    }
+}
+
+impl<T, P> TryFrom<Vec<P>> for ClampedVec<T, P>
+where
+    T: ClampedInteger<P>,
+    P: BatchPrimitive + std::fmt::Debug + std::fmt::Display + Send + Sync + 'static,
+{
+    type Error = anyhow::Error;
+
+    fn try_from(items: Vec<P>) -> Result<Self> {
+        for (i, item) in items.iter().enumerate() {
+            try_clamp(*item, T::MIN, T::MAX)
+                .map_err(anyhow::Error::new)
+                .and_then(T::from_primitive)
+                .with_context(|| format!("element {} is invalid", i))?;
+        }
+
+        Ok(Self {
+            items,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T: ClampedInteger<P>, P: BatchPrimitive> FromIterator<T> for ClampedVec<T, P> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut this = Self::default();
+        this.extend(iter);
+        this
+    }
+}
+
+impl<T: ClampedInteger<P>, P: BatchPrimitive> Extend<T> for ClampedVec<T, P> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.items
+            .extend(iter.into_iter().map(|value| value.into_primitive()));
+    }
+}
+
+impl<T, P> AsRef<[P]> for ClampedVec<T, P> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[P] {
+        &self.items
+    }
+}
+
+impl<T, P: serde::Serialize> serde::Serialize for ClampedVec<T, P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.items.serialize(serializer)
+    }
+}
+
+impl<'de, T, P> serde::Deserialize<'de> for ClampedVec<T, P>
+where
+    T: ClampedInteger<P>,
+    P: BatchPrimitive
+        + std::fmt::Debug
+        + std::fmt::Display
+        + Send
+        + Sync
+        + serde::Deserialize<'de>
+        + 'static,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = Vec::<P>::deserialize(deserializer)?;
+
+        Self::try_from(items).map_err(|e| serde::de::Error::custom(format!("{:#}", e)))
+    }
+}
+
+/// Routes keys to the shard ids allowed by the clamped type `S` with a stable hash, so every node
+/// of a cluster agrees on where a key lives. Keys are spread uniformly over the allowed ids, which
+/// may be narrowed with `without`, such as to skip the gaps that an enum only accepts through its
+/// `#[other]` variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardRouter<S> {
+    spans: Vec<RangeInclusive<u32>>,
+    marker: std::marker::PhantomData<S>,
+}
+
+impl<S: ClampedInteger<u32>> Default for ShardRouter<S> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ClampedInteger<u32>> ShardRouter<S> {
+    /// Create a router over every value within the limits of `S`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            spans: vec![S::MIN..=S::MAX],
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Stop routing to the values in `spans`.
+    pub fn without(mut self, spans: impl IntoIterator<Item = RangeInclusive<u32>>) -> Self {
+        for removed in spans.into_iter().filter(|span| !span.is_empty()) {
+            self.spans = self
+                .spans
+                .into_iter()
+                .flat_map(|span| {
+                    let (start, end) = (*span.start(), *span.end());
+                    let (cut_start, cut_end) = (*removed.start(), *removed.end());
+
+                    let below = (cut_start > start).then(|| start..=(cut_start - 1).min(end));
+                    let above = (cut_end < end).then(|| (cut_end + 1).max(start)..=end);
+
+                    below.into_iter().chain(above)
+                })
+                .filter(|span| !span.is_empty())
+                .collect();
+        }
+
+        self
+    }
+
+    /// Get the spans of shard ids that keys are routed to, in ascending order.
+    #[inline(always)]
+    pub fn spans(&self) -> &[RangeInclusive<u32>] {
+        &self.spans
+    }
+
+    /// Get the number of shard ids that keys are routed to.
+    #[inline(always)]
+    pub fn shard_count(&self) -> u64 {
+        self.spans
+            .iter()
+            .map(|span| (span.end() - span.start()) as u64 + 1)
+            .sum()
+    }
+
+    /// Route a key to its shard by the FNV-1a hash of its bytes, which is the same on every
+    /// platform and compiler version as long as the bytes are, so integer keys should be given in
+    /// a fixed byte order, such as with `to_le_bytes`. Fails when every shard id was removed with
+    /// `without`.
+    pub fn route(&self, key: impl AsRef<[u8]>) -> Result<S> {
+        self.route_hash(fnv1a(key.as_ref()))
+    }
+
+    /// Route a key that was already hashed to its shard.
+    pub fn route_hash(&self, hash: u64) -> Result<S> {
+        let shard = nth_in_spans(&self.spans, hash)
+            .ok_or_else(|| anyhow::anyhow!("there are no shard ids to route to"))?;
+
+        S::from_primitive(shard)
+    }
+}
+
+/// Map `hash` uniformly onto the values of `spans`, which must not overlap, as if they were one
+/// contiguous range. This is the modulo over a set with gaps that `ShardRouter` uses, and returns
+/// `None` when the spans are empty.
+pub fn nth_in_spans<P: RangeOffset>(spans: &[RangeInclusive<P>], hash: u64) -> Option<P> {
+    let len = |span: &RangeInclusive<P>| span.end().offset_from(*span.start()) + 1;
+    let total = spans.iter().map(len).sum::<u128>();
+
+    if total == 0 {
+        return None;
+    }
+
+    // widening multiply, which avoids the bias of `%` when `total` does not divide 2^64
+    let mut index = if total > u64::MAX as u128 {
+        hash as u128
+    } else {
+        (hash as u128 * total) >> 64
+    };
+
+    for span in spans {
+        let len = len(span);
+
+        if index < len {
+            return Some(span.start().offset_by(index));
+        }
+
+        index -= len;
+    }
+
+    None
+}
+
+/// The sending side of a `tokio::sync::watch` channel of clamped values, such as for propagating
+/// a bounded setting like a rate limit to the tasks of a service. Primitives are validated before
+/// they are published, so receivers only ever see valid values.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct ClampedWatch<T> {
+    sender: tokio::sync::watch::Sender<T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Copy> ClampedWatch<T> {
+    #[inline(always)]
+    pub fn new(initial: T) -> Self {
+        Self {
+            sender: tokio::sync::watch::Sender::new(initial),
+        }
+    }
+
+    /// Create a watch along with its first receiver.
+    #[inline(always)]
+    pub fn channel(initial: T) -> (Self, ClampedReceiver<T>) {
+        let watch = Self::new(initial);
+        let receiver = watch.subscribe();
+
+        (watch, receiver)
+    }
+
+    #[inline(always)]
+    pub fn subscribe(&self) -> ClampedReceiver<T> {
+        ClampedReceiver {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// The value that was published last.
+    #[inline(always)]
+    pub fn get(&self) -> T {
+        *self.sender.borrow()
+    }
+
+    /// Publish a value, even when there are no receivers, returning the previous one.
+    #[inline(always)]
+    pub fn send_value(&self, value: T) -> T {
+        self.sender.send_replace(value)
+    }
+
+    /// Validate a primitive and publish it, failing without notifying the receivers if it is not a
+    /// valid value of `T`.
+    pub fn send<P: Copy>(&self, value: P) -> Result<()>
+    where
+        T: ClampedInteger<P>,
+    {
+        self.send_value(T::from_primitive(value)?);
+        Ok(())
+    }
+
+    /// Modify the primitive of the current value in place and publish the result, failing without
+    /// notifying the receivers if it is not a valid value of `T`. The value is locked while
+    /// `modify` runs, so concurrent modifications are not lost.
+    pub fn send_modify<P: Copy>(&self, modify: impl FnOnce(&mut P)) -> Result<()>
+    where
+        T: ClampedInteger<P>,
+    {
+        let mut result = Ok(());
+
+        self.sender.send_if_modified(|value| {
+            let mut primitive = value.into_primitive();
+            modify(&mut primitive);
+
+            match T::from_primitive(primitive) {
+                Ok(modified) => {
+                    *value = modified;
+                    true
+                }
+                Err(e) => {
+                    result = Err(e);
+                    false
+                }
+            }
+        });
+
+        result
+    }
+
+    #[inline(always)]
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+/// The receiving side of a `ClampedWatch`, which yields the clamped values it publishes.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone)]
+pub struct ClampedReceiver<T> {
+    receiver: tokio::sync::watch::Receiver<T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<T: Copy> ClampedReceiver<T> {
+    /// The value that was published last, without marking it as seen.
+    #[inline(always)]
+    pub fn get(&self) -> T {
+        *self.receiver.borrow()
+    }
+
+    /// The value that was published last, marking it as seen.
+    #[inline(always)]
+    pub fn get_and_update(&mut self) -> T {
+        *self.receiver.borrow_and_update()
+    }
+
+    /// Whether a value was published since the last one that was seen, failing when the watch
+    /// was dropped.
+    #[inline(always)]
+    pub fn has_changed(&self) -> Result<bool> {
+        Ok(self.receiver.has_changed()?)
+    }
+
+    /// Wait for a value that was not seen yet and return it, failing when the watch was dropped.
+    pub async fn changed(&mut self) -> Result<T> {
+        self.receiver.changed().await?;
+
+        Ok(self.get_and_update())
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> tokio::sync::watch::Receiver<T> {
+        self.receiver
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`, which unlike the standard hasher is stable across
+/// compiler versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +837,15 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_nth_in_spans() {
+        let spans = [0u8..=1, 10..=11];
+
+        assert_eq!(nth_in_spans(&spans, 0), Some(0));
+        assert_eq!(nth_in_spans(&spans, 1 << 62), Some(1));
+        assert_eq!(nth_in_spans(&spans, 1 << 63), Some(10));
+        assert_eq!(nth_in_spans(&spans, u64::MAX), Some(11));
+        assert_eq!(nth_in_spans::<u8>(&[], 42), None);
+    }
 }
@@ -0,0 +1,11 @@
+// Checks that values outside of the limits can only be built through the unsafe API, that enums
+// stored in a narrower type cannot be read by reference, and that asserted ranges, the coverage of
+// enum variants, including the stricter `coverage = exhaustive`, overlapping range variants,
+// stepped ranges with too many values, the values that `#[maps_to]` converts back to, the
+// intermediate results of `clamped_const!` and the entries of `configure!` are checked at compile
+// time.
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,14 @@
+use checked_rs::prelude::*;
+
+#[clamped(u8 as Hard, default = 0, upper = 100)]
+#[derive(Debug, Clone, Copy)]
+struct Share;
+
+const BASE: Share = Share::new_const(90);
+
+// The sum is back within the limits, but the intermediate `110` is not.
+const TOTAL: Share = clamped_const!(Share: BASE + 20 - 50);
+
+fn main() {
+    let _ = TOTAL;
+}
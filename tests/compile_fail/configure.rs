@@ -0,0 +1,15 @@
+checked_rs::configure! {
+    behavior = Saturating;
+    behavior = Panicking;
+}
+
+checked_rs::configure! {
+    derive(Debug);
+    default = 0;
+}
+
+checked_rs::configure! {
+    behavior = Sometimes;
+}
+
+fn main() {}
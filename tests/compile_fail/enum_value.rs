@@ -0,0 +1,17 @@
+mod signal {
+    use checked_rs::prelude::*;
+
+    #[clamped(u8, default = 1, lower = 1, upper = 9, module = strength)]
+    #[derive(Debug, Clone, Copy)]
+    pub enum Signal {
+        #[eq(1)]
+        Weak,
+        #[range(2..=9)]
+        Strong,
+    }
+}
+
+fn main() {
+    let _ = signal::Signal::Weak(signal::strength::Value(7));
+    let _ = signal::Signal::Strong(signal::strength::StrongValue(70));
+}
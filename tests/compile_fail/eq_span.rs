@@ -0,0 +1,12 @@
+use checked_rs::prelude::*;
+
+#[clamped(u16, default = 0, upper = 999)]
+#[derive(Debug, Clone, Copy)]
+enum Code {
+    #[eq(0, 1..=500)]
+    Listed,
+    #[other]
+    Other,
+}
+
+fn main() {}
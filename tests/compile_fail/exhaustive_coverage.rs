@@ -0,0 +1,23 @@
+use checked_rs::prelude::*;
+
+#[clamped(u8, default = 0, upper = 20, coverage = exhaustive)]
+#[derive(Debug, Clone, Copy)]
+enum Opcode {
+    #[eq(0)]
+    Nop,
+    #[range(4..=9)]
+    Load,
+    #[eq(12)]
+    Store,
+}
+
+#[clamped(u8, default = 0, upper = 3, coverage = exhaustive)]
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    #[eq(0)]
+    Off,
+    #[other]
+    On,
+}
+
+fn main() {}
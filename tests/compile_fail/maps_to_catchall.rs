@@ -0,0 +1,42 @@
+use checked_rs::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Fine,
+    Unknown,
+}
+
+#[clamped(u16, default = 200, lower = 100, upper = 599, maps_to = Status)]
+#[derive(Debug, Clone, Copy)]
+enum Unrepresented {
+    #[eq(200)]
+    #[maps_to(Status::Fine)]
+    Okay,
+    #[other]
+    #[maps_to(Status::Unknown)]
+    Other,
+}
+
+#[clamped(u16, default = 200, lower = 100, upper = 599, maps_to = Status)]
+#[derive(Debug, Clone, Copy)]
+enum Owned {
+    #[eq(200)]
+    #[maps_to(Status::Fine)]
+    Okay,
+    #[other]
+    #[maps_to(Status::Unknown, 200)]
+    Other,
+}
+
+#[clamped(u16, default = 200, lower = 100, upper = 599, maps_to = Status)]
+#[derive(Debug, Clone, Copy)]
+enum NotCatchall {
+    #[eq(200)]
+    #[maps_to(Status::Fine, 200)]
+    Okay,
+    #[other]
+    #[maps_to(Status::Unknown, 302)]
+    Other,
+}
+
+fn main() {}
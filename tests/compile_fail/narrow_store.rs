@@ -0,0 +1,16 @@
+use checked_rs::prelude::*;
+
+#[clamped(usize, default = 0, upper = 1000, store = u16)]
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    #[eq(0)]
+    Empty,
+    #[other]
+    Used,
+}
+
+fn main() {
+    let slot = Slot::default();
+    assert_eq!(slot.into_primitive(), 0);
+    slot.as_primitive();
+}
@@ -0,0 +1,9 @@
+use checked_rs::prelude::*;
+
+#[clamped(u8 as Hard, default = 0, upper = 100)]
+#[derive(Debug, Clone, Copy)]
+struct Share;
+
+fn main() {
+    let _ = Share::new_unchecked(200);
+}
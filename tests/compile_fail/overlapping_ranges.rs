@@ -0,0 +1,36 @@
+use checked_rs::prelude::*;
+
+#[clamped(u8, default = 0)]
+#[derive(Debug, Clone, Copy)]
+enum Partial {
+    #[range(0..=100)]
+    Low,
+    #[range(50..=200)]
+    Mid,
+    #[range(201..=MAX)]
+    High,
+}
+
+#[clamped(u8, default = 0)]
+#[derive(Debug, Clone, Copy)]
+enum Nested {
+    #[range(0..=200)]
+    Wide,
+    #[range(100..=150)]
+    Narrow,
+    #[range(201..=MAX)]
+    High,
+}
+
+#[clamped(u8, default = 0)]
+#[derive(Debug, Clone, Copy)]
+enum SameStart {
+    #[range(0..=200)]
+    Wide,
+    #[range(0..=100)]
+    Narrow,
+    #[range(201..=MAX)]
+    High,
+}
+
+fn main() {}
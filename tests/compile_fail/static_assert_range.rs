@@ -0,0 +1,21 @@
+use checked_rs::{prelude::*, static_assert_range};
+
+#[clamped(u8 as Hard, default = 0, upper = 100)]
+#[derive(Debug, Clone, Copy)]
+struct Percent;
+
+#[clamped(u8, default = 0, upper = 10)]
+#[derive(Debug, Clone, Copy)]
+enum Level {
+    #[eq(0)]
+    Off,
+    #[range(1..=9)]
+    On,
+    #[eq(10)]
+    Max,
+}
+
+static_assert_range!(Percent, 0..=99);
+static_assert_range!(Level, 0..=10, exact = [0, 9]);
+
+fn main() {}
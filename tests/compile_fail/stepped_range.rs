@@ -0,0 +1,12 @@
+use checked_rs::prelude::*;
+
+#[clamped(u16, default = 0, upper = 1000)]
+#[derive(Debug, Clone, Copy)]
+enum Dial {
+    #[range(0.. step 2)]
+    Even,
+    #[other]
+    Odd,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+mod gauge {
+    use checked_rs::prelude::*;
+
+    #[clamped(u8 as Hard, default = 0, upper = 100)]
+    #[derive(Debug, Clone, Copy)]
+    pub struct Percent;
+}
+
+fn main() {
+    let _ = gauge::Percent(200);
+}
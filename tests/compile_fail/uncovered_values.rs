@@ -0,0 +1,28 @@
+use checked_rs::prelude::*;
+
+#[clamped(u64, default = 0)]
+#[derive(Debug, Clone, Copy)]
+enum Offset {
+    #[eq(0)]
+    Start,
+    #[range(10..=MAX)]
+    Rest,
+}
+
+#[clamped(u8, default = 0, upper = 15)]
+#[derive(Debug, Clone, Copy)]
+enum Nibble {
+    #[range(0..4)]
+    Low,
+    #[mask(4)]
+    Flagged,
+}
+
+#[clamped(u8, default = 0, upper = 4)]
+#[derive(Debug, Clone, Copy)]
+enum Even {
+    #[range(0..=4 step 2)]
+    Even,
+}
+
+fn main() {}